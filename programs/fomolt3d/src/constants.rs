@@ -26,3 +26,45 @@ pub const DEFAULT_PROTOCOL_FEE_BPS: u64 = 200;
 
 /// Default referral bonus in basis points (10% of after-fee amount — separate from pot BPS sum)
 pub const DEFAULT_REFERRAL_BONUS_BPS: u64 = 1000;
+
+// --- Bonded keeper cranks (see `instructions::register_keeper`) ---
+
+/// Bonus applied on top of `GameState::keeper_fee_lamports`, in basis points,
+/// when `end_round` is cranked by an active, bonded `KeeperState` instead of
+/// an anonymous caller. 2000 = +20%. Flat rather than `GlobalConfig`-tunable
+/// since it only rewards registration, not anything a round operator would
+/// need to adjust per game.
+pub const KEEPER_BOUNTY_BONUS_BPS: u64 = 2000;
+
+// --- Per-round referral leaderboard (see `state::GameStateExt::top_referrers`) ---
+
+/// How many distinct referrers `GameStateExt::top_referrers` tracks per
+/// round. Only the leader (index 0) is ever paid out via
+/// `instructions::claim_top_referrer_bonus`; the rest exist purely so a
+/// front end can render a live leaderboard. Small and fixed so insertion-sort
+/// maintenance in `buy_keys` stays a handful of comparisons per purchase.
+pub const TOP_REFERRERS_LEADERBOARD_SIZE: usize = 5;
+
+// --- Daily key-holder raffle (see `instructions::record_raffle_snapshot`) ---
+
+/// Minimum seconds between successive raffle days within a round.
+/// `instructions::record_raffle_snapshot` rejects a new day's snapshot until
+/// this many seconds have elapsed since the round started (for day 0) or
+/// since the previous day's snapshot (for later days).
+pub const RAFFLE_INTERVAL_SECS: i64 = 86_400;
+
+// --- Season meta-game (see `state::season`) ---
+
+/// How many distinct players `Season::leaderboard` tracks, ranked by total
+/// volume across the season. Only `SEASON_PAYOUT_BPS.len()` of these ranks
+/// are ever payable via `instructions::claim_season_prize`; the rest exist
+/// purely so a front end can render a live standings board. Small and fixed
+/// so insertion-sort maintenance in `buy_keys` stays a handful of
+/// comparisons per purchase — same tradeoff as `TOP_REFERRERS_LEADERBOARD_SIZE`.
+pub const SEASON_LEADERBOARD_SIZE: usize = 5;
+
+/// `Season::pool_lamports` split across the top 3 volume ranks once a season
+/// is settled — index 0 is the champion's share. Sums to 10,000 (100%); any
+/// rank at or beyond this length earns no prize even if it holds a
+/// leaderboard slot.
+pub const SEASON_PAYOUT_BPS: [u64; 3] = [5000, 3000, 2000];
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+/// Anchor assigns each variant a stable numeric code starting at 6000, in
+/// declaration order. Clients (see `app/src/lib/sdk/errors.ts`) decode
+/// those codes back into names, so this enum is append-only: never reorder,
+/// rename, or delete a variant — add new ones at the end.
 #[error_code]
 pub enum FomoltError {
     /// Buying keys when round is inactive
@@ -81,4 +85,455 @@ pub enum FomoltError {
     /// Player is not in this round
     #[msg("Player is not in this round")]
     PlayerNotInRound,
+
+    /// Vault balance cannot cover a payout once rent-exempt minimum is reserved
+    #[msg("Vault balance is insufficient to cover outstanding obligations")]
+    VaultInsolvent,
+
+    /// The provided block_entry account doesn't match the expected PDA for this wallet
+    #[msg("Block entry does not match wallet")]
+    BlockEntryMismatch,
+
+    /// Wallet is on the blocklist and the action is not permitted under its policy
+    #[msg("Wallet is blocked")]
+    WalletBlocked,
+
+    /// Purchase cost is below the configured minimum — too small for bps splits to round to anything
+    #[msg("Purchase cost is below the configured minimum")]
+    BelowMinimumPurchase,
+
+    /// Winner claim window has not yet elapsed since the round ended
+    #[msg("Winner claim window has not expired yet")]
+    ClaimWindowNotExpired,
+
+    /// The destination round for a forfeited winner pot must be the currently active round
+    #[msg("Target round is not the currently active round")]
+    NotCurrentRound,
+
+    /// buy_keys_via_session called after SessionAuthority.expiry_unix_ts
+    #[msg("Session has expired")]
+    SessionExpired,
+
+    /// Cumulative lamport cost through a session would exceed its spend_limit_lamports
+    #[msg("Session spend limit exceeded")]
+    SessionSpendLimitExceeded,
+
+    /// close_player_state called while keys, referral_earnings, or current_round are nonzero
+    #[msg("Player state still has keys, referral earnings, or an active round")]
+    PlayerStateNotEmpty,
+
+    /// A payout instruction was presented a PlayerState that never ran the
+    /// real `is_new_player` init branch — see `PlayerState::initialized`
+    PlayerStateNotInitialized,
+
+    /// vault_lamports_in - vault_lamports_out doesn't match the vault's live balance
+    #[msg("Vault balance does not match tracked cumulative in/out lamports")]
+    VaultAccountingMismatch,
+
+    /// Forfeiting or consolidating between two GameState accounts from different game lineages
+    #[msg("The two rounds belong to different games")]
+    GameIdMismatch,
+
+    /// Claiming with auto_compound enabled but current_game_state/current_vault were not supplied
+    #[msg("Auto-compound is enabled but no current round was provided to reinvest into")]
+    MissingCompoundTarget,
+
+    /// The provided current_vault doesn't match the expected vault PDA for current_game_state
+    #[msg("Vault does not match the expected PDA for this round")]
+    VaultMismatch,
+
+    /// commit_buy called with budget_lamports == 0
+    #[msg("Commit budget must be greater than zero")]
+    InvalidCommitBudget,
+
+    /// reveal_buy's (keys_to_buy, salt) don't hash to the stored commitment_hash
+    #[msg("Revealed keys and salt do not match the committed hash")]
+    CommitmentHashMismatch,
+
+    /// reveal_buy called in the same slot as the matching commit_buy
+    #[msg("Must wait at least one slot after commit before revealing")]
+    RevealTooSoon,
+
+    /// reveal_buy's cost (priced off total_keys_at_commit) exceeds the escrowed budget
+    #[msg("Revealed purchase cost exceeds the committed budget")]
+    CommitBudgetExceeded,
+
+    /// grant_promo_keys would push promo_keys_granted_this_round past the round's cap
+    #[msg("Granting this many promo keys would exceed the round's cap")]
+    PromoCapExceeded,
+
+    /// grant_promo_keys called with keys == 0
+    #[msg("Promo key grant must be for at least one key")]
+    NoPromoKeysToGrant,
+
+    /// transfer_keys called while GameState::transfers_enabled is false
+    #[msg("Key transfers are disabled for this round")]
+    TransfersDisabled,
+
+    /// transfer_keys called with amount == 0
+    #[msg("Must transfer at least one key")]
+    NoKeysToTransfer,
+
+    /// transfer_keys's sender doesn't hold enough keys to cover the amount
+    #[msg("Sender does not hold enough keys for this transfer")]
+    InsufficientKeys,
+
+    /// transfer_keys called with the sender and recipient being the same player
+    #[msg("Cannot transfer keys to yourself")]
+    CannotTransferToSelf,
+
+    /// init_key_mint/wrap_keys called while GameState::wrapped_keys_enabled is false
+    #[msg("Wrapped keys are disabled for this round")]
+    WrappedKeysDisabled,
+
+    /// wrap_keys called with amount == 0
+    #[msg("Must wrap at least one key")]
+    NoKeysToWrap,
+
+    /// wrap_keys's caller doesn't hold enough keys to cover the amount
+    #[msg("Player does not hold enough keys to wrap")]
+    InsufficientKeysToWrap,
+
+    /// unwrap_keys called with amount == 0
+    #[msg("Must unwrap at least one key")]
+    NoKeysToUnwrap,
+
+    /// unwrap_keys called with amount exceeding GameState::wrapped_keys_total
+    #[msg("Amount exceeds this round's outstanding wrapped key supply")]
+    InsufficientWrappedSupply,
+
+    /// GameState::transition_status called with a `to` that isn't reachable
+    /// from the current `RoundStatus`
+    #[msg("Illegal round lifecycle transition")]
+    InvalidRoundStatusTransition,
+
+    /// fund_keeper_budget called with amount == 0
+    #[msg("Fund amount must be greater than zero")]
+    InvalidFundAmount,
+
+    /// init_player_history/buy_keys called while GameState::purchase_history_enabled is false
+    #[msg("Purchase history is disabled for this round")]
+    PurchaseHistoryDisabled,
+
+    /// The provided player_history account doesn't match the expected PDA for this wallet
+    #[msg("Player history does not match wallet")]
+    PlayerHistoryMismatch,
+
+    /// buy_keys called with GameState::hook_program set but no hook_program account supplied
+    #[msg("Partner hook is enabled for this round but no hook program account was provided")]
+    MissingHookProgram,
+
+    /// The provided hook_program account doesn't match GameState::hook_program
+    #[msg("Hook program does not match the configured partner program")]
+    HookProgramMismatch,
+
+    /// buy_keys's remaining_accounts (passed through to the partner hook CPI) exceed the cap
+    #[msg("Too many accounts passed to the partner hook")]
+    TooManyHookAccounts,
+
+    /// end_round called with GameState::bridge_program set but no bridge_program account supplied
+    #[msg("Cross-chain bridge is enabled for this round but no bridge program account was provided")]
+    MissingBridgeProgram,
+
+    /// The provided bridge_program account doesn't match GameState::bridge_program
+    #[msg("Bridge program does not match the configured attestation program")]
+    BridgeProgramMismatch,
+
+    /// end_round's remaining_accounts (passed through to the bridge attestation CPI) exceed the cap
+    #[msg("Too many accounts passed to the bridge attestation")]
+    TooManyBridgeAccounts,
+
+    /// buy_keys_batch called with an empty amounts vector
+    #[msg("Batch must include at least one purchase amount")]
+    EmptyBatch,
+
+    /// buy_keys_batch's amounts vector exceeds the per-instruction cap
+    #[msg("Too many purchases in a single batch")]
+    TooManyBatchPurchases,
+
+    /// record_dividend_merkle_root called while the round is still active
+    #[msg("Cannot record a dividend Merkle root while the round is still active")]
+    RoundStillActiveForMerkleRoot,
+
+    /// claim_with_proof called before record_dividend_merkle_root
+    #[msg("No dividend Merkle root has been recorded for this round")]
+    MerkleRootNotSet,
+
+    /// claim_with_proof's proof doesn't verify against GameState::dividend_merkle_root
+    #[msg("Merkle proof does not verify against the recorded root")]
+    InvalidMerkleProof,
+
+    /// set_referrer called on a PlayerState that already has a referrer
+    #[msg("Referrer has already been set")]
+    ReferrerAlreadySet,
+
+    /// set_referrer called after the player has already bought keys this round
+    #[msg("Referrer can only be set before buying any keys this round")]
+    ReferrerWindowClosed,
+
+    /// set_referrer's referrer is already referred by the player being attached as its referee
+    #[msg("Referral chain would create a cycle")]
+    ReferralCycleDetected,
+
+    /// set_referrer called to change an existing referrer before GameState::referrer_change_cooldown_secs has elapsed
+    #[msg("Referrer can be changed again only after the configured cooldown")]
+    ReferrerChangeCooldownActive,
+
+    /// buy_keys's cost would push this player's rolling 24h spend past their own set_spend_limit cap
+    #[msg("Purchase would exceed your self-imposed daily spend limit")]
+    SpendLimitExceeded,
+
+    /// buy_keys called on a GameState::kyc_required round without a matching kyc_credential account
+    #[msg("A KYC credential is required to buy keys in this round")]
+    KycCredentialRequired,
+
+    /// sweep_dust_reserve called while GameState::dust_reserve is zero
+    #[msg("There is no dust reserve to sweep for this round")]
+    NothingToSweep,
+
+    /// sweep_unclaimed_dividends called with RollToNextRound policy but next_game_state/next_vault were not supplied
+    #[msg("Rolling unclaimed dividends forward requires the destination round's accounts")]
+    MissingRolloverTarget,
+
+    /// claim or claim_referral_earnings called with player_state.payout_address set but no matching payout_destination account supplied
+    #[msg("A payout_address is set for this player but no matching destination account was provided")]
+    MissingPayoutDestination,
+
+    /// The provided payout_destination doesn't match player_state.payout_address
+    #[msg("Payout destination does not match the configured payout address")]
+    PayoutDestinationMismatch,
+
+    /// unregister_keeper or slash_keeper called on a KeeperState that's already inactive
+    #[msg("This keeper is not currently registered")]
+    KeeperNotActive,
+
+    /// slash_keeper's amount exceeds the keeper_bond vault's slashable balance
+    #[msg("Slash amount exceeds the keeper's bonded balance")]
+    InsufficientBond,
+
+    /// end_round passed a keeper_state account belonging to a different keeper than the caller
+    #[msg("The supplied keeper registration does not belong to the caller")]
+    KeeperMismatch,
+
+    /// claim_to_stake called with no vote_account configured, or one that doesn't match GlobalConfig::approved_stake_vote_account
+    #[msg("The supplied vote account is not the admin-approved stake delegation target")]
+    StakeVoteAccountNotApproved,
+
+    /// claim_to_stake passed a stake_program/stake_config account that doesn't match the native stake program's well-known addresses
+    #[msg("Unexpected stake program account")]
+    InvalidStakeProgramAccount,
+
+    /// deploy_vault_yield or unwind_vault_yield called with no yield_program configured, or one that doesn't match GlobalConfig::yield_program
+    #[msg("The supplied program is not the admin-approved vault yield destination")]
+    YieldProgramNotApproved,
+
+    /// deploy_vault_yield called with GlobalConfig::max_yield_deployment_bps at 0 (the kill-switch default)
+    #[msg("Vault yield deployment is disabled for this round")]
+    YieldDeploymentDisabled,
+
+    /// deploy_vault_yield's lamports would exceed GlobalConfig::max_yield_deployment_bps of the vault's current balance
+    #[msg("Requested deployment exceeds the configured share of the vault's balance")]
+    YieldDeploymentCapExceeded,
+
+    /// deploy_vault_yield or unwind_vault_yield's remaining_accounts exceeded MAX_YIELD_ACCOUNTS
+    #[msg("Too many accounts forwarded to the yield program")]
+    TooManyYieldAccounts,
+
+    /// unwind_vault_yield's lamports exceeds GameStateExt::yield_deployed_lamports
+    #[msg("Unwind amount exceeds the principal currently deployed")]
+    YieldUnwindExceedsDeployed,
+
+    /// unwind_vault_yield's CPI returned less than the requested lamports to the vault
+    #[msg("The yield program returned less than the requested unwind amount")]
+    YieldUnwindShortfall,
+
+    /// strict-invariants builds only: house_fee + frontend_fee + referral_bonus + raffle + winner + dividend + next_round + dust + pot_overflow != cost for a single buy_keys purchase
+    #[msg("Purchase fee/pot split does not conserve the total amount spent")]
+    AccountingMismatch,
+
+    /// end_round's optional game_state_ext account didn't derive from the expected `[b"game_ext", game_state]` seeds
+    #[msg("Supplied game state extension account does not match the expected PDA")]
+    GameStateExtMismatch,
+
+    /// claim_top_referrer_bonus called by a signer who isn't GameStateExt::top_referrers[0].referrer
+    #[msg("Caller is not this round's leading referrer")]
+    NotTopReferrer,
+
+    /// claim_top_referrer_bonus called with GameState::top_referrer_bonus_pool already drained (or never allocated)
+    #[msg("No top-referrer bonus is currently claimable for this round")]
+    NoTopReferrerBonus,
+
+    /// record_raffle_snapshot called before RAFFLE_INTERVAL_SECS has elapsed since round start (day 0) or the previous snapshot (later days)
+    #[msg("Not enough time has elapsed since the last raffle snapshot")]
+    RaffleDayNotElapsed,
+
+    /// record_raffle_snapshot's total_weight argument is 0 — nobody held a key when the snapshot was taken
+    #[msg("Raffle snapshot has no eligible weight to draw from")]
+    RaffleTotalWeightZero,
+
+    /// draw_raffle_ticket called on a RaffleSnapshot whose winning_ticket is already Some
+    #[msg("This raffle day has already been drawn")]
+    RaffleAlreadyDrawn,
+
+    /// claim_raffle_prize called on a RaffleSnapshot whose winning_ticket is still None
+    #[msg("This raffle day has not been drawn yet")]
+    RaffleNotDrawn,
+
+    /// claim_raffle_prize's Merkle proof did not resolve to RaffleSnapshot::merkle_root
+    #[msg("Raffle claim proof is invalid")]
+    InvalidRaffleProof,
+
+    /// claim_raffle_prize's committed weight range doesn't contain RaffleSnapshot::winning_ticket
+    #[msg("Winning ticket does not fall within the claimant's weight range")]
+    RaffleTicketOutOfRange,
+
+    /// cancel_round called on a GameState whose status isn't Active
+    #[msg("Only an active round can be cancelled")]
+    RoundNotCancellable,
+
+    /// refund called against a GameState that was never cancelled
+    #[msg("This round was not cancelled")]
+    RoundNotCancelled,
+
+    /// refund called with PlayerState::contributed_lamports already 0
+    #[msg("Nothing to refund for this player")]
+    NothingToRefund,
+
+    /// claim/claim_and_roll/start_new_round called against a round that was cancelled — use refund instead
+    #[msg("This round was cancelled — use refund instead of claim")]
+    RoundCancelled,
+
+    /// propose_player_migration called against a PlayerState with pending_migration_wallet already Some
+    #[msg("A migration is already pending for this player")]
+    MigrationAlreadyPending,
+
+    /// propose_player_migration's new_wallet argument equals the PlayerState's current wallet
+    #[msg("Cannot migrate a player state to its own wallet")]
+    CannotMigrateToSameWallet,
+
+    /// execute_player_migration called against a PlayerState with pending_migration_wallet None, or not matching the signing new_wallet
+    #[msg("No migration is pending for this player and wallet")]
+    NoMigrationPending,
+
+    /// execute_player_migration called before PlayerState::migration_effective_at has elapsed
+    #[msg("The migration timelock has not elapsed yet")]
+    MigrationTimelockActive,
+
+    /// end_round's winner_account was presented but doesn't match GameState::last_buyer
+    #[msg("winner_account does not match this round's last buyer")]
+    WinnerAccountMismatch,
+
+    /// record_sample called with GlobalConfig::price_sample_interval_slots == 0
+    #[msg("Price history sampling is disabled for this round")]
+    PriceSamplingDisabled,
+
+    /// record_sample called before PriceHistory::last_sampled_slot + GameState::price_sample_interval_slots
+    #[msg("The price sample interval has not elapsed yet")]
+    PriceSampleIntervalNotElapsed,
+
+    /// settle_season called against a Season whose current round hasn't reached end_round yet
+    #[msg("This season's final round has not concluded yet")]
+    SeasonNotYetOver,
+
+    /// settle_season called against a Season whose status is already Settled
+    #[msg("This season has already been settled")]
+    SeasonAlreadySettled,
+
+    /// claim_season_prize called against a Season whose status isn't Settled
+    #[msg("This season has not been settled yet")]
+    SeasonNotSettled,
+
+    /// claim_season_prize called by a signer outside Season::leaderboard's payable ranks (SEASON_PAYOUT_BPS.len())
+    #[msg("Caller did not finish this season in a payable leaderboard rank")]
+    NotOnPayableSeasonLeaderboard,
+
+    /// A supplied Season account doesn't match GameState::current_season_id()'s PDA
+    #[msg("Supplied season account does not match the current season")]
+    SeasonMismatch,
+
+    /// start_new_round's new round isn't exactly GlobalConfig::latest_round + 1
+    #[msg("New round must immediately follow the latest started round")]
+    RoundGapInvalid,
+
+    /// buy_keys's game_state.round doesn't match config.latest_round
+    #[msg("Supplied game round is not the current round")]
+    StaleRound,
+
+    /// Instruction gated off via GlobalConfig::disabled_instructions_bitmask
+    #[msg("This instruction is currently disabled by the admin")]
+    FeatureDisabled,
+
+    /// claim_biggest_buyer_bonus called by a signer who isn't GameState::max_single_buyer
+    #[msg("Caller did not make this round's biggest single purchase")]
+    NotBiggestBuyer,
+
+    /// claim_biggest_buyer_bonus called with GameState::biggest_buyer_bonus_pool already drained (or never allocated)
+    #[msg("No biggest-buyer bonus is currently claimable for this round")]
+    NoBiggestBuyerBonus,
+
+    /// claim_biggest_holder_bonus called by a signer who isn't GameState::largest_holder
+    #[msg("Caller is not this round's largest key holder")]
+    NotLargestHolder,
+
+    /// claim_biggest_holder_bonus called with GameState::biggest_holder_bonus_pool already drained (or never allocated)
+    #[msg("No biggest-holder bonus is currently claimable for this round")]
+    NoBiggestHolderBonus,
+
+    /// register_agent_platform called against a PlayerState with is_agent == false
+    #[msg("Only agent players may register an agent platform")]
+    NotAnAgent,
+
+    /// register_agent_platform called on a PlayerState that already has an agent_platform set
+    #[msg("Agent platform has already been set")]
+    AgentPlatformAlreadySet,
+
+    /// claim_agent_platform_earnings called with AgentPlatform::pending_earnings_lamports == 0
+    #[msg("No agent platform earnings to claim")]
+    NoAgentPlatformEarnings,
+
+    /// buy_keys called with player_state.agent_platform set but no matching agent_platform account supplied
+    #[msg("An agent platform is set for this player but no matching account was provided")]
+    MissingAgentPlatform,
+
+    /// The provided agent_platform account doesn't match player_state.agent_platform
+    #[msg("Agent platform account does not match the registered platform")]
+    AgentPlatformMismatch,
+
+    /// execute_scheduled_buy called with PlayerState::scheduled_buy_interval_secs == 0
+    #[msg("No scheduled buy is configured for this player")]
+    ScheduledBuyNotConfigured,
+
+    /// execute_scheduled_buy called before PlayerState::last_scheduled_buy_at + scheduled_buy_interval_secs has elapsed
+    #[msg("This player's scheduled buy is not due yet")]
+    ScheduledBuyNotDue,
+
+    /// execute_scheduled_buy's cost exceeds PlayerState::prepaid_balance_lamports
+    #[msg("Prepaid balance is insufficient to cover this scheduled buy")]
+    InsufficientPrepaidBalance,
+
+    /// reveal_buy's committed keys_to_buy would push GameState::total_keys past
+    /// GlobalConfig::max_keys_per_round — unlike buy_keys, the amount can't be
+    /// silently clamped since it's baked into the commit-reveal hash
+    #[msg("This round has no supply left for the committed purchase amount")]
+    MaxKeysPerRoundExceeded,
+
+    /// withdraw_prepaid's requested lamports exceeds PlayerState::prepaid_balance_lamports
+    #[msg("Withdrawal amount exceeds the prepaid balance")]
+    PrepaidWithdrawalExceedsBalance,
+
+    /// A payout instruction was presented a PlayerState that never ran the
+    /// real `is_new_player` init branch — see `PlayerState::initialized`
+    PlayerStateNotInitialized,
+
+    /// end_round called with GameState::bridge_program set but no bridge_program account supplied
+    #[msg("Cross-chain bridge is enabled for this round but no bridge program account was provided")]
+    MissingBridgeProgram,
+
+    /// The provided bridge_program account doesn't match GameState::bridge_program
+    #[msg("Bridge program does not match the configured attestation program")]
+    BridgeProgramMismatch,
+
+    /// end_round's remaining_accounts (passed through to the bridge attestation CPI) exceed the cap
+    #[msg("Too many accounts passed to the bridge attestation")]
+    TooManyBridgeAccounts,
 }
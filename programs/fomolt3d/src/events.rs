@@ -1,8 +1,20 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{
+    RoundStatus, RoundingBeneficiary, SponsorAllocation, UnclaimedDividendPolicy,
+    VaultFlowDirection, VaultFlowReason,
+};
+
+/// Current event schema version. Bump this whenever a field is added to or
+/// removed from an existing event so indexers can branch on `version`
+/// instead of guessing from payload length.
+pub const EVENT_SCHEMA_VERSION: u8 = 41;
+
 /// Emitted on every key purchase
 #[event]
 pub struct KeysPurchased {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub player: Pubkey,
     pub is_agent: bool,
@@ -13,21 +25,74 @@ pub struct KeysPurchased {
     pub timestamp: i64,
 }
 
+/// Emitted alongside a buy or claim made by a player with `is_agent = true`,
+/// carrying the agent-supplied `strategy_tag` so off-chain analysis can
+/// segment AI-agent behavior from human play without heuristics. `action`
+/// follows the same free-text convention as `BlockedAttempt::action` (e.g.
+/// `"buy_keys"`, `"claim"`). Never emitted for human players.
+#[event]
+pub struct AgentAction {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub strategy_tag: u32,
+    pub action: String,
+    pub timestamp: i64,
+}
+
 /// Emitted when a referral bonus is earned
 #[event]
 pub struct ReferralEarned {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub player: Pubkey,
     pub referrer: Pubkey,
     pub keys_bought: u64,
     pub lamports_spent: u64,
     pub referrer_lamports: u64,
+    /// True while `GlobalConfig::referral_vesting_enabled` is off (the
+    /// default) — `referrer_lamports` was credited straight to
+    /// `PlayerState::referral_earnings_lamports` and is claimable right
+    /// away. False means it went to `PlayerState::pending_referral_earnings_lamports`
+    /// instead, and only becomes claimable once this round ends.
+    pub vested: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted once per purchase with the full fee/referral/pot breakdown in a
+/// single structured record — `KeysPurchased` and `ReferralEarned` cover the
+/// same buy but split across separate events, which is awkward to reconcile
+/// for batched buys or multi-hop redirects into the next round. `referrer`
+/// and `referral_bonus_lamports` are 0/`None` when the buyer has none;
+/// `dust_lamports` is what integer division left over after the three pot
+/// splits below, whether or not this call routes it into a separate
+/// `dust_reserve` or folds it back into `next_round_lamports`.
+#[event]
+pub struct PurchaseSettled {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub lamports_spent: u64,
+    pub protocol_fee_lamports: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_bonus_lamports: u64,
+    pub pot_contribution: u64,
+    pub raffle_pool_lamports: u64,
+    pub winner_pot_lamports: u64,
+    pub dividend_pool_lamports: u64,
+    pub next_round_lamports: u64,
+    pub dust_lamports: u64,
     pub timestamp: i64,
 }
 
 /// Emitted after every key purchase with high-level game state
 #[event]
 pub struct GameUpdated {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub pot_lamports: u64,
     pub total_keys: u64,
@@ -42,6 +107,8 @@ pub struct GameUpdated {
 /// Emitted when a player claims dividends and/or winner prize
 #[event]
 pub struct Claimed {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub player: Pubkey,
     pub dividend_lamports: u64,
@@ -53,15 +120,135 @@ pub struct Claimed {
 /// Emitted when referral earnings are claimed
 #[event]
 pub struct ReferralClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `register_agent_platform` — `player` has attributed itself to
+/// `platform` for the lifetime of its `PlayerState`.
+#[event]
+pub struct AgentPlatformRegistered {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub platform: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted by `buy_keys` when `GameState::agent_platform_fee_share_bps` of a
+/// purchase's house fee is diverted into `PlayerState::agent_platform`'s
+/// `AgentPlatform::pending_earnings_lamports`, off the top like
+/// `ProtocolFeeCollected`. Not emitted when the buyer has no registered
+/// platform or the bps is 0.
+#[event]
+pub struct AgentPlatformFeeAccrued {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
+    pub platform: Pubkey,
     pub player: Pubkey,
     pub lamports: u64,
     pub timestamp: i64,
 }
 
+/// Emitted when an `AgentPlatform`'s accrued fee share is claimed
+#[event]
+pub struct AgentPlatformEarningsClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub platform: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `end_round` when it carves `GameState::top_referrer_bonus_bps`
+/// of the winner pot out into `GameState::top_referrer_bonus_pool` for
+/// whoever led `GameStateExt::top_referrers` this round. Not emitted if the
+/// bps is 0 or nobody earned a referral bonus this round.
+#[event]
+pub struct TopReferrerBonusAllocated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub referrer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `instructions::claim_top_referrer_bonus` pays out
+/// `GameState::top_referrer_bonus_pool` to the round's leading referrer.
+#[event]
+pub struct TopReferrerBonusClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub referrer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `end_round` when it carves `GameState::biggest_buyer_bonus_bps`
+/// of the winner pot out into `GameState::biggest_buyer_bonus_pool` for
+/// whoever made `GameState::max_single_buy_lamports` this round. Not emitted
+/// if the bps is 0 or nobody bought this round.
+#[event]
+pub struct BiggestBuyerBonusAllocated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `instructions::claim_biggest_buyer_bonus` pays out
+/// `GameState::biggest_buyer_bonus_pool` to the round's biggest single buyer.
+#[event]
+pub struct BiggestBuyerBonusClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub buyer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `end_round` when it carves `GameState::biggest_holder_bonus_bps`
+/// of the winner pot out into `GameState::biggest_holder_bonus_pool` for
+/// `GameState::largest_holder`. Not emitted if the bps is 0 or nobody bought
+/// this round.
+#[event]
+pub struct BiggestHolderBonusAllocated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub holder: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `instructions::claim_biggest_holder_bonus` pays out
+/// `GameState::biggest_holder_bonus_pool` to the round's largest key holder.
+#[event]
+pub struct BiggestHolderBonusClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub holder: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
 /// Emitted when a new round starts
 #[event]
 pub struct RoundStarted {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub carry_over_lamports: u64,
     pub timer_end: i64,
@@ -73,6 +260,21 @@ pub struct RoundStarted {
 /// Emitted when protocol fees are collected
 #[event]
 pub struct ProtocolFeeCollected {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub lamports: u64,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted by `buy_keys` when `GameState::frontend_fee_bps` of a purchase's
+/// cost is paid to the buy's caller-supplied `frontend_wallet`, off the top
+/// like `ProtocolFeeCollected`. Not emitted when `frontend_wallet` is omitted.
+#[event]
+pub struct FrontendFeePaid {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub lamports: u64,
     pub recipient: Pubkey,
@@ -82,6 +284,8 @@ pub struct ProtocolFeeCollected {
 /// Emitted when a round concludes (winner claims or empty round closes)
 #[event]
 pub struct RoundConcluded {
+    pub version: u8,
+    pub game_id: u64,
     pub round: u64,
     pub winner: Pubkey,
     pub winner_lamports: u64,
@@ -91,5 +295,855 @@ pub struct RoundConcluded {
     pub next_round_pot: u64,
     pub round_start: i64,
     pub round_end: i64,
+    pub purchase_count: u64,
+    pub gross_volume_lamports: u64,
+    pub max_single_buy_lamports: u64,
+    pub max_single_buyer: Pubkey,
+    /// Round-duration analytics — see `GameState::round_duration_secs` /
+    /// `average_seconds_between_buys` and the `pot_checkpoint_*` fields.
+    pub round_duration_secs: i64,
+    pub timer_extensions_triggered: u64,
+    pub average_seconds_between_buys: i64,
+    pub pot_checkpoint_25_lamports: u64,
+    pub pot_checkpoint_50_lamports: u64,
+    pub pot_checkpoint_75_lamports: u64,
+    /// See `GameState::genesis_config_hash` — lets an archived round be
+    /// provably matched to the exact `GlobalConfig` snapshot it ran under.
+    pub genesis_config_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted the first time a wallet registers a PlayerState (first ever buy_keys call).
+/// Distinguishes brand-new players from returning players re-entering a round,
+/// which a bare `KeysPurchased` cannot do.
+#[event]
+pub struct PlayerRegistered {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub is_agent: bool,
+    pub referrer: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Emitted when a player's referrer is bound, whether at registration or
+/// via a later dedicated instruction.
+#[event]
+pub struct ReferrerSet {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when `set_referrer` switches an already-set referrer to a new
+/// one (as opposed to `ReferrerSet`, which covers both that initial
+/// attachment and this change — this event adds the prior referrer for
+/// indexers that want to track the full chain of changes).
+#[event]
+pub struct ReferrerChanged {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub old_referrer: Pubkey,
+    pub new_referrer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a player claims their proportional dividend share.
+/// Narrower than `Claimed`, which also folds in the winner prize.
+#[event]
+pub struct DividendsClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub dividend_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when admin creates or updates the global config
+#[event]
+pub struct ConfigUpdated {
+    pub version: u8,
+    pub game_id: u64,
+    pub admin: Pubkey,
+    pub base_price_lamports: u64,
+    pub price_increment_lamports: u64,
+    pub timer_extension_secs: i64,
+    pub max_timer_secs: i64,
+    pub winner_bps: u64,
+    pub dividend_bps: u64,
+    pub next_round_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub referral_bonus_bps: u64,
+    pub protocol_wallet: Pubkey,
+    pub early_bird_key_threshold: u64,
+    pub early_bird_multiplier_bps: u64,
+    pub min_purchase_lamports: u64,
+    pub winner_claim_window_secs: i64,
+    pub final_hour_pot_threshold_lamports: u64,
+    pub final_hour_shrink_interval_keys: u64,
+    pub pot_milestone_interval_lamports: u64,
+    pub pot_milestone_bonus_keys: u64,
+    pub promo_keys_cap_per_round: u64,
+    pub transfers_enabled: bool,
+    pub wrapped_keys_enabled: bool,
+    pub keeper_fee_lamports: u64,
+    pub purchase_history_enabled: bool,
+    pub time_weighted_dividends_enabled: bool,
+    pub hook_program: Pubkey,
+    pub referral_earnings_cap_lamports_per_round: u64,
+    pub referral_decay_threshold_lamports: u64,
+    pub referrer_change_cooldown_secs: i64,
+    pub kyc_required: bool,
+    pub kyc_issuer: Pubkey,
+    pub unclaimed_dividend_policy: UnclaimedDividendPolicy,
+    pub dividend_claim_window_secs: i64,
+    pub max_timer_extensions_per_window: u32,
+    pub timer_extension_window_secs: i64,
+    pub approved_stake_vote_account: Pubkey,
+    pub yield_program: Pubkey,
+    pub max_yield_deployment_bps: u64,
+    pub top_referrer_bonus_bps: u64,
+    pub raffle_bps: u64,
+    pub raffle_daily_payout_bps: u64,
+    pub bridge_program: Pubkey,
+    pub max_pot_lamports: u64,
+    pub auto_payout_winner_enabled: bool,
+    pub min_keys_for_timer_extension: u64,
+    pub price_sample_interval_slots: u64,
+    pub rounding_beneficiary: RoundingBeneficiary,
+    pub season_length_rounds: u64,
+    pub season_fee_bps: u64,
+    pub disabled_instructions_bitmask: u64,
+    pub max_keys_per_round: u64,
+    pub referral_vesting_enabled: bool,
+    pub biggest_buyer_bonus_bps: u64,
+    pub biggest_holder_bonus_bps: u64,
+    pub frontend_fee_bps: u64,
+    pub dividend_apr_window_secs: i64,
+    pub min_remaining_secs: i64,
+    pub agent_platform_fee_share_bps: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a round's pot crosses `final_hour_pot_threshold_lamports`
+/// and the timer-extension shrink (see `math::calculate_timer_extension`)
+/// starts applying to this round.
+#[event]
+pub struct FinalHourActivated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub pot_lamports: u64,
+    pub total_keys: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an unclaimed winner_pot is forfeited to the currently active round
+#[event]
+pub struct WinnerForfeited {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub forfeited_winner: Pubkey,
+    pub forfeited_lamports: u64,
+    pub destination_round: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a blocked wallet's buy_keys or claim attempt is rejected
+#[event]
+pub struct BlockedAttempt {
+    pub version: u8,
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub action: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin adds or removes a wallet from the blocklist
+#[event]
+pub struct BlocklistUpdated {
+    pub version: u8,
+    pub game_id: u64,
+    pub admin: Pubkey,
+    pub wallet: Pubkey,
+    pub blocked: bool,
+    pub allow_claim: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a purchase pushes `pot_lamports` across one or more
+/// `pot_milestone_interval_lamports` boundaries. `milestone_number` is the
+/// highest milestone reached (e.g. `3` for the 300 SOL mark); a single large
+/// buy can skip several at once, in which case only the highest is reported.
+#[event]
+pub struct MilestoneReached {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub milestone_number: u64,
+    pub pot_lamports: u64,
+    pub bonus_keys_granted: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buy leaves `timer_end` where it was, either because the
+/// buyer has already hit `GlobalConfig::max_timer_extensions_per_window` or
+/// because the buy is below `GlobalConfig::min_keys_for_timer_extension` —
+/// the keys still land, this just records that the timer stayed put.
+#[event]
+pub struct TimerExtensionCapped {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub timer_extensions_in_window: u32,
+    pub timer_end: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner authorizes a delegate session for buy_keys_via_session
+#[event]
+pub struct SessionCreated {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub spend_limit_lamports: u64,
+    pub expiry_unix_ts: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a player closes their PlayerState and reclaims its rent
+#[event]
+pub struct PlayerStateClosed {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub rent_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `consolidate_referral_earnings` forwards a player's
+/// unclaimed referral backing from a stale round's vault into the
+/// currently active round's vault.
+#[event]
+pub struct ReferralObligationConsolidated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub destination_round: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a player changes a `PlayerState` preference via `set_preferences`
+#[event]
+pub struct PreferencesUpdated {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub auto_compound: bool,
+    pub payout_address: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Emitted when `commit_buy` locks a budget and a purchase commitment
+#[event]
+pub struct BuyCommitted {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub buyer: Pubkey,
+    pub commit_slot: u64,
+    pub total_keys_at_commit: u64,
+    pub budget_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `reveal_buy` successfully settles a prior commitment,
+/// alongside the usual `KeysPurchased`/`GameUpdated`. Carries the
+/// commit-to-reveal-specific accounting that those two don't: how much of
+/// the escrowed budget went unused and was refunded.
+#[event]
+pub struct BuyRevealed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub buyer: Pubkey,
+    pub keys_bought: u64,
+    pub lamports_spent: u64,
+    pub refunded_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `claim` reinvests a player's dividend share into keys of
+/// the currently active round instead of paying it out as SOL (see
+/// `PlayerState::auto_compound`). `cash_out_lamports` is the dust left over
+/// after buying as many whole keys as the dividend share affords.
+#[event]
+pub struct DividendsCompounded {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub dividend_lamports: u64,
+    pub destination_round: u64,
+    pub keys_bought: u64,
+    pub cash_out_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `claim_to_stake` once the payout has been delegated to
+/// `vote_account` instead of cashed out — see `instructions::claim_to_stake`.
+#[event]
+pub struct ClaimedToStake {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub dividend_lamports: u64,
+    pub winner_lamports: u64,
+    pub total_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `deploy_vault_yield` once the admin has CPI'd a slice of the
+/// vault's idle balance out to `GlobalConfig::yield_program` — see
+/// `instructions::deploy_vault_yield`.
+#[event]
+pub struct VaultYieldDeployed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub admin: Pubkey,
+    pub yield_program: Pubkey,
+    pub lamports_deployed: u64,
+    pub total_deployed_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `unwind_vault_yield` once previously-deployed principal has
+/// been CPI'd back into the vault — see `instructions::unwind_vault_yield`.
+#[event]
+pub struct VaultYieldUnwound {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub admin: Pubkey,
+    pub yield_program: Pubkey,
+    pub lamports_received: u64,
+    pub remaining_deployed_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `grant_promo_keys` mints admin-funded dividend-bearing keys
+/// to a player. `lamports_deposited` is what the admin transferred into the
+/// vault to back those keys' pot/dividend obligations — see
+/// `instructions::grant_promo_keys`.
+#[event]
+pub struct PromoGranted {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub admin: Pubkey,
+    pub player: Pubkey,
+    pub keys_granted: u64,
+    pub lamports_deposited: u64,
+    pub promo_keys_granted_this_round: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `transfer_keys` moves keys (and their proportional dividend
+/// weight) from one player's `PlayerState` to another's within the same
+/// round — see `instructions::transfer_keys`.
+#[event]
+pub struct KeysTransferred {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub keys_transferred: u64,
+    pub weight_transferred: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `init_key_mint` creates the round's wrapped-key SPL mint —
+/// see `instructions::init_key_mint`.
+#[event]
+pub struct KeyMintInitialized {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub key_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when `wrap_keys` moves keys out of a player's `PlayerState` and
+/// mints the equivalent amount of this round's SPL token — see
+/// `instructions::wrap_keys`.
+#[event]
+pub struct KeysWrapped {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub keys_wrapped: u64,
+    pub weight_wrapped: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `unwrap_keys` burns wrapped SPL tokens and restores the
+/// equivalent keys (and pro-rata dividend weight) into a `PlayerState` —
+/// see `instructions::unwrap_keys`.
+#[event]
+pub struct KeysUnwrapped {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub keys_unwrapped: u64,
+    pub weight_unwrapped: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted on every `GameState::transition_status` call, alongside whatever
+/// action-specific event (e.g. `RoundConcluded`, `WinnerForfeited`) already
+/// describes what triggered it. Lets a keeper/indexer drive the round
+/// lifecycle off one generic signal instead of inferring state from several
+/// narrower events.
+#[event]
+pub struct RoundStatusChanged {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub from: RoundStatus,
+    pub to: RoundStatus,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin tops up a game's `KeeperBudget` vault via
+/// `fund_keeper_budget`.
+#[event]
+pub struct KeeperBudgetFunded {
+    pub version: u8,
+    pub game_id: u64,
+    pub admin: Pubkey,
+    pub lamports: u64,
+    pub new_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `end_round` reimburses its caller out of the `KeeperBudget`
+/// vault for cranking a round's Active -> Ended transition. `lamports` may
+/// be less than `GameState::keeper_fee_lamports` (or zero) if the budget
+/// couldn't cover the full configured fee.
+#[event]
+pub struct KeeperReimbursed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub keeper: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `init_player_history` creates a player's `PlayerHistory` PDA.
+#[event]
+pub struct PlayerHistoryInitialized {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when `record_dividend_merkle_root` sets `GameState::dividend_merkle_root`.
+#[event]
+pub struct DividendMerkleRootRecorded {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when `claim_with_proof` pays out a leaf of `GameState::dividend_merkle_root`.
+#[event]
+pub struct DividendClaimedViaProof {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub dividend_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `set_spend_limit` changes a player's self-imposed daily
+/// spend cap — either immediately (first set, or lowering an existing cap)
+/// or by scheduling a raise for `effective_at`, per
+/// `PlayerState::SPEND_LIMIT_INCREASE_DELAY_SECS`.
+#[event]
+pub struct SpendLimitUpdated {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub previous_limit_lamports_per_day: u64,
+    pub new_limit_lamports_per_day: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `issue_kyc_credential` approves a wallet for a KYC-gated game
+#[event]
+pub struct KycCredentialIssued {
+    pub version: u8,
+    pub game_id: u64,
+    pub issuer: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when `sweep_dust_reserve` withdraws a round's accumulated
+/// `GameState::dust_reserve` to the protocol wallet.
+#[event]
+pub struct DustReserveSwept {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub admin: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `claim`/`claim_and_roll` pays out the round's winner prize.
+/// Narrower than `Claimed`, which also folds in the winner's dividend share
+/// — lets an indexer reconstruct winner payouts without filtering out the
+/// dividend-only claims that make up most `Claimed` events.
+#[event]
+pub struct WinnerPaid {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub winner: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted alongside `RoundStarted` when a round's carry-over is forwarded
+/// into a newly created round's vault. Narrower than `RoundStarted`, which
+/// also carries round-config metadata that's irrelevant to an indexer only
+/// interested in tracking where a round's lamports came from.
+#[event]
+pub struct NextRoundSeeded {
+    pub version: u8,
+    pub game_id: u64,
+    pub source_round: u64,
+    pub round: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `buy_keys`/`buy_keys_batch` when a purchase arrives after
+/// `timer_end` and is not redirected into a successor round — either
+/// because the caller didn't supply one, or the buyer didn't qualify to
+/// enter it. Narrower than `RoundConcluded`, which fires for every
+/// round-ending cause and carries full round stats; this just tells the
+/// buyer their specific attempt didn't land, since the instruction still
+/// returns `Ok` to preserve the round's `Active` -> `Ended` transition.
+#[event]
+pub struct BuyRejectedRoundEnded {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub buyer: Pubkey,
+    pub attempted_keys: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `sweep_unclaimed_dividends` acts on a round's
+/// `unclaimed_dividend_policy`. `lamports` is 0 for `Strand` (nothing moves,
+/// this just marks the round as settled against future sweeps) and the
+/// swept amount for `RollToNextRound`/`ToProtocol`. `destination_round` is
+/// only meaningful for `RollToNextRound`.
+#[event]
+pub struct UnclaimedDividendsSwept {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub policy: UnclaimedDividendPolicy,
+    pub lamports: u64,
+    pub destination_round: Option<u64>,
+    pub timestamp: i64,
+}
+
+/// Emitted by `sponsor_pot` — a permissionless top-up of one of the round's
+/// pots, crediting no keys to the sponsor.
+#[event]
+pub struct PotSponsored {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub sponsor: Pubkey,
+    pub lamports: u64,
+    pub allocation: SponsorAllocation,
+    pub timestamp: i64,
+}
+
+/// Emitted by `simulate_strategy` — the projected outcome of buying
+/// `keys_schedule` against the round's current state, computed with no
+/// account mutation. Agents read this back off the simulated transaction's
+/// logs rather than any returned account state.
+#[event]
+pub struct StrategySimulated {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub total_keys_bought: u64,
+    pub total_cost_lamports: u64,
+    pub projected_timer_end: i64,
+    pub projected_total_dividend_pool: u64,
+    pub projected_caller_dividend_share_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `register_keeper` when a new bonded `KeeperState` is created.
+#[event]
+pub struct KeeperRegistered {
+    pub version: u8,
+    pub game_id: u64,
+    pub keeper: Pubkey,
+    pub bond_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `slash_keeper` — admin has forfeited part of a keeper's bond
+/// to `GlobalConfig::protocol_wallet` for misbehavior.
+#[event]
+pub struct KeeperSlashed {
+    pub version: u8,
+    pub game_id: u64,
+    pub keeper: Pubkey,
+    pub lamports: u64,
+    pub remaining_bond_lamports: u64,
+    pub slash_count: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted by `unregister_keeper` — the keeper voluntarily exited, closing
+/// `KeeperState` and reclaiming whatever bond remained.
+#[event]
+pub struct KeeperUnregistered {
+    pub version: u8,
+    pub game_id: u64,
+    pub keeper: Pubkey,
+    pub returned_bond_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::record_raffle_snapshot` when it records a new
+/// day's `RaffleSnapshot`, committing to a Merkle root over every player's
+/// weight range at that instant.
+#[event]
+pub struct RaffleSnapshotRecorded {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub day_index: u64,
+    pub merkle_root: [u8; 32],
+    pub total_weight: u64,
+    pub prize_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::draw_raffle_ticket` when it settles
+/// `RaffleSnapshot::winning_ticket` for a given day.
+#[event]
+pub struct RaffleDrawn {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub day_index: u64,
+    pub winning_ticket: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::claim_raffle_prize` when it pays out a day's
+/// `RaffleSnapshot::prize_lamports` to the winning key holder.
+#[event]
+pub struct RaffleClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub day_index: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::cancel_round` when an admin freezes a
+/// misconfigured round and folds its pot buckets into
+/// `GameState::refund_pool_lamports`.
+#[event]
+pub struct RoundCancelled {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub refund_pool_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::refund` when a player claims back their
+/// `PlayerState::contributed_lamports` from a cancelled round.
+#[event]
+pub struct RefundClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::end_round` alongside `RoundConcluded` when
+/// `GameState::bridge_program` is set and the cross-chain conclusion
+/// attestation CPI succeeds — see `GlobalConfig::bridge_program`.
+#[event]
+pub struct RoundConcludedBridged {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub bridge_program: Pubkey,
+    pub winner: Pubkey,
+    pub winner_lamports: u64,
+    pub pot_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::propose_player_migration` when an admin starts
+/// the timelock to move a `PlayerState` to a new wallet.
+#[event]
+pub struct PlayerMigrationProposed {
+    pub version: u8,
+    pub game_id: u64,
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub admin: Pubkey,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::execute_player_migration` once the new wallet
+/// has claimed the old `PlayerState`'s balances and referral relationships.
+#[event]
+pub struct PlayerMigrationExecuted {
+    pub version: u8,
+    pub game_id: u64,
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub keys: u64,
+    pub dividend_weight: u64,
+    pub referral_earnings_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted whenever a `(slot, total_keys, price)` sample is appended to a
+/// round's `PriceHistory` ring buffer — by `instructions::buy_keys` on a
+/// qualifying buy, or by the permissionless `instructions::record_sample`
+/// crank during a quiet period. See `GlobalConfig::price_sample_interval_slots`.
+#[event]
+pub struct PriceSampleRecorded {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub slot: u64,
+    pub total_keys: u64,
+    pub price_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by the permissionless `instructions::settle_season` once a
+/// season's final round has concluded and its leaderboard ranks are fixed.
+#[event]
+pub struct SeasonSettled {
+    pub version: u8,
+    pub game_id: u64,
+    pub season_id: u64,
+    pub pool_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::claim_season_prize` for each payable
+/// leaderboard rank claimed.
+#[event]
+pub struct SeasonPrizeClaimed {
+    pub version: u8,
+    pub game_id: u64,
+    pub season_id: u64,
+    pub player: Pubkey,
+    pub rank: u8,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted alongside every lamport movement into or out of a round's vault —
+/// a uniform ledger auditors can reconstruct the vault's full flow history
+/// from, instead of correlating the heterogeneous events (`Claimed`,
+/// `ReferralClaimed`, `DustReserveSwept`, ...) each instruction already emits for
+/// its own purposes. `counterparty` is the other side of the transfer (the
+/// player, the protocol wallet, the destination round's vault, ...).
+#[event]
+pub struct VaultFlow {
+    pub version: u8,
+    pub game_id: u64,
+    pub round: u64,
+    pub direction: VaultFlowDirection,
+    pub reason: VaultFlowReason,
+    pub lamports: u64,
+    pub counterparty: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::deposit_prepaid` when a player tops up their
+/// `PlayerState::prepaid_balance_lamports` vault.
+#[event]
+pub struct PrepaidDeposited {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub new_balance_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::set_scheduled_buy` whenever a player configures
+/// or disables their recurring `execute_scheduled_buy` crank.
+#[event]
+pub struct ScheduledBuyConfigured {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub keys_per_buy: u64,
+    pub interval_secs: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `instructions::withdraw_prepaid` when a player pulls lamports
+/// back out of their `PlayerState::prepaid_balance_lamports` vault.
+#[event]
+pub struct PrepaidWithdrawn {
+    pub version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub new_balance_lamports: u64,
     pub timestamp: i64,
 }
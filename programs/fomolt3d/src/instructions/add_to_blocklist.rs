@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::BlocklistUpdated;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey, allow_claim: bool)]
+pub struct AddToBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + BlockEntry::SPACE,
+        seeds = [b"blocked", config.game_id.to_le_bytes().as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub block_entry: Account<'info, BlockEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_add_to_blocklist(
+    ctx: Context<AddToBlocklist>,
+    wallet: Pubkey,
+    allow_claim: bool,
+) -> Result<()> {
+    let entry = &mut ctx.accounts.block_entry;
+    let clock = Clock::get()?;
+
+    entry.game_id = ctx.accounts.config.game_id;
+    entry.wallet = wallet;
+    entry.blocked_at = clock.unix_timestamp;
+    entry.allow_claim = allow_claim;
+    entry.bump = ctx.bumps.block_entry;
+
+    emit!(BlocklistUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: entry.game_id,
+        admin: ctx.accounts.admin.key(),
+        wallet,
+        blocked: true,
+        allow_claim,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
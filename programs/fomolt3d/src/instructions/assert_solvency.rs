@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::state::*;
+
+/// Read-only check for cranks/monitors: verifies the vault holds enough
+/// lamports to cover every obligation currently tracked on GameState, and
+/// that the vault's live balance exactly matches the cumulative
+/// `vault_lamports_in` / `vault_lamports_out` counters. Does not mutate any
+/// state — errors with `VaultInsolvent` if the vault has fallen short of its
+/// obligations, or `VaultAccountingMismatch` if the tracked in/out counters
+/// have drifted from the vault's actual balance (a leak).
+#[derive(Accounts)]
+pub struct AssertSolvency<'info> {
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+}
+
+pub fn handle_assert_solvency(ctx: Context<AssertSolvency>) -> Result<()> {
+    let game = &ctx.accounts.game_state;
+
+    let obligations = game.pending_obligations()?;
+
+    require!(
+        ctx.accounts.vault.lamports() >= obligations,
+        FomoltError::VaultInsolvent
+    );
+
+    let expected_balance = game
+        .vault_lamports_in
+        .checked_sub(game.vault_lamports_out)
+        .ok_or(FomoltError::Overflow)?;
+    require!(
+        ctx.accounts.vault.lamports() == expected_balance,
+        FomoltError::VaultAccountingMismatch
+    );
+
+    Ok(())
+}
@@ -1,8 +1,17 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
 use anchor_lang::system_program;
 
 use crate::errors::FomoltError;
-use crate::events::{GameUpdated, KeysPurchased, ProtocolFeeCollected, ReferralEarned, RoundConcluded};
+use crate::events::{
+    AgentAction, AgentPlatformFeeAccrued, BlockedAttempt, BuyRejectedRoundEnded, Claimed,
+    DividendsClaimed, FinalHourActivated, FrontendFeePaid, GameUpdated, KeeperReimbursed,
+    KeysPurchased, MilestoneReached, PlayerRegistered, PriceSampleRecorded, ProtocolFeeCollected,
+    PurchaseSettled, ReferralEarned, ReferrerSet, RoundConcluded, RoundStatusChanged,
+    TimerExtensionCapped, VaultFlow, WinnerPaid,
+};
+use crate::logic;
 use crate::math;
 use crate::state::*;
 
@@ -11,10 +20,25 @@ pub struct BuyKeys<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    /// Read-only pointer to the current round — see `GlobalConfig::latest_round`.
+    /// Lets a caller who only knows the config PDA (deterministic from
+    /// `game_id`) derive `game_state`'s address themselves instead of
+    /// scanning rounds for the active one. The `game_state` constraint below
+    /// allows either the current round or the one just behind it, since a
+    /// buy racing a fresh `start_new_round` legitimately targets the
+    /// just-ended round and relies on `next_game_state` to redirect it —
+    /// anything staler than that is rejected.
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
     #[account(
         mut,
-        seeds = [b"game", game_state.round.to_le_bytes().as_ref()],
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
         bump = game_state.bump,
+        constraint = (game_state.round == config.latest_round || game_state.round + 1 == config.latest_round) @ FomoltError::StaleRound,
     )]
     pub game_state: Account<'info, GameState>,
 
@@ -22,11 +46,21 @@ pub struct BuyKeys<'info> {
         init_if_needed,
         payer = buyer,
         space = 8 + PlayerState::SPACE,
-        seeds = [b"player", buyer.key().as_ref()],
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
         bump,
     )]
     pub player_state: Account<'info, PlayerState>,
 
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
     /// Game vault PDA that holds SOL
     #[account(
         mut,
@@ -35,6 +69,134 @@ pub struct BuyKeys<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Indexer-friendly mirror of this round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        mut,
+        seeds = [b"snapshot", game_state.key().as_ref()],
+        bump = game_snapshot.bump,
+    )]
+    pub game_snapshot: Account<'info, GameSnapshot>,
+
+    /// Append-only companion to `game_state` for per-round data that isn't
+    /// worth growing `GameState::SPACE` for — see `GameStateExt`. Created
+    /// lazily on whichever buy first crosses a pot milestone this round,
+    /// unlike `game_snapshot` above which always exists from round start.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + GameStateExt::SPACE,
+        seeds = [b"game_ext", game_state.key().as_ref()],
+        bump,
+    )]
+    pub game_state_ext: Account<'info, GameStateExt>,
+
+    /// Ring buffer of recent `(slot, total_keys, price)` samples for this
+    /// round — see `PriceHistory`. Created lazily on whichever buy first
+    /// qualifies to record a sample (`GlobalConfig::price_sample_interval_slots`),
+    /// same as `game_state_ext` above; the permissionless `record_sample`
+    /// crank also touches it.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PriceHistory::SPACE,
+        seeds = [b"price_history", game_state.key().as_ref()],
+        bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    /// Page of this round's append-only holder registry — see `HolderIndex`.
+    /// Seeds derive `page` from `game_state.total_players /
+    /// HolderIndex::PAGE_CAPACITY` as of this buy, so every page after the
+    /// first is only ever created once its predecessor is full; created
+    /// lazily on whichever buy first registers a page's worth of new
+    /// holders, same as `price_history` above.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + HolderIndex::SPACE,
+        seeds = [
+            b"holder_index",
+            game_state.key().as_ref(),
+            (game_state.total_players / HolderIndex::PAGE_CAPACITY as u32).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub holder_index: Account<'info, HolderIndex>,
+
+    /// Aggregates volume/wins across `GlobalConfig::season_length_rounds`
+    /// consecutive rounds — see `Season`. Seeds derive `season_id` from
+    /// `game_state.current_season_id()`, so every round in the same season
+    /// resolves to the same PDA; created lazily on whichever buy first
+    /// lands in it, same as `price_history` above. Populated unconditionally
+    /// (even when the season meta-game is disabled) since its seeds must be
+    /// fully derivable client-side; the handler only credits/funds it when
+    /// `game_state.season_length_rounds > 0`.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + Season::SPACE,
+        seeds = [
+            b"season",
+            game_state.game_id.to_le_bytes().as_ref(),
+            game_state.current_season_id().to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    /// Vault holding `season.pool_lamports`, paid out by
+    /// `instructions::claim_season_prize` once `settle_season` runs.
+    #[account(
+        mut,
+        seeds = [b"season_vault", season.key().as_ref()],
+        bump,
+    )]
+    pub season_vault: SystemAccount<'info>,
+
+    /// Keeper reimbursement vault for this game lineage — pays out the same
+    /// bounty `end_round` pays an explicit keeper, since a buy that arrives
+    /// after `timer_end` cranks the exact same `Active` -> `Ended`
+    /// transition as a matter of course.
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", game_state.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub keeper_budget: SystemAccount<'info>,
+
+    /// Required only to redirect a buy that arrived after `timer_end` into
+    /// the round that follows — the currently active successor to
+    /// `game_state`. Left out of `seeds` (unlike `game_state` above) because
+    /// it can be any round number, not one derivable from already-known
+    /// data; validated manually in the handler instead, the same way
+    /// `claim`'s `current_game_state` validates its own cross-round target.
+    #[account(mut)]
+    pub next_game_state: Option<Account<'info, GameState>>,
+
+    /// Vault for `next_game_state`, validated manually against it in the handler.
+    #[account(mut)]
+    pub next_vault: Option<SystemAccount<'info>>,
+
+    /// Snapshot for `next_game_state`, validated manually against it in the handler.
+    #[account(mut)]
+    pub next_game_snapshot: Option<Account<'info, GameSnapshot>>,
+
+    /// Required only when `player_state.current_round` still points at an
+    /// already-concluded round — settles that round's dividend/winner claim
+    /// in cash before this purchase proceeds, sparing a separate
+    /// `claim_dividends`/`claim_winner` transaction. See
+    /// `FomoltError::MustClaimPreviousRound`. Left out of `seeds` (unlike
+    /// `game_state` above) for the same reason as `next_game_state`:
+    /// validated manually in the handler instead.
+    /// CHECK: Validated manually in handler (game_id + round match)
+    #[account(mut)]
+    pub prior_game_state: Option<Account<'info, GameState>>,
+
+    /// Vault for `prior_game_state`, validated manually against it in the handler.
+    #[account(mut)]
+    pub prior_vault: Option<SystemAccount<'info>>,
+
     /// Protocol fee recipient wallet
     /// CHECK: Validated against game_state.protocol_wallet
     #[account(
@@ -53,20 +215,297 @@ pub struct BuyKeys<'info> {
     #[account(mut)]
     pub referrer_wallet: Option<UncheckedAccount<'info>>,
 
+    /// Optional referrer's lifetime stats — credited with the referral bonus earned.
+    /// CHECK: Validated manually in handler (PDA derivation + referrer match)
+    #[account(mut)]
+    pub referrer_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Optional frontend/agent-platform fee destination — caller-supplied,
+    /// paid `GameState::frontend_fee_bps` off the top of `cost` when present.
+    /// CHECK: Caller-supplied fee destination; no PDA relationship to validate.
+    #[account(mut)]
+    pub frontend_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Required only when `player_state.agent_platform` is set — the
+    /// registered marketplace's `AgentPlatform`, credited
+    /// `GameState::agent_platform_fee_share_bps` of this buy's house fee.
+    /// Validated manually in the handler against `player_state.agent_platform`.
+    #[account(mut)]
+    pub agent_platform: Option<Account<'info, AgentPlatform>>,
+
+    /// Always the canonical `[b"blocked", game_id, buyer]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Required only when `GameState::kyc_required` is set — the buyer's
+    /// `KycCredential` PDA issued via `issue_kyc_credential`.
+    /// CHECK: Validated manually in handler (PDA derivation against buyer)
+    pub kyc_credential: Option<Account<'info, KycCredential>>,
+
+    /// Optional: present only when `GameState::purchase_history_enabled` and
+    /// the buyer has already created their `PlayerHistory` via
+    /// `init_player_history`. CHECK: Validated manually in handler (PDA
+    /// derivation against buyer)
+    #[account(mut)]
+    pub player_history: Option<Account<'info, PlayerHistory>>,
+
+    /// Optional: present only when `GameState::hook_program` is set. CPI-
+    /// notified after a successful purchase — see `GlobalConfig::hook_program`.
+    /// CHECK: Validated against game_state.hook_program in the handler; never
+    /// deserialized, only invoked as a program id.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Opt-in, created only when the caller supplies it — a fresh per-purchase
+    /// proof of this single buy's fee/split breakdown, retrievable on-chain
+    /// long after `GameState` has moved past this round. See `BuyReceipt`.
+    /// Seeds include `game_state.purchase_count` as of this buy, so skipping
+    /// this account costs a normal buyer nothing beyond the rent they'd
+    /// otherwise pay if they opted in.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BuyReceipt::SPACE,
+        seeds = [
+            b"receipt",
+            game_state.key().as_ref(),
+            buyer.key().as_ref(),
+            game_state.purchase_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt: Option<Account<'info, BuyReceipt>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool) -> Result<()> {
+/// Cap on `ctx.remaining_accounts` forwarded to the partner hook CPI — keeps
+/// a misconfigured or malicious hook call from blowing the instruction's
+/// compute budget with an unbounded account list.
+const MAX_HOOK_ACCOUNTS: usize = 4;
+
+/// Anchor instruction sighash for `notify_purchase` (first 8 bytes of
+/// sha256("global:notify_purchase")) — lets a partner hook be a normal
+/// Anchor program exposing a `notify_purchase(round: u64, buyer: Pubkey,
+/// keys: u64, cost: u64)` instruction.
+const HOOK_NOTIFY_DISCRIMINATOR: [u8; 8] = [68, 225, 110, 193, 246, 244, 41, 189];
+
+pub fn handle_buy_keys<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+    keys_to_buy: u64,
+    is_agent: bool,
+    strategy_tag: u32,
+) -> Result<()> {
+    handle_buy_keys_core(ctx, keys_to_buy, is_agent, strategy_tag)
+}
+
+/// Same accounts and core logic as `buy_keys`, but takes a lamport budget
+/// instead of a key count — buys as many keys as the budget affords without
+/// exceeding it. Lets agents and client quote flows say "spend up to X SOL"
+/// instead of pre-computing the inverse bonding curve themselves.
+pub fn handle_buy_keys_with_budget<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+    budget_lamports: u64,
+    is_agent: bool,
+    strategy_tag: u32,
+) -> Result<()> {
+    let keys_to_buy = math::calculate_max_keys(
+        budget_lamports,
+        ctx.accounts.game_state.total_keys,
+        ctx.accounts.game_state.base_price_lamports,
+        ctx.accounts.game_state.price_increment_lamports,
+    )?;
+    require!(keys_to_buy > 0, FomoltError::NoKeysToBuy);
+    handle_buy_keys_core(ctx, keys_to_buy, is_agent, strategy_tag)
+}
+
+/// Cap on `amounts.len()` for `buy_keys_batch` — bounds deserialization and
+/// summation cost for an unbounded client-supplied vector.
+const MAX_BATCH_PURCHASES: usize = 32;
+
+/// Same accounts and core logic as `buy_keys`, but takes a list of tranche
+/// sizes instead of one key count — lets an agent that would otherwise send
+/// several sequential buy transactions (e.g. for a dollar-cost-average
+/// schedule) collapse them into one instruction. `calculate_cost` and
+/// `calculate_key_weight` are both closed-form over a contiguous key range,
+/// so summing the tranches up front and running the core purchase once
+/// against the total is mathematically identical to running it once per
+/// tranche — and it yields the single summed transfer and single aggregated
+/// event a real per-tranche loop could not.
+pub fn handle_buy_keys_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+    amounts: Vec<u64>,
+    is_agent: bool,
+    strategy_tag: u32,
+) -> Result<()> {
+    require!(!amounts.is_empty(), FomoltError::EmptyBatch);
+    require!(
+        amounts.len() <= MAX_BATCH_PURCHASES,
+        FomoltError::TooManyBatchPurchases
+    );
+
+    let mut keys_to_buy: u64 = 0;
+    for amount in amounts {
+        keys_to_buy = keys_to_buy
+            .checked_add(amount)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    handle_buy_keys_core(ctx, keys_to_buy, is_agent, strategy_tag)
+}
+
+fn handle_buy_keys_core<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+    keys_to_buy: u64,
+    is_agent: bool,
+    strategy_tag: u32,
+) -> Result<()> {
     let game = &mut ctx.accounts.game_state;
     let player = &mut ctx.accounts.player_state;
     let clock = Clock::get()?;
 
-    // --- Auto-end check: if timer expired, end the round and return Ok (no-op) ---
+    // --- Auto-end check: if timer expired, end the round, pay the buyer the
+    // same crank bounty `end_round` pays an explicit keeper, and either
+    // redirect the attempted buy into the next round (if one was supplied
+    // and the buyer cleanly qualifies to enter it) or tell them plainly
+    // that it didn't go through, instead of the previous silent `Ok(())`
+    // no-op that swallowed both the fee and the buyer's intent. ---
     if clock.unix_timestamp >= game.timer_end {
-        if game.active {
-            game.active = false;
+        let game_id = game.game_id;
+        let ended_round = game.round;
+        let keeper_budget_bump = ctx.bumps.keeper_budget;
+
+        // --- Decide redirect eligibility up front, entirely from reads —
+        // nothing below this point may fail once we start mutating state
+        // (the round transition and keeper bounty), since a Solana
+        // instruction error rolls back every account change it made,
+        // bounty included. Computing the purchase cost and affordability
+        // here, before anything is touched, means the only way the later
+        // redirect itself can fail is the same checked-arithmetic overflow
+        // every other instruction in this program already accepts as a
+        // practically-unreachable edge case. ---
+        let next_round = ended_round.checked_add(1).ok_or(FomoltError::Overflow)?;
+
+        // --- Apply the same time-based, infallible state transitions the
+        // main path always runs on every buy attempt regardless of outcome,
+        // so the eligibility gates just below read up-to-date values. ---
+        player.apply_pending_spend_limit(clock.unix_timestamp);
+        player.maybe_reset_spend_window(clock.unix_timestamp);
+
+        let redirect_purchase: Option<(u64, u64)> = if keys_to_buy == 0 {
+            None
+        } else {
+            match (
+                ctx.accounts.next_game_state.as_ref(),
+                ctx.accounts.next_vault.as_ref(),
+                ctx.accounts.next_game_snapshot.as_ref(),
+            ) {
+                (Some(next_game), Some(next_vault), Some(next_snapshot)) => {
+                    let player_qualifies = player.player == Pubkey::default()
+                        || player.current_round == 0
+                        || player.current_round == next_round;
+                    let round_matches = next_game.game_id == game_id
+                        && next_game.round == next_round
+                        && next_game.status == RoundStatus::Active;
+
+                    // --- Same gates the main path enforces on every other
+                    // purchase (blocklist, KYC, self-imposed spend limit) —
+                    // a wallet must not be able to bypass all three simply
+                    // by timing its buy to land after the timer expires. ---
+                    let not_blocked =
+                        BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?.is_none();
+                    let kyc_ok = if next_game.kyc_required {
+                        ctx.accounts.kyc_credential.as_ref().is_some_and(|credential| {
+                            let (expected_pda, _) = Pubkey::find_program_address(
+                                &[
+                                    b"kyc",
+                                    game_id.to_le_bytes().as_ref(),
+                                    ctx.accounts.buyer.key().as_ref(),
+                                ],
+                                ctx.program_id,
+                            );
+                            credential.key() == expected_pda
+                        })
+                    } else {
+                        true
+                    };
+
+                    if player_qualifies && round_matches && not_blocked && kyc_ok {
+                        let (expected_vault, _) = Pubkey::find_program_address(
+                            &[b"vault", next_game.key().as_ref()],
+                            ctx.program_id,
+                        );
+                        let (expected_snapshot, _) = Pubkey::find_program_address(
+                            &[b"snapshot", next_game.key().as_ref()],
+                            ctx.program_id,
+                        );
+                        let pdas_match = next_vault.key() == expected_vault
+                            && next_snapshot.key() == expected_snapshot;
+
+                        // --- Sold-out supply cap: same clamp the main path
+                        // applies below (see the `max_keys_per_round` clamp
+                        // further down this function) so a redirected buy
+                        // can't oversell the next round's supply either. ---
+                        let redirect_keys = if next_game.max_keys_per_round > 0 {
+                            keys_to_buy
+                                .min(next_game.max_keys_per_round.saturating_sub(next_game.total_keys))
+                        } else {
+                            keys_to_buy
+                        };
+
+                        let cost = (pdas_match && redirect_keys > 0)
+                            .then(|| {
+                                math::calculate_cost(
+                                    next_game.total_keys,
+                                    redirect_keys,
+                                    next_game.base_price_lamports,
+                                    next_game.price_increment_lamports,
+                                )
+                                .ok()
+                            })
+                            .flatten();
+
+                        cost.filter(|&cost| {
+                            (next_game.min_purchase_lamports == 0
+                                || cost >= next_game.min_purchase_lamports)
+                                && ctx.accounts.buyer.lamports() >= cost
+                                && (player.spend_limit_lamports_per_day == 0
+                                    || player
+                                        .spend_window_lamports
+                                        .checked_add(cost)
+                                        .is_some_and(|total| {
+                                            total <= player.spend_limit_lamports_per_day
+                                        }))
+                        })
+                        .map(|cost| (cost, redirect_keys))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        };
+
+        if game.status == RoundStatus::Active {
+            game.transition_status(RoundStatus::Ended)?;
+            emit!(RoundStatusChanged {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: ended_round,
+                from: RoundStatus::Active,
+                to: RoundStatus::Ended,
+                timestamp: clock.unix_timestamp,
+            });
             emit!(RoundConcluded {
-                round: game.round,
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: ended_round,
                 winner: game.last_buyer,
                 winner_lamports: game.winner_pot,
                 pot_lamports: game.pot_lamports,
@@ -75,25 +514,752 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
                 next_round_pot: game.next_round_pot,
                 round_start: game.round_start,
                 round_end: game.timer_end,
+                purchase_count: game.purchase_count,
+                gross_volume_lamports: game.gross_volume_lamports,
+                max_single_buy_lamports: game.max_single_buy_lamports,
+                max_single_buyer: game.max_single_buyer,
+                round_duration_secs: game.round_duration_secs(),
+                timer_extensions_triggered: game.timer_extensions_triggered,
+                average_seconds_between_buys: game.average_seconds_between_buys(),
+                pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+                pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+                pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+                genesis_config_hash: game.genesis_config_hash,
                 timestamp: clock.unix_timestamp,
             });
         }
+
+        // --- Pay the buyer for cranking the transition above, capped at
+        // whatever the budget can actually spare ---
+        let rent_exempt_min = Rent::get()?.minimum_balance(0);
+        let available = ctx
+            .accounts
+            .keeper_budget
+            .lamports()
+            .saturating_sub(rent_exempt_min);
+        let reimbursement = game.keeper_fee_lamports.min(available);
+        if reimbursement > 0 {
+            let game_id_bytes = game_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"keeper_budget", game_id_bytes.as_ref(), &[keeper_budget_bump]]];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.keeper_budget.to_account_info(),
+                        to: ctx.accounts.buyer.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                reimbursement,
+            )?;
+        }
+        emit!(KeeperReimbursed {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id,
+            round: ended_round,
+            keeper: ctx.accounts.buyer.key(),
+            lamports: reimbursement,
+            timestamp: clock.unix_timestamp,
+        });
+
+        let (cost, keys_to_buy) = match redirect_purchase {
+            Some(pair) => pair,
+            None => {
+                emit!(BuyRejectedRoundEnded {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id,
+                    round: ended_round,
+                    buyer: ctx.accounts.buyer.key(),
+                    attempted_keys: keys_to_buy,
+                    timestamp: clock.unix_timestamp,
+                });
+                return Ok(());
+            }
+        };
+
+        // --- Debit the self-imposed spend limit's rolling window for the
+        // purchase we're about to execute — mirrors the main path's update
+        // right after its own spend-limit check. ---
+        if player.spend_limit_lamports_per_day > 0 {
+            player.spend_window_lamports = player
+                .spend_window_lamports
+                .checked_add(cost)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        // --- Execute the redirected purchase directly against the next
+        // round. This is a bounded, self-contained purchase (protocol fee
+        // + the usual winner/dividend/next-round split) rather than
+        // falling through into the rest of this function — referral
+        // credit, the partner hook CPI, and purchase-history/milestone
+        // bookkeeping are all skipped, since the context they'd need
+        // (e.g. the referrer the buyer intended) was computed against the
+        // round that just ended, and re-deriving it here buys no extra
+        // safety, only more ways for this already-exceptional path to fail. ---
+        let next_game = ctx.accounts.next_game_state.as_mut().unwrap();
+        let timestamp = clock.unix_timestamp;
+
+        if player.player == Pubkey::default() {
+            player.game_id = next_game.game_id;
+            player.player = ctx.accounts.buyer.key();
+            player.bump = ctx.bumps.player_state;
+            player.initialized = true;
+            player.generation = player.generation.wrapping_add(1);
+            player.claimed_dividends_lamports = 0;
+            player.claimed_referral_earnings_lamports = 0;
+            player.referral_earnings_lamports = 0;
+            player.pending_referral_earnings_lamports = 0;
+            player.keys = 0;
+            player.dividend_weight = 0;
+            player.contributed_lamports = 0;
+            player.referrer = None;
+            player.referrer_set_at = 0;
+            player.referral_earnings_round = 0;
+            player.referral_earnings_this_round_lamports = 0;
+            player.spend_limit_lamports_per_day = 0;
+            player.pending_spend_limit_lamports_per_day = None;
+            player.spend_limit_effective_at = 0;
+            player.spend_window_start = timestamp;
+            player.spend_window_lamports = 0;
+            player.timer_extension_window_start = timestamp;
+            player.timer_extensions_in_window = 0;
+            next_game.total_players = next_game
+                .total_players
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+            emit!(PlayerRegistered {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: next_round,
+                player: ctx.accounts.buyer.key(),
+                is_agent,
+                referrer: None,
+                timestamp,
+            });
+        } else if player.current_round != next_round {
+            // current_round == 0: returning player who already claimed
+            player.keys = 0;
+            player.dividend_weight = 0;
+            player.contributed_lamports = 0;
+            player.current_round = next_round;
+            next_game.total_players = next_game
+                .total_players
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        player.current_round = next_round;
+        player.dividend_weight_seconds = 0;
+        player.dividend_seconds_last_update = timestamp;
+        player.is_agent = is_agent;
+        player.strategy_tag = if is_agent { strategy_tag } else { 0 };
+
+        let stats = &mut ctx.accounts.player_stats;
+        if stats.player == Pubkey::default() {
+            stats.game_id = next_game.game_id;
+            stats.player = ctx.accounts.buyer.key();
+            stats.bump = ctx.bumps.player_stats;
+        }
+
+        let fees = logic::compute_fees(
+            cost,
+            next_game.protocol_fee_bps,
+            next_game.frontend_fee_bps,
+            ctx.accounts.frontend_wallet.is_some(),
+        )?;
+        let house_fee = fees.house_fee;
+        let frontend_fee = fees.frontend_fee;
+        let pot_contribution = fees.after_fee;
+
+        if house_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: ctx.accounts.protocol_wallet.to_account_info(),
+                    },
+                ),
+                house_fee,
+            )?;
+            emit!(ProtocolFeeCollected {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: next_round,
+                lamports: house_fee,
+                recipient: ctx.accounts.protocol_wallet.key(),
+                timestamp,
+            });
+        }
+
+        if frontend_fee > 0 {
+            let frontend_wallet = ctx.accounts.frontend_wallet.as_ref().unwrap();
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: frontend_wallet.to_account_info(),
+                    },
+                ),
+                frontend_fee,
+            )?;
+            emit!(FrontendFeePaid {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: next_round,
+                lamports: frontend_fee,
+                recipient: frontend_wallet.key(),
+                timestamp,
+            });
+        }
+
+        let next_vault = ctx.accounts.next_vault.as_ref().unwrap();
+        if pot_contribution > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: next_vault.to_account_info(),
+                    },
+                ),
+                pot_contribution,
+            )?;
+        }
+
+        let next_game = ctx.accounts.next_game_state.as_mut().unwrap();
+        next_game.vault_lamports_in = next_game
+            .vault_lamports_in
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        if pot_contribution > 0 {
+            emit!(VaultFlow {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: next_round,
+                direction: VaultFlowDirection::In,
+                reason: VaultFlowReason::Buy,
+                lamports: pot_contribution,
+                counterparty: ctx.accounts.buyer.key(),
+                timestamp,
+            });
+        }
+
+        // No raffle cut on this path — see `logic::compute_pot_split`'s doc comment.
+        let committed = next_game
+            .winner_pot
+            .checked_add(next_game.total_dividend_pool)
+            .ok_or(FomoltError::Overflow)?;
+        let split = logic::compute_pot_split(
+            pot_contribution,
+            0,
+            next_game.winner_bps,
+            next_game.dividend_bps,
+            next_game.next_round_bps,
+            committed,
+            next_game.max_pot_lamports,
+        )?;
+        let winner_amount = split.winner_amount;
+        let dividend_amount = split.dividend_amount;
+        let next_round_amount = split.next_round_amount;
+        let split_dust = split.dust;
+        let pot_overflow_amount = split.pot_overflow_amount;
+
+        next_game.winner_pot = next_game
+            .winner_pot
+            .checked_add(winner_amount)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.total_dividend_pool = next_game
+            .total_dividend_pool
+            .checked_add(dividend_amount)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.record_dividend_for_apr_window(timestamp, dividend_amount)?;
+        next_game.next_round_pot = next_game
+            .next_round_pot
+            .checked_add(next_round_amount)
+            .ok_or(FomoltError::Overflow)?;
+        match next_game.rounding_beneficiary {
+            RoundingBeneficiary::Protocol => {
+                next_game.dust_reserve = next_game
+                    .dust_reserve
+                    .checked_add(split_dust)
+                    .ok_or(FomoltError::Overflow)?;
+            }
+            RoundingBeneficiary::WinnerPot => {
+                next_game.winner_pot = next_game
+                    .winner_pot
+                    .checked_add(split_dust)
+                    .ok_or(FomoltError::Overflow)?;
+            }
+            RoundingBeneficiary::DividendPool => {
+                next_game.total_dividend_pool = next_game
+                    .total_dividend_pool
+                    .checked_add(split_dust)
+                    .ok_or(FomoltError::Overflow)?;
+                next_game.record_dividend_for_apr_window(timestamp, split_dust)?;
+            }
+            RoundingBeneficiary::NextRoundPot => {
+                next_game.next_round_pot = next_game
+                    .next_round_pot
+                    .checked_add(split_dust)
+                    .ok_or(FomoltError::Overflow)?;
+            }
+        }
+        next_game.pot_overflow_reserve_lamports = next_game
+            .pot_overflow_reserve_lamports
+            .checked_add(pot_overflow_amount)
+            .ok_or(FomoltError::Overflow)?;
+
+        if next_game.time_weighted_dividends_enabled {
+            next_game.sync_dividend_seconds(timestamp)?;
+        }
+
+        let current_price = math::calculate_cost(
+            next_game.total_keys,
+            1,
+            next_game.base_price_lamports,
+            next_game.price_increment_lamports,
+        )?;
+        next_game.sync_price_cumulative(timestamp, current_price)?;
+
+        let weight_earned = math::calculate_key_weight(
+            next_game.total_keys,
+            keys_to_buy,
+            next_game.early_bird_key_threshold,
+            next_game.early_bird_multiplier_bps,
+        )?;
+        player.dividend_weight = player
+            .dividend_weight
+            .checked_add(weight_earned)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.total_weight = next_game
+            .total_weight
+            .checked_add(weight_earned)
+            .ok_or(FomoltError::Overflow)?;
+
+        player.keys = player.keys.checked_add(keys_to_buy).ok_or(FomoltError::Overflow)?;
+        player.contributed_lamports = player
+            .contributed_lamports
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        player.total_contributed_lamports = player
+            .total_contributed_lamports
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.total_keys = next_game
+            .total_keys
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+        if is_agent {
+            next_game.agent_keys_total = next_game
+                .agent_keys_total
+                .checked_add(keys_to_buy)
+                .ok_or(FomoltError::Overflow)?;
+        } else {
+            next_game.human_keys_total = next_game
+                .human_keys_total
+                .checked_add(keys_to_buy)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        next_game.pot_lamports = next_game
+            .pot_lamports
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.last_buyer = ctx.accounts.buyer.key();
+        next_game.purchase_count = next_game
+            .purchase_count
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+        next_game.gross_volume_lamports = next_game
+            .gross_volume_lamports
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+        if cost > next_game.max_single_buy_lamports {
+            next_game.max_single_buy_lamports = cost;
+            next_game.max_single_buyer = ctx.accounts.buyer.key();
+        }
+        if player.keys > next_game.largest_holder_keys {
+            next_game.largest_holder_keys = player.keys;
+            next_game.largest_holder = ctx.accounts.buyer.key();
+        }
+
+        // --- Round-duration analytics — same accrual as the main path above. ---
+        next_game.buy_interval_seconds_total = next_game
+            .buy_interval_seconds_total
+            .checked_add(timestamp.saturating_sub(next_game.last_buy_timestamp))
+            .ok_or(FomoltError::Overflow)?;
+        next_game.last_buy_timestamp = timestamp;
+
+        let elapsed_secs = timestamp.saturating_sub(next_game.round_start);
+        if !next_game.pot_checkpoint_25_reached && elapsed_secs * 4 >= next_game.max_timer_secs {
+            next_game.pot_checkpoint_25_lamports = next_game.pot_lamports;
+            next_game.pot_checkpoint_25_reached = true;
+        }
+        if !next_game.pot_checkpoint_50_reached && elapsed_secs * 2 >= next_game.max_timer_secs {
+            next_game.pot_checkpoint_50_lamports = next_game.pot_lamports;
+            next_game.pot_checkpoint_50_reached = true;
+        }
+        if !next_game.pot_checkpoint_75_reached && elapsed_secs * 4 >= next_game.max_timer_secs * 3
+        {
+            next_game.pot_checkpoint_75_lamports = next_game.pot_lamports;
+            next_game.pot_checkpoint_75_reached = true;
+        }
+
+        ctx.accounts.player_stats.lifetime_keys_bought = ctx
+            .accounts
+            .player_stats
+            .lifetime_keys_bought
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+        ctx.accounts.player_stats.lifetime_lamports_spent = ctx
+            .accounts
+            .player_stats
+            .lifetime_lamports_spent
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+
+        let next_game = ctx.accounts.next_game_state.as_mut().unwrap();
+        let keys_since_final_hour_start = if next_game.final_hour_active {
+            next_game.total_keys.saturating_sub(next_game.final_hour_start_keys)
+        } else {
+            0
+        };
+        // --- Per-wallet timer-extension cap (same rule as the main path,
+        // see below) ---
+        let mut extends_timer = true;
+        if next_game.max_timer_extensions_per_window > 0 {
+            player.maybe_reset_timer_extension_window(
+                timestamp,
+                next_game.timer_extension_window_secs,
+            );
+            extends_timer =
+                player.timer_extensions_in_window < next_game.max_timer_extensions_per_window;
+        }
+        if extends_timer {
+            next_game.timer_end = math::calculate_timer_extension(
+                timestamp,
+                math::TimerExtensionParams {
+                    extension_secs: next_game.timer_extension_secs,
+                    current_timer_end: next_game.timer_end,
+                    round_start: next_game.round_start,
+                    max_timer_secs: next_game.max_timer_secs,
+                    final_hour_active: next_game.final_hour_active,
+                    keys_since_final_hour_start,
+                    final_hour_shrink_interval_keys: next_game.final_hour_shrink_interval_keys,
+                    min_remaining_secs: next_game.min_remaining_secs,
+                },
+            )?;
+            player.timer_extensions_in_window = player
+                .timer_extensions_in_window
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+            next_game.timer_extensions_triggered = next_game
+                .timer_extensions_triggered
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        emit!(KeysPurchased {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id,
+            round: next_round,
+            player: ctx.accounts.buyer.key(),
+            is_agent: player.is_agent,
+            keys_bought: keys_to_buy,
+            total_player_keys: player.keys,
+            lamports_spent: cost,
+            pot_contribution,
+            timestamp,
+        });
+
+        if player.is_agent {
+            emit!(AgentAction {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id,
+                round: next_round,
+                player: ctx.accounts.buyer.key(),
+                strategy_tag: player.strategy_tag,
+                action: "buy_keys".to_string(),
+                timestamp,
+            });
+        }
+
+        emit!(PurchaseSettled {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id,
+            round: next_round,
+            player: ctx.accounts.buyer.key(),
+            lamports_spent: cost,
+            protocol_fee_lamports: house_fee,
+            referrer: None,
+            referral_bonus_lamports: 0,
+            pot_contribution,
+            raffle_pool_lamports: 0,
+            winner_pot_lamports: winner_amount,
+            dividend_pool_lamports: dividend_amount,
+            next_round_lamports: next_round_amount,
+            dust_lamports: split_dust,
+            timestamp,
+        });
+
+        let next_game = ctx.accounts.next_game_state.as_ref().unwrap();
+        let next_key_price = math::calculate_cost(
+            next_game.total_keys,
+            1,
+            next_game.base_price_lamports,
+            next_game.price_increment_lamports,
+        )
+        .unwrap_or(u64::MAX);
+
+        let snapshot = ctx.accounts.next_game_snapshot.as_mut().unwrap();
+        snapshot.pot_lamports = next_game.pot_lamports;
+        snapshot.total_keys = next_game.total_keys;
+        snapshot.timer_end = next_game.timer_end;
+        snapshot.last_buyer = next_game.last_buyer;
+        snapshot.next_key_price = next_key_price;
+
+        emit!(GameUpdated {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id,
+            round: next_round,
+            pot_lamports: next_game.pot_lamports,
+            total_keys: next_game.total_keys,
+            next_key_price,
+            last_buyer: next_game.last_buyer,
+            timer_end: next_game.timer_end,
+            winner_pot: next_game.winner_pot,
+            next_round_pot: next_game.next_round_pot,
+            timestamp,
+        });
+
         return Ok(());
     }
-    require!(game.active, FomoltError::GameNotActive);
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+    // --- Blocklist check: buying is never allowed for a blocked wallet,
+    // regardless of its allow_claim policy ---
+    if BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?.is_some() {
+        emit!(BlockedAttempt {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            wallet: ctx.accounts.buyer.key(),
+            action: "buy_keys".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+        return err!(FomoltError::WalletBlocked);
+    }
+
+    // --- KYC gate: licensed/compliant rounds require a credential PDA ---
+    if game.kyc_required {
+        let credential = ctx
+            .accounts
+            .kyc_credential
+            .as_ref()
+            .ok_or(FomoltError::KycCredentialRequired)?;
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[b"kyc", game.game_id.to_le_bytes().as_ref(), ctx.accounts.buyer.key().as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            credential.key() == expected_pda,
+            FomoltError::KycCredentialRequired
+        );
+    }
+
+    // --- Auto-claim the prior round's payout when the caller supplies it,
+    // sparing a separate `claim_dividends`/`claim_winner` transaction before
+    // this buy can proceed — see `FomoltError::MustClaimPreviousRound`. Pays
+    // out in cash rather than compounding: unlike `claim`'s auto_compound
+    // path there's no reinvestment math to run, since the claimed cash and
+    // the purchase below settle in the very same instruction. Resets
+    // `player.current_round` to 0 on success so the registration branches
+    // just below treat the buyer as a returning player entering `game` fresh.
+    if player.player != Pubkey::default()
+        && player.current_round != 0
+        && player.current_round != game.round
+    {
+        let prior_game = ctx
+            .accounts
+            .prior_game_state
+            .as_mut()
+            .ok_or(FomoltError::MustClaimPreviousRound)?;
+        require!(prior_game.game_id == game.game_id, FomoltError::GameIdMismatch);
+        require!(
+            prior_game.round == player.current_round,
+            FomoltError::PlayerNotInRound
+        );
+        require!(prior_game.status != RoundStatus::Cancelled, FomoltError::RoundCancelled);
+        require!(prior_game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+        let prior_vault = ctx
+            .accounts
+            .prior_vault
+            .as_ref()
+            .ok_or(FomoltError::MustClaimPreviousRound)?;
+        let prior_key = prior_game.key();
+        let (expected_vault, prior_vault_bump) =
+            Pubkey::find_program_address(&[b"vault", prior_key.as_ref()], ctx.program_id);
+        require!(prior_vault.key() == expected_vault, FomoltError::VaultMismatch);
+
+        let dividend_share = if prior_game.time_weighted_dividends_enabled {
+            let round_end = prior_game.timer_end;
+            prior_game.sync_dividend_seconds(round_end)?;
+            player.sync_dividend_seconds(round_end)?;
+            math::calculate_dividend_share_weighted(
+                player.dividend_weight_seconds,
+                prior_game.total_dividend_pool,
+                prior_game.dividend_weight_seconds_total,
+            )?
+        } else {
+            math::calculate_dividend_share(
+                player.dividend_weight,
+                prior_game.total_dividend_pool,
+                prior_game.total_weight,
+            )?
+        };
+
+        let is_winner =
+            ctx.accounts.buyer.key() == prior_game.last_buyer && !prior_game.winner_claimed();
+        let winner_payout = if is_winner { prior_game.winner_pot } else { 0 };
+        let total_payout = dividend_share
+            .checked_add(winner_payout)
+            .ok_or(FomoltError::Overflow)?;
+
+        if total_payout > 0 {
+            let rent_exempt_min = Rent::get()?.minimum_balance(0);
+            let available = prior_vault.lamports().saturating_sub(rent_exempt_min);
+            require!(available >= total_payout, FomoltError::VaultInsolvent);
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: prior_vault.to_account_info(),
+                        to: ctx.accounts.buyer.to_account_info(),
+                    },
+                    &[&[b"vault", prior_key.as_ref(), &[prior_vault_bump]]],
+                ),
+                total_payout,
+            )?;
+
+            prior_game.vault_lamports_out = prior_game
+                .vault_lamports_out
+                .checked_add(total_payout)
+                .ok_or(FomoltError::Overflow)?;
+            prior_game.total_dividend_claimed_lamports = prior_game
+                .total_dividend_claimed_lamports
+                .checked_add(dividend_share)
+                .ok_or(FomoltError::Overflow)?;
+
+            if is_winner {
+                prior_game.transition_status(RoundStatus::Settled)?;
+                emit!(RoundStatusChanged {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: prior_game.game_id,
+                    round: prior_game.round,
+                    from: RoundStatus::Ended,
+                    to: RoundStatus::Settled,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+
+            let prior_round = prior_game.round;
+            let prior_game_id = prior_game.game_id;
+
+            let stats = &mut ctx.accounts.player_stats;
+            stats.lifetime_dividends_earned = stats
+                .lifetime_dividends_earned
+                .checked_add(dividend_share)
+                .ok_or(FomoltError::Overflow)?;
+            if is_winner {
+                stats.rounds_won = stats.rounds_won.checked_add(1).ok_or(FomoltError::Overflow)?;
+            }
+
+            if dividend_share > 0 {
+                emit!(DividendsClaimed {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: prior_game_id,
+                    round: prior_round,
+                    player: ctx.accounts.buyer.key(),
+                    dividend_lamports: dividend_share,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            if winner_payout > 0 {
+                emit!(WinnerPaid {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: prior_game_id,
+                    round: prior_round,
+                    winner: ctx.accounts.buyer.key(),
+                    lamports: winner_payout,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            emit!(Claimed {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: prior_game_id,
+                round: prior_round,
+                player: ctx.accounts.buyer.key(),
+                dividend_lamports: dividend_share,
+                winner_lamports: winner_payout,
+                total_lamports: total_payout,
+                timestamp: clock.unix_timestamp,
+            });
+            if player.is_agent {
+                emit!(AgentAction {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: prior_game_id,
+                    round: prior_round,
+                    player: ctx.accounts.buyer.key(),
+                    strategy_tag: player.strategy_tag,
+                    action: "claim".to_string(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+
+            player.claimed_dividends_lamports = player
+                .claimed_dividends_lamports
+                .checked_add(dividend_share)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.dividend_weight_seconds = 0;
+        player.dividend_seconds_last_update = clock.unix_timestamp;
+        player.current_round = 0;
+    }
 
     // --- Handle player registration / round entry ---
     let is_new_player = player.player == Pubkey::default();
 
     if is_new_player {
         // First-time player initialization
+        player.game_id = game.game_id;
         player.player = ctx.accounts.buyer.key();
         player.bump = ctx.bumps.player_state;
+        player.initialized = true;
+        player.generation = player.generation.wrapping_add(1);
         player.claimed_dividends_lamports = 0;
         player.claimed_referral_earnings_lamports = 0;
         player.referral_earnings_lamports = 0;
+        player.pending_referral_earnings_lamports = 0;
         player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.dividend_weight_seconds = 0;
+        player.dividend_seconds_last_update = clock.unix_timestamp;
         player.current_round = game.round;
+        player.referral_earnings_round = 0;
+        player.referral_earnings_this_round_lamports = 0;
+        player.spend_limit_lamports_per_day = 0;
+        player.pending_spend_limit_lamports_per_day = None;
+        player.spend_limit_effective_at = 0;
+        player.spend_window_start = clock.unix_timestamp;
+        player.spend_window_lamports = 0;
+        player.timer_extension_window_start = clock.unix_timestamp;
+        player.timer_extensions_in_window = 0;
 
         // Set referrer if provided
         if let Some(referrer_state) = &ctx.accounts.referrer_state {
@@ -103,7 +1269,7 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
             );
 
             let (expected_pda, _) = Pubkey::find_program_address(
-                &[b"player", referrer_state.player.as_ref()],
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
                 ctx.program_id,
             );
             require!(
@@ -112,14 +1278,41 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
             );
 
             player.referrer = Some(referrer_state.player);
+            player.referrer_set_at = clock.unix_timestamp;
+
+            emit!(ReferrerSet {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                player: ctx.accounts.buyer.key(),
+                referrer: referrer_state.player,
+                timestamp: clock.unix_timestamp,
+            });
         } else {
             player.referrer = None;
+            player.referrer_set_at = 0;
         }
 
+        let holder_index = &mut ctx.accounts.holder_index;
+        holder_index.game_id = game.game_id;
+        holder_index.round = game.round;
+        holder_index.page = game.total_players / HolderIndex::PAGE_CAPACITY as u32;
+        holder_index.bump = ctx.bumps.holder_index;
+        holder_index.push(ctx.accounts.buyer.key());
+
         game.total_players = game
             .total_players
             .checked_add(1)
             .ok_or(FomoltError::Overflow)?;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.buyer.key(),
+            is_agent,
+            referrer: player.referrer,
+            timestamp: clock.unix_timestamp,
+        });
     } else if player.current_round == 0 {
         // Returning player (claimed from previous round)
         require!(
@@ -127,9 +1320,20 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
             FomoltError::Unauthorized
         );
         player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.dividend_weight_seconds = 0;
+        player.dividend_seconds_last_update = clock.unix_timestamp;
         player.current_round = game.round;
         // Existing referrer preserved
 
+        let holder_index = &mut ctx.accounts.holder_index;
+        holder_index.game_id = game.game_id;
+        holder_index.round = game.round;
+        holder_index.page = game.total_players / HolderIndex::PAGE_CAPACITY as u32;
+        holder_index.bump = ctx.bumps.holder_index;
+        holder_index.push(ctx.accounts.buyer.key());
+
         game.total_players = game
             .total_players
             .checked_add(1)
@@ -147,6 +1351,26 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
 
     // Update is_agent flag
     player.is_agent = is_agent;
+    player.strategy_tag = if is_agent { strategy_tag } else { 0 };
+
+    // --- Lazily initialize lifetime stats profile (round-agnostic, never reset) ---
+    let stats = &mut ctx.accounts.player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = ctx.accounts.buyer.key();
+        stats.bump = ctx.bumps.player_stats;
+    }
+
+    // --- Sold-out supply cap: clamp to whatever supply remains so a
+    // purchase that would cross max_keys_per_round only pays for the keys
+    // actually left, the same way handle_buy_keys_with_budget's
+    // calculate_max_keys clamp avoids ever needing a separate refund
+    // transfer — the cost below is computed from the clamped count. ---
+    let keys_to_buy = if game.max_keys_per_round > 0 {
+        keys_to_buy.min(game.max_keys_per_round.saturating_sub(game.total_keys))
+    } else {
+        keys_to_buy
+    };
 
     // --- 0-key buy = registration only, skip core buy logic ---
     if keys_to_buy == 0 {
@@ -161,13 +1385,39 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         game.price_increment_lamports,
     )?;
 
+    // --- Minimum purchase guard: below this, bps splits round to dust ---
+    require!(
+        game.min_purchase_lamports == 0 || cost >= game.min_purchase_lamports,
+        FomoltError::BelowMinimumPurchase
+    );
+
+    // --- Self-imposed spend limit (set_spend_limit, opt-in responsible-gaming cap) ---
+    player.apply_pending_spend_limit(clock.unix_timestamp);
+    if player.spend_limit_lamports_per_day > 0 {
+        player.maybe_reset_spend_window(clock.unix_timestamp);
+        let spent_after = player
+            .spend_window_lamports
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+        require!(
+            spent_after <= player.spend_limit_lamports_per_day,
+            FomoltError::SpendLimitExceeded
+        );
+        player.spend_window_lamports = spent_after;
+    }
+
     // === Fee Ordering: house fee → referral → pot splits ===
 
-    // Step 1: House fee off the top
-    let house_fee = math::calculate_bps_split(cost, game.protocol_fee_bps)?;
-    let after_fee = cost
-        .checked_sub(house_fee)
-        .ok_or(FomoltError::Overflow)?;
+    // Step 1: House fee and frontend fee off the top
+    let fees = logic::compute_fees(
+        cost,
+        game.protocol_fee_bps,
+        game.frontend_fee_bps,
+        ctx.accounts.frontend_wallet.is_some(),
+    )?;
+    let house_fee = fees.house_fee;
+    let frontend_fee = fees.frontend_fee;
+    let after_fee = fees.after_fee;
 
     // Step 2: Referral from remainder (if applicable)
     let mut referral_bonus_paid = 0u64;
@@ -190,7 +1440,7 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
 
             // Verify PDA derivation
             let (expected_pda, _) = Pubkey::find_program_address(
-                &[b"player", referrer_state.player.as_ref()],
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
                 ctx.program_id,
             );
             require!(
@@ -198,16 +1448,76 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
                 FomoltError::ReferrerNotRegistered
             );
 
-            // Calculate referral: 10% of after-fee amount
-            let referral_bonus =
-                math::calculate_bps_split(after_fee, game.referral_bonus_bps)?;
+            // Per-round referral tracking resets lazily when the round has
+            // moved on since this referrer last earned — same pattern
+            // `player.current_round` uses to detect a stale round.
+            if referrer_state.referral_earnings_round != game.round {
+                referrer_state.referral_earnings_round = game.round;
+                referrer_state.referral_earnings_this_round_lamports = 0;
+            }
+
+            // Diminishing returns: once the referrer has earned past the
+            // decay threshold this round, the bonus bps halves for the rest
+            // of the round. 0 disables the decay.
+            let effective_bonus_bps = if game.referral_decay_threshold_lamports > 0
+                && referrer_state.referral_earnings_this_round_lamports
+                    >= game.referral_decay_threshold_lamports
+            {
+                game.referral_bonus_bps / 2
+            } else {
+                game.referral_bonus_bps
+            };
+
+            let mut referral_bonus =
+                math::calculate_bps_split(after_fee, effective_bonus_bps)?;
+
+            // Hard per-round cap: clamp to whatever room remains under the
+            // cap. 0 disables the cap. Anything clamped off stays in
+            // pot_contribution rather than being dropped.
+            if game.referral_earnings_cap_lamports_per_round > 0 {
+                let remaining = game
+                    .referral_earnings_cap_lamports_per_round
+                    .saturating_sub(referrer_state.referral_earnings_this_round_lamports);
+                referral_bonus = referral_bonus.min(remaining);
+            }
 
             if referral_bonus > 0 {
-                // Credit referrer's pending earnings (round-agnostic — no round check)
-                referrer_state.referral_earnings_lamports = referrer_state
-                    .referral_earnings_lamports
+                // Credit referrer's earnings (round-agnostic — no round
+                // check) straight to the claimable bucket, unless
+                // GlobalConfig::referral_vesting_enabled holds this round's
+                // credits back in pending_referral_earnings_lamports until
+                // the round ends — see instructions::claim_referral_earnings.
+                if game.referral_vesting_enabled {
+                    referrer_state.pending_referral_earnings_lamports = referrer_state
+                        .pending_referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                } else {
+                    referrer_state.referral_earnings_lamports = referrer_state
+                        .referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+                referrer_state.referral_earnings_this_round_lamports = referrer_state
+                    .referral_earnings_this_round_lamports
+                    .checked_add(referral_bonus)
+                    .ok_or(FomoltError::Overflow)?;
+                game.total_referral_obligations = game
+                    .total_referral_obligations
                     .checked_add(referral_bonus)
                     .ok_or(FomoltError::Overflow)?;
+
+                if let Some(referrer_stats) = &mut ctx.accounts.referrer_stats {
+                    require!(
+                        referrer_stats.player == referrer_state.player,
+                        FomoltError::ReferrerMismatch
+                    );
+                    referrer_stats.lifetime_referral_earned = referrer_stats
+                        .lifetime_referral_earned
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+
                 referral_bonus_paid = referral_bonus;
                 pot_contribution = after_fee
                     .checked_sub(referral_bonus)
@@ -216,8 +1526,71 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         }
     }
 
+    // Step 2.5: slice of the house fee diverted into the active season's
+    // prize pool, if the season meta-game is funded — see `state::season`.
+    let season_cut = if game.season_length_rounds > 0 && game.season_fee_bps > 0 {
+        math::calculate_bps_split(house_fee, game.season_fee_bps)?
+    } else {
+        0
+    };
+    // Step 2.6: slice of the house fee diverted into the buyer's registered
+    // agent platform's pending earnings, if one is attached — see
+    // `instructions::register_agent_platform`. Credited straight into the
+    // vault instead of paid out directly, since claiming it requires the
+    // platform's own signature via `instructions::claim_agent_platform_earnings`.
+    let agent_platform_cut = if let Some(platform_key) = player.agent_platform {
+        if game.agent_platform_fee_share_bps > 0 {
+            let ap = ctx
+                .accounts
+                .agent_platform
+                .as_mut()
+                .ok_or(FomoltError::MissingAgentPlatform)?;
+            require!(ap.platform == platform_key, FomoltError::AgentPlatformMismatch);
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"agent_platform",
+                    game.game_id.to_le_bytes().as_ref(),
+                    platform_key.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(ap.key() == expected_pda, FomoltError::AgentPlatformMismatch);
+
+            let cut = math::calculate_bps_split(house_fee, game.agent_platform_fee_share_bps)?;
+            if cut > 0 {
+                ap.pending_earnings_lamports = ap
+                    .pending_earnings_lamports
+                    .checked_add(cut)
+                    .ok_or(FomoltError::Overflow)?;
+                game.total_agent_platform_obligations = game
+                    .total_agent_platform_obligations
+                    .checked_add(cut)
+                    .ok_or(FomoltError::Overflow)?;
+                emit!(AgentPlatformFeeAccrued {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: game.game_id,
+                    round: game.round,
+                    platform: platform_key,
+                    player: player.player,
+                    lamports: cut,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            cut
+        } else {
+            0
+        }
+    } else {
+        0
+    };
+
+    let protocol_cut = house_fee
+        .checked_sub(season_cut)
+        .and_then(|v| v.checked_sub(agent_platform_cut))
+        .ok_or(FomoltError::Overflow)?;
+
     // --- Transfer SOL: house fee from buyer to protocol wallet ---
-    if house_fee > 0 {
+    if protocol_cut > 0 {
         system_program::transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
@@ -226,17 +1599,107 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
                     to: ctx.accounts.protocol_wallet.to_account_info(),
                 },
             ),
-            house_fee,
+            protocol_cut,
         )?;
 
         emit!(ProtocolFeeCollected {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
             round: game.round,
-            lamports: house_fee,
+            lamports: protocol_cut,
             recipient: ctx.accounts.protocol_wallet.key(),
             timestamp: clock.unix_timestamp,
         });
     }
 
+    // --- Transfer SOL: frontend fee from buyer to frontend wallet ---
+    if frontend_fee > 0 {
+        let frontend_wallet = ctx.accounts.frontend_wallet.as_ref().unwrap();
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: frontend_wallet.to_account_info(),
+                },
+            ),
+            frontend_fee,
+        )?;
+
+        emit!(FrontendFeePaid {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            lamports: frontend_fee,
+            recipient: frontend_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // --- Transfer SOL: season cut from buyer to season vault ---
+    if season_cut > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.season_vault.to_account_info(),
+                },
+            ),
+            season_cut,
+        )?;
+
+        ctx.accounts.season.pool_lamports = ctx
+            .accounts
+            .season
+            .pool_lamports
+            .checked_add(season_cut)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    // --- Transfer SOL: agent platform cut from buyer to vault — backs the
+    // AgentPlatform::pending_earnings_lamports credit above, since claiming it
+    // draws on the vault's balance rather than being paid out immediately. ---
+    if agent_platform_cut > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            agent_platform_cut,
+        )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(agent_platform_cut)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::AgentPlatform,
+            lamports: agent_platform_cut,
+            counterparty: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Season leaderboard/volume tracking — independent of whether the pool
+    // is funded, so standings still accrue with `season_fee_bps == 0`.
+    if game.season_length_rounds > 0 {
+        let season = &mut ctx.accounts.season;
+        season.game_id = game.game_id;
+        season.season_id = game.current_season_id();
+        season.start_round = game.current_season_start_round();
+        season.end_round = game.current_season_end_round();
+        season.status = SeasonStatus::Active;
+        season.bump = ctx.bumps.season;
+        season.credit_volume(ctx.accounts.buyer.key(), cost)?;
+    }
+
     // --- Transfer SOL: pot_contribution from buyer to vault ---
     if pot_contribution > 0 {
         system_program::transfer(
@@ -249,6 +1712,20 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
             ),
             pot_contribution,
         )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Buy,
+            lamports: pot_contribution,
+            counterparty: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     // --- Transfer SOL: referral bonus directly from buyer to referrer wallet ---
@@ -279,12 +1756,57 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         )?;
     }
 
-    // Step 3: Pot splits from pot_contribution
-    let winner_amount = math::calculate_bps_split(pot_contribution, game.winner_bps)?;
-    let dividend_amount = math::calculate_bps_split(pot_contribution, game.dividend_bps)?;
-    let next_round_amount = math::calculate_bps_split(pot_contribution, game.next_round_bps)?;
+    // Step 3: Pot splits from pot_contribution. The daily raffle's cut comes
+    // off the top — it isn't part of the must-sum-to-10000 winner/dividend/
+    // next_round group — leaving `splittable` for the three-way split.
+    // Integer division can leave a few lamports of that unaccounted for
+    // (e.g. bps splits on an odd amount) — route that dust into the bucket
+    // `game.rounding_beneficiary` names so every lamport lands in a tracked
+    // bucket; with the default `Protocol` that is `dust_reserve`, which an
+    // admin sweeps out separately via `sweep_dust_reserve`.
+    // Bounded-liability operators cap total pot exposure via
+    // `max_pot_lamports`. Once winner_pot + total_dividend_pool would exceed
+    // it, the excess is redirected into pot_overflow_reserve_lamports (a
+    // reserve that seeds the next round via `start_new_round`) rather than
+    // dropped or left to inflate the pot unbounded. 0 disables the cap.
+    let committed = game
+        .winner_pot
+        .checked_add(game.total_dividend_pool)
+        .ok_or(FomoltError::Overflow)?;
+    let split = logic::compute_pot_split(
+        pot_contribution,
+        game.raffle_bps,
+        game.winner_bps,
+        game.dividend_bps,
+        game.next_round_bps,
+        committed,
+        game.max_pot_lamports,
+    )?;
+    let raffle_amount = split.raffle_amount;
+    let winner_amount = split.winner_amount;
+    let dividend_amount = split.dividend_amount;
+    let next_round_amount = split.next_round_amount;
+    let split_dust = split.dust;
+    let pot_overflow_amount = split.pot_overflow_amount;
+
+    assert_purchase_conserves_cost(
+        cost,
+        house_fee,
+        frontend_fee,
+        referral_bonus_paid,
+        raffle_amount,
+        winner_amount,
+        dividend_amount,
+        next_round_amount,
+        split_dust,
+        pot_overflow_amount,
+    )?;
 
     // --- Update game state ---
+    game.raffle_pool_lamports = game
+        .raffle_pool_lamports
+        .checked_add(raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
     game.winner_pot = game
         .winner_pot
         .checked_add(winner_amount)
@@ -293,37 +1815,382 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         .total_dividend_pool
         .checked_add(dividend_amount)
         .ok_or(FomoltError::Overflow)?;
+    game.record_dividend_for_apr_window(clock.unix_timestamp, dividend_amount)?;
     game.next_round_pot = game
         .next_round_pot
         .checked_add(next_round_amount)
         .ok_or(FomoltError::Overflow)?;
+    match game.rounding_beneficiary {
+        RoundingBeneficiary::Protocol => {
+            game.dust_reserve = game
+                .dust_reserve
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::WinnerPot => {
+            game.winner_pot = game
+                .winner_pot
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::DividendPool => {
+            game.total_dividend_pool = game
+                .total_dividend_pool
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+            game.record_dividend_for_apr_window(clock.unix_timestamp, split_dust)?;
+        }
+        RoundingBeneficiary::NextRoundPot => {
+            game.next_round_pot = game
+                .next_round_pot
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+    }
+    game.pot_overflow_reserve_lamports = game
+        .pot_overflow_reserve_lamports
+        .checked_add(pot_overflow_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Sync time-weighted dividend accumulators before total_weight changes ---
+    if game.time_weighted_dividends_enabled {
+        game.sync_dividend_seconds(clock.unix_timestamp)?;
+        player.sync_dividend_seconds(clock.unix_timestamp)?;
+    }
+
+    // --- Sync the key-price TWAP accumulator before total_keys moves, using
+    // the marginal price that was actually in effect since the last sync ---
+    let current_price = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )?;
+    game.sync_price_cumulative(clock.unix_timestamp, current_price)?;
+
+    // --- Early-bird dividend weight for this purchase (before total_keys moves) ---
+    let weight_earned = math::calculate_key_weight(
+        game.total_keys,
+        keys_to_buy,
+        game.early_bird_key_threshold,
+        game.early_bird_multiplier_bps,
+    )?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_weight = game
+        .total_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
 
     // --- Add keys to player and game ---
     player.keys = player
         .keys
         .checked_add(keys_to_buy)
         .ok_or(FomoltError::Overflow)?;
+    player.contributed_lamports = player
+        .contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    player.total_contributed_lamports = player
+        .total_contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
     game.total_keys = game
         .total_keys
         .checked_add(keys_to_buy)
         .ok_or(FomoltError::Overflow)?;
+    if is_agent {
+        game.agent_keys_total = game
+            .agent_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    } else {
+        game.human_keys_total = game
+            .human_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    }
+    let pot_before = game.pot_lamports;
     game.pot_lamports = game
         .pot_lamports
         .checked_add(cost)
         .ok_or(FomoltError::Overflow)?;
     game.last_buyer = ctx.accounts.buyer.key();
 
-    // --- Extend timer (can only increase, never decrease) ---
-    game.timer_end = math::calculate_timer_extension(
-        clock.unix_timestamp,
-        game.timer_extension_secs,
-        game.timer_end,
-        game.round_start,
-        game.max_timer_secs,
-    )?;
+    // --- Round statistics: purchase count, gross volume excl. carry, largest single buy ---
+    game.purchase_count = game
+        .purchase_count
+        .checked_add(1)
+        .ok_or(FomoltError::Overflow)?;
+    game.gross_volume_lamports = game
+        .gross_volume_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    if cost > game.max_single_buy_lamports {
+        game.max_single_buy_lamports = cost;
+        game.max_single_buyer = ctx.accounts.buyer.key();
+    }
+    if player.keys > game.largest_holder_keys {
+        game.largest_holder_keys = player.keys;
+        game.largest_holder = ctx.accounts.buyer.key();
+    }
+
+    // --- Round-duration analytics: interval since the last buy (for
+    // `average_seconds_between_buys`), and pot-growth checkpoints at 25/50/75%
+    // of the round's planned `max_timer_secs` — surfaced on `RoundConcluded`
+    // for an off-chain indexer's round archive. Both need live accumulation;
+    // neither is reconstructable after the fact from the round's final state. ---
+    game.buy_interval_seconds_total = game
+        .buy_interval_seconds_total
+        .checked_add(clock.unix_timestamp.saturating_sub(game.last_buy_timestamp))
+        .ok_or(FomoltError::Overflow)?;
+    game.last_buy_timestamp = clock.unix_timestamp;
+
+    let elapsed_secs = clock.unix_timestamp.saturating_sub(game.round_start);
+    if !game.pot_checkpoint_25_reached && elapsed_secs * 4 >= game.max_timer_secs {
+        game.pot_checkpoint_25_lamports = game.pot_lamports;
+        game.pot_checkpoint_25_reached = true;
+    }
+    if !game.pot_checkpoint_50_reached && elapsed_secs * 2 >= game.max_timer_secs {
+        game.pot_checkpoint_50_lamports = game.pot_lamports;
+        game.pot_checkpoint_50_reached = true;
+    }
+    if !game.pot_checkpoint_75_reached && elapsed_secs * 4 >= game.max_timer_secs * 3 {
+        game.pot_checkpoint_75_lamports = game.pot_lamports;
+        game.pot_checkpoint_75_reached = true;
+    }
+
+    // --- Lifetime stats: keys bought and lamports spent ---
+    ctx.accounts.player_stats.lifetime_keys_bought = ctx
+        .accounts
+        .player_stats
+        .lifetime_keys_bought
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    ctx.accounts.player_stats.lifetime_lamports_spent = ctx
+        .accounts
+        .player_stats
+        .lifetime_lamports_spent
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Optional purchase history ring buffer (GameState::purchase_history_enabled) ---
+    if game.purchase_history_enabled {
+        if let Some(history) = &mut ctx.accounts.player_history {
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[b"history", game.game_id.to_le_bytes().as_ref(), ctx.accounts.buyer.key().as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                history.key() == expected_pda,
+                FomoltError::PlayerHistoryMismatch
+            );
+            history.record(clock.unix_timestamp, keys_to_buy, cost);
+        }
+    }
+
+    // --- Optional per-purchase receipt (opt-in, see BuyReceipt) ---
+    if let Some(receipt) = &mut ctx.accounts.receipt {
+        receipt.game_id = game.game_id;
+        receipt.round = game.round;
+        receipt.player = ctx.accounts.buyer.key();
+        // `game.purchase_count` was already incremented above; the PDA seed
+        // (validated before this handler ran) used the pre-increment value.
+        receipt.nonce = game
+            .purchase_count
+            .checked_sub(1)
+            .ok_or(FomoltError::Overflow)?;
+        receipt.keys_bought = keys_to_buy;
+        receipt.cost_lamports = cost;
+        receipt.house_fee_lamports = house_fee;
+        receipt.frontend_fee_lamports = frontend_fee;
+        receipt.referral_bonus_lamports = referral_bonus_paid;
+        receipt.pot_contribution_lamports = pot_contribution;
+        receipt.timestamp = clock.unix_timestamp;
+        // `ctx.bumps.receipt` is `Some` whenever `ctx.accounts.receipt` is,
+        // since Anchor only resolves the bump for a seeded account it opened.
+        receipt.bump = ctx.bumps.receipt.unwrap();
+    }
+
+    // --- Final-hour activation: sticky once the pot crosses the threshold ---
+    if !game.final_hour_active
+        && game.final_hour_pot_threshold_lamports > 0
+        && game.pot_lamports >= game.final_hour_pot_threshold_lamports
+    {
+        game.final_hour_active = true;
+        game.final_hour_start_keys = game.total_keys;
+        emit!(FinalHourActivated {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    let keys_since_final_hour_start = if game.final_hour_active {
+        game.total_keys.saturating_sub(game.final_hour_start_keys)
+    } else {
+        0
+    };
+
+    // --- Pot milestones: hype events (and an optional free-key bonus) for
+    // crossing a configured pot size. A single large buy can cross several
+    // at once; only the highest reached is reported.
+    let milestones_crossed = math::calculate_milestones_crossed(
+        pot_before,
+        game.pot_lamports,
+        game.pot_milestone_interval_lamports,
+    );
+    if milestones_crossed > 0 {
+        let bonus_keys = game.pot_milestone_bonus_keys;
+        if bonus_keys > 0 {
+            let bonus_weight = bonus_keys
+                .checked_mul(10_000)
+                .ok_or(FomoltError::Overflow)?;
+            player.keys = player
+                .keys
+                .checked_add(bonus_keys)
+                .ok_or(FomoltError::Overflow)?;
+            player.dividend_weight = player
+                .dividend_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_keys = game
+                .total_keys
+                .checked_add(bonus_keys)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_weight = game
+                .total_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        let milestone_number = game.pot_lamports / game.pot_milestone_interval_lamports;
+        emit!(MilestoneReached {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.buyer.key(),
+            milestone_number,
+            pot_lamports: game.pot_lamports,
+            bonus_keys_granted: bonus_keys,
+            timestamp: clock.unix_timestamp,
+        });
+
+        let ext = &mut ctx.accounts.game_state_ext;
+        ext.game_id = game.game_id;
+        ext.round = game.round;
+        ext.bump = ctx.bumps.game_state_ext;
+        ext.milestones_reached_this_round = ext
+            .milestones_reached_this_round
+            .checked_add(milestones_crossed as u32)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    // --- Sold-out supply cap: if this purchase filled the last remaining
+    // key, end the round immediately regardless of the timer — the last
+    // buyer (this buyer, already recorded above) wins, exactly like the
+    // timer-expiry auto-end at the top of this function, just triggered by
+    // supply instead of the clock. See `GlobalConfig::max_keys_per_round`. ---
+    let sold_out = game.max_keys_per_round > 0 && game.total_keys >= game.max_keys_per_round;
+    if sold_out {
+        game.timer_end = clock.unix_timestamp;
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: game.last_buyer,
+            winner_lamports: game.winner_pot,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            total_players: game.total_players,
+            next_round_pot: game.next_round_pot,
+            round_start: game.round_start,
+            round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // --- Extend timer (can only increase, never decrease), unless this
+    // wallet has hit its per-window cap on timer-extending buys — past the
+    // cap the buy still counts for keys above, it just stops moving
+    // `timer_end`. See `GlobalConfig::max_timer_extensions_per_window`. A buy
+    // below `GlobalConfig::min_keys_for_timer_extension` never extends the
+    // timer at all, and doesn't count against the window cap either — it's
+    // simply too small to be a real, timer-relevant buy. A round that just
+    // sold out has no timer left to extend, so it's skipped entirely. ---
+    if !sold_out {
+        let mut timer_extended = keys_to_buy >= game.min_keys_for_timer_extension;
+        if timer_extended && game.max_timer_extensions_per_window > 0 {
+            player.maybe_reset_timer_extension_window(
+                clock.unix_timestamp,
+                game.timer_extension_window_secs,
+            );
+            timer_extended =
+                player.timer_extensions_in_window < game.max_timer_extensions_per_window;
+        }
+        if timer_extended {
+            game.timer_end = math::calculate_timer_extension(
+                clock.unix_timestamp,
+                math::TimerExtensionParams {
+                    extension_secs: game.timer_extension_secs,
+                    current_timer_end: game.timer_end,
+                    round_start: game.round_start,
+                    max_timer_secs: game.max_timer_secs,
+                    final_hour_active: game.final_hour_active,
+                    keys_since_final_hour_start,
+                    final_hour_shrink_interval_keys: game.final_hour_shrink_interval_keys,
+                    min_remaining_secs: game.min_remaining_secs,
+                },
+            )?;
+            player.timer_extensions_in_window = player
+                .timer_extensions_in_window
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+            game.timer_extensions_triggered = game
+                .timer_extensions_triggered
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+        } else {
+            emit!(TimerExtensionCapped {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                player: ctx.accounts.buyer.key(),
+                timer_extensions_in_window: player.timer_extensions_in_window,
+                timer_end: game.timer_end,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
 
     // --- Emit events ---
     emit!(KeysPurchased {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
         round: game.round,
         player: ctx.accounts.buyer.key(),
         is_agent: player.is_agent,
@@ -334,20 +2201,59 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         timestamp: clock.unix_timestamp,
     });
 
+    if player.is_agent {
+        emit!(AgentAction {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.buyer.key(),
+            strategy_tag: player.strategy_tag,
+            action: "buy_keys".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     if referral_bonus_paid > 0 {
         if let Some(referrer) = player.referrer {
+            let ext = &mut ctx.accounts.game_state_ext;
+            ext.game_id = game.game_id;
+            ext.round = game.round;
+            ext.bump = ctx.bumps.game_state_ext;
+            ext.credit_referrer(referrer, referral_bonus_paid)?;
+
             emit!(ReferralEarned {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
                 round: game.round,
                 player: ctx.accounts.buyer.key(),
                 referrer,
                 keys_bought: keys_to_buy,
                 lamports_spent: cost,
                 referrer_lamports: referral_bonus_paid,
+                vested: !game.referral_vesting_enabled,
                 timestamp: clock.unix_timestamp,
             });
         }
     }
 
+    emit!(PurchaseSettled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.buyer.key(),
+        lamports_spent: cost,
+        protocol_fee_lamports: house_fee,
+        referrer: player.referrer,
+        referral_bonus_lamports: referral_bonus_paid,
+        pot_contribution,
+        raffle_pool_lamports: raffle_amount,
+        winner_pot_lamports: winner_amount,
+        dividend_pool_lamports: dividend_amount,
+        next_round_lamports: next_round_amount,
+        dust_lamports: split_dust,
+        timestamp: clock.unix_timestamp,
+    });
+
     // Calculate next key price for the event
     let next_key_price = math::calculate_cost(
         game.total_keys,
@@ -357,7 +2263,43 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
     )
     .unwrap_or(u64::MAX);
 
+    let snapshot = &mut ctx.accounts.game_snapshot;
+    snapshot.pot_lamports = game.pot_lamports;
+    snapshot.total_keys = game.total_keys;
+    snapshot.timer_end = game.timer_end;
+    snapshot.last_buyer = game.last_buyer;
+    snapshot.next_key_price = next_key_price;
+
+    // --- Sample this round's price history, if enabled and due. A buy that
+    // lands before `price_sample_interval_slots` has elapsed since the last
+    // sample just skips it — the next qualifying buy, or the permissionless
+    // `record_sample` crank during a quiet spell, will catch up. See
+    // `PriceHistory`. ---
+    if game.price_sample_interval_slots > 0 {
+        let history = &mut ctx.accounts.price_history;
+        let due = history.len == 0
+            || clock.slot >= history.last_sampled_slot.saturating_add(game.price_sample_interval_slots);
+        if due {
+            history.game_id = game.game_id;
+            history.round = game.round;
+            history.bump = ctx.bumps.price_history;
+            history.record(clock.slot, game.total_keys, next_key_price);
+
+            emit!(PriceSampleRecorded {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                slot: clock.slot,
+                total_keys: game.total_keys,
+                price_lamports: next_key_price,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
     emit!(GameUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
         round: game.round,
         pot_lamports: game.pot_lamports,
         total_keys: game.total_keys,
@@ -369,5 +2311,102 @@ pub fn handle_buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool)
         timestamp: clock.unix_timestamp,
     });
 
+    // --- Optional partner integration hook (GameState::hook_program) ---
+    if game.hook_program != Pubkey::default() {
+        let hook_program = ctx
+            .accounts
+            .hook_program
+            .as_ref()
+            .ok_or(FomoltError::MissingHookProgram)?;
+        require!(
+            hook_program.key() == game.hook_program,
+            FomoltError::HookProgramMismatch
+        );
+        require!(
+            ctx.remaining_accounts.len() <= MAX_HOOK_ACCOUNTS,
+            FomoltError::TooManyHookAccounts
+        );
+
+        let mut account_metas = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(ctx.remaining_accounts.len() + 1);
+        account_infos.push(hook_program.to_account_info());
+        for account in ctx.remaining_accounts {
+            account_metas.push(if account.is_writable {
+                AccountMeta::new(account.key(), account.is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), account.is_signer)
+            });
+            account_infos.push(account.clone());
+        }
+
+        let mut data = HOOK_NOTIFY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&game.round.to_le_bytes());
+        data.extend_from_slice(ctx.accounts.buyer.key().as_ref());
+        data.extend_from_slice(&keys_to_buy.to_le_bytes());
+        data.extend_from_slice(&cost.to_le_bytes());
+
+        invoke(
+            &Instruction {
+                program_id: hook_program.key(),
+                accounts: account_metas,
+                data,
+            },
+            &account_infos,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// strict-invariants builds only: verifies house_fee + frontend_fee +
+/// referral_bonus + raffle + winner + dividend + next_round + dust +
+/// pot_overflow reconstructs `cost`
+/// exactly, so a future edit to the fee/pot math that breaks conservation
+/// fails loudly on testnets instead of silently leaking or minting
+/// lamports. Compiled out entirely on mainnet builds — the checked-math
+/// splits above already can't overflow, this only guards against the
+/// splits no longer summing to the whole.
+#[cfg(feature = "strict-invariants")]
+#[allow(clippy::too_many_arguments)]
+fn assert_purchase_conserves_cost(
+    cost: u64,
+    house_fee: u64,
+    frontend_fee: u64,
+    referral_bonus: u64,
+    raffle_amount: u64,
+    winner_amount: u64,
+    dividend_amount: u64,
+    next_round_amount: u64,
+    dust: u64,
+    pot_overflow_amount: u64,
+) -> Result<()> {
+    let total = house_fee
+        .checked_add(frontend_fee)
+        .and_then(|v| v.checked_add(referral_bonus))
+        .and_then(|v| v.checked_add(raffle_amount))
+        .and_then(|v| v.checked_add(winner_amount))
+        .and_then(|v| v.checked_add(dividend_amount))
+        .and_then(|v| v.checked_add(next_round_amount))
+        .and_then(|v| v.checked_add(dust))
+        .and_then(|v| v.checked_add(pot_overflow_amount))
+        .ok_or(FomoltError::Overflow)?;
+    require!(total == cost, FomoltError::AccountingMismatch);
+    Ok(())
+}
+
+#[cfg(not(feature = "strict-invariants"))]
+#[allow(clippy::too_many_arguments)]
+fn assert_purchase_conserves_cost(
+    _cost: u64,
+    _house_fee: u64,
+    _frontend_fee: u64,
+    _referral_bonus: u64,
+    _raffle_amount: u64,
+    _winner_amount: u64,
+    _dividend_amount: u64,
+    _next_round_amount: u64,
+    _dust: u64,
+    _pot_overflow_amount: u64,
+) -> Result<()> {
     Ok(())
 }
@@ -0,0 +1,808 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{
+    BlockedAttempt, FinalHourActivated, GameUpdated, KeysPurchased, MilestoneReached,
+    PlayerRegistered,
+    ProtocolFeeCollected, PurchaseSettled, ReferralEarned, ReferrerSet, RoundConcluded,
+    RoundStatusChanged, VaultFlow,
+};
+use crate::logic;
+use crate::math;
+use crate::state::*;
+
+/// Same core buy flow as `BuyKeys`, but signed by a session `delegate`
+/// instead of the player themselves — keys/dividends/referrals are still
+/// attributed to `owner`; the delegate pays the SOL cost from its own
+/// balance and `session_authority` bounds how much it may spend this way.
+#[derive(Accounts)]
+pub struct BuyKeysViaSession<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// The player keys/dividends are attributed to.
+    /// CHECK: Bound to `session_authority` via `has_one`
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), delegate.key().as_ref()],
+        bump = session_authority.bump,
+        has_one = owner @ FomoltError::Unauthorized,
+    )]
+    pub session_authority: Account<'info, SessionAuthority>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Indexer-friendly mirror of this round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        mut,
+        seeds = [b"snapshot", game_state.key().as_ref()],
+        bump = game_snapshot.bump,
+    )]
+    pub game_snapshot: Account<'info, GameSnapshot>,
+
+    /// Append-only companion to `game_state` for per-round data that isn't
+    /// worth growing `GameState::SPACE` for — see `GameStateExt`. Created
+    /// lazily on whichever buy first crosses a pot milestone this round.
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + GameStateExt::SPACE,
+        seeds = [b"game_ext", game_state.key().as_ref()],
+        bump,
+    )]
+    pub game_state_ext: Account<'info, GameStateExt>,
+
+    /// Protocol fee recipient wallet
+    /// CHECK: Validated against game_state.protocol_wallet
+    #[account(
+        mut,
+        constraint = protocol_wallet.key() == game_state.protocol_wallet @ FomoltError::InvalidConfig,
+    )]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    /// Optional referrer's PlayerState — must be writable for referral credit.
+    /// CHECK: Validated manually in handler (PDA derivation + referrer match)
+    #[account(mut)]
+    pub referrer_state: Option<Account<'info, PlayerState>>,
+
+    /// Optional referrer's wallet for direct referral payment.
+    /// CHECK: Validated in handler against referrer_state.player
+    #[account(mut)]
+    pub referrer_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Optional referrer's lifetime stats — credited with the referral bonus earned.
+    /// CHECK: Validated manually in handler (PDA derivation + referrer match)
+    #[account(mut)]
+    pub referrer_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Always the canonical `[b"blocked", game_id, owner]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Required only when `GameState::kyc_required` is set — the owner's
+    /// `KycCredential` PDA issued via `issue_kyc_credential`. Same gate
+    /// `buy_keys` applies to `buyer`; here it's checked against `owner`,
+    /// since keys/dividends are attributed to `owner`, not the delegate.
+    /// CHECK: Validated manually in handler (PDA derivation against owner)
+    pub kyc_credential: Option<Account<'info, KycCredential>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_buy_keys_via_session(
+    ctx: Context<BuyKeysViaSession>,
+    keys_to_buy: u64,
+    is_agent: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < ctx.accounts.session_authority.expiry_unix_ts,
+        FomoltError::SessionExpired
+    );
+
+    let game = &mut ctx.accounts.game_state;
+    let player = &mut ctx.accounts.player_state;
+    let owner_key = ctx.accounts.owner.key();
+
+    // --- Auto-end check: if timer expired, end the round and return Ok (no-op) ---
+    if clock.unix_timestamp >= game.timer_end {
+        if game.status == RoundStatus::Active {
+            game.transition_status(RoundStatus::Ended)?;
+            emit!(RoundStatusChanged {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                from: RoundStatus::Active,
+                to: RoundStatus::Ended,
+                timestamp: clock.unix_timestamp,
+            });
+            emit!(RoundConcluded {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                winner: game.last_buyer,
+                winner_lamports: game.winner_pot,
+                pot_lamports: game.pot_lamports,
+                total_keys: game.total_keys,
+                total_players: game.total_players,
+                next_round_pot: game.next_round_pot,
+                round_start: game.round_start,
+                round_end: game.timer_end,
+                purchase_count: game.purchase_count,
+                gross_volume_lamports: game.gross_volume_lamports,
+                max_single_buy_lamports: game.max_single_buy_lamports,
+                max_single_buyer: game.max_single_buyer,
+                round_duration_secs: game.round_duration_secs(),
+                timer_extensions_triggered: game.timer_extensions_triggered,
+                average_seconds_between_buys: game.average_seconds_between_buys(),
+                pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+                pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+                pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+                genesis_config_hash: game.genesis_config_hash,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        return Ok(());
+    }
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+    // --- Blocklist check: applies to the owner, not the signing delegate ---
+    if BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?.is_some() {
+        emit!(BlockedAttempt {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            wallet: owner_key,
+            action: "buy_keys_via_session".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+        return err!(FomoltError::WalletBlocked);
+    }
+
+    // --- KYC gate: licensed/compliant rounds require a credential PDA,
+    // checked against the owner (see `buy_keys`'s identical gate) ---
+    if game.kyc_required {
+        let credential = ctx
+            .accounts
+            .kyc_credential
+            .as_ref()
+            .ok_or(FomoltError::KycCredentialRequired)?;
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[b"kyc", game.game_id.to_le_bytes().as_ref(), owner_key.as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            credential.key() == expected_pda,
+            FomoltError::KycCredentialRequired
+        );
+    }
+
+    // --- Handle player registration / round entry ---
+    let is_new_player = player.player == Pubkey::default();
+
+    if is_new_player {
+        player.game_id = game.game_id;
+        player.player = owner_key;
+        player.bump = ctx.bumps.player_state;
+        player.initialized = true;
+        player.generation = player.generation.wrapping_add(1);
+        player.claimed_dividends_lamports = 0;
+        player.claimed_referral_earnings_lamports = 0;
+        player.referral_earnings_lamports = 0;
+        player.pending_referral_earnings_lamports = 0;
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.current_round = game.round;
+
+        if let Some(referrer_state) = &ctx.accounts.referrer_state {
+            require!(
+                referrer_state.player != owner_key,
+                FomoltError::CannotReferSelf
+            );
+
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                referrer_state.key() == expected_pda,
+                FomoltError::ReferrerNotRegistered
+            );
+
+            player.referrer = Some(referrer_state.player);
+
+            emit!(ReferrerSet {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                player: owner_key,
+                referrer: referrer_state.player,
+                timestamp: clock.unix_timestamp,
+            });
+        } else {
+            player.referrer = None;
+        }
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: owner_key,
+            is_agent,
+            referrer: player.referrer,
+            timestamp: clock.unix_timestamp,
+        });
+    } else if player.current_round == 0 {
+        require!(player.player == owner_key, FomoltError::Unauthorized);
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.current_round = game.round;
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+    } else if player.current_round == game.round {
+        require!(player.player == owner_key, FomoltError::Unauthorized);
+    } else {
+        return err!(FomoltError::MustClaimPreviousRound);
+    }
+
+    player.is_agent = is_agent;
+
+    let stats = &mut ctx.accounts.player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = owner_key;
+        stats.bump = ctx.bumps.player_stats;
+    }
+
+    // --- Sold-out supply cap: same clamp `buy_keys`'s main path applies —
+    // see `GlobalConfig::max_keys_per_round` — so a session-signed buy can't
+    // oversell a round's supply just because it skips `buy_keys` itself. ---
+    let keys_to_buy = if game.max_keys_per_round > 0 {
+        keys_to_buy.min(game.max_keys_per_round.saturating_sub(game.total_keys))
+    } else {
+        keys_to_buy
+    };
+
+    if keys_to_buy == 0 {
+        return Ok(());
+    }
+
+    let cost = math::calculate_cost(
+        game.total_keys,
+        keys_to_buy,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )?;
+
+    require!(
+        game.min_purchase_lamports == 0 || cost >= game.min_purchase_lamports,
+        FomoltError::BelowMinimumPurchase
+    );
+
+    // --- Session spend limit check ---
+    let session = &mut ctx.accounts.session_authority;
+    let spent_after = session
+        .spent_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    require!(
+        spent_after <= session.spend_limit_lamports,
+        FomoltError::SessionSpendLimitExceeded
+    );
+    session.spent_lamports = spent_after;
+
+    // === Fee Ordering: house fee → referral → pot splits (identical to buy_keys) ===
+
+    let house_fee = math::calculate_bps_split(cost, game.protocol_fee_bps)?;
+    let after_fee = cost.checked_sub(house_fee).ok_or(FomoltError::Overflow)?;
+
+    let mut referral_bonus_paid = 0u64;
+    let mut pot_contribution = after_fee;
+
+    if player.referrer.is_some() {
+        require!(
+            ctx.accounts.referrer_state.is_some(),
+            FomoltError::ReferrerMismatch
+        );
+    }
+
+    if let Some(referrer_state) = &mut ctx.accounts.referrer_state {
+        if let Some(existing_referrer) = player.referrer {
+            require!(
+                referrer_state.player == existing_referrer,
+                FomoltError::ReferrerMismatch
+            );
+
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                referrer_state.key() == expected_pda,
+                FomoltError::ReferrerNotRegistered
+            );
+
+            let referral_bonus = math::calculate_bps_split(after_fee, game.referral_bonus_bps)?;
+
+            if referral_bonus > 0 {
+                if game.referral_vesting_enabled {
+                    referrer_state.pending_referral_earnings_lamports = referrer_state
+                        .pending_referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                } else {
+                    referrer_state.referral_earnings_lamports = referrer_state
+                        .referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+                game.total_referral_obligations = game
+                    .total_referral_obligations
+                    .checked_add(referral_bonus)
+                    .ok_or(FomoltError::Overflow)?;
+
+                if let Some(referrer_stats) = &mut ctx.accounts.referrer_stats {
+                    require!(
+                        referrer_stats.player == referrer_state.player,
+                        FomoltError::ReferrerMismatch
+                    );
+                    referrer_stats.lifetime_referral_earned = referrer_stats
+                        .lifetime_referral_earned
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+
+                referral_bonus_paid = referral_bonus;
+                pot_contribution = after_fee
+                    .checked_sub(referral_bonus)
+                    .ok_or(FomoltError::Overflow)?;
+            }
+        }
+    }
+
+    // --- Transfer SOL: all payouts come from the delegate's own balance ---
+    if house_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.delegate.to_account_info(),
+                    to: ctx.accounts.protocol_wallet.to_account_info(),
+                },
+            ),
+            house_fee,
+        )?;
+
+        emit!(ProtocolFeeCollected {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            lamports: house_fee,
+            recipient: ctx.accounts.protocol_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if pot_contribution > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.delegate.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            pot_contribution,
+        )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Buy,
+            lamports: pot_contribution,
+            counterparty: owner_key,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if referral_bonus_paid > 0 {
+        let referrer_wallet = ctx
+            .accounts
+            .referrer_wallet
+            .as_ref()
+            .ok_or(FomoltError::ReferrerMismatch)?;
+        let referrer_state = ctx
+            .accounts
+            .referrer_state
+            .as_ref()
+            .ok_or(FomoltError::ReferrerMismatch)?;
+        require!(
+            referrer_wallet.key() == referrer_state.player,
+            FomoltError::ReferrerMismatch
+        );
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.delegate.to_account_info(),
+                    to: referrer_wallet.to_account_info(),
+                },
+            ),
+            referral_bonus_paid,
+        )?;
+    }
+
+    // Same invariant-bearing helper `buy_keys` uses, so a session-signed buy
+    // gets the same `max_pot_lamports` cap and rounding-beneficiary routing
+    // instead of an ad-hoc split that could let winner_pot/total_dividend_pool
+    // grow past the configured bound.
+    let committed = game
+        .winner_pot
+        .checked_add(game.total_dividend_pool)
+        .ok_or(FomoltError::Overflow)?;
+    let split = logic::compute_pot_split(
+        pot_contribution,
+        game.raffle_bps,
+        game.winner_bps,
+        game.dividend_bps,
+        game.next_round_bps,
+        committed,
+        game.max_pot_lamports,
+    )?;
+    let raffle_amount = split.raffle_amount;
+    let winner_amount = split.winner_amount;
+    let dividend_amount = split.dividend_amount;
+    let next_round_amount = split.next_round_amount;
+    let split_dust = split.dust;
+    let pot_overflow_amount = split.pot_overflow_amount;
+
+    game.raffle_pool_lamports = game
+        .raffle_pool_lamports
+        .checked_add(raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.winner_pot = game
+        .winner_pot
+        .checked_add(winner_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_pool = game
+        .total_dividend_pool
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.record_dividend_for_apr_window(clock.unix_timestamp, dividend_amount)?;
+    game.next_round_pot = game
+        .next_round_pot
+        .checked_add(next_round_amount)
+        .ok_or(FomoltError::Overflow)?;
+    match game.rounding_beneficiary {
+        RoundingBeneficiary::Protocol => {
+            game.dust_reserve = game
+                .dust_reserve
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::WinnerPot => {
+            game.winner_pot = game
+                .winner_pot
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::DividendPool => {
+            game.total_dividend_pool = game
+                .total_dividend_pool
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+            game.record_dividend_for_apr_window(clock.unix_timestamp, split_dust)?;
+        }
+        RoundingBeneficiary::NextRoundPot => {
+            game.next_round_pot = game
+                .next_round_pot
+                .checked_add(split_dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+    }
+    game.pot_overflow_reserve_lamports = game
+        .pot_overflow_reserve_lamports
+        .checked_add(pot_overflow_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Sync time-weighted dividend accumulators before total_weight changes,
+    // same as `buy_keys` — otherwise a session-signed buy would silently skip
+    // this player's and the round's time-weighted dividend accrual. ---
+    if game.time_weighted_dividends_enabled {
+        game.sync_dividend_seconds(clock.unix_timestamp)?;
+        player.sync_dividend_seconds(clock.unix_timestamp)?;
+    }
+
+    let weight_earned = math::calculate_key_weight(
+        game.total_keys,
+        keys_to_buy,
+        game.early_bird_key_threshold,
+        game.early_bird_multiplier_bps,
+    )?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_weight = game
+        .total_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+
+    player.keys = player
+        .keys
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    player.contributed_lamports = player
+        .contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    player.total_contributed_lamports = player
+        .total_contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_keys = game
+        .total_keys
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    if is_agent {
+        game.agent_keys_total = game
+            .agent_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    } else {
+        game.human_keys_total = game
+            .human_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    }
+    let pot_before = game.pot_lamports;
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    game.last_buyer = owner_key;
+
+    ctx.accounts.player_stats.lifetime_keys_bought = ctx
+        .accounts
+        .player_stats
+        .lifetime_keys_bought
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    ctx.accounts.player_stats.lifetime_lamports_spent = ctx
+        .accounts
+        .player_stats
+        .lifetime_lamports_spent
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Final-hour activation: sticky once the pot crosses the threshold ---
+    if !game.final_hour_active
+        && game.final_hour_pot_threshold_lamports > 0
+        && game.pot_lamports >= game.final_hour_pot_threshold_lamports
+    {
+        game.final_hour_active = true;
+        game.final_hour_start_keys = game.total_keys;
+        emit!(FinalHourActivated {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    let keys_since_final_hour_start = if game.final_hour_active {
+        game.total_keys.saturating_sub(game.final_hour_start_keys)
+    } else {
+        0
+    };
+
+    // --- Pot milestones: hype events (and an optional free-key bonus) for
+    // crossing a configured pot size. A single large buy can cross several
+    // at once; only the highest reached is reported.
+    let milestones_crossed = math::calculate_milestones_crossed(
+        pot_before,
+        game.pot_lamports,
+        game.pot_milestone_interval_lamports,
+    );
+    if milestones_crossed > 0 {
+        let bonus_keys = game.pot_milestone_bonus_keys;
+        if bonus_keys > 0 {
+            let bonus_weight = bonus_keys
+                .checked_mul(10_000)
+                .ok_or(FomoltError::Overflow)?;
+            player.keys = player
+                .keys
+                .checked_add(bonus_keys)
+                .ok_or(FomoltError::Overflow)?;
+            player.dividend_weight = player
+                .dividend_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_keys = game
+                .total_keys
+                .checked_add(bonus_keys)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_weight = game
+                .total_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        let milestone_number = game.pot_lamports / game.pot_milestone_interval_lamports;
+        emit!(MilestoneReached {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: owner_key,
+            milestone_number,
+            pot_lamports: game.pot_lamports,
+            bonus_keys_granted: bonus_keys,
+            timestamp: clock.unix_timestamp,
+        });
+
+        let ext = &mut ctx.accounts.game_state_ext;
+        ext.game_id = game.game_id;
+        ext.round = game.round;
+        ext.bump = ctx.bumps.game_state_ext;
+        ext.milestones_reached_this_round = ext
+            .milestones_reached_this_round
+            .checked_add(milestones_crossed as u32)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    game.timer_end = math::calculate_timer_extension(
+        clock.unix_timestamp,
+        math::TimerExtensionParams {
+            extension_secs: game.timer_extension_secs,
+            current_timer_end: game.timer_end,
+            round_start: game.round_start,
+            max_timer_secs: game.max_timer_secs,
+            final_hour_active: game.final_hour_active,
+            keys_since_final_hour_start,
+            final_hour_shrink_interval_keys: game.final_hour_shrink_interval_keys,
+            min_remaining_secs: game.min_remaining_secs,
+        },
+    )?;
+
+    emit!(KeysPurchased {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: owner_key,
+        is_agent: player.is_agent,
+        keys_bought: keys_to_buy,
+        total_player_keys: player.keys,
+        lamports_spent: cost,
+        pot_contribution,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if referral_bonus_paid > 0 {
+        if let Some(referrer) = player.referrer {
+            let ext = &mut ctx.accounts.game_state_ext;
+            ext.game_id = game.game_id;
+            ext.round = game.round;
+            ext.bump = ctx.bumps.game_state_ext;
+            ext.credit_referrer(referrer, referral_bonus_paid)?;
+
+            emit!(ReferralEarned {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                player: owner_key,
+                referrer,
+                keys_bought: keys_to_buy,
+                lamports_spent: cost,
+                referrer_lamports: referral_bonus_paid,
+                vested: !game.referral_vesting_enabled,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    emit!(PurchaseSettled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: owner_key,
+        lamports_spent: cost,
+        protocol_fee_lamports: house_fee,
+        referrer: player.referrer,
+        referral_bonus_lamports: referral_bonus_paid,
+        pot_contribution,
+        raffle_pool_lamports: raffle_amount,
+        winner_pot_lamports: winner_amount,
+        dividend_pool_lamports: dividend_amount,
+        next_round_lamports: next_round_amount,
+        dust_lamports: split_dust,
+        timestamp: clock.unix_timestamp,
+    });
+
+    let next_key_price = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+
+    let snapshot = &mut ctx.accounts.game_snapshot;
+    snapshot.pot_lamports = game.pot_lamports;
+    snapshot.total_keys = game.total_keys;
+    snapshot.timer_end = game.timer_end;
+    snapshot.last_buyer = game.last_buyer;
+    snapshot.next_key_price = next_key_price;
+
+    emit!(GameUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        pot_lamports: game.pot_lamports,
+        total_keys: game.total_keys,
+        next_key_price,
+        last_buyer: game.last_buyer,
+        timer_end: game.timer_end,
+        winner_pot: game.winner_pot,
+        next_round_pot: game.next_round_pot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
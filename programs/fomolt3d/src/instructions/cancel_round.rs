@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::{RoundCancelled, RoundStatusChanged};
+use crate::state::*;
+
+/// Admin-only escape hatch for a round started with a misconfigured
+/// override (e.g. wrong bps set). Freezes `game_state` in `Cancelled` —
+/// terminal, no further buys/claims/ends — and folds every pot bucket
+/// funded out of players' `pot_contribution` (`winner_pot`,
+/// `total_dividend_pool`, `next_round_pot`, `raffle_pool_lamports`,
+/// `dust_reserve`, `pot_overflow_reserve_lamports`) into
+/// `refund_pool_lamports`, so `instructions::refund`
+/// has a single obligation to pay `PlayerState::contributed_lamports` out
+/// of. Leaves already-drawn raffle prizes (`raffle_prize_pool_pending`) and
+/// referral/top-referrer obligations untouched — those are owed
+/// independently of this round's outcome and remain claimable as normal.
+#[derive(Accounts)]
+pub struct CancelRound<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+pub fn handle_cancel_round(ctx: Context<CancelRound>) -> Result<()> {
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(game.status == RoundStatus::Active, FomoltError::RoundNotCancellable);
+
+    let refunded = game
+        .winner_pot
+        .checked_add(game.total_dividend_pool)
+        .and_then(|v| v.checked_add(game.next_round_pot))
+        .and_then(|v| v.checked_add(game.raffle_pool_lamports))
+        .and_then(|v| v.checked_add(game.dust_reserve))
+        .and_then(|v| v.checked_add(game.pot_overflow_reserve_lamports))
+        .ok_or(FomoltError::Overflow)?;
+
+    game.winner_pot = 0;
+    game.total_dividend_pool = 0;
+    game.next_round_pot = 0;
+    game.raffle_pool_lamports = 0;
+    game.dust_reserve = 0;
+    game.pot_overflow_reserve_lamports = 0;
+    game.refund_pool_lamports = refunded;
+
+    game.transition_status(RoundStatus::Cancelled)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        from: RoundStatus::Active,
+        to: RoundStatus::Cancelled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(RoundCancelled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        refund_pool_lamports: refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
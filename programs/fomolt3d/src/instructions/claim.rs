@@ -2,11 +2,18 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 use crate::errors::FomoltError;
-use crate::events::{Claimed, RoundConcluded};
+use crate::events::{
+    AgentAction, BlockedAttempt, Claimed, DividendsClaimed, DividendsCompounded, RoundConcluded,
+    RoundStatusChanged, VaultFlow, WinnerPaid,
+};
 use crate::math;
 use crate::state::*;
 
 
+/// Combined dividend + winner claim, kept as-is for existing clients. New
+/// integrations should prefer `claim_dividends`/`claim_winner` instead,
+/// which have independent double-claim guards and don't force a winner to
+/// take both payouts in the same transaction.
 #[derive(Accounts)]
 pub struct Claim<'info> {
     #[account(mut)]
@@ -14,20 +21,28 @@ pub struct Claim<'info> {
 
     #[account(
         mut,
-        seeds = [b"game", game_state.round.to_le_bytes().as_ref()],
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
         bump = game_state.bump,
     )]
     pub game_state: Account<'info, GameState>,
 
     #[account(
         mut,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
         bump = player_state.bump,
         has_one = player,
         constraint = player_state.current_round == game_state.round @ FomoltError::PlayerNotInRound,
     )]
     pub player_state: Account<'info, PlayerState>,
 
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        mut,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
     /// Game vault PDA that holds SOL
     #[account(
         mut,
@@ -36,9 +51,46 @@ pub struct Claim<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Required only when `player_state.auto_compound` is true — the
+    /// currently active round that the dividend share gets reinvested into.
+    /// Must belong to the same game lineage as `game_state`. Left out of the
+    /// `seeds` constraint (unlike `game_state` above) because it can be any
+    /// round number, not one derivable from already-known data; validated
+    /// manually in the handler instead, the same way `forfeit_winner_pot`
+    /// validates its own old/current `GameState` pair.
+    /// CHECK: Validated manually in handler (game_id match, active check)
+    #[account(mut)]
+    pub current_game_state: Option<Account<'info, GameState>>,
+
+    /// Vault for `current_game_state`.
+    /// CHECK: Validated manually in handler (PDA derivation against current_game_state)
+    #[account(mut)]
+    pub current_vault: Option<SystemAccount<'info>>,
+
+    /// Required only when `player_state.payout_address` is set — the cash
+    /// payout is sent here instead of to `player`. Must equal
+    /// `player_state.payout_address`; any wallet works, there's no PDA to
+    /// derive since this is an arbitrary beneficiary chosen by the player.
+    /// CHECK: Validated manually in handler (key equality against player_state.payout_address)
+    #[account(mut)]
+    pub payout_destination: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// Legacy combined entry point — see `claim_dividends`/`claim_winner` for
+/// the split instructions with independent double-claim guards.
 pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
     let game_key = ctx.accounts.game_state.key();
     let vault_bump = ctx.bumps.vault;
@@ -46,10 +98,22 @@ pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
     let player = &mut ctx.accounts.player_state;
     let clock = Clock::get()?;
 
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
     // --- Auto-end check ---
-    if clock.unix_timestamp >= game.timer_end && game.active {
-        game.active = false;
+    if clock.unix_timestamp >= game.timer_end && game.status == RoundStatus::Active {
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
         emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
             round: game.round,
             winner: game.last_buyer,
             winner_lamports: game.winner_pot,
@@ -59,23 +123,71 @@ pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
             next_round_pot: game.next_round_pot,
             round_start: game.round_start,
             round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
             timestamp: clock.unix_timestamp,
         });
     }
 
+    // A cancelled round has no winner/dividends left to claim — everything
+    // still owed lives in refund_pool_lamports, payable via `refund` instead.
+    require!(game.status != RoundStatus::Cancelled, FomoltError::RoundCancelled);
+
     // Dividends are only claimable after the round ends
-    require!(!game.active, FomoltError::GameStillActive);
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+    // --- Blocklist check: blocked wallets can only claim if their policy
+    // explicitly allows withdrawing winnings already owed to them ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
 
-    // --- Calculate proportional dividend share ---
-    let dividend_share = math::calculate_dividend_share(
-        player.keys,
-        game.total_dividend_pool,
-        game.total_keys,
-    )?;
+    // --- Calculate proportional dividend share (weight, not raw keys —
+    // early-bird bonuses give some keys more dividend weight than others).
+    // When time-weighted dividends are enabled, sync both accumulators up
+    // to the round's actual end (not `clock.unix_timestamp`, which may be
+    // long after — neither `total_weight` nor `dividend_weight` changes
+    // post-end, so capping at `timer_end` is what every claimant's split
+    // converges to regardless of when they actually claim) and split by
+    // weight-seconds instead of the point-in-time weight snapshot.
+    let dividend_share = if game.time_weighted_dividends_enabled {
+        let round_end = game.timer_end;
+        game.sync_dividend_seconds(round_end)?;
+        player.sync_dividend_seconds(round_end)?;
+        math::calculate_dividend_share_weighted(
+            player.dividend_weight_seconds,
+            game.total_dividend_pool,
+            game.dividend_weight_seconds_total,
+        )?
+    } else {
+        math::calculate_dividend_share(
+            player.dividend_weight,
+            game.total_dividend_pool,
+            game.total_weight,
+        )?
+    };
 
     // --- Check if player is the winner ---
     let is_winner = ctx.accounts.player.key() == game.last_buyer
-        && !game.winner_claimed;
+        && !game.winner_claimed();
 
     let winner_payout = if is_winner { game.winner_pot } else { 0 };
 
@@ -85,33 +197,209 @@ pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
 
     require!(total_payout > 0, FomoltError::NothingToClaim);
 
-    // --- Vault balance check ---
-    require!(
-        ctx.accounts.vault.lamports() >= total_payout,
-        FomoltError::InsufficientFunds
-    );
+    // --- Vault solvency check: payout must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= total_payout, FomoltError::VaultInsolvent);
 
-    // --- Transfer from vault to player via CPI (vault is system-owned PDA) ---
     let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
 
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.player.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        total_payout,
-    )?;
+    // --- Auto-compound: reinvest the dividend share into keys of the
+    // currently active round instead of paying it out as SOL. The winner
+    // prize above is never compounded — it always settles in SOL. ---
+    let mut compounded_keys = 0u64;
+    let mut compounded_cost = 0u64;
+    let mut compounded_weight = 0u64;
+    let mut compound_destination_round = 0u64;
+
+    if player.auto_compound && dividend_share > 0 {
+        let current_vault = ctx
+            .accounts
+            .current_vault
+            .as_ref()
+            .ok_or(FomoltError::MissingCompoundTarget)?
+            .to_account_info();
+        let current_game = ctx
+            .accounts
+            .current_game_state
+            .as_mut()
+            .ok_or(FomoltError::MissingCompoundTarget)?;
+
+        require!(
+            current_game.game_id == game.game_id,
+            FomoltError::GameIdMismatch
+        );
+        require!(current_game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+        let (expected_vault, _) =
+            Pubkey::find_program_address(&[b"vault", current_game.key().as_ref()], ctx.program_id);
+        require!(
+            current_vault.key() == expected_vault,
+            FomoltError::VaultMismatch
+        );
+
+        compounded_keys = math::calculate_max_keys(
+            dividend_share,
+            current_game.total_keys,
+            current_game.base_price_lamports,
+            current_game.price_increment_lamports,
+        )?;
+
+        if compounded_keys > 0 {
+            compounded_cost = math::calculate_cost(
+                current_game.total_keys,
+                compounded_keys,
+                current_game.base_price_lamports,
+                current_game.price_increment_lamports,
+            )?;
+
+            // Same pot/dividend/next-round split as a real buy_keys purchase,
+            // minus the protocol fee and referral slices — this is already-
+            // earned capital moving rounds, not a fresh external purchase.
+            let winner_amount =
+                math::calculate_bps_split(compounded_cost, current_game.winner_bps)?;
+            let dividend_amount =
+                math::calculate_bps_split(compounded_cost, current_game.dividend_bps)?;
+            let next_round_amount = compounded_cost
+                .checked_sub(winner_amount)
+                .and_then(|r| r.checked_sub(dividend_amount))
+                .ok_or(FomoltError::Overflow)?;
+
+            compounded_weight = math::calculate_key_weight(
+                current_game.total_keys,
+                compounded_keys,
+                current_game.early_bird_key_threshold,
+                current_game.early_bird_multiplier_bps,
+            )?;
+
+            current_game.winner_pot = current_game
+                .winner_pot
+                .checked_add(winner_amount)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.total_dividend_pool = current_game
+                .total_dividend_pool
+                .checked_add(dividend_amount)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.next_round_pot = current_game
+                .next_round_pot
+                .checked_add(next_round_amount)
+                .ok_or(FomoltError::Overflow)?;
+            if current_game.time_weighted_dividends_enabled {
+                current_game.sync_dividend_seconds(clock.unix_timestamp)?;
+            }
+            current_game.total_weight = current_game
+                .total_weight
+                .checked_add(compounded_weight)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.total_keys = current_game
+                .total_keys
+                .checked_add(compounded_keys)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.pot_lamports = current_game
+                .pot_lamports
+                .checked_add(compounded_cost)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.total_players = current_game
+                .total_players
+                .checked_add(1)
+                .ok_or(FomoltError::Overflow)?;
+            current_game.vault_lamports_in = current_game
+                .vault_lamports_in
+                .checked_add(compounded_cost)
+                .ok_or(FomoltError::Overflow)?;
+            compound_destination_round = current_game.round;
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: current_vault,
+                    },
+                    signer_seeds,
+                ),
+                compounded_cost,
+            )?;
+        }
+    }
+
+    // --- Cash out whatever wasn't reinvested (winner prize + any dust the
+    // compounded cost didn't use) via CPI (vault is system-owned PDA) ---
+    let cash_payout = total_payout
+        .checked_sub(compounded_cost)
+        .ok_or(FomoltError::Overflow)?;
+
+    if cash_payout > 0 {
+        // --- Resolve the payout destination: player_state.payout_address
+        // when set, otherwise the signer (the pre-existing behavior) ---
+        let payout_to = match player.payout_address {
+            Some(expected) => {
+                let destination = ctx
+                    .accounts
+                    .payout_destination
+                    .as_ref()
+                    .ok_or(FomoltError::MissingPayoutDestination)?;
+                require!(
+                    destination.key() == expected,
+                    FomoltError::PayoutDestinationMismatch
+                );
+                destination.to_account_info()
+            }
+            None => ctx.accounts.player.to_account_info(),
+        };
+        let payout_to_key = payout_to.key();
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: payout_to,
+                },
+                signer_seeds,
+            ),
+            cash_payout,
+        )?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::Out,
+            reason: VaultFlowReason::Claim,
+            lamports: cash_payout,
+            counterparty: payout_to_key,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(total_payout)
+        .ok_or(FomoltError::Overflow)?;
 
     // --- Update game state ---
     // Note: total_dividend_pool and total_keys stay constant through claims.
-    // Double-claim is prevented by resetting player.current_round = 0 below.
+    // Double-claim is prevented by resetting player.current_round = 0 below
+    // (or, when compounding, by moving it to the destination round instead —
+    // re-claiming against this round's game_state then fails the
+    // `current_round == game_state.round` constraint either way).
+    // total_dividend_claimed_lamports is the one running total that does
+    // shrink the gap, so sweep_unclaimed_dividends can tell how much of
+    // total_dividend_pool is genuinely still outstanding.
+    game.total_dividend_claimed_lamports = game
+        .total_dividend_claimed_lamports
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
 
     if is_winner {
-        game.winner_claimed = true;
+        game.transition_status(RoundStatus::Settled)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Ended,
+            to: RoundStatus::Settled,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     // --- Update player state: reset to prevent double-claim ---
@@ -119,10 +407,73 @@ pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
         .claimed_dividends_lamports
         .checked_add(dividend_share)
         .ok_or(FomoltError::Overflow)?;
-    player.keys = 0;
-    player.current_round = 0; // sentinel — prevents re-claim
+    if compounded_keys > 0 {
+        // Reinvested — the player now participates in the destination round
+        // with the keys just bought, not a fresh empty slate.
+        player.keys = compounded_keys;
+        player.dividend_weight = compounded_weight;
+        player.current_round = compound_destination_round;
+    } else {
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.current_round = 0; // sentinel — prevents re-claim
+    }
+    // Weight-seconds accrual always restarts clean, whether the player is
+    // done for good or just reinvested into a different round's fresh tally.
+    player.dividend_weight_seconds = 0;
+    player.dividend_seconds_last_update = clock.unix_timestamp;
+
+    // --- Lifetime stats: dividends earned and round wins ---
+    let stats = &mut ctx.accounts.player_stats;
+    stats.lifetime_dividends_earned = stats
+        .lifetime_dividends_earned
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+    if is_winner {
+        stats.rounds_won = stats.rounds_won.checked_add(1).ok_or(FomoltError::Overflow)?;
+    }
+
+    if dividend_share > 0 {
+        emit!(DividendsClaimed {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            dividend_lamports: dividend_share,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if winner_payout > 0 {
+        emit!(WinnerPaid {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: ctx.accounts.player.key(),
+            lamports: winner_payout,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if compounded_keys > 0 {
+        emit!(DividendsCompounded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            dividend_lamports: dividend_share,
+            destination_round: compound_destination_round,
+            keys_bought: compounded_keys,
+            cash_out_lamports: dividend_share
+                .checked_sub(compounded_cost)
+                .ok_or(FomoltError::Overflow)?,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
     emit!(Claimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
         round: game.round,
         player: ctx.accounts.player.key(),
         dividend_lamports: dividend_share,
@@ -131,5 +482,17 @@ pub fn handle_claim(ctx: Context<Claim>) -> Result<()> {
         timestamp: clock.unix_timestamp,
     });
 
+    if player.is_agent {
+        emit!(AgentAction {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            strategy_tag: player.strategy_tag,
+            action: "claim".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{AgentPlatformEarningsClaimed, BlockedAttempt, VaultFlow};
+use crate::state::*;
+
+/// Pays out an `AgentPlatform`'s accrued fee share, signed by the platform
+/// itself — see `instructions::register_agent_platform` and
+/// `instructions::buy_keys` for how `pending_earnings_lamports` accrues.
+#[derive(Accounts)]
+pub struct ClaimAgentPlatformEarnings<'info> {
+    #[account(mut)]
+    pub platform: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"agent_platform", game_state.game_id.to_le_bytes().as_ref(), platform.key().as_ref()],
+        bump = agent_platform.bump,
+        has_one = platform,
+    )]
+    pub agent_platform: Account<'info, AgentPlatform>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, platform]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), platform.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_agent_platform_earnings(ctx: Context<ClaimAgentPlatformEarnings>) -> Result<()> {
+    let game = &mut ctx.accounts.game_state;
+    let agent_platform = &mut ctx.accounts.agent_platform;
+    let clock = Clock::get()?;
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.platform.key(),
+                action: "claim_agent_platform_earnings".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    let amount = agent_platform.pending_earnings_lamports;
+    require!(amount > 0, FomoltError::NoAgentPlatformEarnings);
+
+    // Cap claim at vault's available balance, same guard
+    // claim_referral_earnings uses — this vault may hold obligations from
+    // other buckets too, and an AgentPlatform's earnings may span rounds.
+    let vault_balance = ctx.accounts.vault.lamports();
+    let reserved = game
+        .winner_pot
+        .checked_add(game.total_dividend_pool)
+        .and_then(|v| v.checked_add(game.next_round_pot))
+        .and_then(|v| v.checked_add(game.total_referral_obligations))
+        .ok_or(FomoltError::Overflow)?;
+    let available = vault_balance.saturating_sub(reserved);
+    let amount = amount.min(available);
+    require!(amount > 0, FomoltError::InsufficientFunds);
+
+    let game_key = game.key();
+    let vault_bump = ctx.bumps.vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.platform.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::AgentPlatform,
+        lamports: amount,
+        counterparty: ctx.accounts.platform.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    agent_platform.claimed_earnings_lamports = agent_platform
+        .claimed_earnings_lamports
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    agent_platform.pending_earnings_lamports = agent_platform
+        .pending_earnings_lamports
+        .checked_sub(amount)
+        .ok_or(FomoltError::Overflow)?;
+    // Obligation may have accrued against a different round's game_state than the
+    // one funding this claim (platform earnings are not round-scoped) — saturate
+    // rather than error so an already-settled obligation can't block the claim.
+    game.total_agent_platform_obligations =
+        game.total_agent_platform_obligations.saturating_sub(amount);
+
+    emit!(AgentPlatformEarningsClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        platform: agent_platform.platform,
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
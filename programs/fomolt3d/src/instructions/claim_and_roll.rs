@@ -0,0 +1,505 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{
+    BlockedAttempt, Claimed, DividendsClaimed, NextRoundSeeded, RoundConcluded, RoundStarted,
+    RoundStatusChanged, VaultFlow, WinnerPaid,
+};
+use crate::math;
+use crate::state::*;
+
+/// `Claim`'s accounts minus its optional auto-compound and payout-redirect
+/// accounts, plus `config` and the next round's `GameState`/vault/snapshot so the
+/// rollover can happen in the same transaction — no separate `start_new_round`
+/// call, no stalling on whoever would otherwise be the one to send it.
+#[derive(Accounts)]
+pub struct ClaimAndRoll<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// The round being claimed from — also the round being rolled over.
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+        constraint = player_state.current_round == game_state.round @ FomoltError::PlayerNotInRound,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        mut,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL — doubles as the rollover's source vault
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// New round's game state PDA
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameState::SPACE,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), (game_state.round + 1).to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub new_game_state: Account<'info, GameState>,
+
+    /// New round's vault
+    /// CHECK: New vault PDA, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", new_game_state.key().as_ref()],
+        bump,
+    )]
+    pub new_vault: SystemAccount<'info>,
+
+    /// Indexer-friendly mirror of the new round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameSnapshot::SPACE,
+        seeds = [b"snapshot", new_game_state.key().as_ref()],
+        bump,
+    )]
+    pub new_game_snapshot: Account<'info, GameSnapshot>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim the caller's dividends (and winner prize, if applicable) from a
+/// concluded round, then immediately roll the game over into the next round
+/// — all in one instruction. Equivalent to calling `claim` followed by
+/// `start_new_round`, except a single crank (or any claimant) can do both at
+/// once instead of the game waiting on a second, separate call.
+///
+/// Only reachable once the round has ended and `total_keys > 0` — an empty
+/// round has nothing to claim, so `claim`'s own `NothingToClaim` check stops
+/// this instruction before the rollover half ever runs.
+pub fn handle_claim_and_roll(ctx: Context<ClaimAndRoll>) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let new_round = ctx.accounts.game_state.round.checked_add(1).ok_or(FomoltError::Overflow)?;
+    let config = &mut ctx.accounts.config;
+    let game = &mut ctx.accounts.game_state;
+    let player = &mut ctx.accounts.player_state;
+    let clock = Clock::get()?;
+
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
+    // --- Auto-end check (same as `claim`) ---
+    if clock.unix_timestamp >= game.timer_end && game.status == RoundStatus::Active {
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: game.last_buyer,
+            winner_lamports: game.winner_pot,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            total_players: game.total_players,
+            next_round_pot: game.next_round_pot,
+            round_start: game.round_start,
+            round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // A cancelled round has no winner/dividends left to claim — everything
+    // still owed lives in refund_pool_lamports, payable via `refund` instead.
+    require!(game.status != RoundStatus::Cancelled, FomoltError::RoundCancelled);
+
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim_and_roll".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    // --- Claim half: same dividend/winner payout as `handle_claim`, paid
+    // straight to the signer (no auto-compound, no `payout_address` redirect) ---
+    let dividend_share = if game.time_weighted_dividends_enabled {
+        let round_end = game.timer_end;
+        game.sync_dividend_seconds(round_end)?;
+        player.sync_dividend_seconds(round_end)?;
+        math::calculate_dividend_share_weighted(
+            player.dividend_weight_seconds,
+            game.total_dividend_pool,
+            game.dividend_weight_seconds_total,
+        )?
+    } else {
+        math::calculate_dividend_share(
+            player.dividend_weight,
+            game.total_dividend_pool,
+            game.total_weight,
+        )?
+    };
+
+    let is_winner = ctx.accounts.player.key() == game.last_buyer && !game.winner_claimed();
+    let winner_payout = if is_winner { game.winner_pot } else { 0 };
+    let total_payout = dividend_share
+        .checked_add(winner_payout)
+        .ok_or(FomoltError::Overflow)?;
+
+    require!(total_payout > 0, FomoltError::NothingToClaim);
+
+    let rent = Rent::get()?;
+    let rent_exempt_min = rent.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= total_payout, FomoltError::VaultInsolvent);
+
+    let vault_signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            vault_signer_seeds,
+        ),
+        total_payout,
+    )?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::Claim,
+        lamports: total_payout,
+        counterparty: ctx.accounts.player.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(total_payout)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_claimed_lamports = game
+        .total_dividend_claimed_lamports
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+
+    if is_winner {
+        game.transition_status(RoundStatus::Settled)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Ended,
+            to: RoundStatus::Settled,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    player.claimed_dividends_lamports = player
+        .claimed_dividends_lamports
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+    player.keys = 0;
+    player.dividend_weight = 0;
+    player.current_round = 0;
+
+    let stats = &mut ctx.accounts.player_stats;
+    stats.lifetime_dividends_earned = stats
+        .lifetime_dividends_earned
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+    if is_winner {
+        stats.rounds_won = stats.rounds_won.checked_add(1).ok_or(FomoltError::Overflow)?;
+    }
+
+    if dividend_share > 0 {
+        emit!(DividendsClaimed {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            dividend_lamports: dividend_share,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if winner_payout > 0 {
+        emit!(WinnerPaid {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: ctx.accounts.player.key(),
+            lamports: winner_payout,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(Claimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.player.key(),
+        dividend_lamports: dividend_share,
+        winner_lamports: winner_payout,
+        total_lamports: total_payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // --- Rollover half: identical in spirit to `handle_start_new_round`.
+    // `total_keys == 0` is unreachable here — an empty round always has
+    // `total_payout == 0` above and returns `NothingToClaim` first, so the
+    // "forward the whole vault" branch `start_new_round` needs for empty
+    // rounds never applies to this combined instruction.
+    let vault_balance = ctx.accounts.vault.lamports();
+    let desired = game.next_round_pot;
+    let remaining = vault_balance.saturating_sub(desired);
+    let min_rent = rent_exempt_min;
+    let carry_over = if remaining > 0 && remaining < min_rent {
+        vault_balance
+    } else {
+        desired
+    };
+
+    let mut new_vault_lamports_in = 0u64;
+    if carry_over > 0 {
+        require!(vault_balance >= carry_over, FomoltError::InsufficientFunds);
+
+        let min_rent_vault = rent.minimum_balance(0);
+        if carry_over < min_rent_vault {
+            let gap = min_rent_vault.saturating_sub(carry_over);
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.new_vault.to_account_info(),
+                    },
+                ),
+                gap,
+            )?;
+            new_vault_lamports_in = new_vault_lamports_in
+                .checked_add(gap)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.new_vault.to_account_info(),
+                },
+                vault_signer_seeds,
+            ),
+            carry_over,
+        )?;
+        game.vault_lamports_out = game
+            .vault_lamports_out
+            .checked_add(carry_over)
+            .ok_or(FomoltError::Overflow)?;
+        new_vault_lamports_in = new_vault_lamports_in
+            .checked_add(carry_over)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::Out,
+            reason: VaultFlowReason::Carry,
+            lamports: carry_over,
+            counterparty: ctx.accounts.new_vault.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: new_round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Carry,
+            lamports: carry_over,
+            counterparty: ctx.accounts.vault.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let new_game = &mut ctx.accounts.new_game_state;
+    new_game.game_id = game.game_id;
+    new_game.round = new_round;
+    new_game.pot_lamports = carry_over;
+    new_game.timer_end = clock
+        .unix_timestamp
+        .checked_add(config.max_timer_secs)
+        .ok_or(FomoltError::Overflow)?;
+    new_game.last_buyer = Pubkey::default();
+    new_game.total_keys = 0;
+    new_game.round_start = clock.unix_timestamp;
+    new_game.status = RoundStatus::Pending;
+    new_game.total_players = 0;
+    new_game.total_dividend_pool = 0;
+    new_game.next_round_pot = 0;
+    new_game.total_referral_obligations = 0;
+    new_game.total_weight = 0;
+    new_game.purchase_count = 0;
+    new_game.gross_volume_lamports = 0;
+    new_game.max_single_buy_lamports = 0;
+    new_game.max_single_buyer = Pubkey::default();
+    new_game.dividend_weight_seconds_total = 0;
+    new_game.dividend_seconds_last_update = new_game.round_start;
+    new_game.winner_pot = carry_over;
+
+    new_game.base_price_lamports = config.base_price_lamports;
+    new_game.price_increment_lamports = config.price_increment_lamports;
+    new_game.timer_extension_secs = config.timer_extension_secs;
+    new_game.max_timer_secs = config.max_timer_secs;
+    new_game.winner_bps = config.winner_bps;
+    new_game.dividend_bps = config.dividend_bps;
+    new_game.next_round_bps = config.next_round_bps;
+    new_game.protocol_fee_bps = config.protocol_fee_bps;
+    new_game.referral_bonus_bps = config.referral_bonus_bps;
+    new_game.protocol_wallet = config.protocol_wallet;
+    new_game.early_bird_key_threshold = config.early_bird_key_threshold;
+    new_game.early_bird_multiplier_bps = config.early_bird_multiplier_bps;
+    new_game.min_purchase_lamports = config.min_purchase_lamports;
+    new_game.winner_claim_window_secs = config.winner_claim_window_secs;
+    new_game.final_hour_pot_threshold_lamports = config.final_hour_pot_threshold_lamports;
+    new_game.final_hour_shrink_interval_keys = config.final_hour_shrink_interval_keys;
+    new_game.final_hour_active = false;
+    new_game.final_hour_start_keys = 0;
+    new_game.pot_milestone_interval_lamports = config.pot_milestone_interval_lamports;
+    new_game.pot_milestone_bonus_keys = config.pot_milestone_bonus_keys;
+    new_game.vault_lamports_in = new_vault_lamports_in;
+    new_game.vault_lamports_out = 0;
+    new_game.keeper_fee_lamports = config.keeper_fee_lamports;
+    new_game.purchase_history_enabled = config.purchase_history_enabled;
+    new_game.time_weighted_dividends_enabled = config.time_weighted_dividends_enabled;
+    new_game.hook_program = config.hook_program;
+    new_game.referral_earnings_cap_lamports_per_round = config.referral_earnings_cap_lamports_per_round;
+    new_game.referral_decay_threshold_lamports = config.referral_decay_threshold_lamports;
+    new_game.referrer_change_cooldown_secs = config.referrer_change_cooldown_secs;
+    new_game.dividend_merkle_root = None;
+    new_game.kyc_required = config.kyc_required;
+    new_game.kyc_issuer = config.kyc_issuer;
+    new_game.dust_reserve = 0;
+    new_game.price_cumulative = 0;
+    new_game.price_last_update = new_game.round_start;
+    new_game.unclaimed_dividend_policy = config.unclaimed_dividend_policy;
+    new_game.dividend_claim_window_secs = config.dividend_claim_window_secs;
+    new_game.max_timer_extensions_per_window = config.max_timer_extensions_per_window;
+    new_game.timer_extension_window_secs = config.timer_extension_window_secs;
+    new_game.total_dividend_claimed_lamports = 0;
+
+    new_game.bump = ctx.bumps.new_game_state;
+
+    config.latest_round = new_round;
+
+    let snapshot = &mut ctx.accounts.new_game_snapshot;
+    snapshot.game_id = new_game.game_id;
+    snapshot.round = new_game.round;
+    snapshot.pot_lamports = new_game.pot_lamports;
+    snapshot.total_keys = new_game.total_keys;
+    snapshot.timer_end = new_game.timer_end;
+    snapshot.last_buyer = new_game.last_buyer;
+    snapshot.next_key_price = math::calculate_cost(
+        new_game.total_keys,
+        1,
+        new_game.base_price_lamports,
+        new_game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+    snapshot.bump = ctx.bumps.new_game_snapshot;
+
+    new_game.transition_status(RoundStatus::Active)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: new_game.game_id,
+        round: new_game.round,
+        from: RoundStatus::Pending,
+        to: RoundStatus::Active,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(RoundStarted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: new_game.game_id,
+        round: new_round,
+        carry_over_lamports: carry_over,
+        timer_end: new_game.timer_end,
+        base_price_lamports: new_game.base_price_lamports,
+        price_increment_lamports: new_game.price_increment_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if carry_over > 0 {
+        emit!(NextRoundSeeded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: new_game.game_id,
+            source_round: new_round - 1,
+            round: new_round,
+            lamports: carry_over,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{BiggestBuyerBonusClaimed, BlockedAttempt, VaultFlow};
+use crate::state::*;
+
+/// Pays out `GameState::biggest_buyer_bonus_pool` — carved out of the winner
+/// pot by `end_round` — to whoever made `GameState::max_single_buy_lamports`
+/// this round. Only ever claimable by that single buyer; a round-scoped
+/// prize like `claim_top_referrer_bonus`, not a running balance. Unlike that
+/// instruction, the leader lives directly on `GameState` rather than a
+/// separate `GameStateExt` PDA, so no extra account is needed here.
+#[derive(Accounts)]
+pub struct ClaimBiggestBuyerBonus<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"config", game_state.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, buyer]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_biggest_buyer_bonus(ctx: Context<ClaimBiggestBuyerBonus>) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .config
+            .is_instruction_disabled(GlobalConfig::FLAG_CLAIM_BIGGEST_BUYER_BONUS),
+        FomoltError::FeatureDisabled
+    );
+
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.buyer.key(),
+                action: "claim_biggest_buyer_bonus".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+    require!(game.max_single_buyer == ctx.accounts.buyer.key(), FomoltError::NotBiggestBuyer);
+
+    let amount = game.biggest_buyer_bonus_pool;
+    require!(amount > 0, FomoltError::NoBiggestBuyerBonus);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.biggest_buyer_bonus_pool = 0;
+
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::RoundBonus,
+        lamports: amount,
+        counterparty: ctx.accounts.buyer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(BiggestBuyerBonusClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        buyer: ctx.accounts.buyer.key(),
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
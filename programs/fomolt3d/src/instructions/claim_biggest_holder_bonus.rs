@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{BiggestHolderBonusClaimed, BlockedAttempt, VaultFlow};
+use crate::state::*;
+
+/// Pays out `GameState::biggest_holder_bonus_pool` — carved out of the
+/// winner pot by `end_round` — to `GameState::largest_holder`, whoever held
+/// `GameState::largest_holder_keys` when the round concluded. Only ever
+/// claimable by that single holder; a round-scoped prize like
+/// `claim_top_referrer_bonus`, not a running balance. Unlike that
+/// instruction, the leader lives directly on `GameState` rather than a
+/// separate `GameStateExt` PDA, so no extra account is needed here.
+#[derive(Accounts)]
+pub struct ClaimBiggestHolderBonus<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"config", game_state.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, holder]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_biggest_holder_bonus(ctx: Context<ClaimBiggestHolderBonus>) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .config
+            .is_instruction_disabled(GlobalConfig::FLAG_CLAIM_BIGGEST_HOLDER_BONUS),
+        FomoltError::FeatureDisabled
+    );
+
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.holder.key(),
+                action: "claim_biggest_holder_bonus".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+    require!(game.largest_holder == ctx.accounts.holder.key(), FomoltError::NotLargestHolder);
+
+    let amount = game.biggest_holder_bonus_pool;
+    require!(amount > 0, FomoltError::NoBiggestHolderBonus);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.holder.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.biggest_holder_bonus_pool = 0;
+
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::RoundBonus,
+        lamports: amount,
+        counterparty: ctx.accounts.holder.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(BiggestHolderBonusClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        holder: ctx.accounts.holder.key(),
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
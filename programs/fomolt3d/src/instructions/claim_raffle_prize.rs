@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use solana_sha256_hasher::hashv;
+
+use crate::errors::FomoltError;
+use crate::events::{BlockedAttempt, RaffleClaimed};
+use crate::state::*;
+
+/// Permissionless: anyone may submit a valid proof on `player`'s behalf,
+/// same shape as `claim_with_proof`. The leaf being proven is
+/// `(player, weight_range_start, weight_range_end)` from the Merkle root
+/// `record_raffle_snapshot` committed for this day; the claim succeeds only
+/// if `raffle_snapshot.winning_ticket` (set by `draw_raffle_ticket`) falls
+/// inside that range. Pays the day's whole `prize_lamports` straight to
+/// `player`'s wallet — no `PlayerState` account is read or required.
+/// `raffle_claim_receipt`'s `init` constraint is the double-claim guard: a
+/// second attempt for the same (day, player) fails on re-initialization.
+#[derive(Accounts)]
+pub struct ClaimRafflePrize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The wallet the claimed leaf was made out to — receives the payout
+    /// directly. Does not need to sign; the Merkle proof is the authorization.
+    /// CHECK: Only used as a lamport destination; identity is the leaf itself
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"raffle", game_state.key().as_ref(), raffle_snapshot.day_index.to_le_bytes().as_ref()],
+        bump = raffle_snapshot.bump,
+    )]
+    pub raffle_snapshot: Account<'info, RaffleSnapshot>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`. Keyed off `player` (the payee), not
+    /// `payer`, since this claim is permissionless — the payer is never the
+    /// one being paid.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RaffleClaimReceipt::SPACE,
+        seeds = [b"raffle_claim", raffle_snapshot.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub raffle_claim_receipt: Account<'info, RaffleClaimReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Recomputes the Merkle root from `leaf` and `proof`, combining each step
+/// with sorted-pair hashing (`hash(min, max)`) so a proof doesn't need to
+/// separately encode which side of each pair the accumulator is on. Same
+/// algorithm as `claim_with_proof::compute_merkle_root`, duplicated here
+/// because the leaf shape differs (a weight range, not a flat amount).
+fn compute_merkle_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed
+}
+
+pub fn handle_claim_raffle_prize(
+    ctx: Context<ClaimRafflePrize>,
+    weight_range_start: u64,
+    weight_range_end: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let snapshot = &ctx.accounts.raffle_snapshot;
+    let clock = Clock::get()?;
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: ctx.accounts.game_state.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim_raffle_prize".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    let winning_ticket = snapshot.winning_ticket.ok_or(FomoltError::RaffleNotDrawn)?;
+    require!(
+        winning_ticket >= weight_range_start && winning_ticket < weight_range_end,
+        FomoltError::RaffleTicketOutOfRange
+    );
+
+    let leaf = hashv(&[
+        ctx.accounts.player.key().as_ref(),
+        &weight_range_start.to_le_bytes(),
+        &weight_range_end.to_le_bytes(),
+    ])
+    .to_bytes();
+    let computed_root = compute_merkle_root(leaf, &proof);
+    require!(computed_root == snapshot.merkle_root, FomoltError::InvalidRaffleProof);
+
+    let prize_lamports = snapshot.prize_lamports;
+    require!(prize_lamports > 0, FomoltError::NothingToClaim);
+
+    // --- Vault solvency check: payout must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= prize_lamports, FomoltError::VaultInsolvent);
+
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        prize_lamports,
+    )?;
+
+    let snapshot = &mut ctx.accounts.raffle_snapshot;
+    snapshot.prize_lamports = 0;
+
+    let game = &mut ctx.accounts.game_state;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(prize_lamports)
+        .ok_or(FomoltError::Overflow)?;
+    game.raffle_prize_pool_pending = game
+        .raffle_prize_pool_pending
+        .checked_sub(prize_lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    let receipt = &mut ctx.accounts.raffle_claim_receipt;
+    receipt.game_id = game.game_id;
+    receipt.round = game.round;
+    receipt.day_index = snapshot.day_index;
+    receipt.player = ctx.accounts.player.key();
+    receipt.lamports = prize_lamports;
+    receipt.bump = ctx.bumps.raffle_claim_receipt;
+
+    emit!(RaffleClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        day_index: snapshot.day_index,
+        player: ctx.accounts.player.key(),
+        lamports: prize_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 use crate::errors::FomoltError;
-use crate::events::{ReferralClaimed, RoundConcluded};
+use crate::events::{
+    BlockedAttempt, ReferralClaimed, RoundConcluded, RoundStatusChanged, VaultFlow,
+};
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -12,14 +14,20 @@ pub struct ClaimReferralEarnings<'info> {
 
     #[account(
         mut,
-        seeds = [b"game", game_state.round.to_le_bytes().as_ref()],
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
         bump = game_state.bump,
     )]
     pub game_state: Account<'info, GameState>,
 
+    #[account(
+        seeds = [b"config", game_state.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
     #[account(
         mut,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
         bump = player_state.bump,
         has_one = player,
     )]
@@ -33,19 +41,70 @@ pub struct ClaimReferralEarnings<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Required only when `player_state.payout_address` is set — the claim
+    /// is sent here instead of to `player`. Must equal
+    /// `player_state.payout_address`.
+    /// CHECK: Validated manually in handler (key equality against player_state.payout_address)
+    #[account(mut)]
+    pub payout_destination: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handle_claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .config
+            .is_instruction_disabled(GlobalConfig::FLAG_CLAIM_REFERRAL_EARNINGS),
+        FomoltError::FeatureDisabled
+    );
+
     let game_key = ctx.accounts.game_state.key();
     let player = &mut ctx.accounts.player_state;
     let game = &mut ctx.accounts.game_state;
     let clock_for_auto = Clock::get()?;
 
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim_referral_earnings".to_string(),
+                timestamp: clock_for_auto.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
     // --- Auto-end check ---
-    if clock_for_auto.unix_timestamp >= game.timer_end && game.active {
-        game.active = false;
+    if clock_for_auto.unix_timestamp >= game.timer_end && game.status == RoundStatus::Active {
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock_for_auto.unix_timestamp,
+        });
         emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
             round: game.round,
             winner: game.last_buyer,
             winner_lamports: game.winner_pot,
@@ -55,10 +114,39 @@ pub fn handle_claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Re
             next_round_pot: game.next_round_pot,
             round_start: game.round_start,
             round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
             timestamp: clock_for_auto.unix_timestamp,
         });
     }
 
+    // --- Vest pending referral earnings from this round, if
+    // GlobalConfig::referral_vesting_enabled held them back — see
+    // instructions::buy_keys. Only vests once the round they were earned in
+    // (this `game_state`, matched via `referral_earnings_round`) has ended;
+    // earnings still pending for a still-active round, or pending against a
+    // different round than the `game_state` supplied here, simply aren't
+    // claimable yet — call again with that round's `game_state` once it ends. ---
+    if player.pending_referral_earnings_lamports > 0
+        && player.referral_earnings_round == game.round
+        && game.status != RoundStatus::Active
+    {
+        player.referral_earnings_lamports = player
+            .referral_earnings_lamports
+            .checked_add(player.pending_referral_earnings_lamports)
+            .ok_or(FomoltError::Overflow)?;
+        player.pending_referral_earnings_lamports = 0;
+    }
+
     let amount = player.referral_earnings_lamports;
     require!(amount > 0, FomoltError::NoReferralEarnings);
 
@@ -79,17 +167,50 @@ pub fn handle_claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Re
     let vault_bump = ctx.bumps.vault;
     let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
 
+    // --- Resolve the payout destination: player_state.payout_address when
+    // set, otherwise the signer (the pre-existing behavior) ---
+    let payout_to = match player.payout_address {
+        Some(expected) => {
+            let destination = ctx
+                .accounts
+                .payout_destination
+                .as_ref()
+                .ok_or(FomoltError::MissingPayoutDestination)?;
+            require!(
+                destination.key() == expected,
+                FomoltError::PayoutDestinationMismatch
+            );
+            destination.to_account_info()
+        }
+        None => ctx.accounts.player.to_account_info(),
+    };
+    let payout_to_key = payout_to.key();
+
     system_program::transfer(
         CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
                 from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.player.to_account_info(),
+                to: payout_to,
             },
             signer_seeds,
         ),
         amount,
     )?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::Referral,
+        lamports: amount,
+        counterparty: payout_to_key,
+        timestamp: clock_for_auto.unix_timestamp,
+    });
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
 
     // --- Update state ---
     player.claimed_referral_earnings_lamports = player
@@ -100,9 +221,15 @@ pub fn handle_claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Re
         .referral_earnings_lamports
         .checked_sub(amount)
         .ok_or(FomoltError::Overflow)?;
+    // Obligation may have accrued against a different round's game_state than the
+    // one funding this claim (referral earnings are not round-scoped) — saturate
+    // rather than error so an already-settled obligation can't block the claim.
+    game.total_referral_obligations = game.total_referral_obligations.saturating_sub(amount);
 
     let clock = Clock::get()?;
     emit!(ReferralClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
         round: game.round,
         player: ctx.accounts.player.key(),
         lamports: amount,
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::SEASON_PAYOUT_BPS;
+use crate::errors::FomoltError;
+use crate::events::{BlockedAttempt, SeasonPrizeClaimed};
+use crate::state::*;
+
+/// Pays a `Season::leaderboard` rank its `constants::SEASON_PAYOUT_BPS`
+/// share of `season.pool_lamports`, sourced from `season_vault`. Only
+/// callable once `settle_season` has fixed the leaderboard. Same
+/// claim-receipt-as-existence-record double-claim guard as
+/// `claim_raffle_prize` — `season_claim_receipt`'s `init` constraint fails
+/// on a second attempt.
+#[derive(Accounts)]
+pub struct ClaimSeasonPrize<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"season", season.game_id.to_le_bytes().as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    /// Vault holding `season.pool_lamports`
+    #[account(
+        mut,
+        seeds = [b"season_vault", season.key().as_ref()],
+        bump,
+    )]
+    pub season_vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", season.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SeasonClaimReceipt::SPACE,
+        seeds = [b"season_claim", season.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub season_claim_receipt: Account<'info, SeasonClaimReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_season_prize(ctx: Context<ClaimSeasonPrize>) -> Result<()> {
+    let season = &ctx.accounts.season;
+    let clock = Clock::get()?;
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: season.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim_season_prize".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    require!(season.status == SeasonStatus::Settled, FomoltError::SeasonNotSettled);
+
+    let rank = season
+        .leaderboard
+        .iter()
+        .position(|e| e.player == ctx.accounts.player.key())
+        .filter(|&i| i < SEASON_PAYOUT_BPS.len())
+        .ok_or(FomoltError::NotOnPayableSeasonLeaderboard)?;
+
+    let amount = crate::math::calculate_bps_split(season.pool_lamports, SEASON_PAYOUT_BPS[rank])?;
+    require!(amount > 0, FomoltError::NothingToClaim);
+
+    let season_key = season.key();
+    let vault_bump = ctx.bumps.season_vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"season_vault", season_key.as_ref(), &[vault_bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.season_vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    let receipt = &mut ctx.accounts.season_claim_receipt;
+    receipt.game_id = season.game_id;
+    receipt.season_id = season.season_id;
+    receipt.player = ctx.accounts.player.key();
+    receipt.rank = rank as u8;
+    receipt.lamports = amount;
+    receipt.bump = ctx.bumps.season_claim_receipt;
+
+    emit!(SeasonPrizeClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: season.game_id,
+        season_id: season.season_id,
+        player: ctx.accounts.player.key(),
+        rank: rank as u8,
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,336 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::stake_history::StakeHistory;
+use solana_stake_interface::{
+    instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+
+use crate::errors::FomoltError;
+use crate::events::{BlockedAttempt, Claimed, ClaimedToStake, RoundConcluded, RoundStatusChanged};
+use crate::math;
+use crate::state::*;
+
+/// Same dividend/winner-payout math as `Claim`, minus auto-compound and a
+/// custom `payout_address` (narrower in scope, same precedent as
+/// `ClaimAndRoll`) — the payout is always delegated to `vote_account`
+/// instead of cashed out.
+#[derive(Accounts)]
+pub struct ClaimToStake<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.game_id == game_state.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+        constraint = player_state.current_round == game_state.round @ FomoltError::PlayerNotInRound,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        mut,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Must equal `GlobalConfig::approved_stake_vote_account` — the single
+    /// validator the admin allows players to auto-stake into. Read (not
+    /// written) by this program; the stake program is the one that actually
+    /// reads/writes its vote-credit bookkeping once delegated.
+    /// CHECK: Validated against config.approved_stake_vote_account below
+    #[account(
+        constraint = vote_account.key() == config.approved_stake_vote_account @ FomoltError::StakeVoteAccountNotApproved,
+    )]
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// Fresh, client-supplied keypair for the stake account created by this
+    /// instruction. Must not already exist — `create_account` fails otherwise.
+    #[account(mut)]
+    pub stake_account: Signer<'info>,
+
+    /// CHECK: Validated against the native stake program's well-known address in the handler
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub stake_history: Sysvar<'info, StakeHistory>,
+
+    /// Legacy account `delegate_stake` still expects in its account list for
+    /// backwards compatibility, even though the stake program no longer
+    /// reads it.
+    /// CHECK: Validated against the native stake config program's well-known address in the handler
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_to_stake(ctx: Context<ClaimToStake>) -> Result<()> {
+    require!(
+        ctx.accounts.stake_program.key() == solana_stake_interface::program::ID,
+        FomoltError::InvalidStakeProgramAccount
+    );
+    require!(
+        ctx.accounts.stake_config.key() == solana_stake_interface::config::ID,
+        FomoltError::InvalidStakeProgramAccount
+    );
+
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let player = &mut ctx.accounts.player_state;
+    let clock = Clock::get()?;
+
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
+    // --- Auto-end check ---
+    if clock.unix_timestamp >= game.timer_end && game.status == RoundStatus::Active {
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: game.last_buyer,
+            winner_lamports: game.winner_pot,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            total_players: game.total_players,
+            next_round_pot: game.next_round_pot,
+            round_start: game.round_start,
+            round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Dividends are only claimable after the round ends
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+    // --- Blocklist check: blocked wallets can only claim if their policy
+    // explicitly allows withdrawing winnings already owed to them ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    // --- Calculate proportional dividend share, same as `claim` ---
+    let dividend_share = if game.time_weighted_dividends_enabled {
+        let round_end = game.timer_end;
+        game.sync_dividend_seconds(round_end)?;
+        player.sync_dividend_seconds(round_end)?;
+        math::calculate_dividend_share_weighted(
+            player.dividend_weight_seconds,
+            game.total_dividend_pool,
+            game.dividend_weight_seconds_total,
+        )?
+    } else {
+        math::calculate_dividend_share(
+            player.dividend_weight,
+            game.total_dividend_pool,
+            game.total_weight,
+        )?
+    };
+
+    // --- Check if player is the winner ---
+    let is_winner = ctx.accounts.player.key() == game.last_buyer
+        && !game.winner_claimed();
+
+    let winner_payout = if is_winner { game.winner_pot } else { 0 };
+
+    let total_payout = dividend_share
+        .checked_add(winner_payout)
+        .ok_or(FomoltError::Overflow)?;
+
+    require!(total_payout > 0, FomoltError::NothingToClaim);
+
+    // --- Vault solvency check: payout must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= total_payout, FomoltError::VaultInsolvent);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    // --- Create, fund, and delegate the stake account in place of a cash
+    // payout. `player` is both staker and withdrawer, so they keep full
+    // control of the stake once delegated — this program never touches it
+    // again. ---
+    let stake_account_info = ctx.accounts.stake_account.to_account_info();
+    let authorized = Authorized {
+        staker: ctx.accounts.player.key(),
+        withdrawer: ctx.accounts.player.key(),
+    };
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &ctx.accounts.vault.key(),
+            &stake_account_info.key(),
+            total_payout,
+            StakeStateV2::size_of() as u64,
+            &solana_stake_interface::program::ID,
+        ),
+        &[
+            ctx.accounts.vault.to_account_info(),
+            stake_account_info.clone(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    invoke(
+        &stake_instruction::initialize(
+            &stake_account_info.key(),
+            &authorized,
+            &Lockup::default(),
+        ),
+        &[stake_account_info.clone(), ctx.accounts.clock.to_account_info()],
+    )?;
+
+    invoke(
+        &stake_instruction::delegate_stake(
+            &stake_account_info.key(),
+            &ctx.accounts.player.key(),
+            &ctx.accounts.vote_account.key(),
+        ),
+        &[
+            stake_account_info.clone(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+        ],
+    )?;
+
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(total_payout)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Update game state ---
+    game.total_dividend_claimed_lamports = game
+        .total_dividend_claimed_lamports
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+
+    if is_winner {
+        game.transition_status(RoundStatus::Settled)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Ended,
+            to: RoundStatus::Settled,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // --- Update player state: reset to prevent double-claim ---
+    player.claimed_dividends_lamports = player
+        .claimed_dividends_lamports
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+    player.keys = 0;
+    player.dividend_weight = 0;
+    player.current_round = 0; // sentinel — prevents re-claim
+    player.dividend_weight_seconds = 0;
+    player.dividend_seconds_last_update = clock.unix_timestamp;
+
+    // --- Lifetime stats: dividends earned and round wins ---
+    let stats = &mut ctx.accounts.player_stats;
+    stats.lifetime_dividends_earned = stats
+        .lifetime_dividends_earned
+        .checked_add(dividend_share)
+        .ok_or(FomoltError::Overflow)?;
+    if is_winner {
+        stats.rounds_won = stats.rounds_won.checked_add(1).ok_or(FomoltError::Overflow)?;
+    }
+
+    emit!(ClaimedToStake {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.player.key(),
+        stake_account: stake_account_info.key(),
+        vote_account: ctx.accounts.vote_account.key(),
+        dividend_lamports: dividend_share,
+        winner_lamports: winner_payout,
+        total_lamports: total_payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(Claimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.player.key(),
+        dividend_lamports: dividend_share,
+        winner_lamports: winner_payout,
+        total_lamports: total_payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{
+    AgentAction, BlockedAttempt, RoundConcluded, RoundStatusChanged, VaultFlow, WinnerPaid,
+};
+use crate::state::*;
+
+/// Slimmer than `ClaimDividends` — no compounding path exists for the winner
+/// prize (it always settles in SOL), so there's nothing to plug a current
+/// round's `GameState`/vault into.
+#[derive(Accounts)]
+pub struct ClaimWinner<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// No `current_round` constraint, unlike `ClaimDividends::player_state` —
+    /// the double-claim guard here is `GameState::winner_claimed()` (the
+    /// round's status), not anything on this account, so a player can claim
+    /// their dividends first (which resets `current_round`) and the winner
+    /// prize after, in either order.
+    #[account(
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        mut,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    /// Required only when `player_state.payout_address` is set — the prize
+    /// is sent here instead of to `player`.
+    /// CHECK: Validated manually in handler (key equality against player_state.payout_address)
+    #[account(mut)]
+    pub payout_destination: Option<SystemAccount<'info>>,
+
+    /// Present only when `GameState::season_length_rounds > 0` — the round's
+    /// `Season`, credited with a win for the leaderboard. Left out of
+    /// `seeds` and validated manually in the handler since its PDA depends
+    /// on `game_state.current_season_id()`, not on account-key data alone.
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays out the winner prize for a concluded round — this player's dividend
+/// share, if any, is untouched and stays claimable via the separate
+/// `claim_dividends` instruction. See `claim_dividends` for the split
+/// rationale; `instructions::claim` remains as the combined entry point for
+/// existing clients that still want both in one call.
+pub fn handle_claim_winner(ctx: Context<ClaimWinner>) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let player = &ctx.accounts.player_state;
+    let clock = Clock::get()?;
+
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
+    // --- Auto-end check (same as `claim`) ---
+    if clock.unix_timestamp >= game.timer_end && game.status == RoundStatus::Active {
+        game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            winner: game.last_buyer,
+            winner_lamports: game.winner_pot,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            total_players: game.total_players,
+            next_round_pot: game.next_round_pot,
+            round_start: game.round_start,
+            round_end: game.timer_end,
+            purchase_count: game.purchase_count,
+            gross_volume_lamports: game.gross_volume_lamports,
+            max_single_buy_lamports: game.max_single_buy_lamports,
+            max_single_buyer: game.max_single_buyer,
+            round_duration_secs: game.round_duration_secs(),
+            timer_extensions_triggered: game.timer_extensions_triggered,
+            average_seconds_between_buys: game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+            genesis_config_hash: game.genesis_config_hash,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // A cancelled round has no winner prize left to claim — everything still
+    // owed lives in refund_pool_lamports, payable via `refund` instead.
+    require!(game.status != RoundStatus::Cancelled, FomoltError::RoundCancelled);
+
+    // The winner prize is only claimable after the round ends
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+    // --- Blocklist check: same policy as `handle_claim` ---
+    if let Some(entry) = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())? {
+        if !entry.allow_claim {
+            emit!(BlockedAttempt {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                wallet: ctx.accounts.player.key(),
+                action: "claim_winner".to_string(),
+                timestamp: clock.unix_timestamp,
+            });
+            return err!(FomoltError::WalletBlocked);
+        }
+    }
+
+    require!(ctx.accounts.player.key() == game.last_buyer, FomoltError::NotWinner);
+    require!(!game.winner_claimed(), FomoltError::WinnerAlreadyClaimed);
+
+    let winner_payout = game.winner_pot;
+    require!(winner_payout > 0, FomoltError::NothingToClaim);
+
+    // --- Vault solvency check: payout must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= winner_payout, FomoltError::VaultInsolvent);
+
+    // --- Resolve the payout destination: player_state.payout_address when
+    // set, otherwise the signer (same preference `claim_dividends` honors) ---
+    let payout_to = match player.payout_address {
+        Some(expected) => {
+            let destination = ctx
+                .accounts
+                .payout_destination
+                .as_ref()
+                .ok_or(FomoltError::MissingPayoutDestination)?;
+            require!(
+                destination.key() == expected,
+                FomoltError::PayoutDestinationMismatch
+            );
+            destination.to_account_info()
+        }
+        None => ctx.accounts.player.to_account_info(),
+    };
+    let payout_to_key = payout_to.key();
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: payout_to,
+            },
+            signer_seeds,
+        ),
+        winner_payout,
+    )?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::ClaimWinner,
+        lamports: winner_payout,
+        counterparty: payout_to_key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(winner_payout)
+        .ok_or(FomoltError::Overflow)?;
+    game.transition_status(RoundStatus::Settled)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        from: RoundStatus::Ended,
+        to: RoundStatus::Settled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    let stats = &mut ctx.accounts.player_stats;
+    stats.rounds_won = stats.rounds_won.checked_add(1).ok_or(FomoltError::Overflow)?;
+
+    // --- Season win credit: only when the season meta-game is enabled and
+    // the caller supplied the current season's PDA ---
+    if game.season_length_rounds > 0 {
+        if let Some(season) = &mut ctx.accounts.season {
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"season",
+                    game.game_id.to_le_bytes().as_ref(),
+                    game.current_season_id().to_le_bytes().as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(season.key() == expected_pda, FomoltError::SeasonMismatch);
+            season.credit_win(ctx.accounts.player.key())?;
+        }
+    }
+
+    emit!(WinnerPaid {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        winner: ctx.accounts.player.key(),
+        lamports: winner_payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if player.is_agent {
+        emit!(AgentAction {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            strategy_tag: player.strategy_tag,
+            action: "claim_winner".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
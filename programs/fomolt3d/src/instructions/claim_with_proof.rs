@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use solana_sha256_hasher::hashv;
+
+use crate::errors::FomoltError;
+use crate::events::DividendClaimedViaProof;
+use crate::state::*;
+
+/// Permissionless: anyone (a relayer, or the player themselves) may submit a
+/// valid proof on `player`'s behalf. Pays `dividend_amount` straight to
+/// `player`'s wallet — no `PlayerState` account is read or required, which
+/// is the whole point for a round with thousands of holders the admin
+/// doesn't want to touch one-by-one. `merkle_claim_receipt`'s `init`
+/// constraint is the double-claim guard: a second attempt for the same
+/// (round, player) fails on re-initialization.
+#[derive(Accounts)]
+#[instruction(dividend_amount: u64)]
+pub struct ClaimWithProof<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The wallet the claimed leaf was made out to — receives the payout
+    /// directly. Does not need to sign; the Merkle proof is the authorization.
+    /// CHECK: Only used as a lamport destination; identity is the leaf itself
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MerkleClaimReceipt::SPACE,
+        seeds = [b"merkle_claim", game_state.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub merkle_claim_receipt: Account<'info, MerkleClaimReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Recomputes the Merkle root from `leaf` and `proof`, combining each step
+/// with sorted-pair hashing (`hash(min, max)`) so a proof doesn't need to
+/// separately encode which side of each pair the accumulator is on.
+fn compute_merkle_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed
+}
+
+pub fn handle_claim_with_proof(
+    ctx: Context<ClaimWithProof>,
+    dividend_amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let game = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    // No separate "round still active" check needed: `record_dividend_merkle_root`
+    // only ever sets this once the round has already ended, and it's never cleared.
+    let merkle_root = game
+        .dividend_merkle_root
+        .ok_or(FomoltError::MerkleRootNotSet)?;
+
+    require!(dividend_amount > 0, FomoltError::NothingToClaim);
+
+    let player_key = ctx.accounts.player.key();
+    let leaf = hashv(&[player_key.as_ref(), &dividend_amount.to_le_bytes()]).to_bytes();
+    require!(
+        compute_merkle_root(leaf, &proof) == merkle_root,
+        FomoltError::InvalidMerkleProof
+    );
+
+    // --- Vault solvency check: payout must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= dividend_amount, FomoltError::VaultInsolvent);
+
+    let game_key = game.key();
+    let vault_bump = ctx.bumps.vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        dividend_amount,
+    )?;
+
+    let game = &mut ctx.accounts.game_state;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_claimed_lamports = game
+        .total_dividend_claimed_lamports
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    let receipt = &mut ctx.accounts.merkle_claim_receipt;
+    receipt.game_id = game.game_id;
+    receipt.round = game.round;
+    receipt.player = player_key;
+    receipt.dividend_lamports = dividend_amount;
+    receipt.bump = ctx.bumps.merkle_claim_receipt;
+
+    emit!(DividendClaimedViaProof {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: player_key,
+        dividend_lamports: dividend_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
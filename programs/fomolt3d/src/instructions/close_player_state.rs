@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::PlayerStateClosed;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClosePlayerState<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+        constraint = player_state.keys == 0 @ FomoltError::PlayerStateNotEmpty,
+        constraint = player_state.referral_earnings_lamports == 0 @ FomoltError::PlayerStateNotEmpty,
+        constraint = player_state.contributed_lamports == 0 @ FomoltError::PlayerStateNotEmpty,
+        constraint = player_state.current_round == 0 @ FomoltError::PlayerStateNotEmpty,
+        constraint = player_state.prepaid_balance_lamports == 0 @ FomoltError::PlayerStateNotEmpty,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+pub fn handle_close_player_state(ctx: Context<ClosePlayerState>) -> Result<()> {
+    let clock = Clock::get()?;
+    let rent_lamports = ctx.accounts.player_state.to_account_info().lamports();
+
+    emit!(PlayerStateClosed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.player_state.game_id,
+        player: ctx.accounts.player.key(),
+        rent_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::BuyCommitted;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CommitBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + BuyCommitment::SPACE,
+        seeds = [b"commitment", game_state.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub commitment: Account<'info, BuyCommitment>,
+
+    /// Escrow PDA holding `budget_lamports` until `reveal_buy` settles or refunds it.
+    /// CHECK: Plain SOL escrow PDA, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"commit_vault", commitment.key().as_ref()],
+        bump,
+    )]
+    pub commit_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_commit_buy(
+    ctx: Context<CommitBuy>,
+    commitment_hash: [u8; 32],
+    budget_lamports: u64,
+) -> Result<()> {
+    let game = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+    require!(clock.unix_timestamp < game.timer_end, FomoltError::TimerExpired);
+    require!(budget_lamports > 0, FomoltError::InvalidCommitBudget);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.commit_vault.to_account_info(),
+            },
+        ),
+        budget_lamports,
+    )?;
+
+    let commitment = &mut ctx.accounts.commitment;
+    commitment.game_id = game.game_id;
+    commitment.round = game.round;
+    commitment.buyer = ctx.accounts.buyer.key();
+    commitment.commitment_hash = commitment_hash;
+    commitment.total_keys_at_commit = game.total_keys;
+    commitment.budget_lamports = budget_lamports;
+    commitment.commit_slot = clock.slot;
+    commitment.bump = ctx.bumps.commitment;
+
+    emit!(BuyCommitted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        buyer: ctx.accounts.buyer.key(),
+        commit_slot: commitment.commit_slot,
+        total_keys_at_commit: commitment.total_keys_at_commit,
+        budget_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
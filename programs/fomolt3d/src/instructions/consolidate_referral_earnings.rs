@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{ReferralObligationConsolidated, VaultFlow};
+use crate::state::*;
+
+/// Permissionless — anyone can crank this to forward a player's stranded
+/// referral-earnings backing from a stale, already-ended round's vault into
+/// the currently active round's vault. Referral earnings are not
+/// round-scoped on `PlayerState` (see `claim_referral_earnings`), so once a
+/// player's balance outlives the round that funded it, this lets the
+/// bookkeeping on both rounds catch up before the player claims, instead of
+/// `claim_referral_earnings` opportunistically draining whatever round it's
+/// pointed at.
+#[derive(Accounts)]
+pub struct ConsolidateReferralEarnings<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The ended round whose vault is still carrying the referral obligation
+    #[account(
+        mut,
+        seeds = [b"game", old_game_state.game_id.to_le_bytes().as_ref(), old_game_state.round.to_le_bytes().as_ref()],
+        bump = old_game_state.bump,
+    )]
+    pub old_game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"config", old_game_state.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// Old round's vault — source of the forwarded lamports
+    #[account(
+        mut,
+        seeds = [b"vault", old_game_state.key().as_ref()],
+        bump,
+    )]
+    pub old_vault: SystemAccount<'info>,
+
+    /// The currently active round receiving the forwarded obligation
+    #[account(
+        mut,
+        seeds = [b"game", current_game_state.game_id.to_le_bytes().as_ref(), current_game_state.round.to_le_bytes().as_ref()],
+        bump = current_game_state.bump,
+        constraint = current_game_state.status == RoundStatus::Active @ FomoltError::GameNotActive,
+    )]
+    pub current_game_state: Account<'info, GameState>,
+
+    /// Current round's vault — destination of the forwarded lamports
+    #[account(
+        mut,
+        seeds = [b"vault", current_game_state.key().as_ref()],
+        bump,
+    )]
+    pub current_vault: SystemAccount<'info>,
+
+    /// The player whose unclaimed referral earnings are being forwarded
+    #[account(
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player_state.player.as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.game_id == old_game_state.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_consolidate_referral_earnings(
+    ctx: Context<ConsolidateReferralEarnings>,
+) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .config
+            .is_instruction_disabled(GlobalConfig::FLAG_CONSOLIDATE_REFERRAL_EARNINGS),
+        FomoltError::FeatureDisabled
+    );
+
+    let old_game_key = ctx.accounts.old_game_state.key();
+    let old_vault_bump = ctx.bumps.old_vault;
+    let old_game = &mut ctx.accounts.old_game_state;
+    let clock = Clock::get()?;
+
+    require!(old_game.status != RoundStatus::Active, FomoltError::GameStillActive);
+    require!(
+        ctx.accounts.current_game_state.game_id == old_game.game_id,
+        FomoltError::GameIdMismatch
+    );
+    require!(
+        ctx.accounts.current_game_state.round != old_game.round,
+        FomoltError::NotCurrentRound
+    );
+
+    let amount = ctx.accounts.player_state.referral_earnings_lamports;
+    require!(amount > 0, FomoltError::NoReferralEarnings);
+
+    // Cap at what's actually available in the old vault — same pattern as
+    // `claim_referral_earnings`, since this obligation may only partially
+    // (or not at all) be backed by this particular stale round's vault.
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.old_vault.lamports().saturating_sub(rent_exempt_min);
+    let amount = amount.min(available);
+    require!(amount > 0, FomoltError::InsufficientFunds);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", old_game_key.as_ref(), &[old_vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.old_vault.to_account_info(),
+                to: ctx.accounts.current_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: old_game.game_id,
+        round: old_game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::Referral,
+        lamports: amount,
+        counterparty: ctx.accounts.current_vault.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    old_game.total_referral_obligations = old_game.total_referral_obligations.saturating_sub(amount);
+    old_game.vault_lamports_out = old_game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    let current_game = &mut ctx.accounts.current_game_state;
+    current_game.total_referral_obligations = current_game
+        .total_referral_obligations
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    current_game.vault_lamports_in = current_game
+        .vault_lamports_in
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: current_game.game_id,
+        round: current_game.round,
+        direction: VaultFlowDirection::In,
+        reason: VaultFlowReason::Referral,
+        lamports: amount,
+        counterparty: old_game_key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(ReferralObligationConsolidated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: old_game.game_id,
+        round: old_game.round,
+        player: ctx.accounts.player_state.player,
+        lamports: amount,
+        destination_round: current_game.round,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
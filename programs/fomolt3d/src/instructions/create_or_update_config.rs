@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::FomoltError;
+use crate::events::ConfigUpdated;
 use crate::math;
 use crate::state::*;
 
@@ -16,9 +17,57 @@ pub struct ConfigParams {
     pub protocol_fee_bps: u64,
     pub referral_bonus_bps: u64,
     pub protocol_wallet: Pubkey,
+    pub early_bird_key_threshold: u64,
+    pub early_bird_multiplier_bps: u64,
+    pub min_purchase_lamports: u64,
+    pub winner_claim_window_secs: i64,
+    pub final_hour_pot_threshold_lamports: u64,
+    pub final_hour_shrink_interval_keys: u64,
+    pub pot_milestone_interval_lamports: u64,
+    pub pot_milestone_bonus_keys: u64,
+    pub promo_keys_cap_per_round: u64,
+    pub transfers_enabled: bool,
+    pub wrapped_keys_enabled: bool,
+    pub keeper_fee_lamports: u64,
+    pub purchase_history_enabled: bool,
+    pub time_weighted_dividends_enabled: bool,
+    pub hook_program: Pubkey,
+    pub referral_earnings_cap_lamports_per_round: u64,
+    pub referral_decay_threshold_lamports: u64,
+    pub referrer_change_cooldown_secs: i64,
+    pub kyc_required: bool,
+    pub kyc_issuer: Pubkey,
+    pub unclaimed_dividend_policy: UnclaimedDividendPolicy,
+    pub dividend_claim_window_secs: i64,
+    pub max_timer_extensions_per_window: u32,
+    pub timer_extension_window_secs: i64,
+    pub approved_stake_vote_account: Pubkey,
+    pub yield_program: Pubkey,
+    pub max_yield_deployment_bps: u64,
+    pub top_referrer_bonus_bps: u64,
+    pub raffle_bps: u64,
+    pub raffle_daily_payout_bps: u64,
+    pub bridge_program: Pubkey,
+    pub max_pot_lamports: u64,
+    pub auto_payout_winner_enabled: bool,
+    pub min_keys_for_timer_extension: u64,
+    pub price_sample_interval_slots: u64,
+    pub rounding_beneficiary: RoundingBeneficiary,
+    pub season_length_rounds: u64,
+    pub season_fee_bps: u64,
+    pub disabled_instructions_bitmask: u64,
+    pub max_keys_per_round: u64,
+    pub referral_vesting_enabled: bool,
+    pub biggest_buyer_bonus_bps: u64,
+    pub biggest_holder_bonus_bps: u64,
+    pub frontend_fee_bps: u64,
+    pub dividend_apr_window_secs: i64,
+    pub min_remaining_secs: i64,
+    pub agent_platform_fee_share_bps: u64,
 }
 
 #[derive(Accounts)]
+#[instruction(game_id: u64)]
 pub struct CreateOrUpdateConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -27,7 +76,7 @@ pub struct CreateOrUpdateConfig<'info> {
         init_if_needed,
         payer = admin,
         space = 8 + GlobalConfig::SPACE,
-        seeds = [b"config"],
+        seeds = [b"config", game_id.to_le_bytes().as_ref()],
         bump,
     )]
     pub config: Account<'info, GlobalConfig>,
@@ -35,20 +84,10 @@ pub struct CreateOrUpdateConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_create_or_update_config(
-    ctx: Context<CreateOrUpdateConfig>,
-    params: ConfigParams,
-) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-
-    // If config already has an admin set (i.e. update), verify signer matches
-    if config.admin != Pubkey::default() {
-        require!(
-            ctx.accounts.admin.key() == config.admin,
-            FomoltError::Unauthorized
-        );
-    }
-
+/// Shared validation for a full `ConfigParams` set — used both when updating
+/// the persistent `GlobalConfig` and when an admin supplies a one-off
+/// `RoundOverrides` at `start_new_round` (see `instructions::start_new_round`).
+pub(crate) fn validate_config_params(params: &ConfigParams) -> Result<()> {
     // Validate pot-split BPS values sum to 10000 (protocol_fee_bps is separate)
     math::validate_bps_sum(
         params.winner_bps,
@@ -64,13 +103,114 @@ pub fn handle_create_or_update_config(
     );
     require!(params.timer_extension_secs > 0, FomoltError::InvalidConfig);
     require!(params.max_timer_secs > 0, FomoltError::InvalidConfig);
+    require!(
+        params.winner_claim_window_secs > 0,
+        FomoltError::InvalidConfig
+    );
+    require!(
+        params.dividend_claim_window_secs > 0,
+        FomoltError::InvalidConfig
+    );
     require!(params.protocol_fee_bps <= 10_000, FomoltError::InvalidConfig);
     require!(params.referral_bonus_bps <= 10_000, FomoltError::InvalidConfig);
     require!(
         params.protocol_wallet != Pubkey::default(),
         FomoltError::InvalidConfig
     );
+    // Early-bird bonus is additive, never a penalty — at least 1x weight.
+    require!(
+        params.early_bird_multiplier_bps >= 10_000,
+        FomoltError::InvalidConfig
+    );
+    // A pot threshold with no shrink interval would activate final-hour mode
+    // but never actually shrink the extension — almost certainly a mistake.
+    if params.final_hour_pot_threshold_lamports > 0 {
+        require!(
+            params.final_hour_shrink_interval_keys > 0,
+            FomoltError::InvalidConfig
+        );
+    }
+    // A bonus with no milestone interval would never actually fire.
+    if params.pot_milestone_bonus_keys > 0 {
+        require!(
+            params.pot_milestone_interval_lamports > 0,
+            FomoltError::InvalidConfig
+        );
+    }
+    // Signed only so 0 reads naturally as "disabled" next to the other
+    // optional i64 durations above — negative would never be meaningful.
+    require!(
+        params.referrer_change_cooldown_secs >= 0,
+        FomoltError::InvalidConfig
+    );
+    // KYC gating with no configured issuer would lock every buyer out —
+    // almost certainly a mistake.
+    if params.kyc_required {
+        require!(
+            params.kyc_issuer != Pubkey::default(),
+            FomoltError::InvalidConfig
+        );
+    }
+    // A cap with no window would have nothing to count over — almost
+    // certainly a mistake.
+    if params.max_timer_extensions_per_window > 0 {
+        require!(
+            params.timer_extension_window_secs > 0,
+            FomoltError::InvalidConfig
+        );
+    }
+    require!(
+        params.max_yield_deployment_bps <= 10_000,
+        FomoltError::InvalidConfig
+    );
+    require!(
+        params.top_referrer_bonus_bps <= 10_000,
+        FomoltError::InvalidConfig
+    );
+    require!(params.raffle_bps <= 10_000, FomoltError::InvalidConfig);
+    require!(
+        params.raffle_daily_payout_bps <= 10_000,
+        FomoltError::InvalidConfig
+    );
+    // Signed only so 0 reads naturally as "disabled" next to the other
+    // optional i64 durations above — negative would never be meaningful.
+    require!(
+        params.dividend_apr_window_secs >= 0,
+        FomoltError::InvalidConfig
+    );
+    require!(
+        params.min_remaining_secs >= 0,
+        FomoltError::InvalidConfig
+    );
+    require!(
+        params.agent_platform_fee_share_bps <= 10_000,
+        FomoltError::InvalidConfig
+    );
 
+    Ok(())
+}
+
+pub fn handle_create_or_update_config(
+    ctx: Context<CreateOrUpdateConfig>,
+    game_id: u64,
+    params: ConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // If config already has an admin set (i.e. update), verify signer matches.
+    // `admin` only needs `is_signer`, so this equally accepts a multisig's
+    // vault PDA signing in via `invoke_signed` (e.g. a Squads execute CPI),
+    // not just an EOA's Ed25519 signature.
+    if config.admin != Pubkey::default() {
+        require!(
+            ctx.accounts.admin.key() == config.admin,
+            FomoltError::Unauthorized
+        );
+    }
+
+    validate_config_params(&params)?;
+
+    config.game_id = game_id;
     config.admin = ctx.accounts.admin.key();
     config.base_price_lamports = params.base_price_lamports;
     config.price_increment_lamports = params.price_increment_lamports;
@@ -83,6 +223,118 @@ pub fn handle_create_or_update_config(
     config.referral_bonus_bps = params.referral_bonus_bps;
     config.protocol_wallet = params.protocol_wallet;
     config.bump = ctx.bumps.config;
+    config.early_bird_key_threshold = params.early_bird_key_threshold;
+    config.early_bird_multiplier_bps = params.early_bird_multiplier_bps;
+    config.min_purchase_lamports = params.min_purchase_lamports;
+    config.winner_claim_window_secs = params.winner_claim_window_secs;
+    config.final_hour_pot_threshold_lamports = params.final_hour_pot_threshold_lamports;
+    config.final_hour_shrink_interval_keys = params.final_hour_shrink_interval_keys;
+    config.pot_milestone_interval_lamports = params.pot_milestone_interval_lamports;
+    config.pot_milestone_bonus_keys = params.pot_milestone_bonus_keys;
+    config.promo_keys_cap_per_round = params.promo_keys_cap_per_round;
+    config.transfers_enabled = params.transfers_enabled;
+    config.wrapped_keys_enabled = params.wrapped_keys_enabled;
+    config.keeper_fee_lamports = params.keeper_fee_lamports;
+    config.purchase_history_enabled = params.purchase_history_enabled;
+    config.time_weighted_dividends_enabled = params.time_weighted_dividends_enabled;
+    config.hook_program = params.hook_program;
+    config.referral_earnings_cap_lamports_per_round = params.referral_earnings_cap_lamports_per_round;
+    config.referral_decay_threshold_lamports = params.referral_decay_threshold_lamports;
+    config.referrer_change_cooldown_secs = params.referrer_change_cooldown_secs;
+    config.kyc_required = params.kyc_required;
+    config.kyc_issuer = params.kyc_issuer;
+    config.unclaimed_dividend_policy = params.unclaimed_dividend_policy;
+    config.dividend_claim_window_secs = params.dividend_claim_window_secs;
+    config.max_timer_extensions_per_window = params.max_timer_extensions_per_window;
+    config.timer_extension_window_secs = params.timer_extension_window_secs;
+    config.approved_stake_vote_account = params.approved_stake_vote_account;
+    config.yield_program = params.yield_program;
+    config.max_yield_deployment_bps = params.max_yield_deployment_bps;
+    config.top_referrer_bonus_bps = params.top_referrer_bonus_bps;
+    config.raffle_bps = params.raffle_bps;
+    config.raffle_daily_payout_bps = params.raffle_daily_payout_bps;
+    config.bridge_program = params.bridge_program;
+    config.max_pot_lamports = params.max_pot_lamports;
+    config.auto_payout_winner_enabled = params.auto_payout_winner_enabled;
+    config.min_keys_for_timer_extension = params.min_keys_for_timer_extension;
+    config.price_sample_interval_slots = params.price_sample_interval_slots;
+    config.rounding_beneficiary = params.rounding_beneficiary;
+    config.season_length_rounds = params.season_length_rounds;
+    config.season_fee_bps = params.season_fee_bps;
+    config.disabled_instructions_bitmask = params.disabled_instructions_bitmask;
+    config.max_keys_per_round = params.max_keys_per_round;
+    config.referral_vesting_enabled = params.referral_vesting_enabled;
+    config.biggest_buyer_bonus_bps = params.biggest_buyer_bonus_bps;
+    config.biggest_holder_bonus_bps = params.biggest_holder_bonus_bps;
+    config.frontend_fee_bps = params.frontend_fee_bps;
+    config.dividend_apr_window_secs = params.dividend_apr_window_secs;
+    config.min_remaining_secs = params.min_remaining_secs;
+    config.agent_platform_fee_share_bps = params.agent_platform_fee_share_bps;
+
+    let clock = Clock::get()?;
+    emit!(ConfigUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: config.game_id,
+        admin: config.admin,
+        base_price_lamports: config.base_price_lamports,
+        price_increment_lamports: config.price_increment_lamports,
+        timer_extension_secs: config.timer_extension_secs,
+        max_timer_secs: config.max_timer_secs,
+        winner_bps: config.winner_bps,
+        dividend_bps: config.dividend_bps,
+        next_round_bps: config.next_round_bps,
+        protocol_fee_bps: config.protocol_fee_bps,
+        referral_bonus_bps: config.referral_bonus_bps,
+        protocol_wallet: config.protocol_wallet,
+        early_bird_key_threshold: config.early_bird_key_threshold,
+        early_bird_multiplier_bps: config.early_bird_multiplier_bps,
+        min_purchase_lamports: config.min_purchase_lamports,
+        winner_claim_window_secs: config.winner_claim_window_secs,
+        final_hour_pot_threshold_lamports: config.final_hour_pot_threshold_lamports,
+        final_hour_shrink_interval_keys: config.final_hour_shrink_interval_keys,
+        pot_milestone_interval_lamports: config.pot_milestone_interval_lamports,
+        pot_milestone_bonus_keys: config.pot_milestone_bonus_keys,
+        promo_keys_cap_per_round: config.promo_keys_cap_per_round,
+        transfers_enabled: config.transfers_enabled,
+        wrapped_keys_enabled: config.wrapped_keys_enabled,
+        keeper_fee_lamports: config.keeper_fee_lamports,
+        purchase_history_enabled: config.purchase_history_enabled,
+        time_weighted_dividends_enabled: config.time_weighted_dividends_enabled,
+        hook_program: config.hook_program,
+        referral_earnings_cap_lamports_per_round: config.referral_earnings_cap_lamports_per_round,
+        referral_decay_threshold_lamports: config.referral_decay_threshold_lamports,
+        referrer_change_cooldown_secs: config.referrer_change_cooldown_secs,
+        kyc_required: config.kyc_required,
+        kyc_issuer: config.kyc_issuer,
+        unclaimed_dividend_policy: config.unclaimed_dividend_policy,
+        dividend_claim_window_secs: config.dividend_claim_window_secs,
+        max_timer_extensions_per_window: config.max_timer_extensions_per_window,
+        timer_extension_window_secs: config.timer_extension_window_secs,
+        approved_stake_vote_account: config.approved_stake_vote_account,
+        yield_program: config.yield_program,
+        max_yield_deployment_bps: config.max_yield_deployment_bps,
+        top_referrer_bonus_bps: config.top_referrer_bonus_bps,
+        raffle_bps: config.raffle_bps,
+        raffle_daily_payout_bps: config.raffle_daily_payout_bps,
+        bridge_program: config.bridge_program,
+        max_pot_lamports: config.max_pot_lamports,
+        auto_payout_winner_enabled: config.auto_payout_winner_enabled,
+        min_keys_for_timer_extension: config.min_keys_for_timer_extension,
+        price_sample_interval_slots: config.price_sample_interval_slots,
+        rounding_beneficiary: config.rounding_beneficiary,
+        season_length_rounds: config.season_length_rounds,
+        season_fee_bps: config.season_fee_bps,
+        disabled_instructions_bitmask: config.disabled_instructions_bitmask,
+        max_keys_per_round: config.max_keys_per_round,
+        referral_vesting_enabled: config.referral_vesting_enabled,
+        biggest_buyer_bonus_bps: config.biggest_buyer_bonus_bps,
+        biggest_holder_bonus_bps: config.biggest_holder_bonus_bps,
+        frontend_fee_bps: config.frontend_fee_bps,
+        dividend_apr_window_secs: config.dividend_apr_window_secs,
+        min_remaining_secs: config.min_remaining_secs,
+        agent_platform_fee_share_bps: config.agent_platform_fee_share_bps,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::SessionCreated;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SessionAuthority::SPACE,
+        seeds = [b"session", owner.key().as_ref(), delegate.as_ref()],
+        bump,
+    )]
+    pub session_authority: Account<'info, SessionAuthority>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_session(
+    ctx: Context<CreateSession>,
+    delegate: Pubkey,
+    spend_limit_lamports: u64,
+    expiry_unix_ts: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(spend_limit_lamports > 0, FomoltError::InvalidConfig);
+    require!(
+        expiry_unix_ts > clock.unix_timestamp,
+        FomoltError::InvalidConfig
+    );
+
+    let session = &mut ctx.accounts.session_authority;
+    session.owner = ctx.accounts.owner.key();
+    session.delegate = delegate;
+    session.spend_limit_lamports = spend_limit_lamports;
+    session.spent_lamports = 0;
+    session.expiry_unix_ts = expiry_unix_ts;
+    session.bump = ctx.bumps.session_authority;
+
+    emit!(SessionCreated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        owner: session.owner,
+        delegate: session.delegate,
+        spend_limit_lamports,
+        expiry_unix_ts,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::VaultYieldDeployed;
+use crate::math;
+use crate::state::*;
+
+/// Cap on `ctx.remaining_accounts` forwarded to the yield program CPI — same
+/// rationale as `buy_keys::MAX_HOOK_ACCOUNTS`.
+const MAX_YIELD_ACCOUNTS: usize = 4;
+
+/// Anchor instruction sighash for `deposit_yield` (first 8 bytes of
+/// sha256("global:deposit_yield")) — lets `GlobalConfig::yield_program` be a
+/// normal Anchor program exposing a `deposit_yield(round: u64, lamports: u64)`
+/// instruction that mints/tracks whatever it does with the deposit.
+const YIELD_DEPOSIT_DISCRIMINATOR: [u8; 8] = [204, 126, 164, 36, 57, 174, 68, 139];
+
+#[derive(Accounts)]
+pub struct DeployVaultYield<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Append-only companion to `game_state` — see `GameStateExt`. Created
+    /// lazily on the first deployment this round, unlike `game_snapshot`
+    /// which always exists from round start.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + GameStateExt::SPACE,
+        seeds = [b"game_ext", game_state.key().as_ref()],
+        bump,
+    )]
+    pub game_state_ext: Account<'info, GameStateExt>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Whitelisted yield destination — see `GlobalConfig::yield_program`.
+    /// This single stored pubkey IS the allowlist; there's no way to CPI
+    /// into an arbitrary program.
+    /// CHECK: Validated against config.yield_program via constraint below
+    #[account(
+        constraint = yield_program.key() == config.yield_program @ FomoltError::YieldProgramNotApproved,
+    )]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// The yield program's own account that receives the deployed lamports
+    /// (e.g. an LST protocol's reserve or deposit account). Its validity
+    /// beyond ownership by `yield_program` is that program's responsibility,
+    /// same as `buy_keys`'s hook accounts.
+    /// CHECK: Forwarded to `yield_program`'s own CPI, never deserialized
+    #[account(mut)]
+    pub yield_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `lamports` of the vault's currently idle balance out to
+/// `GlobalConfig::yield_program`, bounded so the vault always keeps enough
+/// behind to cover `GameState::pending_obligations()` — the round's winner
+/// pot, dividend pool, next-round carry, and referral obligations stay fully
+/// claimable at every point, deployed or not. See
+/// `GlobalConfig::max_yield_deployment_bps` for the kill-switch: 0 blocks
+/// new deployments, but `unwind_vault_yield` can always reclaim what's
+/// already out.
+pub fn handle_deploy_vault_yield<'info>(
+    ctx: Context<'_, '_, '_, 'info, DeployVaultYield<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    require!(lamports > 0, FomoltError::InvalidFundAmount);
+
+    let config = &ctx.accounts.config;
+    require!(
+        config.max_yield_deployment_bps > 0,
+        FomoltError::YieldDeploymentDisabled
+    );
+
+    let game = &mut ctx.accounts.game_state;
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+    let vault_balance = ctx.accounts.vault.lamports();
+    let cap = math::calculate_bps_split(vault_balance, config.max_yield_deployment_bps)?;
+    require!(lamports <= cap, FomoltError::YieldDeploymentCapExceeded);
+
+    let obligations = game.pending_obligations()?;
+    let remaining_after = vault_balance
+        .checked_sub(lamports)
+        .ok_or(FomoltError::Overflow)?;
+    require!(remaining_after >= obligations, FomoltError::VaultInsolvent);
+
+    require!(
+        ctx.remaining_accounts.len() <= MAX_YIELD_ACCOUNTS,
+        FomoltError::TooManyYieldAccounts
+    );
+
+    let game_key = game.key();
+    let vault_bump = ctx.bumps.vault;
+    let vault_signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.yield_vault.to_account_info(),
+            },
+            vault_signer_seeds,
+        ),
+        lamports,
+    )?;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    let ext = &mut ctx.accounts.game_state_ext;
+    ext.game_id = game.game_id;
+    ext.round = game.round;
+    ext.bump = ctx.bumps.game_state_ext;
+    ext.yield_deployed_lamports = ext
+        .yield_deployed_lamports
+        .checked_add(lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    // Notify the yield program so it can record/mint against the deposit —
+    // same generic CPI-forwarding shape as buy_keys's partner hook.
+    let mut account_metas = Vec::with_capacity(ctx.remaining_accounts.len() + 1);
+    let mut account_infos = Vec::with_capacity(ctx.remaining_accounts.len() + 2);
+    account_metas.push(AccountMeta::new(ctx.accounts.yield_vault.key(), false));
+    account_infos.push(ctx.accounts.yield_program.to_account_info());
+    account_infos.push(ctx.accounts.yield_vault.to_account_info());
+    for account in ctx.remaining_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+
+    let mut data = YIELD_DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&game.round.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    invoke(
+        &Instruction {
+            program_id: ctx.accounts.yield_program.key(),
+            accounts: account_metas,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    let clock = Clock::get()?;
+    emit!(VaultYieldDeployed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        admin: ctx.accounts.admin.key(),
+        yield_program: ctx.accounts.yield_program.key(),
+        lamports_deployed: lamports,
+        total_deployed_lamports: ext.yield_deployed_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
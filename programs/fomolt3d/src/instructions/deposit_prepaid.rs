@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::PrepaidDeposited;
+use crate::state::*;
+
+/// Tops up a player's `prepaid` vault, the balance `execute_scheduled_buy`
+/// draws down from on their behalf. See `instructions::set_scheduled_buy` to
+/// actually configure a recurring buy against this balance.
+#[derive(Accounts)]
+pub struct DepositPrepaid<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// This player's prepaid balance vault.
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"prepaid", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub prepaid_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_prepaid(ctx: Context<DepositPrepaid>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, FomoltError::InvalidFundAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.prepaid_vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let player_state = &mut ctx.accounts.player_state;
+    player_state.prepaid_balance_lamports = ctx.accounts.prepaid_vault.lamports();
+
+    emit!(PrepaidDeposited {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        player: player_state.player,
+        lamports,
+        new_balance_lamports: player_state.prepaid_balance_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use crate::errors::FomoltError;
+use crate::events::RaffleDrawn;
+use crate::state::*;
+
+/// Permissionless crank: draws the winning ticket for a day whose snapshot
+/// has already been recorded via `record_raffle_snapshot`. Anyone can call
+/// this once — it only reads `raffle_snapshot` and writes its
+/// `winning_ticket`, no lamports move here (that's `claim_raffle_prize`'s
+/// job once a holder proves their range contains the ticket).
+///
+/// The ticket is derived from `hashv([raffle_snapshot key, slot, unix_timestamp])
+/// mod total_weight`, truncated to a `u64`. This program has no VRF or other
+/// external randomness oracle, so the entropy is fully on-chain and a
+/// validator producing the block this lands in could in principle bias which
+/// slot/timestamp pair it observes. A production deployment should replace
+/// this with a committed VRF (e.g. Switchboard VRF) between
+/// `record_raffle_snapshot` and this instruction; this placeholder is
+/// deliberately simple and its limitation is documented rather than hidden.
+#[derive(Accounts)]
+pub struct DrawRaffleTicket<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"raffle", game_state.key().as_ref(), raffle_snapshot.day_index.to_le_bytes().as_ref()],
+        bump = raffle_snapshot.bump,
+    )]
+    pub raffle_snapshot: Account<'info, RaffleSnapshot>,
+}
+
+pub fn handle_draw_raffle_ticket(ctx: Context<DrawRaffleTicket>) -> Result<()> {
+    let snapshot = &mut ctx.accounts.raffle_snapshot;
+    let clock = Clock::get()?;
+
+    require!(snapshot.winning_ticket.is_none(), FomoltError::RaffleAlreadyDrawn);
+
+    let entropy = hashv(&[
+        snapshot.key().as_ref(),
+        &clock.slot.to_le_bytes(),
+        &clock.unix_timestamp.to_le_bytes(),
+    ]);
+    let seed = u64::from_le_bytes(entropy.to_bytes()[..8].try_into().unwrap());
+    let winning_ticket = seed % snapshot.total_weight;
+
+    snapshot.winning_ticket = Some(winning_ticket);
+
+    emit!(RaffleDrawn {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: snapshot.game_id,
+        round: snapshot.round,
+        day_index: snapshot.day_index,
+        winning_ticket,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program;
+
+use crate::constants::KEEPER_BOUNTY_BONUS_BPS;
+use crate::errors::FomoltError;
+use crate::events::{
+    BiggestBuyerBonusAllocated, BiggestHolderBonusAllocated, KeeperReimbursed, RoundConcluded,
+    RoundConcludedBridged, RoundStatusChanged, TopReferrerBonusAllocated, WinnerPaid,
+};
+use crate::math;
+use crate::state::*;
+
+/// Permissionless — anyone (typically an off-chain keeper bot or a
+/// Clockwork-style thread) can crank this once `timer_end` has passed.
+/// Performs only the `Active` -> `Ended` transition that every other
+/// instruction already does as an incidental side effect of a buy or claim,
+/// so a round can conclude on schedule even if nobody happens to interact
+/// with it. The caller is reimbursed `GameState::keeper_fee_lamports` from
+/// `keeper_budget`, capped at whatever's actually available. If the caller
+/// presents their own active `KeeperState` (see
+/// `instructions::register_keeper`), the reimbursement is boosted by
+/// `constants::KEEPER_BOUNTY_BONUS_BPS` as a priority incentive for bonded
+/// keepers over anonymous crankers.
+#[derive(Accounts)]
+pub struct EndRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Keeper reimbursement vault for this game lineage
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", game_state.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub keeper_budget: SystemAccount<'info>,
+
+    /// Present only when the caller wants to claim the bonded-keeper bounty.
+    /// CHECK: Validated manually in handler (PDA derivation against `payer`, must be active)
+    pub keeper_state: Option<Account<'info, KeeperState>>,
+
+    /// Present only if this round had at least one referred buy — a round
+    /// with zero buys never creates a `GameStateExt` (see `buy_keys`), but
+    /// `end_round` must still be callable on it.
+    /// CHECK: Validated manually in handler (PDA derivation against `game_state`)
+    pub game_state_ext: Option<Account<'info, GameStateExt>>,
+
+    /// Optional: present only when `GameState::bridge_program` is set. CPI-
+    /// notified once the round transitions to `Ended` — see
+    /// `GlobalConfig::bridge_program`.
+    /// CHECK: Validated against game_state.bridge_program in the handler;
+    /// never deserialized, only invoked as a program id.
+    pub bridge_program: Option<UncheckedAccount<'info>>,
+
+    /// Game vault PDA that holds SOL — only debited when
+    /// `GameState::auto_payout_winner_enabled` is set and the auto-payout
+    /// below actually fires.
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Required only to receive an auto-payout — see
+    /// `GameState::auto_payout_winner_enabled`. Left out entirely (or
+    /// mismatched against `GameState::last_buyer`) simply means the round
+    /// concludes without one, leaving the prize claimable via `claim` as
+    /// before.
+    /// CHECK: Validated manually in handler (key equality against game_state.last_buyer)
+    #[account(mut)]
+    pub winner_account: Option<SystemAccount<'info>>,
+
+    /// Always the canonical `[b"blocked", game_id, last_buyer]` PDA, whether
+    /// or not it's actually initialized — required (not `Option`) so the
+    /// auto-payout branch below can't force a payout to a blocked winner
+    /// simply by a keeper omitting this account. See `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), game_state.last_buyer.as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Cap on `ctx.remaining_accounts` forwarded to the bridge attestation CPI —
+/// keeps a misconfigured or malicious bridge program from blowing the
+/// instruction's compute budget with an unbounded account list.
+const MAX_BRIDGE_ACCOUNTS: usize = 4;
+
+/// Anchor instruction sighash for `notify_round_concluded` (first 8 bytes of
+/// sha256("global:notify_round_concluded")) — lets the bridge attestation
+/// target be a normal Anchor program exposing a `notify_round_concluded(round:
+/// u64, winner: Pubkey, winner_lamports: u64, pot_lamports: u64)` instruction.
+const BRIDGE_NOTIFY_DISCRIMINATOR: [u8; 8] = [128, 27, 4, 18, 88, 101, 150, 84];
+
+pub fn handle_end_round<'info>(ctx: Context<'_, '_, '_, 'info, EndRound<'info>>) -> Result<()> {
+    let game_id = ctx.accounts.game_state.game_id;
+    let keeper_budget_bump = ctx.bumps.keeper_budget;
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+    require!(clock.unix_timestamp >= game.timer_end, FomoltError::TimerNotExpired);
+
+    game.transition_status(RoundStatus::Ended)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        from: RoundStatus::Active,
+        to: RoundStatus::Ended,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(RoundConcluded {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        winner: game.last_buyer,
+        winner_lamports: game.winner_pot,
+        pot_lamports: game.pot_lamports,
+        total_keys: game.total_keys,
+        total_players: game.total_players,
+        next_round_pot: game.next_round_pot,
+        round_start: game.round_start,
+        round_end: game.timer_end,
+        purchase_count: game.purchase_count,
+        gross_volume_lamports: game.gross_volume_lamports,
+        max_single_buy_lamports: game.max_single_buy_lamports,
+        max_single_buyer: game.max_single_buyer,
+        round_duration_secs: game.round_duration_secs(),
+        timer_extensions_triggered: game.timer_extensions_triggered,
+        average_seconds_between_buys: game.average_seconds_between_buys(),
+        pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+        pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+        pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+        genesis_config_hash: game.genesis_config_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // --- Optional cross-chain conclusion attestation (GameState::bridge_program) ---
+    if game.bridge_program != Pubkey::default() {
+        let bridge_program = ctx
+            .accounts
+            .bridge_program
+            .as_ref()
+            .ok_or(FomoltError::MissingBridgeProgram)?;
+        require!(
+            bridge_program.key() == game.bridge_program,
+            FomoltError::BridgeProgramMismatch
+        );
+        require!(
+            ctx.remaining_accounts.len() <= MAX_BRIDGE_ACCOUNTS,
+            FomoltError::TooManyBridgeAccounts
+        );
+
+        let mut account_metas = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(ctx.remaining_accounts.len() + 1);
+        account_infos.push(bridge_program.to_account_info());
+        for account in ctx.remaining_accounts {
+            account_metas.push(if account.is_writable {
+                AccountMeta::new(account.key(), account.is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), account.is_signer)
+            });
+            account_infos.push(account.clone());
+        }
+
+        let mut data = BRIDGE_NOTIFY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&game.round.to_le_bytes());
+        data.extend_from_slice(game.last_buyer.as_ref());
+        data.extend_from_slice(&game.winner_pot.to_le_bytes());
+        data.extend_from_slice(&game.pot_lamports.to_le_bytes());
+
+        invoke(
+            &Instruction {
+                program_id: bridge_program.key(),
+                accounts: account_metas,
+                data,
+            },
+            &account_infos,
+        )?;
+
+        emit!(RoundConcludedBridged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            bridge_program: game.bridge_program,
+            winner: game.last_buyer,
+            winner_lamports: game.winner_pot,
+            pot_lamports: game.pot_lamports,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // --- Carve the top-referrer bonus out of the winner pot, if configured
+    // and someone actually earned a referral bonus this round. Moves
+    // lamports between two GameState-resident obligation buckets, so
+    // `GameState::pending_obligations` (and therefore `assert_solvency`)
+    // sees no change in the total owed. ---
+    if game.top_referrer_bonus_bps > 0 {
+        if let Some(ext) = &ctx.accounts.game_state_ext {
+            let (expected_pda, _) =
+                Pubkey::find_program_address(&[b"game_ext", game.key().as_ref()], ctx.program_id);
+            require!(ext.key() == expected_pda, FomoltError::GameStateExtMismatch);
+
+            let leader = ext.top_referrers[0];
+            if leader.referrer != Pubkey::default() {
+                let bonus = math::calculate_bps_split(game.winner_pot, game.top_referrer_bonus_bps)?;
+                if bonus > 0 {
+                    game.winner_pot = game
+                        .winner_pot
+                        .checked_sub(bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                    game.top_referrer_bonus_pool = bonus;
+
+                    emit!(TopReferrerBonusAllocated {
+                        version: crate::events::EVENT_SCHEMA_VERSION,
+                        game_id: game.game_id,
+                        round: game.round,
+                        referrer: leader.referrer,
+                        lamports: bonus,
+                        timestamp: clock.unix_timestamp,
+                    });
+                }
+            }
+        }
+    }
+
+    // --- Carve the biggest-buyer and biggest-holder bonuses out of the
+    // winner pot, if configured and someone actually bought this round.
+    // Same GameState-resident obligation-bucket move as the top-referrer
+    // bonus above, and skipped entirely when nobody bought (leader fields
+    // still at their Pubkey::default() sentinel). ---
+    if game.biggest_buyer_bonus_bps > 0 && game.max_single_buyer != Pubkey::default() {
+        let bonus = math::calculate_bps_split(game.winner_pot, game.biggest_buyer_bonus_bps)?;
+        if bonus > 0 {
+            game.winner_pot = game.winner_pot.checked_sub(bonus).ok_or(FomoltError::Overflow)?;
+            game.biggest_buyer_bonus_pool = bonus;
+
+            emit!(BiggestBuyerBonusAllocated {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                buyer: game.max_single_buyer,
+                lamports: bonus,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    if game.biggest_holder_bonus_bps > 0 && game.largest_holder != Pubkey::default() {
+        let bonus = math::calculate_bps_split(game.winner_pot, game.biggest_holder_bonus_bps)?;
+        if bonus > 0 {
+            game.winner_pot = game.winner_pot.checked_sub(bonus).ok_or(FomoltError::Overflow)?;
+            game.biggest_holder_bonus_pool = bonus;
+
+            emit!(BiggestHolderBonusAllocated {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                holder: game.largest_holder,
+                lamports: bonus,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    // --- Auto-payout the winner, removing the stranded-winner scenario, if
+    // GameState::auto_payout_winner_enabled was set for this round. Any
+    // reason the payout can't safely proceed here (no winner_account
+    // supplied, last_buyer is a PDA/program-owned wallet, last_buyer is
+    // blocked with allow_claim = false, or the vault is short) is treated as
+    // a skip rather than an error — the prize simply stays claimable via
+    // `claim` as it always was. A caller-supplied winner_account that
+    // doesn't even match last_buyer, however, is an outright mistake. ---
+    if game.auto_payout_winner_enabled
+        && game.winner_pot > 0
+        && game.last_buyer != Pubkey::default()
+        && !game.winner_claimed()
+    {
+        if let Some(winner_account) = &ctx.accounts.winner_account {
+            require!(
+                winner_account.key() == game.last_buyer,
+                FomoltError::WinnerAccountMismatch
+            );
+
+            let blocked = BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?
+                .is_some_and(|entry| !entry.allow_claim);
+
+            let rent_exempt_min = Rent::get()?.minimum_balance(0);
+            let vault_available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+
+            if !blocked
+                && winner_account.owner == &anchor_lang::system_program::ID
+                && vault_available >= game.winner_pot
+            {
+                let game_state_key = game.key();
+                let signer_seeds: &[&[&[u8]]] =
+                    &[&[b"vault", game_state_key.as_ref(), &[vault_bump]]];
+
+                system_program::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.vault.to_account_info(),
+                            to: winner_account.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    game.winner_pot,
+                )?;
+
+                let winner_lamports = game.winner_pot;
+                game.vault_lamports_out = game
+                    .vault_lamports_out
+                    .checked_add(winner_lamports)
+                    .ok_or(FomoltError::Overflow)?;
+                game.winner_pot = 0;
+
+                let from_status = game.status;
+                game.transition_status(RoundStatus::Settled)?;
+                emit!(RoundStatusChanged {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: game.game_id,
+                    round: game.round,
+                    from: from_status,
+                    to: RoundStatus::Settled,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                emit!(WinnerPaid {
+                    version: crate::events::EVENT_SCHEMA_VERSION,
+                    game_id: game.game_id,
+                    round: game.round,
+                    winner: game.last_buyer,
+                    lamports: winner_lamports,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+    }
+
+    // --- Reimburse the caller, capped at what the budget can actually spare ---
+    let mut fee = game.keeper_fee_lamports;
+    if let Some(keeper_state) = &ctx.accounts.keeper_state {
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[b"keeper", game_id.to_le_bytes().as_ref(), ctx.accounts.payer.key().as_ref()],
+            ctx.program_id,
+        );
+        require!(keeper_state.key() == expected_pda, FomoltError::KeeperMismatch);
+        if keeper_state.active {
+            let bonus = fee
+                .checked_mul(KEEPER_BOUNTY_BONUS_BPS)
+                .ok_or(FomoltError::Overflow)?
+                / 10_000;
+            fee = fee.checked_add(bonus).ok_or(FomoltError::Overflow)?;
+        }
+    }
+
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx
+        .accounts
+        .keeper_budget
+        .lamports()
+        .saturating_sub(rent_exempt_min);
+    let reimbursement = fee.min(available);
+
+    if reimbursement > 0 {
+        let game_id_bytes = game_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[b"keeper_budget", game_id_bytes.as_ref(), &[keeper_budget_bump]]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.keeper_budget.to_account_info(),
+                    to: ctx.accounts.payer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            reimbursement,
+        )?;
+    }
+
+    emit!(KeeperReimbursed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id,
+        round: game.round,
+        keeper: ctx.accounts.payer.key(),
+        lamports: reimbursement,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::PlayerMigrationExecuted;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(old_wallet: Pubkey)]
+pub struct ExecutePlayerMigration<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    /// The lost/compromised wallet's `PlayerState`, closed back to
+    /// `new_wallet` once its balances have been copied over.
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"player", old_player_state.game_id.to_le_bytes().as_ref(), old_wallet.as_ref()],
+        bump = old_player_state.bump,
+        constraint = old_player_state.pending_migration_wallet == Some(new_wallet.key()) @ FomoltError::NoMigrationPending,
+    )]
+    pub old_player_state: Account<'info, PlayerState>,
+
+    /// `new_wallet`'s brand new `PlayerState` — `init` (not `init_if_needed`)
+    /// so a wallet that already plays this game lineage can't clobber an
+    /// existing account of its own via migration.
+    #[account(
+        init,
+        payer = new_wallet,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", old_player_state.game_id.to_le_bytes().as_ref(), new_wallet.key().as_ref()],
+        bump,
+    )]
+    pub new_player_state: Account<'info, PlayerState>,
+
+    /// Present only when `old_player_state.current_round` is this game's
+    /// currently active round and `old_wallet` occupies `last_buyer`,
+    /// `max_single_buyer`, and/or `largest_holder` — repointed to
+    /// `new_wallet` so a live round's winner-prize and leaderboard claims
+    /// still resolve correctly after migration. Left out otherwise,
+    /// mirroring how `claim`'s `current_game_state` is only required
+    /// conditionally.
+    /// CHECK: Validated manually in handler (PDA derivation + game_id/round match)
+    #[account(mut)]
+    pub game_state: Option<Account<'info, GameState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Admin-assisted self-custody recovery, step 2: once
+/// `PlayerState::migration_effective_at` has passed, the claimed new owner
+/// signs to actually claim `old_wallet`'s keys, dividend weight, referral
+/// earnings, and referral relationships into their own wallet's
+/// `PlayerState`, closing the old one. Requiring `new_wallet`'s own
+/// signature here (not just the admin's) means completing a recovery still
+/// needs the new owner to prove control of the destination wallet, on top
+/// of the admin having proposed it and the timelock having elapsed.
+pub fn handle_execute_player_migration(
+    ctx: Context<ExecutePlayerMigration>,
+    old_wallet: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let old = &ctx.accounts.old_player_state;
+    require!(old.migration_ready(clock.unix_timestamp), FomoltError::MigrationTimelockActive);
+
+    let game_id = old.game_id;
+    let current_round = old.current_round;
+    let new_wallet = ctx.accounts.new_wallet.key();
+
+    let new = &mut ctx.accounts.new_player_state;
+    new.game_id = game_id;
+    new.player = new_wallet;
+    new.bump = ctx.bumps.new_player_state;
+    new.initialized = true;
+    new.generation = 1;
+    new.keys = old.keys;
+    new.current_round = current_round;
+    new.claimed_dividends_lamports = old.claimed_dividends_lamports;
+    new.referrer = old.referrer;
+    new.referral_earnings_lamports = old.referral_earnings_lamports;
+    new.claimed_referral_earnings_lamports = old.claimed_referral_earnings_lamports;
+    new.is_agent = old.is_agent;
+    new.dividend_weight = old.dividend_weight;
+    new.auto_compound = old.auto_compound;
+    new.dividend_weight_seconds = old.dividend_weight_seconds;
+    new.dividend_seconds_last_update = old.dividend_seconds_last_update;
+    new.referral_earnings_round = old.referral_earnings_round;
+    new.referral_earnings_this_round_lamports = old.referral_earnings_this_round_lamports;
+    new.referrer_set_at = old.referrer_set_at;
+    new.spend_limit_lamports_per_day = old.spend_limit_lamports_per_day;
+    new.pending_spend_limit_lamports_per_day = old.pending_spend_limit_lamports_per_day;
+    new.spend_limit_effective_at = old.spend_limit_effective_at;
+    new.spend_window_start = old.spend_window_start;
+    new.spend_window_lamports = old.spend_window_lamports;
+    new.timer_extension_window_start = old.timer_extension_window_start;
+    new.timer_extensions_in_window = old.timer_extensions_in_window;
+    new.payout_address = old.payout_address;
+    new.contributed_lamports = old.contributed_lamports;
+    new.total_contributed_lamports = old.total_contributed_lamports;
+    new.pending_migration_wallet = None;
+    new.migration_effective_at = 0;
+
+    let keys = new.keys;
+    let dividend_weight = new.dividend_weight;
+    let referral_earnings_lamports = new.referral_earnings_lamports;
+
+    if let Some(game) = ctx.accounts.game_state.as_mut() {
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[
+                b"game",
+                game_id.to_le_bytes().as_ref(),
+                current_round.to_le_bytes().as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require!(game.key() == expected_pda, FomoltError::GameIdMismatch);
+
+        if game.last_buyer == old_wallet {
+            game.last_buyer = new_wallet;
+        }
+        if game.max_single_buyer == old_wallet {
+            game.max_single_buyer = new_wallet;
+        }
+        if game.largest_holder == old_wallet {
+            game.largest_holder = new_wallet;
+        }
+    }
+
+    emit!(PlayerMigrationExecuted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id,
+        old_wallet,
+        new_wallet,
+        keys,
+        dividend_weight,
+        referral_earnings_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,454 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{
+    BlockedAttempt, KeysPurchased, ProtocolFeeCollected, PurchaseSettled, RoundConcluded,
+    RoundStatusChanged, VaultFlow,
+};
+use crate::logic;
+use crate::math;
+use crate::state::*;
+
+/// Permissionless: anyone (typically an off-chain keeper bot) can crank this
+/// once `PlayerState::scheduled_buy_interval_secs` has elapsed since
+/// `last_scheduled_buy_at`, purchasing `scheduled_buy_keys` keys for
+/// `player_state` out of its own `prepaid` balance — see
+/// `instructions::deposit_prepaid` and `instructions::set_scheduled_buy`.
+/// Same core purchase math as `buy_keys` (bonding-curve cost,
+/// `logic::compute_pot_split`, dividend weight, timer extension), scaled down
+/// like `buy_keys_via_session`: no referral, KYC, hooks, wrapped keys, promo
+/// keys, or purchase history. Blocklist and self-imposed daily spend limit
+/// ARE enforced, same as `buy_keys` — a standing schedule must not keep
+/// crediting a blocked or self-excluded player just because the crank is
+/// permissionless.
+#[derive(Accounts)]
+pub struct ExecuteScheduledBuy<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player_state.player.as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// This player's prepaid balance vault.
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"prepaid", player_state.game_id.to_le_bytes().as_ref(), player_state.player.as_ref()],
+        bump,
+    )]
+    pub prepaid_vault: SystemAccount<'info>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Protocol fee recipient wallet
+    /// CHECK: Validated against game_state.protocol_wallet
+    #[account(
+        mut,
+        constraint = protocol_wallet.key() == game_state.protocol_wallet @ FomoltError::InvalidConfig,
+    )]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    /// Always the canonical `[b"blocked", game_id, player]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// or self-excluded player can't have a stale scheduled buy keep cranking
+    /// on their behalf just because the permissionless caller omits the
+    /// account. See `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", player_state.game_id.to_le_bytes().as_ref(), player_state.player.as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_execute_scheduled_buy(ctx: Context<ExecuteScheduledBuy>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &mut ctx.accounts.game_state;
+
+    // --- Auto-end check: if timer expired, end the round and no-op ---
+    if clock.unix_timestamp >= game.timer_end {
+        if game.status == RoundStatus::Active {
+            game.transition_status(RoundStatus::Ended)?;
+            emit!(RoundStatusChanged {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                from: RoundStatus::Active,
+                to: RoundStatus::Ended,
+                timestamp: clock.unix_timestamp,
+            });
+            emit!(RoundConcluded {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                winner: game.last_buyer,
+                winner_lamports: game.winner_pot,
+                pot_lamports: game.pot_lamports,
+                total_keys: game.total_keys,
+                total_players: game.total_players,
+                next_round_pot: game.next_round_pot,
+                round_start: game.round_start,
+                round_end: game.timer_end,
+                purchase_count: game.purchase_count,
+                gross_volume_lamports: game.gross_volume_lamports,
+                max_single_buy_lamports: game.max_single_buy_lamports,
+                max_single_buyer: game.max_single_buyer,
+                round_duration_secs: game.round_duration_secs(),
+                timer_extensions_triggered: game.timer_extensions_triggered,
+                average_seconds_between_buys: game.average_seconds_between_buys(),
+                pot_checkpoint_25_lamports: game.pot_checkpoint_25_lamports,
+                pot_checkpoint_50_lamports: game.pot_checkpoint_50_lamports,
+                pot_checkpoint_75_lamports: game.pot_checkpoint_75_lamports,
+                genesis_config_hash: game.genesis_config_hash,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        return Ok(());
+    }
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+    let player = &mut ctx.accounts.player_state;
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+
+    // --- Blocklist check: a blocked/self-excluded player's standing
+    // schedule must not keep buying on their behalf just because the crank
+    // is permissionless — same gate `buy_keys` applies to the buyer. ---
+    if BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?.is_some() {
+        emit!(BlockedAttempt {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            wallet: player.player,
+            action: "execute_scheduled_buy".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+        return err!(FomoltError::WalletBlocked);
+    }
+
+    require!(
+        player.scheduled_buy_interval_secs > 0 && player.scheduled_buy_keys > 0,
+        FomoltError::ScheduledBuyNotConfigured
+    );
+    require!(
+        clock.unix_timestamp
+            >= player
+                .last_scheduled_buy_at
+                .saturating_add(player.scheduled_buy_interval_secs),
+        FomoltError::ScheduledBuyNotDue
+    );
+
+    if player.current_round != game.round {
+        require!(player.current_round == 0, FomoltError::MustClaimPreviousRound);
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.current_round = game.round;
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    // --- Sold-out supply cap: same clamp `buy_keys`'s main path applies —
+    // see `GlobalConfig::max_keys_per_round` — so a recurring scheduled buy
+    // can't oversell a round's supply either. A fully-clamped-to-zero result
+    // is a no-op rather than an error, mirroring `buy_keys`'s own 0-key
+    // registration-only path — a crank shouldn't hard-fail just because
+    // supply ran out between scheduling and execution.
+    let keys_to_buy = if game.max_keys_per_round > 0 {
+        player
+            .scheduled_buy_keys
+            .min(game.max_keys_per_round.saturating_sub(game.total_keys))
+    } else {
+        player.scheduled_buy_keys
+    };
+    if keys_to_buy == 0 {
+        return Ok(());
+    }
+    let cost = math::calculate_cost(
+        game.total_keys,
+        keys_to_buy,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )?;
+    require!(
+        game.min_purchase_lamports == 0 || cost >= game.min_purchase_lamports,
+        FomoltError::BelowMinimumPurchase
+    );
+    require!(
+        cost <= player.prepaid_balance_lamports,
+        FomoltError::InsufficientPrepaidBalance
+    );
+
+    // --- Self-imposed spend limit (set_spend_limit, opt-in responsible-gaming
+    // cap) — same gate `buy_keys` applies, so a standing schedule can't keep
+    // drawing down the prepaid balance past a cap the player set for themselves. ---
+    player.apply_pending_spend_limit(clock.unix_timestamp);
+    if player.spend_limit_lamports_per_day > 0 {
+        player.maybe_reset_spend_window(clock.unix_timestamp);
+        let spent_after = player
+            .spend_window_lamports
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+        require!(
+            spent_after <= player.spend_limit_lamports_per_day,
+            FomoltError::SpendLimitExceeded
+        );
+        player.spend_window_lamports = spent_after;
+    }
+
+    let house_fee = math::calculate_bps_split(cost, game.protocol_fee_bps)?;
+    let pot_contribution = cost.checked_sub(house_fee).ok_or(FomoltError::Overflow)?;
+
+    let player_key = player.player;
+    let game_id_bytes = player.game_id.to_le_bytes();
+    let prepaid_bump = ctx.bumps.prepaid_vault;
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[b"prepaid", game_id_bytes.as_ref(), player_key.as_ref(), &[prepaid_bump]]];
+
+    if house_fee > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.prepaid_vault.to_account_info(),
+                    to: ctx.accounts.protocol_wallet.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            house_fee,
+        )?;
+        emit!(ProtocolFeeCollected {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            lamports: house_fee,
+            recipient: ctx.accounts.protocol_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if pot_contribution > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.prepaid_vault.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            pot_contribution,
+        )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Buy,
+            lamports: pot_contribution,
+            counterparty: player_key,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    player.prepaid_balance_lamports = ctx.accounts.prepaid_vault.lamports();
+
+    let committed = game
+        .winner_pot
+        .checked_add(game.total_dividend_pool)
+        .ok_or(FomoltError::Overflow)?;
+    let split = logic::compute_pot_split(
+        pot_contribution,
+        game.raffle_bps,
+        game.winner_bps,
+        game.dividend_bps,
+        game.next_round_bps,
+        committed,
+        game.max_pot_lamports,
+    )?;
+
+    game.raffle_pool_lamports = game
+        .raffle_pool_lamports
+        .checked_add(split.raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.winner_pot = game
+        .winner_pot
+        .checked_add(split.winner_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_pool = game
+        .total_dividend_pool
+        .checked_add(split.dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.record_dividend_for_apr_window(clock.unix_timestamp, split.dividend_amount)?;
+    game.next_round_pot = game
+        .next_round_pot
+        .checked_add(split.next_round_amount)
+        .ok_or(FomoltError::Overflow)?;
+    match game.rounding_beneficiary {
+        RoundingBeneficiary::Protocol => {
+            game.dust_reserve = game
+                .dust_reserve
+                .checked_add(split.dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::WinnerPot => {
+            game.winner_pot = game
+                .winner_pot
+                .checked_add(split.dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        RoundingBeneficiary::DividendPool => {
+            game.total_dividend_pool = game
+                .total_dividend_pool
+                .checked_add(split.dust)
+                .ok_or(FomoltError::Overflow)?;
+            game.record_dividend_for_apr_window(clock.unix_timestamp, split.dust)?;
+        }
+        RoundingBeneficiary::NextRoundPot => {
+            game.next_round_pot = game
+                .next_round_pot
+                .checked_add(split.dust)
+                .ok_or(FomoltError::Overflow)?;
+        }
+    }
+    game.pot_overflow_reserve_lamports = game
+        .pot_overflow_reserve_lamports
+        .checked_add(split.pot_overflow_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    if game.time_weighted_dividends_enabled {
+        game.sync_dividend_seconds(clock.unix_timestamp)?;
+        player.sync_dividend_seconds(clock.unix_timestamp)?;
+    }
+
+    let weight_earned = math::calculate_key_weight(
+        game.total_keys,
+        keys_to_buy,
+        game.early_bird_key_threshold,
+        game.early_bird_multiplier_bps,
+    )?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_weight = game
+        .total_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+
+    player.keys = player.keys.checked_add(keys_to_buy).ok_or(FomoltError::Overflow)?;
+    player.contributed_lamports = player
+        .contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    player.total_contributed_lamports = player
+        .total_contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_keys = game
+        .total_keys
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    if player.is_agent {
+        game.agent_keys_total = game
+            .agent_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    } else {
+        game.human_keys_total = game
+            .human_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    }
+    game.pot_lamports = game.pot_lamports.checked_add(cost).ok_or(FomoltError::Overflow)?;
+    game.last_buyer = player_key;
+
+    game.purchase_count = game
+        .purchase_count
+        .checked_add(1)
+        .ok_or(FomoltError::Overflow)?;
+    game.gross_volume_lamports = game
+        .gross_volume_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    if cost > game.max_single_buy_lamports {
+        game.max_single_buy_lamports = cost;
+        game.max_single_buyer = player_key;
+    }
+    if player.keys > game.largest_holder_keys {
+        game.largest_holder_keys = player.keys;
+        game.largest_holder = player_key;
+    }
+
+    game.timer_end = math::calculate_timer_extension(
+        clock.unix_timestamp,
+        math::TimerExtensionParams {
+            extension_secs: game.timer_extension_secs,
+            current_timer_end: game.timer_end,
+            round_start: game.round_start,
+            max_timer_secs: game.max_timer_secs,
+            final_hour_active: game.final_hour_active,
+            keys_since_final_hour_start: game.total_keys.saturating_sub(game.final_hour_start_keys),
+            final_hour_shrink_interval_keys: game.final_hour_shrink_interval_keys,
+            min_remaining_secs: game.min_remaining_secs,
+        },
+    )?;
+
+    player.last_scheduled_buy_at = clock.unix_timestamp;
+
+    emit!(KeysPurchased {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: player_key,
+        is_agent: player.is_agent,
+        keys_bought: keys_to_buy,
+        total_player_keys: player.keys,
+        lamports_spent: cost,
+        pot_contribution,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PurchaseSettled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: player_key,
+        lamports_spent: cost,
+        protocol_fee_lamports: house_fee,
+        referrer: player.referrer,
+        referral_bonus_lamports: 0,
+        pot_contribution,
+        raffle_pool_lamports: split.raffle_amount,
+        winner_pot_lamports: split.winner_amount,
+        dividend_pool_lamports: split.dividend_amount,
+        next_round_lamports: split.next_round_amount,
+        dust_lamports: split.dust,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{RoundStatusChanged, WinnerForfeited};
+use crate::state::*;
+
+/// Permissionless — anyone can crank this once the claim window has lapsed.
+/// `old_game_state` is the stale, already-ended round whose `winner_pot` is
+/// stranded; `current_game_state` is the round receiving the rollover and
+/// must be the one currently active.
+#[derive(Accounts)]
+pub struct ForfeitWinnerPot<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The ended round whose unclaimed winner prize is being forfeited
+    #[account(
+        mut,
+        seeds = [b"game", old_game_state.game_id.to_le_bytes().as_ref(), old_game_state.round.to_le_bytes().as_ref()],
+        bump = old_game_state.bump,
+    )]
+    pub old_game_state: Account<'info, GameState>,
+
+    /// Old round's vault — source of the forfeited lamports
+    #[account(
+        mut,
+        seeds = [b"vault", old_game_state.key().as_ref()],
+        bump,
+    )]
+    pub old_vault: SystemAccount<'info>,
+
+    /// The currently active round receiving the forfeited prize
+    #[account(
+        mut,
+        seeds = [b"game", current_game_state.game_id.to_le_bytes().as_ref(), current_game_state.round.to_le_bytes().as_ref()],
+        bump = current_game_state.bump,
+        constraint = current_game_state.status == RoundStatus::Active @ FomoltError::GameNotActive,
+    )]
+    pub current_game_state: Account<'info, GameState>,
+
+    /// Current round's vault — destination of the forfeited lamports
+    #[account(
+        mut,
+        seeds = [b"vault", current_game_state.key().as_ref()],
+        bump,
+    )]
+    pub current_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_forfeit_winner_pot(ctx: Context<ForfeitWinnerPot>) -> Result<()> {
+    let old_game_key = ctx.accounts.old_game_state.key();
+    let old_vault_bump = ctx.bumps.old_vault;
+    let old_game = &mut ctx.accounts.old_game_state;
+    let clock = Clock::get()?;
+
+    require!(old_game.status != RoundStatus::Active, FomoltError::GameStillActive);
+    require!(!old_game.winner_claimed(), FomoltError::WinnerAlreadyClaimed);
+    require!(old_game.winner_pot > 0, FomoltError::NothingToClaim);
+
+    let forfeit_at = old_game
+        .timer_end
+        .checked_add(old_game.winner_claim_window_secs)
+        .ok_or(FomoltError::Overflow)?;
+    require!(
+        clock.unix_timestamp >= forfeit_at,
+        FomoltError::ClaimWindowNotExpired
+    );
+
+    require!(
+        ctx.accounts.current_game_state.game_id == old_game.game_id,
+        FomoltError::GameIdMismatch
+    );
+    require!(
+        ctx.accounts.current_game_state.round != old_game.round,
+        FomoltError::NotCurrentRound
+    );
+
+    let forfeited_winner = old_game.last_buyer;
+    let forfeited_lamports = old_game.winner_pot;
+
+    // --- Vault solvency check: forfeiture must not dip below rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.old_vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= forfeited_lamports, FomoltError::VaultInsolvent);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", old_game_key.as_ref(), &[old_vault_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.old_vault.to_account_info(),
+                to: ctx.accounts.current_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        forfeited_lamports,
+    )?;
+
+    old_game.transition_status(RoundStatus::Archived)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: old_game.game_id,
+        round: old_game.round,
+        from: RoundStatus::Ended,
+        to: RoundStatus::Archived,
+        timestamp: clock.unix_timestamp,
+    });
+    old_game.winner_pot = 0;
+    old_game.vault_lamports_out = old_game
+        .vault_lamports_out
+        .checked_add(forfeited_lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    let current_game = &mut ctx.accounts.current_game_state;
+    current_game.winner_pot = current_game
+        .winner_pot
+        .checked_add(forfeited_lamports)
+        .ok_or(FomoltError::Overflow)?;
+    current_game.vault_lamports_in = current_game
+        .vault_lamports_in
+        .checked_add(forfeited_lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    emit!(WinnerForfeited {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: old_game.game_id,
+        round: old_game.round,
+        forfeited_winner,
+        forfeited_lamports,
+        destination_round: current_game.round,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
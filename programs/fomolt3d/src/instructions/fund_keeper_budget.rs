@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::KeeperBudgetFunded;
+use crate::state::*;
+
+/// Admin-only: tops up the game's `KeeperBudget` vault, which `end_round`
+/// draws from to reimburse whoever cranks it. The vault is a bare
+/// lamport-holding PDA (same shape as the round `vault` PDAs), shared across
+/// every round of this game lineage rather than reset per round.
+#[derive(Accounts)]
+pub struct FundKeeperBudget<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// Keeper reimbursement vault for this game lineage
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", config.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub keeper_budget: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_fund_keeper_budget(ctx: Context<FundKeeperBudget>, amount: u64) -> Result<()> {
+    require!(amount > 0, FomoltError::InvalidFundAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.admin.to_account_info(),
+                to: ctx.accounts.keeper_budget.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let clock = Clock::get()?;
+    emit!(KeeperBudgetFunded {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.config.game_id,
+        admin: ctx.accounts.admin.key(),
+        lamports: amount,
+        new_balance: ctx.accounts.keeper_budget.lamports(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{GameUpdated, PlayerRegistered, PromoGranted};
+use crate::math;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey, keys: u64)]
+pub struct GrantPromoKeys<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// The quest/giveaway winner receiving the keys — not a signer, since the
+    /// admin grants these on the player's behalf.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.as_ref()],
+        bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL — the admin's backing deposit lands here.
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Indexer-friendly mirror of this round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        mut,
+        seeds = [b"snapshot", game_state.key().as_ref()],
+        bump = game_snapshot.bump,
+    )]
+    pub game_snapshot: Account<'info, GameSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints `keys` dividend-bearing keys to `player`, backed by the admin
+/// depositing that purchase's full bonding-curve cost into the vault from
+/// their own balance — the same lamports a real buyer would have paid. That
+/// deposit is split into `winner_pot` / `total_dividend_pool` / `next_round_pot`
+/// exactly like `buy_keys`'s `pot_contribution`, so the new dividend weight is
+/// backed by freshly deposited funds instead of diluting existing holders'
+/// claims, and `assert_solvency` stays satisfied.
+///
+/// Unlike a real buy, this never touches `last_buyer` or `timer_end` — a
+/// promo grant isn't a competitive action and shouldn't let the admin extend
+/// the round or steal the winner slot — and it bypasses the blocklist, since
+/// it's a deliberate admin action rather than player self-service.
+pub fn handle_grant_promo_keys(
+    ctx: Context<GrantPromoKeys>,
+    player: Pubkey,
+    keys: u64,
+) -> Result<()> {
+    require!(keys > 0, FomoltError::NoPromoKeysToGrant);
+
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+    require!(
+        game.status == RoundStatus::Active && clock.unix_timestamp < game.timer_end,
+        FomoltError::GameNotActive
+    );
+
+    let granted_this_round = game
+        .promo_keys_granted_this_round
+        .checked_add(keys)
+        .ok_or(FomoltError::Overflow)?;
+    require!(
+        game.promo_keys_cap_per_round == 0 || granted_this_round <= game.promo_keys_cap_per_round,
+        FomoltError::PromoCapExceeded
+    );
+
+    let player_account = &mut ctx.accounts.player_state;
+
+    // --- Handle player registration / round entry (mirrors buy_keys, minus
+    // referrer handling — a promo grant has no referrer to credit) ---
+    let is_new_player = player_account.player == Pubkey::default();
+
+    if is_new_player {
+        player_account.game_id = game.game_id;
+        player_account.player = player;
+        player_account.bump = ctx.bumps.player_state;
+        player_account.initialized = true;
+        player_account.generation = player_account.generation.wrapping_add(1);
+        player_account.claimed_dividends_lamports = 0;
+        player_account.claimed_referral_earnings_lamports = 0;
+        player_account.referral_earnings_lamports = 0;
+        player_account.pending_referral_earnings_lamports = 0;
+        player_account.referrer = None;
+        player_account.keys = 0;
+        player_account.dividend_weight = 0;
+        player_account.current_round = game.round;
+        player_account.is_agent = false;
+        player_account.auto_compound = false;
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player,
+            is_agent: false,
+            referrer: None,
+            timestamp: clock.unix_timestamp,
+        });
+    } else if player_account.current_round == 0 {
+        // Returning player (claimed from previous round)
+        player_account.keys = 0;
+        player_account.dividend_weight = 0;
+        player_account.current_round = game.round;
+        // Existing referrer preserved
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+    } else if player_account.current_round != game.round {
+        // In a different round — must claim first
+        return err!(FomoltError::MustClaimPreviousRound);
+    }
+    // else: already in this round — continue granting
+
+    // --- Lazily initialize lifetime stats profile (round-agnostic, never reset) ---
+    let stats = &mut ctx.accounts.player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = player;
+        stats.bump = ctx.bumps.player_stats;
+    }
+
+    // --- Price the grant off the live curve, same as a real buy of `keys` ---
+    let cost = math::calculate_cost(
+        game.total_keys,
+        keys,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )?;
+
+    // --- Admin deposits the full cost into the vault, backing the grant ---
+    if cost > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(cost)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    // --- Pot splits from the deposited cost, same ordering as buy_keys's
+    // pot_contribution split (no house fee or referral on a promo grant) ---
+    let winner_amount = math::calculate_bps_split(cost, game.winner_bps)?;
+    let dividend_amount = math::calculate_bps_split(cost, game.dividend_bps)?;
+    let next_round_amount = math::calculate_bps_split(cost, game.next_round_bps)?;
+    let split_dust = cost
+        .checked_sub(winner_amount)
+        .and_then(|r| r.checked_sub(dividend_amount))
+        .and_then(|r| r.checked_sub(next_round_amount))
+        .ok_or(FomoltError::Overflow)?;
+    let next_round_amount = next_round_amount
+        .checked_add(split_dust)
+        .ok_or(FomoltError::Overflow)?;
+
+    game.winner_pot = game
+        .winner_pot
+        .checked_add(winner_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_pool = game
+        .total_dividend_pool
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.next_round_pot = game
+        .next_round_pot
+        .checked_add(next_round_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Dividend weight for the granted keys (before total_keys moves) ---
+    let weight_earned = math::calculate_key_weight(
+        game.total_keys,
+        keys,
+        game.early_bird_key_threshold,
+        game.early_bird_multiplier_bps,
+    )?;
+    player_account.dividend_weight = player_account
+        .dividend_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_weight = game
+        .total_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Add keys to player and game ---
+    player_account.keys = player_account
+        .keys
+        .checked_add(keys)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_keys = game.total_keys.checked_add(keys).ok_or(FomoltError::Overflow)?;
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    game.promo_keys_granted_this_round = granted_this_round;
+
+    // Calculate next key price for the event
+    let next_key_price = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+
+    let snapshot = &mut ctx.accounts.game_snapshot;
+    snapshot.pot_lamports = game.pot_lamports;
+    snapshot.total_keys = game.total_keys;
+    snapshot.next_key_price = next_key_price;
+
+    emit!(PromoGranted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        admin: ctx.accounts.admin.key(),
+        player,
+        keys_granted: keys,
+        lamports_deposited: cost,
+        promo_keys_granted_this_round: game.promo_keys_granted_this_round,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(GameUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        pot_lamports: game.pot_lamports,
+        total_keys: game.total_keys,
+        next_key_price,
+        last_buyer: game.last_buyer,
+        timer_end: game.timer_end,
+        winner_pot: game.winner_pot,
+        next_round_pot: game.next_round_pot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::errors::FomoltError;
+use crate::events::KeyMintInitialized;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitKeyMint<'info> {
+    /// Permissionless crank — anyone may pay to create a round's key mint,
+    /// same as `consolidate_referral_earnings`.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Pure signer PDA used as this round's mint (and freeze) authority —
+    /// holds no data, same role as `vault` for SOL.
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", game_state.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: SystemAccount<'info>,
+
+    /// This round's wrapped-key SPL mint. Decimals 0 — wrapped tokens are
+    /// 1:1 with whole keys, there's no fractional key.
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"key_mint", game_state.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub key_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the currently active round's wrapped-key SPL mint, gated by
+/// `GameState::wrapped_keys_enabled`. Must be called once before the first
+/// `wrap_keys` of a round — `wrap_keys`/`unwrap_keys` take the mint as a
+/// plain (non-`init`) account and fail if it doesn't exist yet.
+pub fn handle_init_key_mint(ctx: Context<InitKeyMint>) -> Result<()> {
+    require!(
+        ctx.accounts.game_state.wrapped_keys_enabled,
+        FomoltError::WrappedKeysDisabled
+    );
+
+    let clock = Clock::get()?;
+    emit!(KeyMintInitialized {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.game_state.game_id,
+        round: ctx.accounts.game_state.round,
+        key_mint: ctx.accounts.key_mint.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
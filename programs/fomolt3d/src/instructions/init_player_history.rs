@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::PlayerHistoryInitialized;
+use crate::state::*;
+
+/// Self-service: only the player benefits from their own `PlayerHistory`, so
+/// only they pay for it — same shape as `create_session`, unlike the
+/// permissionless cranks (`init_key_mint`, `end_round`) that anyone can fund.
+#[derive(Accounts)]
+pub struct InitPlayerHistory<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PlayerHistory::SPACE,
+        seeds = [b"history", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_history: Account<'info, PlayerHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_init_player_history(ctx: Context<InitPlayerHistory>) -> Result<()> {
+    require!(
+        ctx.accounts.game_state.purchase_history_enabled,
+        FomoltError::PurchaseHistoryDisabled
+    );
+
+    let clock = Clock::get()?;
+
+    let history = &mut ctx.accounts.player_history;
+    history.game_id = ctx.accounts.game_state.game_id;
+    history.player = ctx.accounts.player.key();
+    history.entries = [PurchaseRecord::default(); PlayerHistory::CAPACITY];
+    history.next_index = 0;
+    history.len = 0;
+    history.bump = ctx.bumps.player_history;
+
+    emit!(PlayerHistoryInitialized {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: history.game_id,
+        player: history.player,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
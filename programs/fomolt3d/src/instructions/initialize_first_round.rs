@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
 
 use crate::errors::FomoltError;
-use crate::events::RoundStarted;
+use crate::events::{RoundStarted, RoundStatusChanged};
+use crate::math;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -10,7 +12,8 @@ pub struct InitializeFirstRound<'info> {
     pub admin: Signer<'info>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
         bump = config.bump,
         constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
     )]
@@ -20,7 +23,7 @@ pub struct InitializeFirstRound<'info> {
         init,
         payer = admin,
         space = 8 + GameState::SPACE,
-        seeds = [b"game", 1u64.to_le_bytes().as_ref()],
+        seeds = [b"game", config.game_id.to_le_bytes().as_ref(), 1u64.to_le_bytes().as_ref()],
         bump,
     )]
     pub game_state: Account<'info, GameState>,
@@ -34,14 +37,25 @@ pub struct InitializeFirstRound<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Indexer-friendly mirror of this round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GameSnapshot::SPACE,
+        seeds = [b"snapshot", game_state.key().as_ref()],
+        bump,
+    )]
+    pub game_snapshot: Account<'info, GameSnapshot>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handle_initialize_first_round(ctx: Context<InitializeFirstRound>) -> Result<()> {
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let game = &mut ctx.accounts.game_state;
     let clock = Clock::get()?;
 
+    game.game_id = config.game_id;
     game.round = 1;
     game.pot_lamports = 0;
     game.timer_end = clock
@@ -51,12 +65,21 @@ pub fn handle_initialize_first_round(ctx: Context<InitializeFirstRound>) -> Resu
     game.last_buyer = Pubkey::default();
     game.total_keys = 0;
     game.round_start = clock.unix_timestamp;
-    game.active = true;
-    game.winner_claimed = false;
+    game.status = RoundStatus::Pending;
     game.total_players = 0;
     game.total_dividend_pool = 0;
     game.next_round_pot = 0;
     game.winner_pot = 0;
+    game.total_referral_obligations = 0;
+    game.total_weight = 0;
+    game.purchase_count = 0;
+    game.gross_volume_lamports = 0;
+    game.max_single_buy_lamports = 0;
+    game.max_single_buyer = Pubkey::default();
+    game.largest_holder_keys = 0;
+    game.largest_holder = Pubkey::default();
+    game.dividend_weight_seconds_total = 0;
+    game.dividend_seconds_last_update = game.round_start;
 
     // Snapshot config parameters
     game.base_price_lamports = config.base_price_lamports;
@@ -69,10 +92,117 @@ pub fn handle_initialize_first_round(ctx: Context<InitializeFirstRound>) -> Resu
     game.protocol_fee_bps = config.protocol_fee_bps;
     game.referral_bonus_bps = config.referral_bonus_bps;
     game.protocol_wallet = config.protocol_wallet;
+    game.early_bird_key_threshold = config.early_bird_key_threshold;
+    game.early_bird_multiplier_bps = config.early_bird_multiplier_bps;
+    game.min_purchase_lamports = config.min_purchase_lamports;
+    game.winner_claim_window_secs = config.winner_claim_window_secs;
+    game.final_hour_pot_threshold_lamports = config.final_hour_pot_threshold_lamports;
+    game.final_hour_shrink_interval_keys = config.final_hour_shrink_interval_keys;
+    game.final_hour_active = false;
+    game.final_hour_start_keys = 0;
+    game.pot_milestone_interval_lamports = config.pot_milestone_interval_lamports;
+    game.pot_milestone_bonus_keys = config.pot_milestone_bonus_keys;
+    game.vault_lamports_in = 0;
+    game.vault_lamports_out = 0;
+    game.promo_keys_cap_per_round = config.promo_keys_cap_per_round;
+    game.promo_keys_granted_this_round = 0;
+    game.transfers_enabled = config.transfers_enabled;
+    game.wrapped_keys_enabled = config.wrapped_keys_enabled;
+    game.wrapped_keys_total = 0;
+    game.wrapped_weight_total = 0;
+    game.keeper_fee_lamports = config.keeper_fee_lamports;
+    game.purchase_history_enabled = config.purchase_history_enabled;
+    game.time_weighted_dividends_enabled = config.time_weighted_dividends_enabled;
+    game.hook_program = config.hook_program;
+    game.referral_earnings_cap_lamports_per_round = config.referral_earnings_cap_lamports_per_round;
+    game.referral_decay_threshold_lamports = config.referral_decay_threshold_lamports;
+    game.referrer_change_cooldown_secs = config.referrer_change_cooldown_secs;
+    game.dividend_merkle_root = None;
+    game.kyc_required = config.kyc_required;
+    game.kyc_issuer = config.kyc_issuer;
+    game.dust_reserve = 0;
+    game.price_cumulative = 0;
+    game.price_last_update = game.round_start;
+    game.unclaimed_dividend_policy = config.unclaimed_dividend_policy;
+    game.dividend_claim_window_secs = config.dividend_claim_window_secs;
+    game.max_timer_extensions_per_window = config.max_timer_extensions_per_window;
+    game.timer_extension_window_secs = config.timer_extension_window_secs;
+    game.total_dividend_claimed_lamports = 0;
+    game.top_referrer_bonus_bps = config.top_referrer_bonus_bps;
+    game.top_referrer_bonus_pool = 0;
+    game.raffle_bps = config.raffle_bps;
+    game.raffle_daily_payout_bps = config.raffle_daily_payout_bps;
+    game.raffle_pool_lamports = 0;
+    game.raffle_prize_pool_pending = 0;
+    game.refund_pool_lamports = 0;
+    game.bridge_program = config.bridge_program;
+    game.max_pot_lamports = config.max_pot_lamports;
+    game.pot_overflow_reserve_lamports = 0;
+    game.timer_extensions_triggered = 0;
+    game.last_buy_timestamp = game.round_start;
+    game.buy_interval_seconds_total = 0;
+    game.pot_checkpoint_25_lamports = 0;
+    game.pot_checkpoint_50_lamports = 0;
+    game.pot_checkpoint_75_lamports = 0;
+    game.pot_checkpoint_25_reached = false;
+    game.pot_checkpoint_50_reached = false;
+    game.pot_checkpoint_75_reached = false;
+    game.auto_payout_winner_enabled = config.auto_payout_winner_enabled;
+    game.min_keys_for_timer_extension = config.min_keys_for_timer_extension;
+    game.price_sample_interval_slots = config.price_sample_interval_slots;
+    game.rounding_beneficiary = config.rounding_beneficiary;
+    game.season_length_rounds = config.season_length_rounds;
+    game.season_fee_bps = config.season_fee_bps;
+    game.agent_keys_total = 0;
+    game.human_keys_total = 0;
+    game.max_keys_per_round = config.max_keys_per_round;
+    game.referral_vesting_enabled = config.referral_vesting_enabled;
+    game.biggest_buyer_bonus_bps = config.biggest_buyer_bonus_bps;
+    game.biggest_buyer_bonus_pool = 0;
+    game.biggest_holder_bonus_bps = config.biggest_holder_bonus_bps;
+    game.biggest_holder_bonus_pool = 0;
+    game.frontend_fee_bps = config.frontend_fee_bps;
+    game.dividend_apr_window_secs = config.dividend_apr_window_secs;
+    game.dividend_apr_window_start = game.round_start;
+    game.dividend_apr_window_dividend_lamports = 0;
+    game.min_remaining_secs = config.min_remaining_secs;
+    game.agent_platform_fee_share_bps = config.agent_platform_fee_share_bps;
+    game.total_agent_platform_obligations = 0;
+    game.genesis_config_hash = hashv(&[&config.try_to_vec().unwrap()]).to_bytes();
 
     game.bump = ctx.bumps.game_state;
 
+    config.latest_round = 1;
+
+    let snapshot = &mut ctx.accounts.game_snapshot;
+    snapshot.game_id = game.game_id;
+    snapshot.round = game.round;
+    snapshot.pot_lamports = game.pot_lamports;
+    snapshot.total_keys = game.total_keys;
+    snapshot.timer_end = game.timer_end;
+    snapshot.last_buyer = game.last_buyer;
+    snapshot.next_key_price = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+    snapshot.bump = ctx.bumps.game_snapshot;
+
+    game.transition_status(RoundStatus::Active)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        from: RoundStatus::Pending,
+        to: RoundStatus::Active,
+        timestamp: clock.unix_timestamp,
+    });
+
     emit!(RoundStarted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
         round: 1,
         carry_over_lamports: 0,
         timer_end: game.timer_end,
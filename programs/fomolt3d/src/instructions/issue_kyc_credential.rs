@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::KycCredentialIssued;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct IssueKycCredential<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.kyc_issuer == issuer.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + KycCredential::SPACE,
+        seeds = [b"kyc", config.game_id.to_le_bytes().as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub kyc_credential: Account<'info, KycCredential>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_issue_kyc_credential(
+    ctx: Context<IssueKycCredential>,
+    wallet: Pubkey,
+) -> Result<()> {
+    let credential = &mut ctx.accounts.kyc_credential;
+    let clock = Clock::get()?;
+
+    credential.game_id = ctx.accounts.config.game_id;
+    credential.wallet = wallet;
+    credential.issuer = ctx.accounts.issuer.key();
+    credential.issued_at = clock.unix_timestamp;
+    credential.bump = ctx.bumps.kyc_credential;
+
+    emit!(KycCredentialIssued {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: credential.game_id,
+        issuer: credential.issuer,
+        wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,11 +3,123 @@ pub mod initialize_first_round;
 pub mod start_new_round;
 pub mod buy_keys;
 pub mod claim;
+pub mod claim_dividends;
+pub mod claim_winner;
+pub mod claim_and_roll;
+pub mod claim_to_stake;
 pub mod claim_referral_earnings;
+pub mod assert_solvency;
+pub mod add_to_blocklist;
+pub mod remove_from_blocklist;
+pub mod forfeit_winner_pot;
+pub mod create_session;
+pub mod buy_keys_via_session;
+pub mod close_player_state;
+pub mod consolidate_referral_earnings;
+pub mod set_preferences;
+pub mod commit_buy;
+pub mod reveal_buy;
+pub mod grant_promo_keys;
+pub mod transfer_keys;
+pub mod init_key_mint;
+pub mod wrap_keys;
+pub mod unwrap_keys;
+pub mod fund_keeper_budget;
+pub mod end_round;
+pub mod init_player_history;
+pub mod record_dividend_merkle_root;
+pub mod claim_with_proof;
+pub mod set_referrer;
+pub mod set_spend_limit;
+pub mod issue_kyc_credential;
+pub mod sweep_dust_reserve;
+pub mod sweep_unclaimed_dividends;
+pub mod simulate_strategy;
+pub mod sponsor_pot;
+pub mod register_keeper;
+pub mod slash_keeper;
+pub mod unregister_keeper;
+pub mod deploy_vault_yield;
+pub mod unwind_vault_yield;
+pub mod claim_top_referrer_bonus;
+pub mod claim_biggest_buyer_bonus;
+pub mod claim_biggest_holder_bonus;
+pub mod record_raffle_snapshot;
+pub mod draw_raffle_ticket;
+pub mod claim_raffle_prize;
+pub mod cancel_round;
+pub mod refund;
+pub mod propose_player_migration;
+pub mod execute_player_migration;
+pub mod record_sample;
+pub mod settle_season;
+pub mod claim_season_prize;
+pub mod register_agent_platform;
+pub mod claim_agent_platform_earnings;
+pub mod deposit_prepaid;
+pub mod set_scheduled_buy;
+pub mod execute_scheduled_buy;
+pub mod withdraw_prepaid;
 
 pub use create_or_update_config::*;
 pub use initialize_first_round::*;
 pub use start_new_round::*;
 pub use buy_keys::*;
 pub use claim::*;
+pub use claim_dividends::*;
+pub use claim_winner::*;
+pub use claim_and_roll::*;
+pub use claim_to_stake::*;
 pub use claim_referral_earnings::*;
+pub use assert_solvency::*;
+pub use add_to_blocklist::*;
+pub use remove_from_blocklist::*;
+pub use forfeit_winner_pot::*;
+pub use create_session::*;
+pub use buy_keys_via_session::*;
+pub use close_player_state::*;
+pub use consolidate_referral_earnings::*;
+pub use set_preferences::*;
+pub use commit_buy::*;
+pub use reveal_buy::*;
+pub use grant_promo_keys::*;
+pub use transfer_keys::*;
+pub use init_key_mint::*;
+pub use wrap_keys::*;
+pub use unwrap_keys::*;
+pub use fund_keeper_budget::*;
+pub use end_round::*;
+pub use init_player_history::*;
+pub use record_dividend_merkle_root::*;
+pub use claim_with_proof::*;
+pub use set_referrer::*;
+pub use set_spend_limit::*;
+pub use issue_kyc_credential::*;
+pub use sweep_dust_reserve::*;
+pub use sweep_unclaimed_dividends::*;
+pub use simulate_strategy::*;
+pub use sponsor_pot::*;
+pub use register_keeper::*;
+pub use slash_keeper::*;
+pub use unregister_keeper::*;
+pub use deploy_vault_yield::*;
+pub use unwind_vault_yield::*;
+pub use claim_top_referrer_bonus::*;
+pub use claim_biggest_buyer_bonus::*;
+pub use claim_biggest_holder_bonus::*;
+pub use record_raffle_snapshot::*;
+pub use draw_raffle_ticket::*;
+pub use claim_raffle_prize::*;
+pub use cancel_round::*;
+pub use refund::*;
+pub use propose_player_migration::*;
+pub use execute_player_migration::*;
+pub use record_sample::*;
+pub use settle_season::*;
+pub use claim_season_prize::*;
+pub use register_agent_platform::*;
+pub use claim_agent_platform_earnings::*;
+pub use deposit_prepaid::*;
+pub use set_scheduled_buy::*;
+pub use execute_scheduled_buy::*;
+pub use withdraw_prepaid::*;
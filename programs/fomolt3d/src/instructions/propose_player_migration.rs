@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::PlayerMigrationProposed;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(old_wallet: Pubkey, new_wallet: Pubkey)]
+pub struct ProposePlayerMigration<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// The lost/compromised wallet's `PlayerState` — `old_wallet` can't sign,
+    /// so its PDA is derived from the argument instead of a `Signer`.
+    #[account(
+        mut,
+        seeds = [b"player", config.game_id.to_le_bytes().as_ref(), old_wallet.as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.initialized @ FomoltError::PlayerStateNotInitialized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+/// Admin-assisted self-custody recovery, step 1: starts the
+/// `PLAYER_MIGRATION_TIMELOCK_SECS` countdown to re-bind `old_wallet`'s
+/// `PlayerState` — balances, keys, and referral relationships — to
+/// `new_wallet`. Nothing moves yet; `instructions::execute_player_migration`
+/// does the actual transfer once the timelock elapses, and only if
+/// `new_wallet` itself signs that step, so the admin alone can't complete a
+/// recovery without the claimed new owner also proving control of it.
+pub fn handle_propose_player_migration(
+    ctx: Context<ProposePlayerMigration>,
+    old_wallet: Pubkey,
+    new_wallet: Pubkey,
+) -> Result<()> {
+    require!(new_wallet != old_wallet, FomoltError::CannotMigrateToSameWallet);
+
+    let player_state = &mut ctx.accounts.player_state;
+    require!(
+        player_state.pending_migration_wallet.is_none(),
+        FomoltError::MigrationAlreadyPending
+    );
+
+    let clock = Clock::get()?;
+    let effective_at = clock
+        .unix_timestamp
+        .checked_add(PLAYER_MIGRATION_TIMELOCK_SECS)
+        .ok_or(FomoltError::Overflow)?;
+
+    player_state.pending_migration_wallet = Some(new_wallet);
+    player_state.migration_effective_at = effective_at;
+
+    emit!(PlayerMigrationProposed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        old_wallet,
+        new_wallet,
+        admin: ctx.accounts.admin.key(),
+        effective_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
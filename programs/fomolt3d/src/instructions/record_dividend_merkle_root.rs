@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::DividendMerkleRootRecorded;
+use crate::state::*;
+
+/// Admin-only crank: records a Merkle root over (player, dividend_amount)
+/// leaves for an already-ended round, computed off-chain from the round's
+/// final `PlayerState` snapshot. Lets `claim_with_proof` pay out thousands
+/// of holders without each of them needing a `PlayerState` account at all —
+/// the existing `claim` instruction is untouched and keeps working for
+/// anyone who'd rather claim directly.
+#[derive(Accounts)]
+pub struct RecordDividendMerkleRoot<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+pub fn handle_record_dividend_merkle_root(
+    ctx: Context<RecordDividendMerkleRoot>,
+    merkle_root: [u8; 32],
+) -> Result<()> {
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(
+        game.status != RoundStatus::Active,
+        FomoltError::RoundStillActiveForMerkleRoot
+    );
+
+    game.dividend_merkle_root = Some(merkle_root);
+
+    emit!(DividendMerkleRootRecorded {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        merkle_root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
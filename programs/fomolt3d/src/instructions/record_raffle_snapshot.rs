@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::RAFFLE_INTERVAL_SECS;
+use crate::errors::FomoltError;
+use crate::events::RaffleSnapshotRecorded;
+use crate::math;
+use crate::state::*;
+
+/// Admin-only crank: opens day `day_index` of the round's daily key-holder
+/// raffle, committing to a Merkle root over every player's
+/// `(player, weight_range_start, weight_range_end)` leaf computed off-chain
+/// from `PlayerState::dividend_weight` at this instant — the program can't
+/// enumerate every `PlayerState` PDA to build this itself. Carves this day's
+/// prize out of `GameState::raffle_pool_lamports` up front so
+/// `draw_raffle_ticket`/`claim_raffle_prize` never need to touch that pool
+/// again for this day. Callable once per day-of-round; `day_index` must not
+/// name a day whose `RAFFLE_INTERVAL_SECS` window hasn't opened yet.
+#[derive(Accounts)]
+#[instruction(day_index: u64)]
+pub struct RecordRaffleSnapshot<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RaffleSnapshot::SPACE,
+        seeds = [b"raffle", game_state.key().as_ref(), day_index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub raffle_snapshot: Account<'info, RaffleSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_record_raffle_snapshot(
+    ctx: Context<RecordRaffleSnapshot>,
+    day_index: u64,
+    merkle_root: [u8; 32],
+    total_weight: u64,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    let elapsed = clock.unix_timestamp.saturating_sub(game.round_start);
+    let day_start = (day_index as i64)
+        .checked_mul(RAFFLE_INTERVAL_SECS)
+        .ok_or(FomoltError::Overflow)?;
+    require!(day_start <= elapsed, FomoltError::RaffleDayNotElapsed);
+    require!(total_weight > 0, FomoltError::RaffleTotalWeightZero);
+
+    let prize_lamports =
+        math::calculate_bps_split(game.raffle_pool_lamports, game.raffle_daily_payout_bps)?;
+    game.raffle_pool_lamports = game
+        .raffle_pool_lamports
+        .checked_sub(prize_lamports)
+        .ok_or(FomoltError::Overflow)?;
+    game.raffle_prize_pool_pending = game
+        .raffle_prize_pool_pending
+        .checked_add(prize_lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    let snapshot = &mut ctx.accounts.raffle_snapshot;
+    snapshot.game_id = game.game_id;
+    snapshot.round = game.round;
+    snapshot.day_index = day_index;
+    snapshot.merkle_root = merkle_root;
+    snapshot.total_weight = total_weight;
+    snapshot.winning_ticket = None;
+    snapshot.prize_lamports = prize_lamports;
+    snapshot.bump = ctx.bumps.raffle_snapshot;
+
+    emit!(RaffleSnapshotRecorded {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        day_index,
+        merkle_root,
+        total_weight,
+        prize_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::PriceSampleRecorded;
+use crate::math;
+use crate::state::*;
+
+/// Permissionless crank: appends a `(slot, total_keys, price)` sample to
+/// `price_history` for rounds that have gone quiet for longer than
+/// `GameState::price_sample_interval_slots` — `buy_keys` already samples on
+/// qualifying buys, this just covers the gaps between them. Anyone can call
+/// it; there's nothing to authorize since it only ever appends a read of
+/// already-public state.
+#[derive(Accounts)]
+pub struct RecordSample<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Ring buffer of recent price samples for this round — see `PriceHistory`.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + PriceHistory::SPACE,
+        seeds = [b"price_history", game_state.key().as_ref()],
+        bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_record_sample(ctx: Context<RecordSample>) -> Result<()> {
+    let game = &ctx.accounts.game_state;
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+    require!(
+        game.price_sample_interval_slots > 0,
+        FomoltError::PriceSamplingDisabled
+    );
+
+    let clock = Clock::get()?;
+    let history = &mut ctx.accounts.price_history;
+    let due = history.len == 0
+        || clock.slot >= history.last_sampled_slot.saturating_add(game.price_sample_interval_slots);
+    require!(due, FomoltError::PriceSampleIntervalNotElapsed);
+
+    let price_lamports = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+
+    history.game_id = game.game_id;
+    history.round = game.round;
+    history.bump = ctx.bumps.price_history;
+    history.record(clock.slot, game.total_keys, price_lamports);
+
+    emit!(PriceSampleRecorded {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        slot: clock.slot,
+        total_keys: game.total_keys,
+        price_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
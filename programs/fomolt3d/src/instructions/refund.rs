@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::RefundClaimed;
+use crate::state::*;
+
+/// Self-claim, only once `cancel_round` has moved `game_state` to
+/// `Cancelled`. Pays `player_state.contributed_lamports` back 1:1 and
+/// drains it to 0 — same decrement-to-zero shape as
+/// `claim_referral_earnings`, including its `payout_address` redirect.
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Required only when `player_state.payout_address` is set — the refund
+    /// is sent here instead of to `player`. Must equal
+    /// `player_state.payout_address`.
+    /// CHECK: Validated manually in handler (key equality against player_state.payout_address)
+    #[account(mut)]
+    pub payout_destination: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_refund(ctx: Context<Refund>) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let game = &mut ctx.accounts.game_state;
+    let player = &mut ctx.accounts.player_state;
+    let clock = Clock::get()?;
+
+    require!(player.initialized, FomoltError::PlayerStateNotInitialized);
+    require!(game.status == RoundStatus::Cancelled, FomoltError::RoundNotCancelled);
+
+    let amount = player.contributed_lamports;
+    require!(amount > 0, FomoltError::NothingToRefund);
+
+    // --- Vault solvency check: refund must not dip below the rent-exempt minimum ---
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= amount, FomoltError::VaultInsolvent);
+
+    let vault_bump = ctx.bumps.vault;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    // --- Resolve the payout destination: player_state.payout_address when
+    // set, otherwise the signer (the pre-existing behavior) ---
+    let payout_to = match player.payout_address {
+        Some(expected) => {
+            let destination = ctx
+                .accounts
+                .payout_destination
+                .as_ref()
+                .ok_or(FomoltError::MissingPayoutDestination)?;
+            require!(
+                destination.key() == expected,
+                FomoltError::PayoutDestinationMismatch
+            );
+            destination.to_account_info()
+        }
+        None => ctx.accounts.player.to_account_info(),
+    };
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: payout_to,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.refund_pool_lamports = game
+        .refund_pool_lamports
+        .checked_sub(amount)
+        .ok_or(FomoltError::Overflow)?;
+    player.contributed_lamports = 0;
+
+    emit!(RefundClaimed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.player.key(),
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::AgentPlatformRegistered;
+use crate::state::*;
+
+/// Attributes an agent's `PlayerState` to a marketplace `platform` for the
+/// lifetime of the account, requiring `platform` to co-sign — this
+/// codebase's usual "signed allowlist" idiom, same as the single-pubkey
+/// co-signers on `GlobalConfig` (see `instructions::deploy_vault_yield`).
+/// Once set, `GameState::agent_platform_fee_share_bps` of every future
+/// `buy_keys` house fee from this player is diverted to `platform`'s
+/// `AgentPlatform` — see `instructions::buy_keys` and
+/// `instructions::claim_agent_platform_earnings`.
+#[derive(Accounts)]
+pub struct RegisterAgentPlatform<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The marketplace being attached — must co-sign to prove it accepts
+    /// this agent's volume, mirroring this codebase's other single-pubkey
+    /// "allowlist" checks (a co-signature rather than an off-chain
+    /// signature-verification scheme this program has never used).
+    pub platform: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+        constraint = player_state.is_agent @ FomoltError::NotAnAgent,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + AgentPlatform::SPACE,
+        seeds = [b"agent_platform", game_state.game_id.to_le_bytes().as_ref(), platform.key().as_ref()],
+        bump,
+    )]
+    pub agent_platform: Account<'info, AgentPlatform>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_agent_platform(ctx: Context<RegisterAgentPlatform>) -> Result<()> {
+    require!(
+        ctx.accounts.player_state.agent_platform.is_none(),
+        FomoltError::AgentPlatformAlreadySet
+    );
+
+    let clock = Clock::get()?;
+    let agent_platform = &mut ctx.accounts.agent_platform;
+    if agent_platform.platform == Pubkey::default() {
+        agent_platform.game_id = ctx.accounts.game_state.game_id;
+        agent_platform.platform = ctx.accounts.platform.key();
+        agent_platform.pending_earnings_lamports = 0;
+        agent_platform.claimed_earnings_lamports = 0;
+        agent_platform.agent_count = 0;
+        agent_platform.registered_at = clock.unix_timestamp;
+        agent_platform.bump = ctx.bumps.agent_platform;
+    }
+    agent_platform.agent_count = agent_platform
+        .agent_count
+        .checked_add(1)
+        .ok_or(FomoltError::Overflow)?;
+
+    ctx.accounts.player_state.agent_platform = Some(ctx.accounts.platform.key());
+
+    emit!(AgentPlatformRegistered {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.game_state.game_id,
+        player: ctx.accounts.player.key(),
+        platform: ctx.accounts.platform.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
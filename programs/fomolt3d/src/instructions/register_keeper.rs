@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::KeeperRegistered;
+use crate::state::*;
+
+/// Permissionless: anyone can post a bond to register as a priority keeper
+/// for this game lineage's round-op cranks (see `instructions::end_round`).
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + KeeperState::SPACE,
+        seeds = [b"keeper", config.game_id.to_le_bytes().as_ref(), keeper.key().as_ref()],
+        bump,
+    )]
+    pub keeper_state: Account<'info, KeeperState>,
+
+    /// This keeper's bond vault
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_bond", config.game_id.to_le_bytes().as_ref(), keeper.key().as_ref()],
+        bump,
+    )]
+    pub keeper_bond: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_keeper(ctx: Context<RegisterKeeper>, bond_lamports: u64) -> Result<()> {
+    require!(bond_lamports > 0, FomoltError::InvalidFundAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.keeper.to_account_info(),
+                to: ctx.accounts.keeper_bond.to_account_info(),
+            },
+        ),
+        bond_lamports,
+    )?;
+
+    let clock = Clock::get()?;
+    let keeper_state = &mut ctx.accounts.keeper_state;
+    keeper_state.game_id = ctx.accounts.config.game_id;
+    keeper_state.keeper = ctx.accounts.keeper.key();
+    keeper_state.bond_lamports = ctx.accounts.keeper_bond.lamports();
+    keeper_state.registered_at = clock.unix_timestamp;
+    keeper_state.active = true;
+    keeper_state.slash_count = 0;
+    keeper_state.bump = ctx.bumps.keeper_state;
+
+    emit!(KeeperRegistered {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: keeper_state.game_id,
+        keeper: keeper_state.keeper,
+        bond_lamports: keeper_state.bond_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
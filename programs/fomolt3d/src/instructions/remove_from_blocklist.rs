@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::BlocklistUpdated;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RemoveFromBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"blocked", block_entry.game_id.to_le_bytes().as_ref(), block_entry.wallet.as_ref()],
+        bump = block_entry.bump,
+        constraint = block_entry.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub block_entry: Account<'info, BlockEntry>,
+}
+
+pub fn handle_remove_from_blocklist(ctx: Context<RemoveFromBlocklist>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    emit!(BlocklistUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.block_entry.game_id,
+        admin: ctx.accounts.admin.key(),
+        wallet: ctx.accounts.block_entry.wallet,
+        blocked: false,
+        allow_claim: ctx.accounts.block_entry.allow_claim,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
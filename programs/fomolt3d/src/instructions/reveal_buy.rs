@@ -0,0 +1,744 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use solana_sha256_hasher::hashv;
+
+use crate::errors::FomoltError;
+use crate::events::{
+    BlockedAttempt, BuyRevealed, FinalHourActivated, GameUpdated, KeysPurchased,
+    MilestoneReached, PlayerRegistered, ProtocolFeeCollected, PurchaseSettled, ReferralEarned,
+    ReferrerSet, VaultFlow,
+};
+use crate::math;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevealBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"commitment", game_state.key().as_ref(), buyer.key().as_ref()],
+        bump = commitment.bump,
+        has_one = buyer,
+        constraint = commitment.round == game_state.round @ FomoltError::PlayerNotInRound,
+    )]
+    pub commitment: Account<'info, BuyCommitment>,
+
+    /// Escrow PDA holding the budget locked by the matching `commit_buy`.
+    /// CHECK: Plain SOL escrow PDA, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"commit_vault", commitment.key().as_ref()],
+        bump,
+    )]
+    pub commit_vault: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Indexer-friendly mirror of this round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        mut,
+        seeds = [b"snapshot", game_state.key().as_ref()],
+        bump = game_snapshot.bump,
+    )]
+    pub game_snapshot: Account<'info, GameSnapshot>,
+
+    /// Append-only companion to `game_state` for per-round data that isn't
+    /// worth growing `GameState::SPACE` for — see `GameStateExt`. Created
+    /// lazily on whichever buy first crosses a pot milestone this round.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + GameStateExt::SPACE,
+        seeds = [b"game_ext", game_state.key().as_ref()],
+        bump,
+    )]
+    pub game_state_ext: Account<'info, GameStateExt>,
+
+    /// Protocol fee recipient wallet
+    /// CHECK: Validated against game_state.protocol_wallet
+    #[account(
+        mut,
+        constraint = protocol_wallet.key() == game_state.protocol_wallet @ FomoltError::InvalidConfig,
+    )]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    /// Optional referrer's PlayerState — must be writable for referral credit.
+    /// CHECK: Validated manually in handler (PDA derivation + referrer match)
+    #[account(mut)]
+    pub referrer_state: Option<Account<'info, PlayerState>>,
+
+    /// Optional referrer's wallet for direct referral payment.
+    /// CHECK: Validated in handler against referrer_state.player
+    #[account(mut)]
+    pub referrer_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Optional referrer's lifetime stats — credited with the referral bonus earned.
+    /// CHECK: Validated manually in handler (PDA derivation + referrer match)
+    #[account(mut)]
+    pub referrer_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Always the canonical `[b"blocked", game_id, buyer]` PDA, whether or
+    /// not it's actually initialized — required (not `Option`) so a blocked
+    /// wallet can't skip the check simply by omitting the account. See
+    /// `state::BlockEntry::load`.
+    /// CHECK: loaded manually via `BlockEntry::load`; address pinned by `seeds`
+    #[account(
+        seeds = [b"blocked", game_state.game_id.to_le_bytes().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub block_entry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_reveal_buy(
+    ctx: Context<RevealBuy>,
+    keys_to_buy: u64,
+    salt: [u8; 32],
+    is_agent: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let buyer_key = ctx.accounts.buyer.key();
+    let commit_vault_bump = ctx.bumps.commit_vault;
+
+    // --- Verify the reveal matches what was committed ---
+    let computed_hash = hashv(&[
+        &keys_to_buy.to_le_bytes()[..],
+        &salt[..],
+        buyer_key.as_ref(),
+    ])
+    .to_bytes();
+    require!(
+        computed_hash == ctx.accounts.commitment.commitment_hash,
+        FomoltError::CommitmentHashMismatch
+    );
+    require!(
+        clock.slot > ctx.accounts.commitment.commit_slot,
+        FomoltError::RevealTooSoon
+    );
+    require!(keys_to_buy > 0, FomoltError::NoKeysToBuy);
+
+    let game = &mut ctx.accounts.game_state;
+    let player = &mut ctx.accounts.player_state;
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+    require!(clock.unix_timestamp < game.timer_end, FomoltError::TimerExpired);
+
+    // --- Blocklist check: revealing is never allowed for a blocked wallet,
+    // regardless of its allow_claim policy ---
+    if BlockEntry::load(&ctx.accounts.block_entry.to_account_info())?.is_some() {
+        emit!(BlockedAttempt {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            wallet: buyer_key,
+            action: "reveal_buy".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+        return err!(FomoltError::WalletBlocked);
+    }
+
+    // --- Handle player registration / round entry ---
+    let is_new_player = player.player == Pubkey::default();
+
+    if is_new_player {
+        player.game_id = game.game_id;
+        player.player = buyer_key;
+        player.bump = ctx.bumps.player_state;
+        player.initialized = true;
+        player.generation = player.generation.wrapping_add(1);
+        player.claimed_dividends_lamports = 0;
+        player.claimed_referral_earnings_lamports = 0;
+        player.referral_earnings_lamports = 0;
+        player.pending_referral_earnings_lamports = 0;
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.current_round = game.round;
+
+        if let Some(referrer_state) = &ctx.accounts.referrer_state {
+            require!(
+                referrer_state.player != buyer_key,
+                FomoltError::CannotReferSelf
+            );
+
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                referrer_state.key() == expected_pda,
+                FomoltError::ReferrerNotRegistered
+            );
+
+            player.referrer = Some(referrer_state.player);
+
+            emit!(ReferrerSet {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                player: buyer_key,
+                referrer: referrer_state.player,
+                timestamp: clock.unix_timestamp,
+            });
+        } else {
+            player.referrer = None;
+        }
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: buyer_key,
+            is_agent,
+            referrer: player.referrer,
+            timestamp: clock.unix_timestamp,
+        });
+    } else if player.current_round == 0 {
+        require!(player.player == buyer_key, FomoltError::Unauthorized);
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.contributed_lamports = 0;
+        player.current_round = game.round;
+
+        game.total_players = game
+            .total_players
+            .checked_add(1)
+            .ok_or(FomoltError::Overflow)?;
+    } else if player.current_round == game.round {
+        require!(player.player == buyer_key, FomoltError::Unauthorized);
+    } else {
+        return err!(FomoltError::MustClaimPreviousRound);
+    }
+
+    player.is_agent = is_agent;
+
+    // --- Lazily initialize lifetime stats profile (round-agnostic, never reset) ---
+    let stats = &mut ctx.accounts.player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = buyer_key;
+        stats.bump = ctx.bumps.player_stats;
+    }
+
+    // --- Sold-out supply cap: `buy_keys`'s main path silently clamps
+    // `keys_to_buy` down to whatever supply remains, but the amount here is
+    // baked into `commitment`'s hash — it can't be adjusted after the fact,
+    // so a reveal that would oversell `max_keys_per_round` is rejected
+    // outright instead. See `GlobalConfig::max_keys_per_round`. ---
+    require!(
+        game.max_keys_per_round == 0
+            || game
+                .total_keys
+                .checked_add(keys_to_buy)
+                .is_some_and(|total| total <= game.max_keys_per_round),
+        FomoltError::MaxKeysPerRoundExceeded
+    );
+
+    // --- Price the reveal off total_keys_at_commit, not the live curve
+    // position — this is the whole point of committing first. ---
+    let cost = math::calculate_cost(
+        ctx.accounts.commitment.total_keys_at_commit,
+        keys_to_buy,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )?;
+
+    require!(
+        game.min_purchase_lamports == 0 || cost >= game.min_purchase_lamports,
+        FomoltError::BelowMinimumPurchase
+    );
+    require!(
+        cost <= ctx.accounts.commitment.budget_lamports,
+        FomoltError::CommitBudgetExceeded
+    );
+
+    // === Fee Ordering: house fee → referral → pot splits (same as buy_keys) ===
+
+    let house_fee = math::calculate_bps_split(cost, game.protocol_fee_bps)?;
+    let after_fee = cost.checked_sub(house_fee).ok_or(FomoltError::Overflow)?;
+
+    let mut referral_bonus_paid = 0u64;
+    let mut pot_contribution = after_fee;
+
+    if player.referrer.is_some() {
+        require!(
+            ctx.accounts.referrer_state.is_some(),
+            FomoltError::ReferrerMismatch
+        );
+    }
+
+    if let Some(referrer_state) = &mut ctx.accounts.referrer_state {
+        if let Some(existing_referrer) = player.referrer {
+            require!(
+                referrer_state.player == existing_referrer,
+                FomoltError::ReferrerMismatch
+            );
+
+            let (expected_pda, _) = Pubkey::find_program_address(
+                &[b"player", game.game_id.to_le_bytes().as_ref(), referrer_state.player.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                referrer_state.key() == expected_pda,
+                FomoltError::ReferrerNotRegistered
+            );
+
+            let referral_bonus = math::calculate_bps_split(after_fee, game.referral_bonus_bps)?;
+
+            if referral_bonus > 0 {
+                if game.referral_vesting_enabled {
+                    referrer_state.pending_referral_earnings_lamports = referrer_state
+                        .pending_referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                } else {
+                    referrer_state.referral_earnings_lamports = referrer_state
+                        .referral_earnings_lamports
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+                game.total_referral_obligations = game
+                    .total_referral_obligations
+                    .checked_add(referral_bonus)
+                    .ok_or(FomoltError::Overflow)?;
+
+                if let Some(referrer_stats) = &mut ctx.accounts.referrer_stats {
+                    require!(
+                        referrer_stats.player == referrer_state.player,
+                        FomoltError::ReferrerMismatch
+                    );
+                    referrer_stats.lifetime_referral_earned = referrer_stats
+                        .lifetime_referral_earned
+                        .checked_add(referral_bonus)
+                        .ok_or(FomoltError::Overflow)?;
+                }
+
+                referral_bonus_paid = referral_bonus;
+                pot_contribution = after_fee
+                    .checked_sub(referral_bonus)
+                    .ok_or(FomoltError::Overflow)?;
+            }
+        }
+    }
+
+    // --- Transfer SOL: all of cost moves out of commit_vault, which must
+    // sign for itself since the buyer already escrowed the funds at commit
+    // time ---
+    let commitment_key = ctx.accounts.commitment.key();
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[b"commit_vault", commitment_key.as_ref(), &[commit_vault_bump]]];
+
+    if house_fee > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.commit_vault.to_account_info(),
+                    to: ctx.accounts.protocol_wallet.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            house_fee,
+        )?;
+
+        emit!(ProtocolFeeCollected {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            lamports: house_fee,
+            recipient: ctx.accounts.protocol_wallet.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if pot_contribution > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.commit_vault.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            pot_contribution,
+        )?;
+        game.vault_lamports_in = game
+            .vault_lamports_in
+            .checked_add(pot_contribution)
+            .ok_or(FomoltError::Overflow)?;
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Buy,
+            lamports: pot_contribution,
+            counterparty: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if referral_bonus_paid > 0 {
+        let referrer_wallet = ctx
+            .accounts
+            .referrer_wallet
+            .as_ref()
+            .ok_or(FomoltError::ReferrerMismatch)?;
+        let referrer_state = ctx
+            .accounts
+            .referrer_state
+            .as_ref()
+            .ok_or(FomoltError::ReferrerMismatch)?;
+        require!(
+            referrer_wallet.key() == referrer_state.player,
+            FomoltError::ReferrerMismatch
+        );
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.commit_vault.to_account_info(),
+                    to: referrer_wallet.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            referral_bonus_paid,
+        )?;
+    }
+
+    // --- Refund whatever was left of the committed budget ---
+    let refunded_lamports = ctx
+        .accounts
+        .commitment
+        .budget_lamports
+        .checked_sub(cost)
+        .ok_or(FomoltError::Overflow)?;
+    if refunded_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.commit_vault.to_account_info(),
+                    to: ctx.accounts.buyer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refunded_lamports,
+        )?;
+    }
+
+    // Step 3: Pot splits from pot_contribution — same dust-into-next-round
+    // handling as buy_keys, with the raffle's cut carved off the top first.
+    let raffle_amount = math::calculate_bps_split(pot_contribution, game.raffle_bps)?;
+    let splittable = pot_contribution
+        .checked_sub(raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
+    let winner_amount = math::calculate_bps_split(splittable, game.winner_bps)?;
+    let dividend_amount = math::calculate_bps_split(splittable, game.dividend_bps)?;
+    let next_round_amount = math::calculate_bps_split(splittable, game.next_round_bps)?;
+    let split_dust = splittable
+        .checked_sub(winner_amount)
+        .and_then(|r| r.checked_sub(dividend_amount))
+        .and_then(|r| r.checked_sub(next_round_amount))
+        .ok_or(FomoltError::Overflow)?;
+    let next_round_amount = next_round_amount
+        .checked_add(split_dust)
+        .ok_or(FomoltError::Overflow)?;
+
+    game.raffle_pool_lamports = game
+        .raffle_pool_lamports
+        .checked_add(raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.winner_pot = game
+        .winner_pot
+        .checked_add(winner_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_dividend_pool = game
+        .total_dividend_pool
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.next_round_pot = game
+        .next_round_pot
+        .checked_add(next_round_amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    // --- Early-bird dividend weight, priced off the live total_keys (the
+    // frozen total_keys_at_commit only governs cost, not weighting) ---
+    let weight_earned = math::calculate_key_weight(
+        game.total_keys,
+        keys_to_buy,
+        game.early_bird_key_threshold,
+        game.early_bird_multiplier_bps,
+    )?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_weight = game
+        .total_weight
+        .checked_add(weight_earned)
+        .ok_or(FomoltError::Overflow)?;
+
+    player.keys = player
+        .keys
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    player.contributed_lamports = player
+        .contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    player.total_contributed_lamports = player
+        .total_contributed_lamports
+        .checked_add(pot_contribution)
+        .ok_or(FomoltError::Overflow)?;
+    game.total_keys = game
+        .total_keys
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    if is_agent {
+        game.agent_keys_total = game
+            .agent_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    } else {
+        game.human_keys_total = game
+            .human_keys_total
+            .checked_add(keys_to_buy)
+            .ok_or(FomoltError::Overflow)?;
+    }
+    let pot_before = game.pot_lamports;
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+    game.last_buyer = buyer_key;
+
+    ctx.accounts.player_stats.lifetime_keys_bought = ctx
+        .accounts
+        .player_stats
+        .lifetime_keys_bought
+        .checked_add(keys_to_buy)
+        .ok_or(FomoltError::Overflow)?;
+    ctx.accounts.player_stats.lifetime_lamports_spent = ctx
+        .accounts
+        .player_stats
+        .lifetime_lamports_spent
+        .checked_add(cost)
+        .ok_or(FomoltError::Overflow)?;
+
+    if !game.final_hour_active
+        && game.final_hour_pot_threshold_lamports > 0
+        && game.pot_lamports >= game.final_hour_pot_threshold_lamports
+    {
+        game.final_hour_active = true;
+        game.final_hour_start_keys = game.total_keys;
+        emit!(FinalHourActivated {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            pot_lamports: game.pot_lamports,
+            total_keys: game.total_keys,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    let keys_since_final_hour_start = if game.final_hour_active {
+        game.total_keys.saturating_sub(game.final_hour_start_keys)
+    } else {
+        0
+    };
+
+    let milestones_crossed = math::calculate_milestones_crossed(
+        pot_before,
+        game.pot_lamports,
+        game.pot_milestone_interval_lamports,
+    );
+    if milestones_crossed > 0 {
+        let bonus_keys = game.pot_milestone_bonus_keys;
+        if bonus_keys > 0 {
+            let bonus_weight = bonus_keys.checked_mul(10_000).ok_or(FomoltError::Overflow)?;
+            player.keys = player.keys.checked_add(bonus_keys).ok_or(FomoltError::Overflow)?;
+            player.dividend_weight = player
+                .dividend_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_keys = game
+                .total_keys
+                .checked_add(bonus_keys)
+                .ok_or(FomoltError::Overflow)?;
+            game.total_weight = game
+                .total_weight
+                .checked_add(bonus_weight)
+                .ok_or(FomoltError::Overflow)?;
+        }
+
+        let milestone_number = game.pot_lamports / game.pot_milestone_interval_lamports;
+        emit!(MilestoneReached {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: buyer_key,
+            milestone_number,
+            pot_lamports: game.pot_lamports,
+            bonus_keys_granted: bonus_keys,
+            timestamp: clock.unix_timestamp,
+        });
+
+        let ext = &mut ctx.accounts.game_state_ext;
+        ext.game_id = game.game_id;
+        ext.round = game.round;
+        ext.bump = ctx.bumps.game_state_ext;
+        ext.milestones_reached_this_round = ext
+            .milestones_reached_this_round
+            .checked_add(milestones_crossed as u32)
+            .ok_or(FomoltError::Overflow)?;
+    }
+
+    game.timer_end = math::calculate_timer_extension(
+        clock.unix_timestamp,
+        math::TimerExtensionParams {
+            extension_secs: game.timer_extension_secs,
+            current_timer_end: game.timer_end,
+            round_start: game.round_start,
+            max_timer_secs: game.max_timer_secs,
+            final_hour_active: game.final_hour_active,
+            keys_since_final_hour_start,
+            final_hour_shrink_interval_keys: game.final_hour_shrink_interval_keys,
+            min_remaining_secs: game.min_remaining_secs,
+        },
+    )?;
+
+    emit!(KeysPurchased {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: buyer_key,
+        is_agent: player.is_agent,
+        keys_bought: keys_to_buy,
+        total_player_keys: player.keys,
+        lamports_spent: cost,
+        pot_contribution,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if referral_bonus_paid > 0 {
+        if let Some(referrer) = player.referrer {
+            let ext = &mut ctx.accounts.game_state_ext;
+            ext.game_id = game.game_id;
+            ext.round = game.round;
+            ext.bump = ctx.bumps.game_state_ext;
+            ext.credit_referrer(referrer, referral_bonus_paid)?;
+
+            emit!(ReferralEarned {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                player: buyer_key,
+                referrer,
+                keys_bought: keys_to_buy,
+                lamports_spent: cost,
+                referrer_lamports: referral_bonus_paid,
+                vested: !game.referral_vesting_enabled,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    emit!(PurchaseSettled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: buyer_key,
+        lamports_spent: cost,
+        protocol_fee_lamports: house_fee,
+        referrer: player.referrer,
+        referral_bonus_lamports: referral_bonus_paid,
+        pot_contribution,
+        raffle_pool_lamports: raffle_amount,
+        winner_pot_lamports: winner_amount,
+        dividend_pool_lamports: dividend_amount,
+        next_round_lamports: next_round_amount,
+        dust_lamports: split_dust,
+        timestamp: clock.unix_timestamp,
+    });
+
+    let next_key_price = math::calculate_cost(
+        game.total_keys,
+        1,
+        game.base_price_lamports,
+        game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+
+    let snapshot = &mut ctx.accounts.game_snapshot;
+    snapshot.pot_lamports = game.pot_lamports;
+    snapshot.total_keys = game.total_keys;
+    snapshot.timer_end = game.timer_end;
+    snapshot.last_buyer = game.last_buyer;
+    snapshot.next_key_price = next_key_price;
+
+    emit!(GameUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        pot_lamports: game.pot_lamports,
+        total_keys: game.total_keys,
+        next_key_price,
+        last_buyer: game.last_buyer,
+        timer_end: game.timer_end,
+        winner_pot: game.winner_pot,
+        next_round_pot: game.next_round_pot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(BuyRevealed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        buyer: buyer_key,
+        keys_bought: keys_to_buy,
+        lamports_spent: cost,
+        refunded_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
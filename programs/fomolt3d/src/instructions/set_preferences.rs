@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::events::PreferencesUpdated;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetPreferences<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+pub fn handle_set_preferences(
+    ctx: Context<SetPreferences>,
+    auto_compound: bool,
+    payout_address: Option<Pubkey>,
+) -> Result<()> {
+    let player_state = &mut ctx.accounts.player_state;
+    player_state.auto_compound = auto_compound;
+    player_state.payout_address = payout_address;
+
+    emit!(PreferencesUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        player: ctx.accounts.player.key(),
+        auto_compound,
+        payout_address,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
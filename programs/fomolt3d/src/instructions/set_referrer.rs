@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::{ReferrerChanged, ReferrerSet};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetReferrer<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+        constraint = player_state.current_round == game_state.round @ FomoltError::PlayerNotInRound,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// The referrer being attached — must already have a `PlayerState` in
+    /// this same game lineage.
+    /// CHECK: Validated manually in handler (PDA derivation + self-referral/cycle checks)
+    pub referrer_state: Account<'info, PlayerState>,
+}
+
+/// Bound on how many ancestor accounts `handle_set_referrer` will walk
+/// through `ctx.remaining_accounts` when looking for a referral ring deeper
+/// than the direct two-party case already checked against `referrer_state`
+/// itself. Keeps the walk's compute cost capped regardless of how long a
+/// chain a malicious caller constructs.
+const MAX_REFERRAL_CHAIN_DEPTH: usize = 8;
+
+/// Lets a player attach a referrer for the first time, or — once
+/// `GameState::referrer_change_cooldown_secs` is configured — switch an
+/// already-set referrer to a different one after that cooldown has elapsed
+/// since `PlayerState::referrer_set_at`. Either way, nothing is allowed once
+/// the player has already bought keys this round (`ReferrerWindowClosed`):
+/// that's what stops a player from farming bonuses by churning referrers
+/// mid-round, on top of the cooldown itself.
+pub fn handle_set_referrer<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetReferrer<'info>>,
+) -> Result<()> {
+    let game = &ctx.accounts.game_state;
+    let player_state = &mut ctx.accounts.player_state;
+    let referrer_state = &ctx.accounts.referrer_state;
+    let clock = Clock::get()?;
+
+    require!(
+        referrer_state.player != player_state.player,
+        FomoltError::CannotReferSelf
+    );
+
+    let (expected_pda, _) = Pubkey::find_program_address(
+        &[
+            b"player",
+            player_state.game_id.to_le_bytes().as_ref(),
+            referrer_state.player.as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require!(
+        referrer_state.key() == expected_pda,
+        FomoltError::ReferrerNotRegistered
+    );
+
+    // Direct 2-cycle guard: the referrer can't already be referred by the
+    // very player it's about to receive as a referee.
+    require!(
+        referrer_state.referrer != Some(player_state.player),
+        FomoltError::ReferralCycleDetected
+    );
+
+    // Deeper rings (A -> B -> C -> A): walk the referrer's own ancestry,
+    // bounded by MAX_REFERRAL_CHAIN_DEPTH, via whatever PlayerState accounts
+    // the caller supplied as remaining_accounts. A caller who omits them (or
+    // stops partway) only gets weaker protection for their own chain, not a
+    // way to force an invalid one through — each supplied account is still
+    // independently PDA- and ownership-validated.
+    assert_no_deeper_referral_cycle(
+        player_state.game_id,
+        player_state.player,
+        referrer_state.referrer,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+
+    require!(player_state.keys == 0, FomoltError::ReferrerWindowClosed);
+
+    let old_referrer = player_state.referrer;
+    if old_referrer.is_some() {
+        require!(
+            game.referrer_change_cooldown_secs > 0,
+            FomoltError::ReferrerAlreadySet
+        );
+        let earliest_change = player_state
+            .referrer_set_at
+            .checked_add(game.referrer_change_cooldown_secs)
+            .ok_or(FomoltError::Overflow)?;
+        require!(
+            clock.unix_timestamp >= earliest_change,
+            FomoltError::ReferrerChangeCooldownActive
+        );
+    }
+
+    player_state.referrer = Some(referrer_state.player);
+    player_state.referrer_set_at = clock.unix_timestamp;
+
+    match old_referrer {
+        None => emit!(ReferrerSet {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: player_state.game_id,
+            player: player_state.player,
+            referrer: referrer_state.player,
+            timestamp: clock.unix_timestamp,
+        }),
+        Some(prev_referrer) => emit!(ReferrerChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: player_state.game_id,
+            player: player_state.player,
+            old_referrer: prev_referrer,
+            new_referrer: referrer_state.player,
+            timestamp: clock.unix_timestamp,
+        }),
+    }
+
+    Ok(())
+}
+
+/// Walks up to `MAX_REFERRAL_CHAIN_DEPTH` ancestors starting from
+/// `first_ancestor` (the direct referrer's own referrer), consuming one of
+/// `remaining_accounts` per hop. Each hop's account must be the correctly
+/// derived `PlayerState` PDA for the expected ancestor, so a caller can't
+/// substitute an unrelated account to fake an early end-of-chain. Stops as
+/// soon as the chain runs out, the depth cap is hit, or the accounts run out
+/// — whichever comes first.
+fn assert_no_deeper_referral_cycle<'info>(
+    game_id: u64,
+    player: Pubkey,
+    first_ancestor: Option<Pubkey>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<()> {
+    let mut current = first_ancestor;
+
+    for account in remaining_accounts.iter().take(MAX_REFERRAL_CHAIN_DEPTH) {
+        let Some(ancestor_pubkey) = current else {
+            break;
+        };
+        require!(ancestor_pubkey != player, FomoltError::ReferralCycleDetected);
+
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[b"player", game_id.to_le_bytes().as_ref(), ancestor_pubkey.as_ref()],
+            program_id,
+        );
+        require!(account.key() == expected_pda, FomoltError::ReferrerNotRegistered);
+
+        let ancestor_state: Account<PlayerState> = Account::try_from(account)?;
+        current = ancestor_state.referrer;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ScheduledBuyConfigured;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetScheduledBuy<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+/// Configures (or disables, by passing `interval_secs == 0`) a recurring
+/// `execute_scheduled_buy` crank against `PlayerState::prepaid_balance_lamports`.
+/// Doesn't touch `last_scheduled_buy_at` — lowering the interval on an
+/// existing schedule can make the very next crank immediately due rather
+/// than restarting the wait.
+pub fn handle_set_scheduled_buy(
+    ctx: Context<SetScheduledBuy>,
+    keys_per_buy: u64,
+    interval_secs: i64,
+) -> Result<()> {
+    let player_state = &mut ctx.accounts.player_state;
+    player_state.scheduled_buy_keys = keys_per_buy;
+    player_state.scheduled_buy_interval_secs = interval_secs;
+
+    emit!(ScheduledBuyConfigured {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        player: player_state.player,
+        keys_per_buy,
+        interval_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
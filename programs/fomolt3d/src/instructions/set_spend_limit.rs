@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::SpendLimitUpdated;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetSpendLimit<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+/// Opt-in self-imposed cap on lamports spent on key purchases per rolling
+/// `SPEND_WINDOW_SECS` window. `new_limit_lamports_per_day == 0` means no
+/// limit.
+///
+/// Lowering the cap (or setting one for the first time) applies immediately.
+/// Raising it — including removing it entirely by passing 0 — is delayed by
+/// `SPEND_LIMIT_INCREASE_DELAY_SECS` so a player can't undo their own
+/// guardrail mid-binge; the raise is only promoted into
+/// `PlayerState::spend_limit_lamports_per_day` once that delay has elapsed,
+/// via `PlayerState::apply_pending_spend_limit`.
+pub fn handle_set_spend_limit(
+    ctx: Context<SetSpendLimit>,
+    new_limit_lamports_per_day: u64,
+) -> Result<()> {
+    let player_state = &mut ctx.accounts.player_state;
+    let clock = Clock::get()?;
+
+    player_state.apply_pending_spend_limit(clock.unix_timestamp);
+
+    let previous_limit = player_state.spend_limit_lamports_per_day;
+    let current_effective = if previous_limit == 0 { u64::MAX } else { previous_limit };
+    let requested_effective = if new_limit_lamports_per_day == 0 {
+        u64::MAX
+    } else {
+        new_limit_lamports_per_day
+    };
+
+    let effective_at = if requested_effective <= current_effective {
+        player_state.spend_limit_lamports_per_day = new_limit_lamports_per_day;
+        player_state.pending_spend_limit_lamports_per_day = None;
+        clock.unix_timestamp
+    } else {
+        let effective_at = clock
+            .unix_timestamp
+            .checked_add(SPEND_LIMIT_INCREASE_DELAY_SECS)
+            .ok_or(FomoltError::Overflow)?;
+        player_state.pending_spend_limit_lamports_per_day = Some(new_limit_lamports_per_day);
+        player_state.spend_limit_effective_at = effective_at;
+        effective_at
+    };
+
+    emit!(SpendLimitUpdated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        player: player_state.player,
+        previous_limit_lamports_per_day: previous_limit,
+        new_limit_lamports_per_day,
+        effective_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::SeasonSettled;
+use crate::state::*;
+
+/// Permissionless — anyone can crank this once `season.end_round` has
+/// concluded, fixing the leaderboard ranks `claim_season_prize` pays out
+/// against. `end_round`'s status is read straight off the `GameState` PDA
+/// for that round (fully derivable from `season.game_id`/`end_round`), the
+/// same cross-round-lookup shape `buy_keys` uses for `next_game_state`.
+#[derive(Accounts)]
+pub struct SettleSeason<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"season", season.game_id.to_le_bytes().as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    #[account(
+        seeds = [b"game", season.game_id.to_le_bytes().as_ref(), season.end_round.to_le_bytes().as_ref()],
+        bump = end_round_game_state.bump,
+    )]
+    pub end_round_game_state: Account<'info, GameState>,
+}
+
+pub fn handle_settle_season(ctx: Context<SettleSeason>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let end_round_game = &ctx.accounts.end_round_game_state;
+
+    require!(season.status == SeasonStatus::Active, FomoltError::SeasonAlreadySettled);
+    require!(
+        end_round_game.status != RoundStatus::Active,
+        FomoltError::SeasonNotYetOver
+    );
+
+    season.status = SeasonStatus::Settled;
+
+    let clock = Clock::get()?;
+    emit!(SeasonSettled {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: season.game_id,
+        season_id: season.season_id,
+        pool_lamports: season.pool_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::StrategySimulated;
+use crate::math;
+use crate::state::*;
+
+/// Cap on `keys_schedule.len()` — bounds compute cost for an unbounded
+/// client-supplied vector, same rationale as `buy_keys_batch`'s
+/// `MAX_BATCH_PURCHASES`.
+const MAX_SCHEDULE_STEPS: usize = 32;
+
+/// Read-only planner primitive for agents: projects the cumulative cost,
+/// resulting timer trajectory, and the caller's resulting dividend share if
+/// `keys_schedule` were bought against this round right now, tranche by
+/// tranche, without moving any lamports or touching any account. Mirrors
+/// `buy_keys`'s bonding-curve/fee/timer math exactly, but skips anything
+/// that isn't a pure function of `GameState` and the schedule itself —
+/// referral bonuses, KYC/blocklist gating, and partner hook CPIs all depend
+/// on accounts a dry run has no reason to require, so the projection assumes
+/// none of them apply.
+#[derive(Accounts)]
+pub struct SimulateStrategy<'info> {
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// The caller's existing PlayerState in this round, if any — used to
+    /// project their post-schedule dividend share starting from the keys
+    /// they already hold. Omitted (Anchor `Option<Account>` sentinel) for an
+    /// agent that hasn't bought in yet, treated as holding zero keys.
+    pub player_state: Option<Account<'info, PlayerState>>,
+}
+
+pub fn handle_simulate_strategy(
+    ctx: Context<SimulateStrategy>,
+    keys_schedule: Vec<u64>,
+) -> Result<()> {
+    require!(!keys_schedule.is_empty(), FomoltError::EmptyBatch);
+    require!(
+        keys_schedule.len() <= MAX_SCHEDULE_STEPS,
+        FomoltError::TooManyBatchPurchases
+    );
+
+    let game = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    let mut supply = game.total_keys;
+    let mut timer_end = game.timer_end;
+    let mut dividend_pool = game.total_dividend_pool;
+    let mut total_cost = 0u64;
+    let mut keys_since_final_hour_start = if game.final_hour_active {
+        game.total_keys.saturating_sub(game.final_hour_start_keys)
+    } else {
+        0
+    };
+
+    for keys_to_buy in keys_schedule {
+        require!(keys_to_buy > 0, FomoltError::NoKeysToBuy);
+
+        let cost = math::calculate_cost(
+            supply,
+            keys_to_buy,
+            game.base_price_lamports,
+            game.price_increment_lamports,
+        )?;
+        total_cost = total_cost.checked_add(cost).ok_or(FomoltError::Overflow)?;
+
+        let house_fee = math::calculate_bps_split(cost, game.protocol_fee_bps)?;
+        let pot_contribution = cost.checked_sub(house_fee).ok_or(FomoltError::Overflow)?;
+        let dividend_amount = math::calculate_bps_split(pot_contribution, game.dividend_bps)?;
+        dividend_pool = dividend_pool
+            .checked_add(dividend_amount)
+            .ok_or(FomoltError::Overflow)?;
+
+        timer_end = math::calculate_timer_extension(
+            clock.unix_timestamp,
+            math::TimerExtensionParams {
+                extension_secs: game.timer_extension_secs,
+                current_timer_end: timer_end,
+                round_start: game.round_start,
+                max_timer_secs: game.max_timer_secs,
+                final_hour_active: game.final_hour_active,
+                keys_since_final_hour_start,
+                final_hour_shrink_interval_keys: game.final_hour_shrink_interval_keys,
+                min_remaining_secs: game.min_remaining_secs,
+            },
+        )?;
+
+        supply = supply.checked_add(keys_to_buy).ok_or(FomoltError::Overflow)?;
+        if game.final_hour_active {
+            keys_since_final_hour_start = keys_since_final_hour_start
+                .checked_add(keys_to_buy)
+                .ok_or(FomoltError::Overflow)?;
+        }
+    }
+
+    let total_keys_bought = supply
+        .checked_sub(game.total_keys)
+        .ok_or(FomoltError::Overflow)?;
+    let caller_keys_before = ctx
+        .accounts
+        .player_state
+        .as_ref()
+        .map(|p| p.keys)
+        .unwrap_or(0);
+    let projected_caller_keys = caller_keys_before
+        .checked_add(total_keys_bought)
+        .ok_or(FomoltError::Overflow)?;
+    let projected_dividend_share =
+        math::calculate_dividend_share(projected_caller_keys, dividend_pool, supply)?;
+
+    emit!(StrategySimulated {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        total_keys_bought,
+        total_cost_lamports: total_cost,
+        projected_timer_end: timer_end,
+        projected_total_dividend_pool: dividend_pool,
+        projected_caller_dividend_share_lamports: projected_dividend_share,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::KeeperSlashed;
+use crate::state::*;
+
+/// Admin-only: forfeits part (or all) of a misbehaving keeper's bond to
+/// `GlobalConfig::protocol_wallet`. Does not deregister the keeper — a
+/// slashed keeper keeps cranking with whatever bond remains until either
+/// the admin slashes it to zero or the keeper calls `unregister_keeper`
+/// itself.
+#[derive(Accounts)]
+pub struct SlashKeeper<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"keeper", config.game_id.to_le_bytes().as_ref(), keeper_state.keeper.as_ref()],
+        bump = keeper_state.bump,
+        constraint = keeper_state.active @ FomoltError::KeeperNotActive,
+    )]
+    pub keeper_state: Account<'info, KeeperState>,
+
+    /// This keeper's bond vault
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_bond", config.game_id.to_le_bytes().as_ref(), keeper_state.keeper.as_ref()],
+        bump,
+    )]
+    pub keeper_bond: SystemAccount<'info>,
+
+    /// Protocol fee recipient wallet — receives slashed bonds
+    /// CHECK: Validated against config.protocol_wallet
+    #[account(
+        mut,
+        constraint = protocol_wallet.key() == config.protocol_wallet @ FomoltError::InvalidConfig,
+    )]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_slash_keeper(ctx: Context<SlashKeeper>, amount: u64) -> Result<()> {
+    require!(amount > 0, FomoltError::InvalidFundAmount);
+
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let slashable = ctx
+        .accounts
+        .keeper_bond
+        .lamports()
+        .saturating_sub(rent_exempt_min);
+    require!(amount <= slashable, FomoltError::InsufficientBond);
+
+    let game_id_bytes = ctx.accounts.config.game_id.to_le_bytes();
+    let keeper_bytes = ctx.accounts.keeper_state.keeper;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"keeper_bond",
+        game_id_bytes.as_ref(),
+        keeper_bytes.as_ref(),
+        &[ctx.bumps.keeper_bond],
+    ]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.keeper_bond.to_account_info(),
+                to: ctx.accounts.protocol_wallet.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    let keeper_state = &mut ctx.accounts.keeper_state;
+    keeper_state.bond_lamports = ctx.accounts.keeper_bond.lamports();
+    keeper_state.slash_count = keeper_state
+        .slash_count
+        .checked_add(1)
+        .ok_or(FomoltError::Overflow)?;
+
+    emit!(KeeperSlashed {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: keeper_state.game_id,
+        keeper: keeper_state.keeper,
+        lamports: amount,
+        remaining_bond_lamports: keeper_state.bond_lamports,
+        slash_count: keeper_state.slash_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::PotSponsored;
+use crate::state::*;
+
+/// Permissionless: anyone can top up one of the active round's pots without
+/// buying keys. Marketing partners boosting a round's pot, or anyone else
+/// seeding it, use this instead of `buy_keys`.
+#[derive(Accounts)]
+pub struct SponsorPot<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_sponsor_pot(
+    ctx: Context<SponsorPot>,
+    lamports: u64,
+    allocation: SponsorAllocation,
+) -> Result<()> {
+    require!(lamports > 0, FomoltError::InvalidFundAmount);
+
+    let game = &mut ctx.accounts.game_state;
+    require!(game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    match allocation {
+        SponsorAllocation::WinnerPot => {
+            game.winner_pot = game.winner_pot.checked_add(lamports).ok_or(FomoltError::Overflow)?;
+        }
+        SponsorAllocation::DividendPool => {
+            game.total_dividend_pool = game
+                .total_dividend_pool
+                .checked_add(lamports)
+                .ok_or(FomoltError::Overflow)?;
+        }
+        SponsorAllocation::NextRoundPot => {
+            game.next_round_pot = game
+                .next_round_pot
+                .checked_add(lamports)
+                .ok_or(FomoltError::Overflow)?;
+        }
+    }
+    game.pot_lamports = game.pot_lamports.checked_add(lamports).ok_or(FomoltError::Overflow)?;
+    game.vault_lamports_in = game
+        .vault_lamports_in
+        .checked_add(lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    emit!(PotSponsored {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        sponsor: ctx.accounts.sponsor.key(),
+        lamports,
+        allocation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use solana_sha256_hasher::hashv;
 
 use crate::errors::FomoltError;
-use crate::events::{RoundConcluded, RoundStarted};
+use crate::events::{
+    NextRoundSeeded, RoundConcluded, RoundStarted, RoundStatusChanged, VaultFlow,
+};
+use crate::instructions::create_or_update_config::{validate_config_params, ConfigParams};
+use crate::math;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -11,7 +16,8 @@ pub struct StartNewRound<'info> {
     pub payer: Signer<'info>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
         bump = config.bump,
     )]
     pub config: Account<'info, GlobalConfig>,
@@ -19,8 +25,9 @@ pub struct StartNewRound<'info> {
     /// Previous round's game state
     #[account(
         mut,
-        seeds = [b"game", prev_game_state.round.to_le_bytes().as_ref()],
+        seeds = [b"game", prev_game_state.game_id.to_le_bytes().as_ref(), prev_game_state.round.to_le_bytes().as_ref()],
         bump = prev_game_state.bump,
+        constraint = prev_game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
     )]
     pub prev_game_state: Account<'info, GameState>,
 
@@ -29,7 +36,7 @@ pub struct StartNewRound<'info> {
         init,
         payer = payer,
         space = 8 + GameState::SPACE,
-        seeds = [b"game", (prev_game_state.round + 1).to_le_bytes().as_ref()],
+        seeds = [b"game", prev_game_state.game_id.to_le_bytes().as_ref(), (prev_game_state.round + 1).to_le_bytes().as_ref()],
         bump,
     )]
     pub new_game_state: Account<'info, GameState>,
@@ -51,24 +58,58 @@ pub struct StartNewRound<'info> {
     )]
     pub new_vault: SystemAccount<'info>,
 
+    /// Indexer-friendly mirror of the new round's hot `GameState` fields — see `GameSnapshot`.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameSnapshot::SPACE,
+        seeds = [b"snapshot", new_game_state.key().as_ref()],
+        bump,
+    )]
+    pub new_game_snapshot: Account<'info, GameSnapshot>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
+pub fn handle_start_new_round(
+    ctx: Context<StartNewRound>,
+    overrides: Option<ConfigParams>,
+) -> Result<()> {
     let prev_game_key = ctx.accounts.prev_game_state.key();
     let prev_vault_bump = ctx.bumps.prev_vault;
     let prev_game = &mut ctx.accounts.prev_game_state;
-    let config = &ctx.accounts.config;
+    let config = &mut ctx.accounts.config;
     let new_game = &mut ctx.accounts.new_game_state;
     let clock = Clock::get()?;
 
+    // A one-off override (e.g. a short-timer "blitz" round, a zero-fee promo
+    // round) is only ever a deliberate admin action — it never mutates the
+    // persistent GlobalConfig, so every round after it reverts to normal.
+    if let Some(params) = &overrides {
+        require!(
+            ctx.accounts.payer.key() == config.admin,
+            FomoltError::Unauthorized
+        );
+        validate_config_params(params)?;
+    }
+
     // --- Validate round can overflow (seed already computed with +1 above) ---
     require!(prev_game.round < u64::MAX, FomoltError::Overflow);
 
     // --- Auto-end check: if timer expired, conclude the round ---
-    if clock.unix_timestamp >= prev_game.timer_end && prev_game.active {
-        prev_game.active = false;
+    if clock.unix_timestamp >= prev_game.timer_end && prev_game.status == RoundStatus::Active {
+        prev_game.transition_status(RoundStatus::Ended)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: prev_game.game_id,
+            round: prev_game.round,
+            from: RoundStatus::Active,
+            to: RoundStatus::Ended,
+            timestamp: clock.unix_timestamp,
+        });
         emit!(RoundConcluded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: prev_game.game_id,
             round: prev_game.round,
             winner: if prev_game.total_keys == 0 { Pubkey::default() } else { prev_game.last_buyer },
             winner_lamports: if prev_game.total_keys == 0 { 0 } else { prev_game.winner_pot },
@@ -78,27 +119,52 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
             next_round_pot: prev_game.next_round_pot,
             round_start: prev_game.round_start,
             round_end: prev_game.timer_end,
+            purchase_count: prev_game.purchase_count,
+            gross_volume_lamports: prev_game.gross_volume_lamports,
+            max_single_buy_lamports: prev_game.max_single_buy_lamports,
+            max_single_buyer: prev_game.max_single_buyer,
+            round_duration_secs: prev_game.round_duration_secs(),
+            timer_extensions_triggered: prev_game.timer_extensions_triggered,
+            average_seconds_between_buys: prev_game.average_seconds_between_buys(),
+            pot_checkpoint_25_lamports: prev_game.pot_checkpoint_25_lamports,
+            pot_checkpoint_50_lamports: prev_game.pot_checkpoint_50_lamports,
+            pot_checkpoint_75_lamports: prev_game.pot_checkpoint_75_lamports,
+            genesis_config_hash: prev_game.genesis_config_hash,
             timestamp: clock.unix_timestamp,
         });
     }
 
     // Previous round must be inactive
-    require!(!prev_game.active, FomoltError::GameStillActive);
+    require!(prev_game.status != RoundStatus::Active, FomoltError::GameStillActive);
 
-    // Mark empty rounds as concluded (no winner to claim)
-    if prev_game.total_keys == 0 {
-        prev_game.winner_claimed = true;
+    // Mark empty rounds as settled (no winner to claim) — a cancelled round
+    // is already terminal and has no such transition defined, so leave it as-is.
+    if prev_game.total_keys == 0 && prev_game.status != RoundStatus::Cancelled {
+        prev_game.transition_status(RoundStatus::Settled)?;
+        emit!(RoundStatusChanged {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: prev_game.game_id,
+            round: prev_game.round,
+            from: RoundStatus::Ended,
+            to: RoundStatus::Settled,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     // For empty rounds (no buys), forward the entire prev vault balance
     // to prevent carry-over lamports from being permanently trapped.
-    // For normal rounds, forward only next_round_pot (other vault funds
-    // belong to players who haven't claimed yet).
+    // For normal rounds, forward next_round_pot plus whatever spilled into
+    // pot_overflow_reserve_lamports (other vault funds belong to players
+    // who haven't claimed yet) — both buckets seed the new round's pot the
+    // same way.
     let vault_balance = ctx.accounts.prev_vault.lamports();
     let carry_over = if prev_game.total_keys == 0 {
         vault_balance
     } else {
-        let desired = prev_game.next_round_pot;
+        let desired = prev_game
+            .next_round_pot
+            .checked_add(prev_game.pot_overflow_reserve_lamports)
+            .ok_or(FomoltError::Overflow)?;
         let remaining = vault_balance.saturating_sub(desired);
         // If the remaining balance would sit below rent-exempt, drain the
         // full vault to zero so the runtime GCs it cleanly. The tiny dust
@@ -114,6 +180,7 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
     };
 
     // --- Vault balance check before carry-over transfer ---
+    let mut new_vault_lamports_in = 0u64;
     if carry_over > 0 {
         require!(
             vault_balance >= carry_over,
@@ -137,6 +204,9 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
                 ),
                 gap,
             )?;
+            new_vault_lamports_in = new_vault_lamports_in
+                .checked_add(gap)
+                .ok_or(FomoltError::Overflow)?;
         }
 
         let signer_seeds: &[&[&[u8]]] =
@@ -153,6 +223,13 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
             ),
             carry_over,
         )?;
+        prev_game.vault_lamports_out = prev_game
+            .vault_lamports_out
+            .checked_add(carry_over)
+            .ok_or(FomoltError::Overflow)?;
+        new_vault_lamports_in = new_vault_lamports_in
+            .checked_add(carry_over)
+            .ok_or(FomoltError::Overflow)?;
     }
 
     let new_round = prev_game
@@ -160,40 +237,369 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
         .checked_add(1)
         .ok_or(FomoltError::Overflow)?;
 
+    if carry_over > 0 {
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: prev_game.game_id,
+            round: prev_game.round,
+            direction: VaultFlowDirection::Out,
+            reason: VaultFlowReason::Carry,
+            lamports: carry_over,
+            counterparty: ctx.accounts.new_vault.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(VaultFlow {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: prev_game.game_id,
+            round: new_round,
+            direction: VaultFlowDirection::In,
+            reason: VaultFlowReason::Carry,
+            lamports: carry_over,
+            counterparty: ctx.accounts.prev_vault.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // `new_game_state`'s PDA is already derived from `prev_game_state.round + 1`
+    // above, so a stale or forked `prev_game_state` could only ever target a
+    // round that's either already initialized (fails on `init` re-init) or
+    // is the true next round. This check makes that invariant explicit and
+    // gives it a dedicated error instead of an opaque "account already in
+    // use" — see `GlobalConfig::latest_round`.
+    require!(
+        new_round == config.latest_round.checked_add(1).ok_or(FomoltError::Overflow)?,
+        FomoltError::RoundGapInvalid
+    );
+
+    // Snapshot either the one-off override or the persistent GlobalConfig —
+    // never both, never a mix of the two.
+    let (
+        max_timer_secs,
+        base_price_lamports,
+        price_increment_lamports,
+        timer_extension_secs,
+        winner_bps,
+        dividend_bps,
+        next_round_bps,
+        protocol_fee_bps,
+        referral_bonus_bps,
+        protocol_wallet,
+        early_bird_key_threshold,
+        early_bird_multiplier_bps,
+        min_purchase_lamports,
+        winner_claim_window_secs,
+        final_hour_pot_threshold_lamports,
+        final_hour_shrink_interval_keys,
+        pot_milestone_interval_lamports,
+        pot_milestone_bonus_keys,
+        promo_keys_cap_per_round,
+        transfers_enabled,
+        wrapped_keys_enabled,
+        keeper_fee_lamports,
+        purchase_history_enabled,
+        time_weighted_dividends_enabled,
+        hook_program,
+        referral_earnings_cap_lamports_per_round,
+        referral_decay_threshold_lamports,
+        referrer_change_cooldown_secs,
+        kyc_required,
+        kyc_issuer,
+        unclaimed_dividend_policy,
+        dividend_claim_window_secs,
+        max_timer_extensions_per_window,
+        timer_extension_window_secs,
+        top_referrer_bonus_bps,
+        raffle_bps,
+        raffle_daily_payout_bps,
+        bridge_program,
+        max_pot_lamports,
+        auto_payout_winner_enabled,
+        min_keys_for_timer_extension,
+        price_sample_interval_slots,
+        rounding_beneficiary,
+        season_length_rounds,
+        season_fee_bps,
+        max_keys_per_round,
+        referral_vesting_enabled,
+        biggest_buyer_bonus_bps,
+        biggest_holder_bonus_bps,
+        frontend_fee_bps,
+        dividend_apr_window_secs,
+        min_remaining_secs,
+        agent_platform_fee_share_bps,
+    ) = match &overrides {
+        Some(params) => (
+            params.max_timer_secs,
+            params.base_price_lamports,
+            params.price_increment_lamports,
+            params.timer_extension_secs,
+            params.winner_bps,
+            params.dividend_bps,
+            params.next_round_bps,
+            params.protocol_fee_bps,
+            params.referral_bonus_bps,
+            params.protocol_wallet,
+            params.early_bird_key_threshold,
+            params.early_bird_multiplier_bps,
+            params.min_purchase_lamports,
+            params.winner_claim_window_secs,
+            params.final_hour_pot_threshold_lamports,
+            params.final_hour_shrink_interval_keys,
+            params.pot_milestone_interval_lamports,
+            params.pot_milestone_bonus_keys,
+            params.promo_keys_cap_per_round,
+            params.transfers_enabled,
+            params.wrapped_keys_enabled,
+            params.keeper_fee_lamports,
+            params.purchase_history_enabled,
+            params.time_weighted_dividends_enabled,
+            params.hook_program,
+            params.referral_earnings_cap_lamports_per_round,
+            params.referral_decay_threshold_lamports,
+            params.referrer_change_cooldown_secs,
+            params.kyc_required,
+            params.kyc_issuer,
+            params.unclaimed_dividend_policy,
+            params.dividend_claim_window_secs,
+            params.max_timer_extensions_per_window,
+            params.timer_extension_window_secs,
+            params.top_referrer_bonus_bps,
+            params.raffle_bps,
+            params.raffle_daily_payout_bps,
+            params.bridge_program,
+            params.max_pot_lamports,
+            params.auto_payout_winner_enabled,
+            params.min_keys_for_timer_extension,
+            params.price_sample_interval_slots,
+            params.rounding_beneficiary,
+            params.season_length_rounds,
+            params.season_fee_bps,
+            params.max_keys_per_round,
+            params.referral_vesting_enabled,
+            params.biggest_buyer_bonus_bps,
+            params.biggest_holder_bonus_bps,
+            params.frontend_fee_bps,
+            params.dividend_apr_window_secs,
+            params.min_remaining_secs,
+            params.agent_platform_fee_share_bps,
+        ),
+        None => (
+            config.max_timer_secs,
+            config.base_price_lamports,
+            config.price_increment_lamports,
+            config.timer_extension_secs,
+            config.winner_bps,
+            config.dividend_bps,
+            config.next_round_bps,
+            config.protocol_fee_bps,
+            config.referral_bonus_bps,
+            config.protocol_wallet,
+            config.early_bird_key_threshold,
+            config.early_bird_multiplier_bps,
+            config.min_purchase_lamports,
+            config.winner_claim_window_secs,
+            config.final_hour_pot_threshold_lamports,
+            config.final_hour_shrink_interval_keys,
+            config.pot_milestone_interval_lamports,
+            config.pot_milestone_bonus_keys,
+            config.promo_keys_cap_per_round,
+            config.transfers_enabled,
+            config.wrapped_keys_enabled,
+            config.keeper_fee_lamports,
+            config.purchase_history_enabled,
+            config.time_weighted_dividends_enabled,
+            config.hook_program,
+            config.referral_earnings_cap_lamports_per_round,
+            config.referral_decay_threshold_lamports,
+            config.referrer_change_cooldown_secs,
+            config.kyc_required,
+            config.kyc_issuer,
+            config.unclaimed_dividend_policy,
+            config.dividend_claim_window_secs,
+            config.max_timer_extensions_per_window,
+            config.timer_extension_window_secs,
+            config.top_referrer_bonus_bps,
+            config.raffle_bps,
+            config.raffle_daily_payout_bps,
+            config.bridge_program,
+            config.max_pot_lamports,
+            config.auto_payout_winner_enabled,
+            config.min_keys_for_timer_extension,
+            config.price_sample_interval_slots,
+            config.rounding_beneficiary,
+            config.season_length_rounds,
+            config.season_fee_bps,
+            config.max_keys_per_round,
+            config.referral_vesting_enabled,
+            config.biggest_buyer_bonus_bps,
+            config.biggest_holder_bonus_bps,
+            config.frontend_fee_bps,
+            config.dividend_apr_window_secs,
+            config.min_remaining_secs,
+            config.agent_platform_fee_share_bps,
+        ),
+    };
+
+    // Hash whichever config source actually supplied this round's snapshot —
+    // the one-off `overrides`, if the caller passed one, otherwise the
+    // persisted `GlobalConfig` — so `genesis_config_hash` reflects the
+    // parameters the round really ran under. See `initialize_first_round`.
+    let genesis_config_hash = match &overrides {
+        Some(params) => hashv(&[&params.try_to_vec().unwrap()]).to_bytes(),
+        None => hashv(&[&config.try_to_vec().unwrap()]).to_bytes(),
+    };
+
+    new_game.game_id = prev_game.game_id;
     new_game.round = new_round;
     new_game.pot_lamports = carry_over;
     new_game.timer_end = clock
         .unix_timestamp
-        .checked_add(config.max_timer_secs)
+        .checked_add(max_timer_secs)
         .ok_or(FomoltError::Overflow)?;
     new_game.last_buyer = Pubkey::default();
     new_game.total_keys = 0;
     new_game.round_start = clock.unix_timestamp;
-    new_game.active = true;
-    new_game.winner_claimed = false;
+    new_game.status = RoundStatus::Pending;
     new_game.total_players = 0;
     new_game.total_dividend_pool = 0;
     new_game.next_round_pot = 0;
+    new_game.total_referral_obligations = 0;
+    new_game.total_weight = 0;
+    new_game.purchase_count = 0;
+    new_game.gross_volume_lamports = 0;
+    new_game.max_single_buy_lamports = 0;
+    new_game.max_single_buyer = Pubkey::default();
+    new_game.largest_holder_keys = 0;
+    new_game.largest_holder = Pubkey::default();
+    new_game.dividend_weight_seconds_total = 0;
+    new_game.dividend_seconds_last_update = new_game.round_start;
     // Seed winner_pot with carry-over so it's in a claimable bucket.
     // The first buyer wins these funds if the round has activity;
     // if the round is empty, the full vault forwards to the next round.
     new_game.winner_pot = carry_over;
 
-    // Snapshot config parameters
-    new_game.base_price_lamports = config.base_price_lamports;
-    new_game.price_increment_lamports = config.price_increment_lamports;
-    new_game.timer_extension_secs = config.timer_extension_secs;
-    new_game.max_timer_secs = config.max_timer_secs;
-    new_game.winner_bps = config.winner_bps;
-    new_game.dividend_bps = config.dividend_bps;
-    new_game.next_round_bps = config.next_round_bps;
-    new_game.protocol_fee_bps = config.protocol_fee_bps;
-    new_game.referral_bonus_bps = config.referral_bonus_bps;
-    new_game.protocol_wallet = config.protocol_wallet;
+    // Snapshot config parameters (or the round's override, if provided)
+    new_game.base_price_lamports = base_price_lamports;
+    new_game.price_increment_lamports = price_increment_lamports;
+    new_game.timer_extension_secs = timer_extension_secs;
+    new_game.max_timer_secs = max_timer_secs;
+    new_game.winner_bps = winner_bps;
+    new_game.dividend_bps = dividend_bps;
+    new_game.next_round_bps = next_round_bps;
+    new_game.protocol_fee_bps = protocol_fee_bps;
+    new_game.referral_bonus_bps = referral_bonus_bps;
+    new_game.protocol_wallet = protocol_wallet;
+    new_game.early_bird_key_threshold = early_bird_key_threshold;
+    new_game.early_bird_multiplier_bps = early_bird_multiplier_bps;
+    new_game.min_purchase_lamports = min_purchase_lamports;
+    new_game.winner_claim_window_secs = winner_claim_window_secs;
+    new_game.final_hour_pot_threshold_lamports = final_hour_pot_threshold_lamports;
+    new_game.final_hour_shrink_interval_keys = final_hour_shrink_interval_keys;
+    new_game.final_hour_active = false;
+    new_game.final_hour_start_keys = 0;
+    new_game.pot_milestone_interval_lamports = pot_milestone_interval_lamports;
+    new_game.pot_milestone_bonus_keys = pot_milestone_bonus_keys;
+    new_game.vault_lamports_in = new_vault_lamports_in;
+    new_game.vault_lamports_out = 0;
+    new_game.promo_keys_cap_per_round = promo_keys_cap_per_round;
+    new_game.promo_keys_granted_this_round = 0;
+    new_game.transfers_enabled = transfers_enabled;
+    new_game.wrapped_keys_enabled = wrapped_keys_enabled;
+    new_game.wrapped_keys_total = 0;
+    new_game.wrapped_weight_total = 0;
+    new_game.keeper_fee_lamports = keeper_fee_lamports;
+    new_game.purchase_history_enabled = purchase_history_enabled;
+    new_game.time_weighted_dividends_enabled = time_weighted_dividends_enabled;
+    new_game.hook_program = hook_program;
+    new_game.referral_earnings_cap_lamports_per_round = referral_earnings_cap_lamports_per_round;
+    new_game.referral_decay_threshold_lamports = referral_decay_threshold_lamports;
+    new_game.referrer_change_cooldown_secs = referrer_change_cooldown_secs;
+    new_game.dividend_merkle_root = None;
+    new_game.kyc_required = kyc_required;
+    new_game.kyc_issuer = kyc_issuer;
+    new_game.dust_reserve = 0;
+    new_game.price_cumulative = 0;
+    new_game.price_last_update = new_game.round_start;
+    new_game.unclaimed_dividend_policy = unclaimed_dividend_policy;
+    new_game.dividend_claim_window_secs = dividend_claim_window_secs;
+    new_game.max_timer_extensions_per_window = max_timer_extensions_per_window;
+    new_game.timer_extension_window_secs = timer_extension_window_secs;
+    new_game.total_dividend_claimed_lamports = 0;
+    new_game.top_referrer_bonus_bps = top_referrer_bonus_bps;
+    new_game.top_referrer_bonus_pool = 0;
+    new_game.raffle_bps = raffle_bps;
+    new_game.raffle_daily_payout_bps = raffle_daily_payout_bps;
+    new_game.raffle_pool_lamports = 0;
+    new_game.raffle_prize_pool_pending = 0;
+    new_game.refund_pool_lamports = 0;
+    new_game.bridge_program = bridge_program;
+    new_game.max_pot_lamports = max_pot_lamports;
+    new_game.pot_overflow_reserve_lamports = 0;
+    new_game.timer_extensions_triggered = 0;
+    new_game.last_buy_timestamp = new_game.round_start;
+    new_game.buy_interval_seconds_total = 0;
+    new_game.pot_checkpoint_25_lamports = 0;
+    new_game.pot_checkpoint_50_lamports = 0;
+    new_game.pot_checkpoint_75_lamports = 0;
+    new_game.pot_checkpoint_25_reached = false;
+    new_game.pot_checkpoint_50_reached = false;
+    new_game.pot_checkpoint_75_reached = false;
+    new_game.auto_payout_winner_enabled = auto_payout_winner_enabled;
+    new_game.min_keys_for_timer_extension = min_keys_for_timer_extension;
+    new_game.price_sample_interval_slots = price_sample_interval_slots;
+    new_game.rounding_beneficiary = rounding_beneficiary;
+    new_game.season_length_rounds = season_length_rounds;
+    new_game.season_fee_bps = season_fee_bps;
+    new_game.agent_keys_total = 0;
+    new_game.human_keys_total = 0;
+    new_game.max_keys_per_round = max_keys_per_round;
+    new_game.referral_vesting_enabled = referral_vesting_enabled;
+    new_game.biggest_buyer_bonus_bps = biggest_buyer_bonus_bps;
+    new_game.biggest_buyer_bonus_pool = 0;
+    new_game.biggest_holder_bonus_bps = biggest_holder_bonus_bps;
+    new_game.biggest_holder_bonus_pool = 0;
+    new_game.frontend_fee_bps = frontend_fee_bps;
+    new_game.dividend_apr_window_secs = dividend_apr_window_secs;
+    new_game.dividend_apr_window_start = new_game.round_start;
+    new_game.dividend_apr_window_dividend_lamports = 0;
+    new_game.min_remaining_secs = min_remaining_secs;
+    new_game.agent_platform_fee_share_bps = agent_platform_fee_share_bps;
+    new_game.total_agent_platform_obligations = 0;
+    new_game.genesis_config_hash = genesis_config_hash;
 
     new_game.bump = ctx.bumps.new_game_state;
 
+    config.latest_round = new_round;
+
+    let snapshot = &mut ctx.accounts.new_game_snapshot;
+    snapshot.game_id = new_game.game_id;
+    snapshot.round = new_game.round;
+    snapshot.pot_lamports = new_game.pot_lamports;
+    snapshot.total_keys = new_game.total_keys;
+    snapshot.timer_end = new_game.timer_end;
+    snapshot.last_buyer = new_game.last_buyer;
+    snapshot.next_key_price = math::calculate_cost(
+        new_game.total_keys,
+        1,
+        new_game.base_price_lamports,
+        new_game.price_increment_lamports,
+    )
+    .unwrap_or(u64::MAX);
+    snapshot.bump = ctx.bumps.new_game_snapshot;
+
+    new_game.transition_status(RoundStatus::Active)?;
+    emit!(RoundStatusChanged {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: new_game.game_id,
+        round: new_game.round,
+        from: RoundStatus::Pending,
+        to: RoundStatus::Active,
+        timestamp: clock.unix_timestamp,
+    });
+
     emit!(RoundStarted {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: new_game.game_id,
         round: new_round,
         carry_over_lamports: carry_over,
         timer_end: new_game.timer_end,
@@ -202,5 +608,16 @@ pub fn handle_start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
         timestamp: clock.unix_timestamp,
     });
 
+    if carry_over > 0 {
+        emit!(NextRoundSeeded {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: new_game.game_id,
+            source_round: new_round - 1,
+            round: new_round,
+            lamports: carry_over,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{DustReserveSwept, VaultFlow};
+use crate::state::*;
+
+/// Admin-only: withdraws a round's accumulated `GameState::dust_reserve` —
+/// the truncation remainder `buy_keys`'s three-way pot split can't avoid —
+/// to the protocol wallet. Callable on any round regardless of its
+/// `RoundStatus`, since dust can accrue while a round is still active and
+/// there's no reason to make the admin wait for it to end.
+#[derive(Accounts)]
+pub struct SweepDustReserve<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Protocol fee recipient wallet
+    /// CHECK: Validated against game_state.protocol_wallet
+    #[account(
+        mut,
+        constraint = protocol_wallet.key() == game_state.protocol_wallet @ FomoltError::InvalidConfig,
+    )]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_sweep_dust_reserve(ctx: Context<SweepDustReserve>) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+
+    let amount = game.dust_reserve;
+    require!(amount > 0, FomoltError::NothingToSweep);
+
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+    require!(available >= amount, FomoltError::VaultInsolvent);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.protocol_wallet.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    game.dust_reserve = 0;
+    game.vault_lamports_out = game
+        .vault_lamports_out
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+
+    let clock = Clock::get()?;
+    emit!(VaultFlow {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        direction: VaultFlowDirection::Out,
+        reason: VaultFlowReason::Sweep,
+        lamports: amount,
+        counterparty: ctx.accounts.protocol_wallet.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(DustReserveSwept {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        admin: ctx.accounts.admin.key(),
+        lamports: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::{UnclaimedDividendsSwept, VaultFlow};
+use crate::state::*;
+
+/// Permissionless — anyone can crank this once
+/// `GameState::dividend_claim_window_secs` has elapsed past `timer_end` and
+/// some of `total_dividend_pool` is still unclaimed. What happens to that
+/// remainder is entirely determined by `game_state.unclaimed_dividend_policy`,
+/// snapshotted at round creation — this instruction just carries it out.
+#[derive(Accounts)]
+pub struct SweepUnclaimedDividends<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The ended round whose unclaimed dividend remainder is being swept
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// `game_state`'s vault — source of the swept lamports
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Protocol fee recipient — only consulted for `UnclaimedDividendPolicy::ToProtocol`.
+    /// CHECK: Validated against game_state.protocol_wallet
+    #[account(mut)]
+    pub protocol_wallet: UncheckedAccount<'info>,
+
+    /// Required only for `UnclaimedDividendPolicy::RollToNextRound` — the
+    /// currently active round receiving the rollover. Left out of `seeds`
+    /// (unlike `game_state` above) because it can be any round number, not
+    /// one derivable from already-known data; validated manually in the
+    /// handler instead, the same way `forfeit_winner_pot` validates its own
+    /// old/current `GameState` pair.
+    /// CHECK: Validated manually in handler (game_id match, active check)
+    #[account(mut)]
+    pub next_game_state: Option<Account<'info, GameState>>,
+
+    /// Vault for `next_game_state`, validated manually against it in the handler.
+    #[account(mut)]
+    pub next_vault: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_sweep_unclaimed_dividends(ctx: Context<SweepUnclaimedDividends>) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let vault_bump = ctx.bumps.vault;
+    let game = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(game.status != RoundStatus::Active, FomoltError::GameStillActive);
+
+    let unclaimed = game
+        .total_dividend_pool
+        .saturating_sub(game.total_dividend_claimed_lamports);
+    require!(unclaimed > 0, FomoltError::NothingToSweep);
+
+    let sweep_at = game
+        .timer_end
+        .checked_add(game.dividend_claim_window_secs)
+        .ok_or(FomoltError::Overflow)?;
+    require!(
+        clock.unix_timestamp >= sweep_at,
+        FomoltError::ClaimWindowNotExpired
+    );
+
+    let policy = game.unclaimed_dividend_policy;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", game_key.as_ref(), &[vault_bump]]];
+
+    let (lamports, destination_round) = match policy {
+        UnclaimedDividendPolicy::Strand => (0u64, None),
+        UnclaimedDividendPolicy::RollToNextRound => {
+            let next_vault = ctx
+                .accounts
+                .next_vault
+                .as_ref()
+                .ok_or(FomoltError::MissingRolloverTarget)?
+                .to_account_info();
+            let next_vault_key = next_vault.key();
+            let next_game = ctx
+                .accounts
+                .next_game_state
+                .as_mut()
+                .ok_or(FomoltError::MissingRolloverTarget)?;
+
+            require!(next_game.game_id == game.game_id, FomoltError::GameIdMismatch);
+            require!(next_game.round != game.round, FomoltError::NotCurrentRound);
+            require!(next_game.status == RoundStatus::Active, FomoltError::GameNotActive);
+
+            let (expected_vault, _) =
+                Pubkey::find_program_address(&[b"vault", next_game.key().as_ref()], ctx.program_id);
+            require!(next_vault.key() == expected_vault, FomoltError::VaultMismatch);
+
+            let rent_exempt_min = Rent::get()?.minimum_balance(0);
+            let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+            require!(available >= unclaimed, FomoltError::VaultInsolvent);
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: next_vault,
+                    },
+                    signer_seeds,
+                ),
+                unclaimed,
+            )?;
+            emit!(VaultFlow {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                direction: VaultFlowDirection::Out,
+                reason: VaultFlowReason::Sweep,
+                lamports: unclaimed,
+                counterparty: next_vault_key,
+                timestamp: clock.unix_timestamp,
+            });
+
+            next_game.next_round_pot = next_game
+                .next_round_pot
+                .checked_add(unclaimed)
+                .ok_or(FomoltError::Overflow)?;
+            next_game.vault_lamports_in = next_game
+                .vault_lamports_in
+                .checked_add(unclaimed)
+                .ok_or(FomoltError::Overflow)?;
+            emit!(VaultFlow {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: next_game.game_id,
+                round: next_game.round,
+                direction: VaultFlowDirection::In,
+                reason: VaultFlowReason::Sweep,
+                lamports: unclaimed,
+                counterparty: game_key,
+                timestamp: clock.unix_timestamp,
+            });
+
+            (unclaimed, Some(next_game.round))
+        }
+        UnclaimedDividendPolicy::ToProtocol => {
+            require!(
+                ctx.accounts.protocol_wallet.key() == game.protocol_wallet,
+                FomoltError::InvalidConfig
+            );
+
+            let rent_exempt_min = Rent::get()?.minimum_balance(0);
+            let available = ctx.accounts.vault.lamports().saturating_sub(rent_exempt_min);
+            require!(available >= unclaimed, FomoltError::VaultInsolvent);
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.protocol_wallet.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                unclaimed,
+            )?;
+            emit!(VaultFlow {
+                version: crate::events::EVENT_SCHEMA_VERSION,
+                game_id: game.game_id,
+                round: game.round,
+                direction: VaultFlowDirection::Out,
+                reason: VaultFlowReason::Sweep,
+                lamports: unclaimed,
+                counterparty: ctx.accounts.protocol_wallet.key(),
+                timestamp: clock.unix_timestamp,
+            });
+
+            (unclaimed, None)
+        }
+    };
+
+    if lamports > 0 {
+        game.vault_lamports_out = game
+            .vault_lamports_out
+            .checked_add(lamports)
+            .ok_or(FomoltError::Overflow)?;
+    }
+    // Marks the round's dividend pool as fully accounted for, regardless of
+    // policy — `Strand` leaves the lamports sitting in the vault (still
+    // technically claimable by a late holdout), it just stops this crank
+    // from re-reporting the same remainder every time it's called.
+    game.total_dividend_claimed_lamports = game.total_dividend_pool;
+
+    emit!(UnclaimedDividendsSwept {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        policy,
+        lamports,
+        destination_round,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::events::{KeysTransferred, PlayerRegistered};
+use crate::math;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(to: Pubkey, amount: u64)]
+pub struct TransferKeys<'info> {
+    #[account(mut)]
+    pub from: Signer<'info>,
+
+    #[account(
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), from.key().as_ref()],
+        bump = from_player_state.bump,
+        constraint = from_player_state.player == from.key() @ FomoltError::Unauthorized,
+    )]
+    pub from_player_state: Account<'info, PlayerState>,
+
+    /// The recipient's PlayerState — not a signer, since `from` is the one
+    /// authorizing this transfer. `init_if_needed` covers a recipient who
+    /// hasn't bought into this round (or at all) yet, same as a fresh buyer
+    /// in `buy_keys`.
+    #[account(
+        init_if_needed,
+        payer = from,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), to.as_ref()],
+        bump,
+    )]
+    pub to_player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile for the recipient
+    #[account(
+        init_if_needed,
+        payer = from,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), to.as_ref()],
+        bump,
+    )]
+    pub to_player_stats: Account<'info, PlayerStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` keys, and their proportional dividend weight, from
+/// `from`'s `PlayerState` to `to`'s within the currently active round.
+/// Enables secondary OTC markets and lets an agent consolidate positions
+/// spread across multiple wallets into one.
+///
+/// The transferred weight is `amount * from.dividend_weight / from.keys`,
+/// rounded down via `math::calculate_dividend_share` (the same x*y/z shape
+/// used to size a player's dividend payout) — any fractional weight lost to
+/// rounding stays with the sender, so `GameState::total_weight` is exactly
+/// conserved across the transfer.
+///
+/// Gated by `GameState::transfers_enabled` (see `GlobalConfig::transfers_enabled`)
+/// and, like any other in-round action, requires the round still be active.
+pub fn handle_transfer_keys(ctx: Context<TransferKeys>, to: Pubkey, amount: u64) -> Result<()> {
+    require!(amount > 0, FomoltError::NoKeysToTransfer);
+    require!(
+        to != ctx.accounts.from.key(),
+        FomoltError::CannotTransferToSelf
+    );
+
+    let game = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+    require!(
+        game.status == RoundStatus::Active && clock.unix_timestamp < game.timer_end,
+        FomoltError::GameNotActive
+    );
+    require!(game.transfers_enabled, FomoltError::TransfersDisabled);
+
+    let from_player = &mut ctx.accounts.from_player_state;
+    require!(
+        from_player.current_round == game.round,
+        FomoltError::MustClaimPreviousRound
+    );
+    require!(from_player.keys >= amount, FomoltError::InsufficientKeys);
+
+    let weight_transferred =
+        math::calculate_dividend_share(amount, from_player.dividend_weight, from_player.keys)?;
+
+    from_player.keys = from_player
+        .keys
+        .checked_sub(amount)
+        .ok_or(FomoltError::Overflow)?;
+    from_player.dividend_weight = from_player
+        .dividend_weight
+        .checked_sub(weight_transferred)
+        .ok_or(FomoltError::Overflow)?;
+
+    let to_player = &mut ctx.accounts.to_player_state;
+
+    // --- Handle recipient registration / round entry (mirrors buy_keys,
+    // minus referrer handling — receiving a transfer doesn't set one) ---
+    let is_new_player = to_player.player == Pubkey::default();
+
+    if is_new_player {
+        to_player.game_id = game.game_id;
+        to_player.player = to;
+        to_player.bump = ctx.bumps.to_player_state;
+        to_player.initialized = true;
+        to_player.generation = to_player.generation.wrapping_add(1);
+        to_player.claimed_dividends_lamports = 0;
+        to_player.claimed_referral_earnings_lamports = 0;
+        to_player.referral_earnings_lamports = 0;
+        to_player.pending_referral_earnings_lamports = 0;
+        to_player.referrer = None;
+        to_player.keys = 0;
+        to_player.dividend_weight = 0;
+        to_player.current_round = game.round;
+        to_player.is_agent = false;
+        to_player.auto_compound = false;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: to,
+            is_agent: false,
+            referrer: None,
+            timestamp: clock.unix_timestamp,
+        });
+    } else if to_player.current_round == 0 {
+        // Returning player (claimed from previous round)
+        to_player.keys = 0;
+        to_player.dividend_weight = 0;
+        to_player.current_round = game.round;
+        // Existing referrer preserved
+    } else if to_player.current_round != game.round {
+        // In a different round — must claim first
+        return err!(FomoltError::MustClaimPreviousRound);
+    }
+    // else: already in this round — continue receiving
+
+    // --- Lazily initialize lifetime stats profile (round-agnostic, never reset) ---
+    let stats = &mut ctx.accounts.to_player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = to;
+        stats.bump = ctx.bumps.to_player_stats;
+    }
+
+    to_player.keys = to_player
+        .keys
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    to_player.dividend_weight = to_player
+        .dividend_weight
+        .checked_add(weight_transferred)
+        .ok_or(FomoltError::Overflow)?;
+
+    emit!(KeysTransferred {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        from: ctx.accounts.from.key(),
+        to,
+        keys_transferred: amount,
+        weight_transferred,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::KeeperUnregistered;
+use crate::state::*;
+
+/// The keeper's own exit path: closes `KeeperState` and returns whatever
+/// bond remains (after any `slash_keeper` deductions) to the keeper's
+/// wallet. Unlike `slash_keeper`, this is self-serve — no admin involved.
+#[derive(Accounts)]
+pub struct UnregisterKeeper<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        close = keeper,
+        seeds = [b"keeper", keeper_state.game_id.to_le_bytes().as_ref(), keeper.key().as_ref()],
+        bump = keeper_state.bump,
+        has_one = keeper,
+        constraint = keeper_state.active @ FomoltError::KeeperNotActive,
+    )]
+    pub keeper_state: Account<'info, KeeperState>,
+
+    /// This keeper's bond vault
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"keeper_bond", keeper_state.game_id.to_le_bytes().as_ref(), keeper.key().as_ref()],
+        bump,
+    )]
+    pub keeper_bond: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_unregister_keeper(ctx: Context<UnregisterKeeper>) -> Result<()> {
+    let returned_bond_lamports = ctx.accounts.keeper_bond.lamports();
+
+    if returned_bond_lamports > 0 {
+        let game_id_bytes = ctx.accounts.keeper_state.game_id.to_le_bytes();
+        let keeper_key = ctx.accounts.keeper.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"keeper_bond",
+            game_id_bytes.as_ref(),
+            keeper_key.as_ref(),
+            &[ctx.bumps.keeper_bond],
+        ]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.keeper_bond.to_account_info(),
+                    to: ctx.accounts.keeper.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            returned_bond_lamports,
+        )?;
+    }
+
+    emit!(KeeperUnregistered {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.keeper_state.game_id,
+        keeper: ctx.accounts.keeper.key(),
+        returned_bond_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+use crate::errors::FomoltError;
+use crate::events::VaultYieldUnwound;
+use crate::state::*;
+
+/// Cap on `ctx.remaining_accounts` forwarded to the yield program CPI — same
+/// rationale as `buy_keys::MAX_HOOK_ACCOUNTS`.
+const MAX_YIELD_ACCOUNTS: usize = 4;
+
+/// Anchor instruction sighash for `withdraw_yield` (first 8 bytes of
+/// sha256("global:withdraw_yield")) — the inverse of
+/// `deploy_vault_yield::YIELD_DEPOSIT_DISCRIMINATOR`. The yield program is
+/// expected to transfer `lamports` back into `vault` itself as part of
+/// executing this CPI.
+const YIELD_WITHDRAW_DISCRIMINATOR: [u8; 8] = [62, 9, 132, 32, 96, 57, 101, 82];
+
+#[derive(Accounts)]
+pub struct UnwindVaultYield<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", config.game_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ FomoltError::Unauthorized,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        constraint = game_state.game_id == config.game_id @ FomoltError::GameIdMismatch,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Must already exist — only `deploy_vault_yield` can create it, and
+    /// there's nothing to unwind before that's ever run this round.
+    #[account(
+        mut,
+        seeds = [b"game_ext", game_state.key().as_ref()],
+        bump = game_state_ext.bump,
+    )]
+    pub game_state_ext: Account<'info, GameStateExt>,
+
+    /// Game vault PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"vault", game_state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Whitelisted yield destination — see `GlobalConfig::yield_program`.
+    /// CHECK: Validated against config.yield_program via constraint below
+    #[account(
+        constraint = yield_program.key() == config.yield_program @ FomoltError::YieldProgramNotApproved,
+    )]
+    pub yield_program: UncheckedAccount<'info>,
+
+    /// The same yield program account `deploy_vault_yield` deposited into.
+    /// CHECK: Forwarded to `yield_program`'s own CPI, never deserialized
+    #[account(mut)]
+    pub yield_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Reclaims previously-deployed principal (see `deploy_vault_yield`) by
+/// CPI'ing into `GlobalConfig::yield_program`'s own withdraw instruction,
+/// which is expected to transfer lamports back into `vault` directly. Unlike
+/// deployment, this is never gated by `max_yield_deployment_bps` or round
+/// status — an admin can always unwind, even with the kill-switch at 0 or
+/// the round already `Ended`, so deployed capital never gets stranded.
+pub fn handle_unwind_vault_yield<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnwindVaultYield<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    require!(lamports > 0, FomoltError::InvalidFundAmount);
+
+    let ext = &mut ctx.accounts.game_state_ext;
+    require!(
+        lamports <= ext.yield_deployed_lamports,
+        FomoltError::YieldUnwindExceedsDeployed
+    );
+
+    require!(
+        ctx.remaining_accounts.len() <= MAX_YIELD_ACCOUNTS,
+        FomoltError::TooManyYieldAccounts
+    );
+
+    let mut account_metas = Vec::with_capacity(ctx.remaining_accounts.len() + 2);
+    let mut account_infos = Vec::with_capacity(ctx.remaining_accounts.len() + 3);
+    account_infos.push(ctx.accounts.yield_program.to_account_info());
+    account_metas.push(AccountMeta::new(ctx.accounts.yield_vault.key(), false));
+    account_infos.push(ctx.accounts.yield_vault.to_account_info());
+    account_metas.push(AccountMeta::new(ctx.accounts.vault.key(), false));
+    account_infos.push(ctx.accounts.vault.to_account_info());
+    for account in ctx.remaining_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+
+    let mut data = YIELD_WITHDRAW_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&ctx.accounts.game_state.round.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    let vault_balance_before = ctx.accounts.vault.lamports();
+
+    invoke(
+        &Instruction {
+            program_id: ctx.accounts.yield_program.key(),
+            accounts: account_metas,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    let vault_balance_after = ctx.accounts.vault.lamports();
+    let received = vault_balance_after
+        .checked_sub(vault_balance_before)
+        .ok_or(FomoltError::Overflow)?;
+    require!(received >= lamports, FomoltError::YieldUnwindShortfall);
+
+    let game = &mut ctx.accounts.game_state;
+    game.vault_lamports_in = game
+        .vault_lamports_in
+        .checked_add(received)
+        .ok_or(FomoltError::Overflow)?;
+
+    let ext = &mut ctx.accounts.game_state_ext;
+    ext.yield_deployed_lamports = ext
+        .yield_deployed_lamports
+        .checked_sub(lamports)
+        .ok_or(FomoltError::Overflow)?;
+
+    let clock = Clock::get()?;
+    emit!(VaultYieldUnwound {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        admin: ctx.accounts.admin.key(),
+        yield_program: ctx.accounts.yield_program.key(),
+        lamports_received: received,
+        remaining_deployed_lamports: ext.yield_deployed_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
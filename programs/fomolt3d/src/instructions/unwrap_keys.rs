@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::errors::FomoltError;
+use crate::events::{KeysUnwrapped, PlayerRegistered};
+use crate::math;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UnwrapKeys<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// `init_if_needed` covers a holder who bought a wrapped token secondhand
+    /// and has never held a `PlayerState` in this round before, same as a
+    /// fresh buyer in `buy_keys` or a recipient in `transfer_keys`.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerState::SPACE,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Lifetime, round-agnostic player profile
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerStats::SPACE,
+        seeds = [b"stats", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"key_mint", game_state.key().as_ref()],
+        bump,
+    )]
+    pub key_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = key_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Burns `amount` of this round's wrapped-key SPL token and restores the
+/// equivalent keys, plus a pro-rata share of `GameState::wrapped_weight_total`,
+/// into the caller's `PlayerState` — registering them into the round first
+/// if they don't already hold a position in it, same as `transfer_keys`'s
+/// recipient path. This is the only way back into a dividend-bearing
+/// position once keys have been wrapped, and the burn-checked gate the
+/// request asked for: a wrapped-token holder cannot `claim` directly, they
+/// must unwrap first.
+///
+/// Deliberately NOT gated on `GameState::wrapped_keys_enabled` — disabling
+/// wraps should only stop new ones, never strand existing wrapped supply.
+///
+/// The pro-rata weight is `amount * wrapped_weight_total / wrapped_keys_total`,
+/// rounded down via `math::calculate_dividend_share`. When this unwrap fully
+/// drains the pool, any floor-rounding dust left in `wrapped_weight_total`
+/// is swept into this unwrap rather than stranded.
+pub fn handle_unwrap_keys(ctx: Context<UnwrapKeys>, amount: u64) -> Result<()> {
+    require!(amount > 0, FomoltError::NoKeysToUnwrap);
+
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.game_state.status == RoundStatus::Active
+            && clock.unix_timestamp < ctx.accounts.game_state.timer_end,
+        FomoltError::GameNotActive
+    );
+    require!(
+        amount <= ctx.accounts.game_state.wrapped_keys_total,
+        FomoltError::InsufficientWrappedSupply
+    );
+
+    let game = &mut ctx.accounts.game_state;
+    let mut weight_unwrapped =
+        math::calculate_dividend_share(amount, game.wrapped_weight_total, game.wrapped_keys_total)?;
+
+    let remaining_wrapped_keys = game
+        .wrapped_keys_total
+        .checked_sub(amount)
+        .ok_or(FomoltError::Overflow)?;
+    if remaining_wrapped_keys == 0 {
+        // Sweep any floor-rounding dust so the pool zeroes out exactly.
+        weight_unwrapped = game.wrapped_weight_total;
+    }
+    game.wrapped_keys_total = remaining_wrapped_keys;
+    game.wrapped_weight_total = game
+        .wrapped_weight_total
+        .checked_sub(weight_unwrapped)
+        .ok_or(FomoltError::Overflow)?;
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.key_mint.to_account_info(),
+                from: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let player = &mut ctx.accounts.player_state;
+    let is_new_player = player.player == Pubkey::default();
+
+    if is_new_player {
+        player.game_id = game.game_id;
+        player.player = ctx.accounts.player.key();
+        player.bump = ctx.bumps.player_state;
+        player.initialized = true;
+        player.generation = player.generation.wrapping_add(1);
+        player.claimed_dividends_lamports = 0;
+        player.claimed_referral_earnings_lamports = 0;
+        player.referral_earnings_lamports = 0;
+        player.pending_referral_earnings_lamports = 0;
+        player.referrer = None;
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.current_round = game.round;
+        player.is_agent = false;
+        player.auto_compound = false;
+
+        emit!(PlayerRegistered {
+            version: crate::events::EVENT_SCHEMA_VERSION,
+            game_id: game.game_id,
+            round: game.round,
+            player: ctx.accounts.player.key(),
+            is_agent: false,
+            referrer: None,
+            timestamp: clock.unix_timestamp,
+        });
+    } else if player.current_round == 0 {
+        // Returning player (claimed from previous round)
+        player.keys = 0;
+        player.dividend_weight = 0;
+        player.current_round = game.round;
+        // Existing referrer preserved
+    } else if player.current_round != game.round {
+        // In a different round — must claim first
+        return err!(FomoltError::MustClaimPreviousRound);
+    }
+    // else: already in this round — continue receiving
+
+    let stats = &mut ctx.accounts.player_stats;
+    if stats.player == Pubkey::default() {
+        stats.game_id = game.game_id;
+        stats.player = ctx.accounts.player.key();
+        stats.bump = ctx.bumps.player_stats;
+    }
+
+    player.keys = player.keys.checked_add(amount).ok_or(FomoltError::Overflow)?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_add(weight_unwrapped)
+        .ok_or(FomoltError::Overflow)?;
+
+    emit!(KeysUnwrapped {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: game.game_id,
+        round: game.round,
+        player: ctx.accounts.player.key(),
+        keys_unwrapped: amount,
+        weight_unwrapped,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
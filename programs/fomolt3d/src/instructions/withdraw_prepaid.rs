@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::FomoltError;
+use crate::events::PrepaidWithdrawn;
+use crate::state::*;
+
+/// Pulls lamports back out of a player's `prepaid` vault — the counterpart to
+/// `instructions::deposit_prepaid`. Without this, a player who deposits and
+/// then disables their `execute_scheduled_buy` crank (see
+/// `instructions::set_scheduled_buy`) would have no way to reclaim the
+/// remaining balance, and `ClosePlayerState`'s zero-balance guard would
+/// permanently strand it.
+#[derive(Accounts)]
+pub struct WithdrawPrepaid<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = player,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// This player's prepaid balance vault.
+    /// CHECK: This is a PDA used only as a SOL vault, validated by seeds
+    #[account(
+        mut,
+        seeds = [b"prepaid", player_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub prepaid_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_withdraw_prepaid(ctx: Context<WithdrawPrepaid>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, FomoltError::InvalidFundAmount);
+
+    let player_state = &mut ctx.accounts.player_state;
+    require!(
+        lamports <= player_state.prepaid_balance_lamports,
+        FomoltError::PrepaidWithdrawalExceedsBalance
+    );
+
+    let player_key = player_state.player;
+    let game_id_bytes = player_state.game_id.to_le_bytes();
+    let prepaid_bump = ctx.bumps.prepaid_vault;
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[b"prepaid", game_id_bytes.as_ref(), player_key.as_ref(), &[prepaid_bump]]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.prepaid_vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        lamports,
+    )?;
+
+    player_state.prepaid_balance_lamports = ctx.accounts.prepaid_vault.lamports();
+
+    emit!(PrepaidWithdrawn {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: player_state.game_id,
+        player: player_key,
+        lamports,
+        new_balance_lamports: player_state.prepaid_balance_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
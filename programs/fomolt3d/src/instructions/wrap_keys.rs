@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::errors::FomoltError;
+use crate::events::KeysWrapped;
+use crate::math;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct WrapKeys<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game_state.game_id.to_le_bytes().as_ref(), game_state.round.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player", game_state.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.player == player.key() @ FomoltError::Unauthorized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// CHECK: PDA used only as a CPI signer, validated by seeds
+    #[account(
+        seeds = [b"mint_authority", game_state.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"key_mint", game_state.key().as_ref()],
+        bump,
+    )]
+    pub key_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = key_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` keys, and their proportional dividend weight, out of the
+/// caller's `PlayerState` and into this round's wrapped-key pool
+/// (`GameState::wrapped_keys_total`/`wrapped_weight_total`), minting
+/// `amount` SPL tokens 1:1 in exchange. The minted tokens are freely
+/// transferable and composable with DEXes/lending — see `unwrap_keys` for
+/// how they're redeemed back into a dividend-bearing position.
+///
+/// The weight carved out is `amount * dividend_weight / keys`, rounded down
+/// via `math::calculate_dividend_share` (same shape as `transfer_keys`).
+/// `GameState::total_weight` is untouched either way — wrapping only moves
+/// weight between a `PlayerState` and the wrapped pool, never in or out of
+/// the total.
+pub fn handle_wrap_keys(ctx: Context<WrapKeys>, amount: u64) -> Result<()> {
+    require!(amount > 0, FomoltError::NoKeysToWrap);
+    require!(
+        ctx.accounts.game_state.wrapped_keys_enabled,
+        FomoltError::WrappedKeysDisabled
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.game_state.status == RoundStatus::Active
+            && clock.unix_timestamp < ctx.accounts.game_state.timer_end,
+        FomoltError::GameNotActive
+    );
+
+    let player = &mut ctx.accounts.player_state;
+    require!(
+        player.current_round == ctx.accounts.game_state.round,
+        FomoltError::MustClaimPreviousRound
+    );
+    require!(player.keys >= amount, FomoltError::InsufficientKeysToWrap);
+
+    let weight_wrapped =
+        math::calculate_dividend_share(amount, player.dividend_weight, player.keys)?;
+
+    player.keys = player.keys.checked_sub(amount).ok_or(FomoltError::Overflow)?;
+    player.dividend_weight = player
+        .dividend_weight
+        .checked_sub(weight_wrapped)
+        .ok_or(FomoltError::Overflow)?;
+
+    let game = &mut ctx.accounts.game_state;
+    game.wrapped_keys_total = game
+        .wrapped_keys_total
+        .checked_add(amount)
+        .ok_or(FomoltError::Overflow)?;
+    game.wrapped_weight_total = game
+        .wrapped_weight_total
+        .checked_add(weight_wrapped)
+        .ok_or(FomoltError::Overflow)?;
+
+    let game_key = game.key();
+    let mint_authority_bump = ctx.bumps.mint_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"mint_authority",
+        game_key.as_ref(),
+        &[mint_authority_bump],
+    ]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.key_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    emit!(KeysWrapped {
+        version: crate::events::EVENT_SCHEMA_VERSION,
+        game_id: ctx.accounts.game_state.game_id,
+        round: ctx.accounts.game_state.round,
+        player: ctx.accounts.player.key(),
+        keys_wrapped: amount,
+        weight_wrapped,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -4,12 +4,14 @@ pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod logic;
 pub mod math;
 pub mod state;
 #[cfg(test)]
 mod test_scenarios;
 
 use instructions::*;
+use state::SponsorAllocation;
 
 declare_id!("EebbWtjHyocWPwZaQ4k2L61mSdW6y175knsEwppTpdWw");
 
@@ -19,28 +21,566 @@ pub mod fomolt3d {
 
     pub fn create_or_update_config(
         ctx: Context<CreateOrUpdateConfig>,
+        game_id: u64,
         params: ConfigParams,
     ) -> Result<()> {
-        instructions::create_or_update_config::handle_create_or_update_config(ctx, params)
+        instructions::create_or_update_config::handle_create_or_update_config(ctx, game_id, params)
     }
 
     pub fn initialize_first_round(ctx: Context<InitializeFirstRound>) -> Result<()> {
         instructions::initialize_first_round::handle_initialize_first_round(ctx)
     }
 
-    pub fn start_new_round(ctx: Context<StartNewRound>) -> Result<()> {
-        instructions::start_new_round::handle_start_new_round(ctx)
+    /// `overrides`: an optional one-off `ConfigParams` set for this round only
+    /// (e.g. a short-timer "blitz" round or a zero-protocol-fee promo round).
+    /// Requires the admin's signature; the persistent `GlobalConfig` is left
+    /// untouched either way.
+    pub fn start_new_round(
+        ctx: Context<StartNewRound>,
+        overrides: Option<ConfigParams>,
+    ) -> Result<()> {
+        instructions::start_new_round::handle_start_new_round(ctx, overrides)
     }
 
-    pub fn buy_keys(ctx: Context<BuyKeys>, keys_to_buy: u64, is_agent: bool) -> Result<()> {
-        instructions::buy_keys::handle_buy_keys(ctx, keys_to_buy, is_agent)
+    pub fn buy_keys<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+        keys_to_buy: u64,
+        is_agent: bool,
+        strategy_tag: u32,
+    ) -> Result<()> {
+        instructions::buy_keys::handle_buy_keys(ctx, keys_to_buy, is_agent, strategy_tag)
+    }
+
+    /// Exact-budget buy mode: spends up to `budget_lamports`, buying as many
+    /// keys as that affords (see `math::calculate_max_keys`).
+    pub fn buy_keys_with_budget<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+        budget_lamports: u64,
+        is_agent: bool,
+        strategy_tag: u32,
+    ) -> Result<()> {
+        instructions::buy_keys::handle_buy_keys_with_budget(ctx, budget_lamports, is_agent, strategy_tag)
+    }
+
+    /// Batched buy mode: collapses several tranche sizes (e.g. a
+    /// dollar-cost-average schedule) into one instruction, one summed
+    /// transfer, and one aggregated event (see `handle_buy_keys_batch`).
+    pub fn buy_keys_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyKeys<'info>>,
+        amounts: Vec<u64>,
+        is_agent: bool,
+        strategy_tag: u32,
+    ) -> Result<()> {
+        instructions::buy_keys::handle_buy_keys_batch(ctx, amounts, is_agent, strategy_tag)
     }
 
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
         instructions::claim::handle_claim(ctx)
     }
 
+    /// Claim just this player's dividend share — see `instructions::claim_dividends`.
+    pub fn claim_dividends(ctx: Context<ClaimDividends>) -> Result<()> {
+        instructions::claim_dividends::handle_claim_dividends(ctx)
+    }
+
+    /// Claim just the winner prize — see `instructions::claim_winner`.
+    pub fn claim_winner(ctx: Context<ClaimWinner>) -> Result<()> {
+        instructions::claim_winner::handle_claim_winner(ctx)
+    }
+
+    /// Claim from the just-ended round and start the next one in the same
+    /// transaction — lets any claimant (or a crank) keep the game moving
+    /// without a separate `start_new_round` call.
+    pub fn claim_and_roll(ctx: Context<ClaimAndRoll>) -> Result<()> {
+        instructions::claim_and_roll::handle_claim_and_roll(ctx)
+    }
+
+    /// Claim the same dividend/winner payout as `claim`, but delegate it to
+    /// `GlobalConfig::approved_stake_vote_account` instead of cashing it
+    /// out — see `instructions::claim_to_stake`.
+    pub fn claim_to_stake(ctx: Context<ClaimToStake>) -> Result<()> {
+        instructions::claim_to_stake::handle_claim_to_stake(ctx)
+    }
+
     pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
         instructions::claim_referral_earnings::handle_claim_referral_earnings(ctx)
     }
+
+    /// Claims `GameState::top_referrer_bonus_pool` — see
+    /// `instructions::claim_top_referrer_bonus`.
+    pub fn claim_top_referrer_bonus(ctx: Context<ClaimTopReferrerBonus>) -> Result<()> {
+        instructions::claim_top_referrer_bonus::handle_claim_top_referrer_bonus(ctx)
+    }
+
+    /// Claims `GameState::biggest_buyer_bonus_pool` — see
+    /// `instructions::claim_biggest_buyer_bonus`.
+    pub fn claim_biggest_buyer_bonus(ctx: Context<ClaimBiggestBuyerBonus>) -> Result<()> {
+        instructions::claim_biggest_buyer_bonus::handle_claim_biggest_buyer_bonus(ctx)
+    }
+
+    /// Claims `GameState::biggest_holder_bonus_pool` — see
+    /// `instructions::claim_biggest_holder_bonus`.
+    pub fn claim_biggest_holder_bonus(ctx: Context<ClaimBiggestHolderBonus>) -> Result<()> {
+        instructions::claim_biggest_holder_bonus::handle_claim_biggest_holder_bonus(ctx)
+    }
+
+    pub fn assert_solvency(ctx: Context<AssertSolvency>) -> Result<()> {
+        instructions::assert_solvency::handle_assert_solvency(ctx)
+    }
+
+    /// `allow_claim`: if true, the wallet can still withdraw dividends/winnings
+    /// already owed to it — it just can't buy new keys. If false, blocked entirely.
+    pub fn add_to_blocklist(
+        ctx: Context<AddToBlocklist>,
+        wallet: Pubkey,
+        allow_claim: bool,
+    ) -> Result<()> {
+        instructions::add_to_blocklist::handle_add_to_blocklist(ctx, wallet, allow_claim)
+    }
+
+    pub fn remove_from_blocklist(ctx: Context<RemoveFromBlocklist>) -> Result<()> {
+        instructions::remove_from_blocklist::handle_remove_from_blocklist(ctx)
+    }
+
+    /// Permissionless: rolls an old round's unclaimed `winner_pot` into the
+    /// currently active round once `winner_claim_window_secs` has elapsed
+    /// since the old round's timer ended. Anyone can crank this.
+    pub fn forfeit_winner_pot(ctx: Context<ForfeitWinnerPot>) -> Result<()> {
+        instructions::forfeit_winner_pot::handle_forfeit_winner_pot(ctx)
+    }
+
+    /// Authorizes `delegate` to sign `buy_keys_via_session` on the caller's
+    /// behalf, up to `spend_limit_lamports` total lamport cost and before
+    /// `expiry_unix_ts`. Lets an agent use its own hot wallet instead of the
+    /// principal's main keypair for every buy.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        delegate: Pubkey,
+        spend_limit_lamports: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        instructions::create_session::handle_create_session(
+            ctx,
+            delegate,
+            spend_limit_lamports,
+            expiry_unix_ts,
+        )
+    }
+
+    /// Same as `buy_keys`, but signed by a session delegate instead of the
+    /// player — keys/dividends are attributed to `owner`, the delegate pays
+    /// the SOL cost from its own balance, and `session_authority` bounds how
+    /// much it may spend this way.
+    pub fn buy_keys_via_session(
+        ctx: Context<BuyKeysViaSession>,
+        keys_to_buy: u64,
+        is_agent: bool,
+    ) -> Result<()> {
+        instructions::buy_keys_via_session::handle_buy_keys_via_session(ctx, keys_to_buy, is_agent)
+    }
+
+    /// Closes a player's PlayerState and refunds its rent to them. Only
+    /// allowed once they hold no keys, have no pending referral earnings,
+    /// and aren't mid-round (`current_round == 0`). The account can be
+    /// re-created later via `init_if_needed` in `buy_keys` — closing zeroes
+    /// the account entirely, so there's no stale data to worry about.
+    pub fn close_player_state(ctx: Context<ClosePlayerState>) -> Result<()> {
+        instructions::close_player_state::handle_close_player_state(ctx)
+    }
+
+    /// Permissionless: forwards a player's unclaimed referral-earnings
+    /// backing from a stale, already-ended round's vault into the currently
+    /// active round's vault, keeping `total_referral_obligations` in sync on
+    /// both sides. Anyone can crank this.
+    pub fn consolidate_referral_earnings(
+        ctx: Context<ConsolidateReferralEarnings>,
+    ) -> Result<()> {
+        instructions::consolidate_referral_earnings::handle_consolidate_referral_earnings(ctx)
+    }
+
+    /// Sets `player_state.auto_compound` and `player_state.payout_address`.
+    /// When `auto_compound` is true, `claim` reinvests this player's
+    /// dividend share into keys of the currently active round instead of
+    /// paying it out as SOL. When `payout_address` is set, `claim` and
+    /// `claim_referral_earnings` send cash payouts there instead of to the
+    /// signer.
+    pub fn set_preferences(
+        ctx: Context<SetPreferences>,
+        auto_compound: bool,
+        payout_address: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_preferences::handle_set_preferences(ctx, auto_compound, payout_address)
+    }
+
+    /// Locks `budget_lamports` into an escrow PDA and records a hash of the
+    /// real purchase (`keys_to_buy`, a caller-chosen `salt`, and the buyer)
+    /// together with the round's `total_keys` at this instant. The matching
+    /// `reveal_buy` must land in a later slot and always prices off this
+    /// frozen `total_keys`, so nothing about the eventual purchase can be
+    /// inferred or front-run from the commitment alone.
+    pub fn commit_buy(
+        ctx: Context<CommitBuy>,
+        commitment_hash: [u8; 32],
+        budget_lamports: u64,
+    ) -> Result<()> {
+        instructions::commit_buy::handle_commit_buy(ctx, commitment_hash, budget_lamports)
+    }
+
+    /// Settles a prior `commit_buy`: verifies `(keys_to_buy, salt, buyer)`
+    /// hashes to the stored commitment, requires the current slot be later
+    /// than the commit slot, and prices the purchase off the round's
+    /// `total_keys` as of the commit rather than its live value. Pays out
+    /// of the commitment's escrow, refunds any unused budget to the buyer,
+    /// and closes the commitment, refunding its rent.
+    pub fn reveal_buy(
+        ctx: Context<RevealBuy>,
+        keys_to_buy: u64,
+        salt: [u8; 32],
+        is_agent: bool,
+    ) -> Result<()> {
+        instructions::reveal_buy::handle_reveal_buy(ctx, keys_to_buy, salt, is_agent)
+    }
+
+    /// Admin-only: mints `keys` dividend-bearing keys to `player`, funded by
+    /// the admin depositing that purchase's bonding-curve cost into the vault
+    /// from their own balance — so the grant's pot/dividend obligations are
+    /// backed exactly like a real buy and `assert_solvency` stays satisfied.
+    /// Bounded by `GameState::promo_keys_cap_per_round`; emits `PromoGranted`.
+    /// For marketing quests and giveaways.
+    pub fn grant_promo_keys(
+        ctx: Context<GrantPromoKeys>,
+        player: Pubkey,
+        keys: u64,
+    ) -> Result<()> {
+        instructions::grant_promo_keys::handle_grant_promo_keys(ctx, player, keys)
+    }
+
+    /// Moves `amount` keys (and their proportional dividend weight) from the
+    /// caller's `PlayerState` to `to`'s within the same round. Enables
+    /// secondary OTC markets and lets an agent consolidate positions spread
+    /// across wallets. Gated by `GameState::transfers_enabled`; emits
+    /// `KeysTransferred`.
+    pub fn transfer_keys(ctx: Context<TransferKeys>, to: Pubkey, amount: u64) -> Result<()> {
+        instructions::transfer_keys::handle_transfer_keys(ctx, to, amount)
+    }
+
+    /// Permissionless: creates the currently active round's wrapped-key SPL
+    /// mint, gated by `GameState::wrapped_keys_enabled`. Anyone can crank
+    /// this, same as `consolidate_referral_earnings`. Must run once before
+    /// the round's first `wrap_keys`.
+    pub fn init_key_mint(ctx: Context<InitKeyMint>) -> Result<()> {
+        instructions::init_key_mint::handle_init_key_mint(ctx)
+    }
+
+    /// Moves `amount` keys (and their proportional dividend weight) out of
+    /// the caller's `PlayerState` and mints the equivalent amount of this
+    /// round's wrapped-key SPL token, making keys composable with DEXes and
+    /// lending. See `unwrap_keys` to redeem back into a dividend-bearing
+    /// position. Emits `KeysWrapped`.
+    pub fn wrap_keys(ctx: Context<WrapKeys>, amount: u64) -> Result<()> {
+        instructions::wrap_keys::handle_wrap_keys(ctx, amount)
+    }
+
+    /// Burns `amount` of this round's wrapped-key SPL token and restores the
+    /// equivalent keys, plus a pro-rata dividend weight, into the caller's
+    /// `PlayerState` — registering them into the round first if needed. The
+    /// only way back into a claimable position once keys are wrapped. Emits
+    /// `KeysUnwrapped`.
+    pub fn unwrap_keys(ctx: Context<UnwrapKeys>, amount: u64) -> Result<()> {
+        instructions::unwrap_keys::handle_unwrap_keys(ctx, amount)
+    }
+
+    /// Admin-only: deposits `amount` lamports into the game's `KeeperBudget`
+    /// vault, from which `end_round` reimburses whoever cranks it.
+    pub fn fund_keeper_budget(ctx: Context<FundKeeperBudget>, amount: u64) -> Result<()> {
+        instructions::fund_keeper_budget::handle_fund_keeper_budget(ctx, amount)
+    }
+
+    /// Permissionless: ends the currently active round once its timer has
+    /// expired, without requiring a buy or claim to trigger it. Built for
+    /// off-chain automation (a keeper bot, a Clockwork-style thread) so a
+    /// round concludes on schedule instead of waiting on the next player
+    /// action. Anyone can crank this; the caller is reimbursed from the
+    /// game's `KeeperBudget` vault per `GlobalConfig::keeper_fee_lamports`.
+    pub fn end_round<'info>(ctx: Context<'_, '_, '_, 'info, EndRound<'info>>) -> Result<()> {
+        instructions::end_round::handle_end_round(ctx)
+    }
+
+    /// Self-service: creates the caller's `PlayerHistory` ring buffer, gated
+    /// by `GameState::purchase_history_enabled`. Must run once before
+    /// `buy_keys` can start recording that player's purchases into it.
+    pub fn init_player_history(ctx: Context<InitPlayerHistory>) -> Result<()> {
+        instructions::init_player_history::handle_init_player_history(ctx)
+    }
+
+    /// Admin-only crank: records a Merkle root over (player, dividend_amount)
+    /// leaves for an already-ended round, enabling compressed mass
+    /// distribution via `claim_with_proof` alongside the existing direct
+    /// `claim` path.
+    pub fn record_dividend_merkle_root(
+        ctx: Context<RecordDividendMerkleRoot>,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::record_dividend_merkle_root::handle_record_dividend_merkle_root(
+            ctx,
+            merkle_root,
+        )
+    }
+
+    /// Permissionless: pays out a leaf of `GameState::dividend_merkle_root`
+    /// to `player` once a valid Merkle proof is supplied. Does not touch or
+    /// require a `PlayerState` account.
+    pub fn claim_with_proof(
+        ctx: Context<ClaimWithProof>,
+        dividend_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_with_proof::handle_claim_with_proof(ctx, dividend_amount, proof)
+    }
+
+    /// Lets a player attach a referrer for the first time, or — once
+    /// `GlobalConfig::referrer_change_cooldown_secs` is configured — switch an
+    /// already-set referrer after that cooldown has elapsed. Either way,
+    /// nothing is allowed once the player has bought keys this round.
+    /// Permissionless otherwise — only the player themselves can set their
+    /// own referrer. `ctx.remaining_accounts` may optionally carry the new
+    /// referrer's own referral ancestors (its referrer, that referrer's
+    /// referrer, and so on) so a multi-level ring can be rejected, not just a
+    /// direct two-party one — see `MAX_REFERRAL_CHAIN_DEPTH`.
+    pub fn set_referrer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetReferrer<'info>>,
+    ) -> Result<()> {
+        instructions::set_referrer::handle_set_referrer(ctx)
+    }
+
+    /// Sets or updates a player's self-imposed daily spend cap for key
+    /// purchases — see `instructions::set_spend_limit` for the raise-delay
+    /// and rolling-window semantics.
+    pub fn set_spend_limit(
+        ctx: Context<SetSpendLimit>,
+        new_limit_lamports_per_day: u64,
+    ) -> Result<()> {
+        instructions::set_spend_limit::handle_set_spend_limit(ctx, new_limit_lamports_per_day)
+    }
+
+    /// Admin-approved-issuer-only: approves `wallet` for a KYC-gated game by
+    /// creating its `KycCredential` PDA. Only callable by
+    /// `GlobalConfig::kyc_issuer` — see `instructions::issue_kyc_credential`.
+    pub fn issue_kyc_credential(
+        ctx: Context<IssueKycCredential>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::issue_kyc_credential::handle_issue_kyc_credential(ctx, wallet)
+    }
+
+    /// Admin-only: withdraws a round's accumulated `GameState::dust_reserve`
+    /// (the leftover from `buy_keys`'s three-way pot split) to the protocol
+    /// wallet. Callable on any round, active or ended.
+    pub fn sweep_dust_reserve(ctx: Context<SweepDustReserve>) -> Result<()> {
+        instructions::sweep_dust_reserve::handle_sweep_dust_reserve(ctx)
+    }
+
+    /// Permissionless: once `GameState::dividend_claim_window_secs` has
+    /// elapsed since the round ended, carries out `GameState::unclaimed_dividend_policy`
+    /// against whatever's left of `total_dividend_pool` still unclaimed —
+    /// strand it, roll it into the active round's carry, or sweep it to the
+    /// protocol wallet, depending on how the round's config was set.
+    pub fn sweep_unclaimed_dividends(ctx: Context<SweepUnclaimedDividends>) -> Result<()> {
+        instructions::sweep_unclaimed_dividends::handle_sweep_unclaimed_dividends(ctx)
+    }
+
+    /// Read-only: projects the cost, timer trajectory, and dividend share of
+    /// buying `keys_schedule` against this round right now. See
+    /// `instructions::simulate_strategy` and the `StrategySimulated` event.
+    pub fn simulate_strategy(ctx: Context<SimulateStrategy>, keys_schedule: Vec<u64>) -> Result<()> {
+        instructions::simulate_strategy::handle_simulate_strategy(ctx, keys_schedule)
+    }
+
+    /// Permissionless: tops up `game_state`'s winner pot, dividend pool, or
+    /// next-round carry directly, crediting no keys. Lets a marketing
+    /// partner (or anyone) seed a round's pot without playing it.
+    pub fn sponsor_pot(
+        ctx: Context<SponsorPot>,
+        lamports: u64,
+        allocation: SponsorAllocation,
+    ) -> Result<()> {
+        instructions::sponsor_pot::handle_sponsor_pot(ctx, lamports, allocation)
+    }
+
+    /// Permissionless: posts a bond to register as a priority keeper for
+    /// this game lineage's round-op cranks — see `instructions::end_round`.
+    pub fn register_keeper(ctx: Context<RegisterKeeper>, bond_lamports: u64) -> Result<()> {
+        instructions::register_keeper::handle_register_keeper(ctx, bond_lamports)
+    }
+
+    /// Admin-only: forfeits part of a misbehaving keeper's bond to the
+    /// protocol wallet.
+    pub fn slash_keeper(ctx: Context<SlashKeeper>, amount: u64) -> Result<()> {
+        instructions::slash_keeper::handle_slash_keeper(ctx, amount)
+    }
+
+    /// The keeper's own exit path: closes the registration and returns
+    /// whatever bond remains.
+    pub fn unregister_keeper(ctx: Context<UnregisterKeeper>) -> Result<()> {
+        instructions::unregister_keeper::handle_unregister_keeper(ctx)
+    }
+
+    /// Admin-only: CPIs a bounded slice of the vault's idle balance out to
+    /// `GlobalConfig::yield_program` — see `instructions::deploy_vault_yield`.
+    pub fn deploy_vault_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeployVaultYield<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::deploy_vault_yield::handle_deploy_vault_yield(ctx, lamports)
+    }
+
+    /// Admin-only: reclaims previously-deployed principal from
+    /// `GlobalConfig::yield_program` back into the vault — see
+    /// `instructions::unwind_vault_yield`.
+    pub fn unwind_vault_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnwindVaultYield<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::unwind_vault_yield::handle_unwind_vault_yield(ctx, lamports)
+    }
+
+    /// Admin-only: opens day `day_index` of the round's daily key-holder
+    /// raffle — see `instructions::record_raffle_snapshot`.
+    pub fn record_raffle_snapshot(
+        ctx: Context<RecordRaffleSnapshot>,
+        day_index: u64,
+        merkle_root: [u8; 32],
+        total_weight: u64,
+    ) -> Result<()> {
+        instructions::record_raffle_snapshot::handle_record_raffle_snapshot(
+            ctx,
+            day_index,
+            merkle_root,
+            total_weight,
+        )
+    }
+
+    /// Permissionless: draws the winning ticket for an already-recorded
+    /// raffle day — see `instructions::draw_raffle_ticket`.
+    pub fn draw_raffle_ticket(ctx: Context<DrawRaffleTicket>) -> Result<()> {
+        instructions::draw_raffle_ticket::handle_draw_raffle_ticket(ctx)
+    }
+
+    /// Permissionless: pays out a day's raffle prize to whoever's proven
+    /// weight range contains the winning ticket — see
+    /// `instructions::claim_raffle_prize`.
+    pub fn claim_raffle_prize(
+        ctx: Context<ClaimRafflePrize>,
+        weight_range_start: u64,
+        weight_range_end: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_raffle_prize::handle_claim_raffle_prize(
+            ctx,
+            weight_range_start,
+            weight_range_end,
+            proof,
+        )
+    }
+
+    /// Admin-only: freezes a misconfigured `Active` round and enables
+    /// `refund` claims — see `instructions::cancel_round`.
+    pub fn cancel_round(ctx: Context<CancelRound>) -> Result<()> {
+        instructions::cancel_round::handle_cancel_round(ctx)
+    }
+
+    /// Self-claim: pays a player's `contributed_lamports` back once their
+    /// round has been cancelled — see `instructions::refund`.
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        instructions::refund::handle_refund(ctx)
+    }
+
+    /// Admin-only, step 1 of self-custody recovery: starts the timelock to
+    /// re-bind `old_wallet`'s `PlayerState` to `new_wallet` — see
+    /// `instructions::propose_player_migration`.
+    pub fn propose_player_migration(
+        ctx: Context<ProposePlayerMigration>,
+        old_wallet: Pubkey,
+        new_wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_player_migration::handle_propose_player_migration(
+            ctx, old_wallet, new_wallet,
+        )
+    }
+
+    /// New-wallet-signed, step 2 of self-custody recovery: once the timelock
+    /// elapses, claims the old wallet's `PlayerState` — see
+    /// `instructions::execute_player_migration`.
+    pub fn execute_player_migration(
+        ctx: Context<ExecutePlayerMigration>,
+        old_wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::execute_player_migration::handle_execute_player_migration(ctx, old_wallet)
+    }
+
+    /// Permissionless: appends a `PriceHistory` sample for a round that's
+    /// gone quiet longer than `GameState::price_sample_interval_slots` — see
+    /// `instructions::record_sample`.
+    pub fn record_sample(ctx: Context<RecordSample>) -> Result<()> {
+        instructions::record_sample::handle_record_sample(ctx)
+    }
+
+    /// Permissionless: fixes a `Season`'s leaderboard ranks once its final
+    /// round has concluded — see `instructions::settle_season`.
+    pub fn settle_season(ctx: Context<SettleSeason>) -> Result<()> {
+        instructions::settle_season::handle_settle_season(ctx)
+    }
+
+    /// Pays a settled `Season`'s leaderboard rank its prize share — see
+    /// `instructions::claim_season_prize`.
+    pub fn claim_season_prize(ctx: Context<ClaimSeasonPrize>) -> Result<()> {
+        instructions::claim_season_prize::handle_claim_season_prize(ctx)
+    }
+
+    /// Attributes the caller's agent `PlayerState` to `platform` for the
+    /// lifetime of the account — requires `platform`'s own signature. See
+    /// `instructions::register_agent_platform`.
+    pub fn register_agent_platform(ctx: Context<RegisterAgentPlatform>) -> Result<()> {
+        instructions::register_agent_platform::handle_register_agent_platform(ctx)
+    }
+
+    /// Platform-signed: pays out an `AgentPlatform`'s accrued
+    /// `GameState::agent_platform_fee_share_bps` earnings — see
+    /// `instructions::claim_agent_platform_earnings`.
+    pub fn claim_agent_platform_earnings(
+        ctx: Context<ClaimAgentPlatformEarnings>,
+    ) -> Result<()> {
+        instructions::claim_agent_platform_earnings::handle_claim_agent_platform_earnings(ctx)
+    }
+
+    /// Tops up the caller's `PlayerState::prepaid_balance_lamports` vault —
+    /// see `instructions::deposit_prepaid`.
+    pub fn deposit_prepaid(ctx: Context<DepositPrepaid>, lamports: u64) -> Result<()> {
+        instructions::deposit_prepaid::handle_deposit_prepaid(ctx, lamports)
+    }
+
+    /// Configures (or disables) a recurring `execute_scheduled_buy` crank
+    /// against the caller's prepaid balance — see
+    /// `instructions::set_scheduled_buy`.
+    pub fn set_scheduled_buy(
+        ctx: Context<SetScheduledBuy>,
+        keys_per_buy: u64,
+        interval_secs: i64,
+    ) -> Result<()> {
+        instructions::set_scheduled_buy::handle_set_scheduled_buy(ctx, keys_per_buy, interval_secs)
+    }
+
+    /// Permissionless: purchases a player's configured `scheduled_buy_keys`
+    /// out of their prepaid balance once due — see
+    /// `instructions::execute_scheduled_buy`.
+    pub fn execute_scheduled_buy(ctx: Context<ExecuteScheduledBuy>) -> Result<()> {
+        instructions::execute_scheduled_buy::handle_execute_scheduled_buy(ctx)
+    }
+
+    /// Pulls lamports back out of the caller's
+    /// `PlayerState::prepaid_balance_lamports` vault — see
+    /// `instructions::withdraw_prepaid`.
+    pub fn withdraw_prepaid(ctx: Context<WithdrawPrepaid>, lamports: u64) -> Result<()> {
+        instructions::withdraw_prepaid::handle_withdraw_prepaid(ctx, lamports)
+    }
 }
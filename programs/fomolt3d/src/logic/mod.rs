@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+use crate::math;
+
+/// House fee and frontend fee taken off the top of `cost`, in that order —
+/// see `buy_keys`'s "Fee Ordering" comment. Shared by `buy_keys`'s main path
+/// and its timer-redirect path, which apply it against `GameState` and the
+/// next round's `GameState` respectively.
+pub struct FeeSplit {
+    pub house_fee: u64,
+    pub frontend_fee: u64,
+    pub after_fee: u64,
+}
+
+/// `charge_frontend_fee` mirrors whether the caller supplied a
+/// `frontend_wallet` account — when false, `frontend_fee_bps` is ignored the
+/// same way `buy_keys` treats a missing account as "no fee due" regardless
+/// of what `GameState::frontend_fee_bps` holds.
+pub fn compute_fees(
+    cost: u64,
+    protocol_fee_bps: u64,
+    frontend_fee_bps: u64,
+    charge_frontend_fee: bool,
+) -> Result<FeeSplit> {
+    let house_fee = math::calculate_bps_split(cost, protocol_fee_bps)?;
+    let frontend_fee = if charge_frontend_fee {
+        math::calculate_bps_split(cost, frontend_fee_bps)?
+    } else {
+        0
+    };
+    let after_fee = cost
+        .checked_sub(house_fee)
+        .and_then(|v| v.checked_sub(frontend_fee))
+        .ok_or(FomoltError::Overflow)?;
+    Ok(FeeSplit {
+        house_fee,
+        frontend_fee,
+        after_fee,
+    })
+}
+
+/// Winner/dividend/next-round pot split from `pot_contribution`, with an
+/// optional raffle cut taken off the top first (`raffle_bps == 0` skips it —
+/// `buy_keys`'s timer-redirect path always passes 0, since the raffle
+/// meta-game only participates in normal-path purchases) and
+/// `math::apply_pot_cap` applied to the winner/dividend halves before
+/// returning. Rounding dust is returned separately so the caller can route
+/// it per `RoundingBeneficiary`, same as `apply_pot_cap`'s overflow.
+pub struct PotSplit {
+    pub raffle_amount: u64,
+    pub winner_amount: u64,
+    pub dividend_amount: u64,
+    pub next_round_amount: u64,
+    pub dust: u64,
+    pub pot_overflow_amount: u64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn compute_pot_split(
+    pot_contribution: u64,
+    raffle_bps: u64,
+    winner_bps: u64,
+    dividend_bps: u64,
+    next_round_bps: u64,
+    committed: u64,
+    max_pot_lamports: u64,
+) -> Result<PotSplit> {
+    let raffle_amount = math::calculate_bps_split(pot_contribution, raffle_bps)?;
+    let splittable = pot_contribution
+        .checked_sub(raffle_amount)
+        .ok_or(FomoltError::Overflow)?;
+    let winner_amount = math::calculate_bps_split(splittable, winner_bps)?;
+    let dividend_amount = math::calculate_bps_split(splittable, dividend_bps)?;
+    let next_round_amount = math::calculate_bps_split(splittable, next_round_bps)?;
+    let dust = splittable
+        .checked_sub(winner_amount)
+        .and_then(|r| r.checked_sub(dividend_amount))
+        .and_then(|r| r.checked_sub(next_round_amount))
+        .ok_or(FomoltError::Overflow)?;
+
+    let (winner_amount, dividend_amount, pot_overflow_amount) =
+        math::apply_pot_cap(committed, max_pot_lamports, winner_amount, dividend_amount)?;
+
+    Ok(PotSplit {
+        raffle_amount,
+        winner_amount,
+        dividend_amount,
+        next_round_amount,
+        dust,
+        pot_overflow_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ===== compute_fees =====
+
+    #[test]
+    fn fees_no_frontend_fee_when_not_charged() {
+        let split = compute_fees(1_000_000_000, 200, 500, false).unwrap();
+        assert_eq!(split.house_fee, 20_000_000); // 2%
+        assert_eq!(split.frontend_fee, 0);
+        assert_eq!(split.after_fee, 980_000_000);
+    }
+
+    #[test]
+    fn fees_charges_frontend_fee_when_wallet_present() {
+        let split = compute_fees(1_000_000_000, 200, 500, true).unwrap();
+        assert_eq!(split.house_fee, 20_000_000); // 2%
+        assert_eq!(split.frontend_fee, 50_000_000); // 5%
+        assert_eq!(split.after_fee, 930_000_000);
+    }
+
+    #[test]
+    fn fees_zero_bps_conserves_cost() {
+        let split = compute_fees(12_345, 0, 0, true).unwrap();
+        assert_eq!(split.house_fee, 0);
+        assert_eq!(split.frontend_fee, 0);
+        assert_eq!(split.after_fee, 12_345);
+    }
+
+    #[test]
+    fn fees_conserve_cost_across_various_amounts() {
+        for cost in [1u64, 100, 999, 10_000_000, 1_000_000_000] {
+            let split = compute_fees(cost, 200, 300, true).unwrap();
+            assert_eq!(split.house_fee + split.frontend_fee + split.after_fee, cost);
+        }
+    }
+
+    // ===== compute_pot_split =====
+
+    #[test]
+    fn pot_split_standard_bps_with_no_raffle() {
+        let split = compute_pot_split(1_000_000_000, 0, 4800, 4500, 700, 0, 0).unwrap();
+        assert_eq!(split.raffle_amount, 0);
+        assert_eq!(split.winner_amount, 480_000_000);
+        assert_eq!(split.dividend_amount, 450_000_000);
+        assert_eq!(split.next_round_amount, 70_000_000);
+        assert_eq!(split.dust, 0);
+        assert_eq!(split.pot_overflow_amount, 0);
+    }
+
+    #[test]
+    fn pot_split_raffle_cut_taken_before_three_way_split() {
+        // 1% raffle cut off the top, remaining 99% split 4800/4500/700
+        let split = compute_pot_split(1_000_000_000, 100, 4800, 4500, 700, 0, 0).unwrap();
+        assert_eq!(split.raffle_amount, 10_000_000);
+        let splittable = 990_000_000u64;
+        assert_eq!(split.winner_amount, 475_200_000); // 48% of 990M
+        assert_eq!(split.dividend_amount, 445_500_000); // 45% of 990M
+        assert_eq!(split.next_round_amount, 69_300_000); // 7% of 990M
+    }
+
+    #[test]
+    fn pot_split_zero_raffle_bps_disables_raffle_cut() {
+        let split = compute_pot_split(1_000_000_000, 0, 4800, 4500, 700, 0, 0).unwrap();
+        assert_eq!(split.raffle_amount, 0);
+    }
+
+    #[test]
+    fn pot_split_applies_pot_cap_and_reports_overflow() {
+        // committed=1_000, cap=1_100 leaves only 100 lamports of room, so 700
+        // of the 800 winner+dividend must be cut — same fixture as
+        // `math::tests::pot_cap_takes_from_dividend_first`.
+        let split = compute_pot_split(800, 0, 6250, 3750, 0, 1_000, 1_100).unwrap();
+        assert_eq!(split.winner_amount, 100);
+        assert_eq!(split.dividend_amount, 0);
+        assert_eq!(split.pot_overflow_amount, 700);
+    }
+
+    #[test]
+    fn pot_split_conserves_pot_contribution() {
+        for pot_contribution in [1u64, 999, 1_000_000, 1_000_000_000] {
+            let split = compute_pot_split(pot_contribution, 100, 4800, 4500, 700, 0, 0).unwrap();
+            let accounted = split.raffle_amount
+                + split.winner_amount
+                + split.dividend_amount
+                + split.next_round_amount
+                + split.dust;
+            assert_eq!(accounted, pot_contribution);
+        }
+    }
+}
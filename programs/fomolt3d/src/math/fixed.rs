@@ -0,0 +1,117 @@
+//! Fixed-point primitives backing the bps/curve math in the parent module.
+//!
+//! Everything here is Q64.64: an integer part plus a 64-bit fractional part
+//! (`fraction` below is always in `[0, 2^64)`, i.e. `fraction / 2^64` is the
+//! fractional value). Every division in this module rounds DOWN — lamports
+//! are indivisible, so a payout can never be rounded up to more than the
+//! exact rational amount owed. The truncated remainder ("dust") always stays
+//! behind rather than being paid to anyone; see `calculate_bps_split` and
+//! `calculate_cost` in the parent module for where that dust actually ends
+//! up (it's left uncredited in the vault).
+//!
+//! The integer and fractional parts are kept as separate fields rather than
+//! packed into one shifted `u128`, so a large integer part (up to `u128`,
+//! not just `u64`) never has to survive a `<< 64` on its way in.
+
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+
+const FRACTIONAL_BITS: u32 = 64;
+
+/// A non-negative Q64.64 fixed-point number, as `integer + fraction / 2^64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Q64F64 {
+    integer: u128,
+    fraction: u128,
+}
+
+impl Q64F64 {
+    /// `numerator / denominator`, exact (no precision lost) and with the
+    /// fractional remainder kept around rather than discarded immediately —
+    /// callers that only need the floored integer should use
+    /// [`floor_to_u64`](Self::floor_to_u64).
+    fn exact_div(numerator: u128, denominator: u128) -> Result<Self> {
+        require!(denominator > 0, FomoltError::Overflow);
+        let integer = numerator / denominator;
+        let remainder = numerator % denominator;
+        let fraction = (remainder << FRACTIONAL_BITS) / denominator;
+        Ok(Q64F64 { integer, fraction })
+    }
+
+    /// Truncates the fractional part — the rounding-down half of this
+    /// module's invariant described above.
+    fn floor_to_u64(self) -> Result<u64> {
+        u64::try_from(self.integer).map_err(|_| FomoltError::Overflow.into())
+    }
+}
+
+/// `value * numerator / denominator`, rounded down. The primitive behind
+/// `calculate_bps_split` (numerator/denominator = bps/10_000).
+pub(crate) fn mul_div_floor(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let product = (value as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(FomoltError::Overflow)?;
+    Q64F64::exact_div(product, denominator as u128)?.floor_to_u64()
+}
+
+/// `numerator / denominator`, rounded down, for a `u128` intermediate that
+/// may already exceed `u64` (e.g. the arithmetic-series term in
+/// `calculate_cost`, before it's known to fit back into a `u64` total).
+pub(crate) fn div_floor_u128(numerator: u128, denominator: u128) -> Result<u128> {
+    Ok(Q64F64::exact_div(numerator, denominator)?.integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn mul_div_floor_matches_known_bps_splits() {
+        assert_eq!(mul_div_floor(1_000_000_000, 4800, 10_000).unwrap(), 480_000_000);
+        assert_eq!(mul_div_floor(99, 4800, 10_000).unwrap(), 47);
+    }
+
+    #[test]
+    fn mul_div_floor_handles_near_max_u64_values() {
+        let result = mul_div_floor(u64::MAX / 2, 4800, 10_000).unwrap();
+        assert!(result > 0 && result < u64::MAX / 2);
+    }
+
+    #[test]
+    fn div_floor_u128_matches_known_series_terms() {
+        assert_eq!(div_floor_u128(9, 2).unwrap(), 4);
+        assert_eq!(div_floor_u128(10, 2).unwrap(), 5);
+    }
+
+    proptest! {
+        #[test]
+        fn mul_div_floor_is_exact_and_never_rounds_up(
+            value in 0u64..=u64::MAX,
+            numerator in 0u64..=10_000,
+            denominator in 1u64..=10_000,
+        ) {
+            let exact = (value as u128) * (numerator as u128);
+            let reference_floor = exact / denominator as u128;
+
+            // numerator <= denominator in every real caller (bps <= 10_000),
+            // so the result always fits back into a u64; outside that the
+            // function is expected to report Overflow rather than truncate.
+            match mul_div_floor(value, numerator, denominator) {
+                Ok(result) => prop_assert_eq!(result as u128, reference_floor),
+                Err(_) => prop_assert!(reference_floor > u64::MAX as u128),
+            }
+        }
+
+        #[test]
+        fn div_floor_u128_never_rounds_up(
+            numerator in 0u128..1_000_000_000_000_000_000,
+            denominator in 1u128..1_000_000,
+        ) {
+            let result = div_floor_u128(numerator, denominator).unwrap();
+            prop_assert!(result * denominator <= numerator);
+            prop_assert!((result + 1) * denominator > numerator);
+        }
+    }
+}
@@ -0,0 +1,1284 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::FomoltError;
+
+pub mod fixed;
+
+/// Calculate total cost for buying `n` keys starting from supply `k`.
+/// Uses the closed-form arithmetic series formula with u128 intermediates.
+/// cost = n * base_price + price_increment * n * (2k + n - 1) / 2
+pub fn calculate_cost(
+    current_supply: u64,
+    keys_to_buy: u64,
+    base_price: u64,
+    price_increment: u64,
+) -> Result<u64> {
+    let n = keys_to_buy as u128;
+    let k = current_supply as u128;
+    let base = base_price as u128;
+    let inc = price_increment as u128;
+
+    let base_cost = n.checked_mul(base).ok_or(FomoltError::Overflow)?;
+
+    let series_numerator = n
+        .checked_mul(
+            k.checked_mul(2)
+                .ok_or(FomoltError::Overflow)?
+                .checked_add(n)
+                .ok_or(FomoltError::Overflow)?
+                .checked_sub(1)
+                .ok_or(FomoltError::Overflow)?,
+        )
+        .ok_or(FomoltError::Overflow)?;
+
+    let series_cost = fixed::div_floor_u128(
+        inc.checked_mul(series_numerator).ok_or(FomoltError::Overflow)?,
+        2,
+    )?;
+
+    let total = base_cost
+        .checked_add(series_cost)
+        .ok_or(FomoltError::Overflow)?;
+
+    u64::try_from(total).map_err(|_| FomoltError::Overflow.into())
+}
+
+/// Calculate the dividend-weight earned for buying `keys_to_buy` keys
+/// starting at `current_supply`, under an early-bird bonus schedule: keys
+/// sold before `early_bird_threshold` earn `multiplier_bps` weight instead
+/// of the standard 10_000 (1x). A threshold of 0 disables the bonus.
+///
+/// Weight is scaled by bps so `calculate_dividend_share` can be called with
+/// weight/total_weight in place of keys/total_keys unchanged — a weight of
+/// 10_000 is worth exactly one "full" key, so the ratio (and therefore the
+/// resulting share) is identical to the unweighted formula whenever no
+/// bonus applies.
+pub fn calculate_key_weight(
+    current_supply: u64,
+    keys_to_buy: u64,
+    early_bird_threshold: u64,
+    multiplier_bps: u64,
+) -> Result<u64> {
+    let keys_in_window = early_bird_threshold
+        .saturating_sub(current_supply)
+        .min(keys_to_buy);
+    let keys_outside_window = keys_to_buy
+        .checked_sub(keys_in_window)
+        .ok_or(FomoltError::Overflow)?;
+
+    let bonus_weight = (keys_in_window as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(FomoltError::Overflow)?;
+    let standard_weight = (keys_outside_window as u128)
+        .checked_mul(10_000u128)
+        .ok_or(FomoltError::Overflow)?;
+
+    let total = bonus_weight
+        .checked_add(standard_weight)
+        .ok_or(FomoltError::Overflow)?;
+
+    u64::try_from(total).map_err(|_| FomoltError::Overflow.into())
+}
+
+/// Inverse of `calculate_cost`: the largest `n` such that
+/// `calculate_cost(current_supply, n, ..) <= budget`. Used by the
+/// exact-budget buy mode and mirrored client-side for price quotes
+/// (see `app/src/lib/sdk/estimates.ts`'s `maxKeysForBudget`).
+///
+/// Binary search over `calculate_cost` rather than solving the quadratic
+/// directly — avoids an integer square root and stays exactly consistent
+/// with whatever `calculate_cost` computes, including its overflow checks.
+pub fn calculate_max_keys(
+    budget: u64,
+    current_supply: u64,
+    base_price: u64,
+    price_increment: u64,
+) -> Result<u64> {
+    if budget == 0 {
+        return Ok(0);
+    }
+
+    // Grow the upper bound until the cost exceeds the budget (or we hit
+    // u64::MAX, meaning the curve never exceeds the budget at any key count).
+    let mut hi = 1u64;
+    while let Ok(cost) = calculate_cost(current_supply, hi, base_price, price_increment) {
+        if cost > budget {
+            break;
+        }
+        if hi == u64::MAX {
+            return Ok(u64::MAX);
+        }
+        hi = hi.saturating_mul(2);
+    }
+
+    // Binary search for the largest affordable `n` in (lo, hi].
+    let mut lo = 0u64;
+    while lo + 1 < hi {
+        let mid = lo + (hi - lo) / 2;
+        match calculate_cost(current_supply, mid, base_price, price_increment) {
+            Ok(cost) if cost <= budget => lo = mid,
+            _ => hi = mid,
+        }
+    }
+
+    Ok(lo)
+}
+
+/// Calculate a BPS-based revenue split: amount * bps / 10_000, rounded down
+/// (see `math::fixed` for the rounding-direction invariant this relies on).
+pub fn calculate_bps_split(amount: u64, bps: u64) -> Result<u64> {
+    fixed::mul_div_floor(amount, bps, 10_000)
+}
+
+/// Calculate a player's proportional dividend share at round end.
+/// Returns: (player_keys * total_dividend_pool) / total_keys
+pub fn calculate_dividend_share(
+    player_keys: u64,
+    total_dividend_pool: u64,
+    total_keys: u64,
+) -> Result<u64> {
+    if total_keys == 0 || player_keys == 0 {
+        return Ok(0);
+    }
+    u64::try_from(
+        (player_keys as u128)
+            .checked_mul(total_dividend_pool as u128)
+            .ok_or(FomoltError::Overflow)?
+            .checked_div(total_keys as u128)
+            .ok_or(FomoltError::Overflow)?,
+    )
+    .map_err(|_| FomoltError::Overflow.into())
+}
+
+/// Same as `calculate_dividend_share`, but using weight-seconds (weight held
+/// multiplied by seconds held) instead of a point-in-time weight — see
+/// `GameState::time_weighted_dividends_enabled`. Both inputs already come
+/// out of `GameState`/`PlayerState` as u128, so unlike the point-in-time
+/// split there's no u64->u128 upcast to do here.
+pub fn calculate_dividend_share_weighted(
+    player_weight_seconds: u128,
+    total_dividend_pool: u64,
+    total_weight_seconds: u128,
+) -> Result<u64> {
+    if total_weight_seconds == 0 || player_weight_seconds == 0 {
+        return Ok(0);
+    }
+    u64::try_from(
+        player_weight_seconds
+            .checked_mul(total_dividend_pool as u128)
+            .ok_or(FomoltError::Overflow)?
+            .checked_div(total_weight_seconds)
+            .ok_or(FomoltError::Overflow)?,
+    )
+    .map_err(|_| FomoltError::Overflow.into())
+}
+
+/// Calculate the new timer_end after a key purchase.
+/// Timer can only increase (monotonic), capped at round_start + max_timer_secs.
+///
+/// `final_hour_active` and `final_hour_shrink_interval_keys` implement the
+/// endgame convergence mode (see `GlobalConfig::final_hour_pot_threshold_lamports`):
+/// once the pot crosses the configured threshold, `extension_secs` halves
+/// every `final_hour_shrink_interval_keys` keys sold since activation
+/// (`keys_since_final_hour_start`), so minimum buys can no longer extend the
+/// round indefinitely.
+///
+/// `min_remaining_secs` (see `GlobalConfig::min_remaining_secs`) floors the
+/// result at `current_time + min_remaining_secs`, guaranteeing every buy
+/// leaves at least that much time on the clock even when the halved
+/// `effective_extension` above would otherwise round down to less. 0
+/// disables the floor. The floor is applied before the `max_timer` cap, so
+/// it can never push the round past `round_start + max_timer_secs`.
+///
+/// Bundled into `TimerExtensionParams` rather than taken as bare arguments —
+/// `current_time` stays a positional parameter since every caller already
+/// has it on hand as "now", distinct from the round/timer configuration.
+pub struct TimerExtensionParams {
+    pub extension_secs: i64,
+    pub current_timer_end: i64,
+    pub round_start: i64,
+    pub max_timer_secs: i64,
+    pub final_hour_active: bool,
+    pub keys_since_final_hour_start: u64,
+    pub final_hour_shrink_interval_keys: u64,
+    pub min_remaining_secs: i64,
+}
+
+pub fn calculate_timer_extension(
+    current_time: i64,
+    params: TimerExtensionParams,
+) -> Result<i64> {
+    let TimerExtensionParams {
+        extension_secs,
+        current_timer_end,
+        round_start,
+        max_timer_secs,
+        final_hour_active,
+        keys_since_final_hour_start,
+        final_hour_shrink_interval_keys,
+        min_remaining_secs,
+    } = params;
+    let effective_extension = if final_hour_active && final_hour_shrink_interval_keys > 0 {
+        let halvings = (keys_since_final_hour_start / final_hour_shrink_interval_keys).min(62);
+        extension_secs >> halvings
+    } else {
+        extension_secs
+    };
+    let new_timer = current_time
+        .checked_add(effective_extension)
+        .ok_or(FomoltError::Overflow)?;
+    let min_floor = current_time
+        .checked_add(min_remaining_secs)
+        .ok_or(FomoltError::Overflow)?;
+    let max_timer = round_start
+        .checked_add(max_timer_secs)
+        .ok_or(FomoltError::Overflow)?;
+    Ok(new_timer.max(current_timer_end).max(min_floor).min(max_timer))
+}
+
+/// Validate that pot-split BPS values sum to 10_000.
+/// Protocol fee and referral bonus are separate — not included in this sum.
+pub fn validate_bps_sum(
+    winner_bps: u64,
+    dividend_bps: u64,
+    next_round_bps: u64,
+) -> Result<()> {
+    let sum = winner_bps
+        .checked_add(dividend_bps)
+        .ok_or(FomoltError::Overflow)?
+        .checked_add(next_round_bps)
+        .ok_or(FomoltError::Overflow)?;
+    require!(sum == 10_000, FomoltError::InvalidConfig);
+    Ok(())
+}
+
+/// How many `interval_lamports`-sized pot milestones this purchase crossed,
+/// e.g. `old_pot=80, new_pot=220, interval=100` crosses milestone 1 and 2, so
+/// returns `2`. `interval_lamports == 0` disables milestones entirely.
+pub fn calculate_milestones_crossed(old_pot: u64, new_pot: u64, interval_lamports: u64) -> u64 {
+    if interval_lamports == 0 {
+        return 0;
+    }
+    (new_pot / interval_lamports).saturating_sub(old_pot / interval_lamports)
+}
+
+/// Clamp `winner_amount` + `dividend_amount` so that, added to whatever is
+/// already committed to the pot (`committed` = `winner_pot +
+/// total_dividend_pool` at the time of the purchase), the total never
+/// exceeds `max_pot_lamports`. Excess is pulled from `dividend_amount`
+/// first, then `winner_amount`, and returned separately as `overflow` so the
+/// caller can route it into `pot_overflow_reserve_lamports` instead of
+/// dropping it. `max_pot_lamports == 0` disables the cap.
+/// Returns `(capped_winner_amount, capped_dividend_amount, overflow)`.
+pub fn apply_pot_cap(
+    committed: u64,
+    max_pot_lamports: u64,
+    winner_amount: u64,
+    dividend_amount: u64,
+) -> Result<(u64, u64, u64)> {
+    if max_pot_lamports == 0 {
+        return Ok((winner_amount, dividend_amount, 0));
+    }
+    let remaining = max_pot_lamports.saturating_sub(committed);
+    let total = winner_amount
+        .checked_add(dividend_amount)
+        .ok_or(FomoltError::Overflow)?;
+    if total <= remaining {
+        return Ok((winner_amount, dividend_amount, 0));
+    }
+    let overflow = total.checked_sub(remaining).ok_or(FomoltError::Overflow)?;
+    let dividend_cut = overflow.min(dividend_amount);
+    let winner_cut = overflow.checked_sub(dividend_cut).ok_or(FomoltError::Overflow)?;
+    let capped_dividend = dividend_amount
+        .checked_sub(dividend_cut)
+        .ok_or(FomoltError::Overflow)?;
+    let capped_winner = winner_amount
+        .checked_sub(winner_cut)
+        .ok_or(FomoltError::Overflow)?;
+    Ok((capped_winner, capped_dividend, overflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // ===== calculate_cost tests =====
+
+    #[test]
+    fn cost_first_key() {
+        let cost = calculate_cost(0, 1, 10_000_000, 1_000_000).unwrap();
+        // cost = 1 * 10M + 1M * 1 * (0 + 1 - 1) / 2 = 10M + 0 = 10M
+        assert_eq!(cost, 10_000_000);
+    }
+
+    #[test]
+    fn cost_second_key() {
+        let cost = calculate_cost(1, 1, 10_000_000, 1_000_000).unwrap();
+        // cost = 1 * 10M + 1M * 1 * (2 + 1 - 1) / 2 = 10M + 1M = 11M
+        assert_eq!(cost, 11_000_000);
+    }
+
+    #[test]
+    fn cost_batch_of_10_from_zero() {
+        let cost = calculate_cost(0, 10, 10_000_000, 1_000_000).unwrap();
+        // cost = 10 * 10M + 1M * 10 * (0 + 10 - 1) / 2
+        //      = 100M + 1M * 10 * 9 / 2 = 100M + 45M = 145M
+        assert_eq!(cost, 145_000_000);
+    }
+
+    #[test]
+    fn cost_batch_of_5_from_supply_100() {
+        let cost = calculate_cost(100, 5, 10_000_000, 1_000_000).unwrap();
+        // cost = 5 * 10M + 1M * 5 * (200 + 5 - 1) / 2
+        //      = 50M + 1M * 5 * 204 / 2 = 50M + 510M = 560M
+        assert_eq!(cost, 560_000_000);
+    }
+
+    #[test]
+    fn cost_at_high_supply() {
+        let cost = calculate_cost(1000, 1, 10_000_000, 1_000_000).unwrap();
+        // cost = 1 * 10M + 1M * 1 * (2000 + 1 - 1) / 2 = 10M + 1000M = 1.01B
+        assert_eq!(cost, 1_010_000_000);
+    }
+
+    #[test]
+    fn cost_no_overflow_at_100k_supply() {
+        let result = calculate_cost(100_000, 1, 10_000_000, 1_000_000);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn cost_custom_params() {
+        let cost = calculate_cost(0, 1, 5_000_000, 500_000).unwrap();
+        assert_eq!(cost, 5_000_000);
+    }
+
+    #[test]
+    fn cost_sum_matches_individual() {
+        // Buying 5 keys at once should cost the same as buying them one at a time
+        let batch_cost = calculate_cost(10, 5, 10_000_000, 1_000_000).unwrap();
+        let mut individual_total = 0u64;
+        for i in 0..5u64 {
+            individual_total += calculate_cost(10 + i, 1, 10_000_000, 1_000_000).unwrap();
+        }
+        assert_eq!(batch_cost, individual_total);
+    }
+
+    #[test]
+    fn cost_zero_keys_underflows() {
+        // n=0 causes (2k + 0 - 1) to underflow when k=0
+        let result = calculate_cost(0, 0, 10_000_000, 1_000_000);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cost_zero_base_price() {
+        let cost = calculate_cost(0, 1, 0, 1_000_000).unwrap();
+        // cost = 0 + 1M * 1 * (0) / 2 = 0
+        assert_eq!(cost, 0);
+    }
+
+    #[test]
+    fn cost_zero_increment() {
+        let cost = calculate_cost(100, 5, 10_000_000, 0).unwrap();
+        // cost = 5 * 10M + 0 = 50M — flat price regardless of supply
+        assert_eq!(cost, 50_000_000);
+    }
+
+    #[test]
+    fn cost_large_batch() {
+        // 1000 keys from supply 0
+        let cost = calculate_cost(0, 1000, 10_000_000, 1_000_000).unwrap();
+        // cost = 1000 * 10M + 1M * 1000 * 999 / 2 = 10B + 499.5B = ~509.5B
+        let expected = 10_000_000_000u64 + 499_500_000_000u64;
+        assert_eq!(cost, expected);
+    }
+
+    // ===== calculate_key_weight tests =====
+
+    #[test]
+    fn key_weight_disabled_threshold_matches_plain_keys() {
+        // threshold=0 means no bonus window — weight is just keys * 10_000
+        let weight = calculate_key_weight(50, 5, 0, 20_000).unwrap();
+        assert_eq!(weight, 5 * 10_000);
+    }
+
+    #[test]
+    fn key_weight_fully_inside_window() {
+        // All 5 keys bought fall before the threshold of 100
+        let weight = calculate_key_weight(0, 5, 100, 20_000).unwrap();
+        assert_eq!(weight, 5 * 20_000);
+    }
+
+    #[test]
+    fn key_weight_fully_outside_window() {
+        // Buying starts at supply 100, threshold is 100 — none qualify
+        let weight = calculate_key_weight(100, 5, 100, 20_000).unwrap();
+        assert_eq!(weight, 5 * 10_000);
+    }
+
+    #[test]
+    fn key_weight_straddles_window_boundary() {
+        // Threshold 100, starting at supply 98, buying 5: keys 98,99 are
+        // bonus (2 keys), keys 100,101,102 are standard (3 keys)
+        let weight = calculate_key_weight(98, 5, 100, 20_000).unwrap();
+        assert_eq!(weight, 2 * 20_000 + 3 * 10_000);
+    }
+
+    #[test]
+    fn key_weight_proportional_share_unchanged_when_disabled() {
+        // With the bonus disabled, weight-based share must equal the
+        // plain keys-based share exactly (not just approximately).
+        let pool = 1_000_000_000u64;
+        let total_keys = 77u64;
+        let player_keys = 13u64;
+
+        let share_by_keys = calculate_dividend_share(player_keys, pool, total_keys).unwrap();
+
+        let player_weight = calculate_key_weight(0, player_keys, 0, 10_000).unwrap();
+        let total_weight = calculate_key_weight(0, total_keys, 0, 10_000).unwrap();
+        let share_by_weight = calculate_dividend_share(player_weight, pool, total_weight).unwrap();
+
+        assert_eq!(share_by_keys, share_by_weight);
+    }
+
+    // ===== calculate_max_keys tests =====
+
+    #[test]
+    fn max_keys_zero_budget() {
+        assert_eq!(calculate_max_keys(0, 0, 10_000_000, 1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn max_keys_budget_below_first_key() {
+        // First key costs 10M; 9M can't afford any
+        assert_eq!(calculate_max_keys(9_000_000, 0, 10_000_000, 1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn max_keys_exact_budget() {
+        // Buying 1 key from supply 0 costs exactly 10M
+        assert_eq!(calculate_max_keys(10_000_000, 0, 10_000_000, 1_000_000).unwrap(), 1);
+    }
+
+    #[test]
+    fn max_keys_matches_batch_cost() {
+        // 145M buys exactly 10 keys from supply 0 (see cost_batch_of_10_from_zero)
+        let n = calculate_max_keys(145_000_000, 0, 10_000_000, 1_000_000).unwrap();
+        assert_eq!(n, 10);
+        // One more lamport of budget still isn't enough for an 11th key
+        let n2 = calculate_max_keys(145_000_000 + 1_000_000, 0, 10_000_000, 1_000_000).unwrap();
+        assert_eq!(n2, 10);
+    }
+
+    #[test]
+    fn max_keys_from_nonzero_supply() {
+        let n = calculate_max_keys(560_000_000, 100, 10_000_000, 1_000_000).unwrap();
+        assert_eq!(n, 5); // see cost_batch_of_5_from_supply_100
+    }
+
+    #[test]
+    fn max_keys_zero_increment_flat_price() {
+        // Flat 10M per key, budget of 55M buys exactly 5
+        let n = calculate_max_keys(55_000_000, 0, 10_000_000, 0).unwrap();
+        assert_eq!(n, 5);
+    }
+
+    #[test]
+    fn max_keys_huge_budget_does_not_panic() {
+        // Just needs to terminate and return something sane, not overflow-panic
+        let n = calculate_max_keys(u64::MAX, 0, 10_000_000, 1_000_000).unwrap();
+        assert!(n > 0);
+    }
+
+    proptest! {
+        #[test]
+        fn max_keys_affordable_and_tight(
+            budget in 0u64..1_000_000_000_000,
+            supply in 0u64..100_000,
+            base in 1u64..100_000_000,
+            inc in 0u64..1_000_000,
+        ) {
+            let n = calculate_max_keys(budget, supply, base, inc).unwrap();
+
+            // Spending on n keys must never exceed the budget.
+            let cost_n = if n == 0 { 0 } else { calculate_cost(supply, n, base, inc).unwrap() };
+            prop_assert!(cost_n <= budget);
+
+            // One more key must not have fit (tightness).
+            let cost_n_plus_1 = calculate_cost(supply, n + 1, base, inc).unwrap();
+            prop_assert!(cost_n_plus_1 > budget);
+        }
+    }
+
+    // ===== calculate_bps_split tests =====
+
+    #[test]
+    fn bps_split_standard_winner() {
+        // 48% of 1 SOL
+        let result = calculate_bps_split(1_000_000_000, 4800).unwrap();
+        assert_eq!(result, 480_000_000);
+    }
+
+    #[test]
+    fn bps_split_standard_dividend() {
+        // 45% of 1 SOL
+        let result = calculate_bps_split(1_000_000_000, 4500).unwrap();
+        assert_eq!(result, 450_000_000);
+    }
+
+    #[test]
+    fn bps_split_standard_next_round() {
+        // 7% of 1 SOL
+        let result = calculate_bps_split(1_000_000_000, 700).unwrap();
+        assert_eq!(result, 70_000_000);
+    }
+
+    #[test]
+    fn bps_splits_sum_equals_total() {
+        let cost = 1_000_000_000u64; // 1 SOL
+        let winner = calculate_bps_split(cost, 4800).unwrap();
+        let dividend = calculate_bps_split(cost, 4500).unwrap();
+        let next_round = calculate_bps_split(cost, 700).unwrap();
+        assert_eq!(winner + dividend + next_round, cost);
+    }
+
+    #[test]
+    fn bps_split_zero_amount() {
+        let result = calculate_bps_split(0, 4800).unwrap();
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn bps_split_zero_bps() {
+        let result = calculate_bps_split(1_000_000_000, 0).unwrap();
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn bps_split_full_10000() {
+        let result = calculate_bps_split(1_000_000_000, 10_000).unwrap();
+        assert_eq!(result, 1_000_000_000);
+    }
+
+    #[test]
+    fn bps_split_small_amount_precision() {
+        // 100 lamports * 4500 bps = 45 lamports (integer division)
+        let result = calculate_bps_split(100, 4500).unwrap();
+        assert_eq!(result, 45);
+    }
+
+    #[test]
+    fn bps_split_rounding() {
+        // 99 lamports * 4800 bps / 10000 = 47.52 → truncated to 47
+        let result = calculate_bps_split(99, 4800).unwrap();
+        assert_eq!(result, 47);
+    }
+
+    #[test]
+    fn bps_referral_bonus_from_after_fee() {
+        // Referral bonus is 10% (1000 bps) of after-fee amount
+        let cost = 1_000_000_000u64;
+        let house_fee = calculate_bps_split(cost, 200).unwrap(); // 2%
+        let after_fee = cost - house_fee; // 980M
+        let referral = calculate_bps_split(after_fee, 1000).unwrap();
+        assert_eq!(referral, 98_000_000); // 9.8% of gross cost
+    }
+
+    // ===== calculate_dividend_share tests =====
+
+    #[test]
+    fn dividend_share_single_holder() {
+        // Only holder gets entire pool
+        let share = calculate_dividend_share(10, 1_000_000_000, 10).unwrap();
+        assert_eq!(share, 1_000_000_000);
+    }
+
+    #[test]
+    fn dividend_share_equal_holders() {
+        // Two equal holders split evenly
+        let share = calculate_dividend_share(50, 1_000_000_000, 100).unwrap();
+        assert_eq!(share, 500_000_000);
+    }
+
+    #[test]
+    fn dividend_share_proportional() {
+        let pool = 1_000_000_000u64;
+        let total = 100u64;
+
+        let share_30 = calculate_dividend_share(30, pool, total).unwrap();
+        let share_70 = calculate_dividend_share(70, pool, total).unwrap();
+
+        assert_eq!(share_30, 300_000_000); // 30%
+        assert_eq!(share_70, 700_000_000); // 70%
+        assert_eq!(share_30 + share_70, pool); // conserved
+    }
+
+    #[test]
+    fn dividend_share_zero_keys() {
+        let share = calculate_dividend_share(0, 1_000_000_000, 100).unwrap();
+        assert_eq!(share, 0);
+    }
+
+    #[test]
+    fn dividend_share_zero_pool() {
+        let share = calculate_dividend_share(50, 0, 100).unwrap();
+        assert_eq!(share, 0);
+    }
+
+    #[test]
+    fn dividend_share_zero_total_keys() {
+        let share = calculate_dividend_share(50, 1_000_000_000, 0).unwrap();
+        assert_eq!(share, 0);
+    }
+
+    #[test]
+    fn dividend_share_rounding_dust() {
+        // 3 holders with 1 key each, pool = 100 lamports
+        // Each gets 33, total claimed = 99, dust = 1
+        let s1 = calculate_dividend_share(1, 100, 3).unwrap();
+        let s2 = calculate_dividend_share(1, 100, 3).unwrap();
+        let s3 = calculate_dividend_share(1, 100, 3).unwrap();
+        assert_eq!(s1, 33);
+        assert_eq!(s2, 33);
+        assert_eq!(s3, 33);
+        assert_eq!(s1 + s2 + s3, 99); // 1 lamport dust
+    }
+
+    #[test]
+    fn dividend_share_large_pool() {
+        // 100k keys, 1000 SOL pool
+        let pool = 1_000_000_000_000u64; // 1000 SOL
+        let share = calculate_dividend_share(1000, pool, 100_000).unwrap();
+        assert_eq!(share, 10_000_000_000); // 1% of 1000 SOL = 10 SOL
+    }
+
+    #[test]
+    fn dividend_share_all_keys() {
+        // Player holds all keys
+        let share = calculate_dividend_share(100, 500_000_000, 100).unwrap();
+        assert_eq!(share, 500_000_000);
+    }
+
+    // ===== calculate_dividend_share_weighted tests =====
+
+    #[test]
+    fn dividend_share_weighted_proportional() {
+        let pool = 1_000_000_000u64;
+        let total = 100u128;
+
+        let share_30 = calculate_dividend_share_weighted(30, pool, total).unwrap();
+        let share_70 = calculate_dividend_share_weighted(70, pool, total).unwrap();
+
+        assert_eq!(share_30, 300_000_000);
+        assert_eq!(share_70, 700_000_000);
+    }
+
+    #[test]
+    fn dividend_share_weighted_zero_player_seconds() {
+        let share = calculate_dividend_share_weighted(0, 1_000_000_000, 100).unwrap();
+        assert_eq!(share, 0);
+    }
+
+    #[test]
+    fn dividend_share_weighted_zero_total_seconds() {
+        // No one has held weight long enough to accrue any seconds yet.
+        let share = calculate_dividend_share_weighted(0, 1_000_000_000, 0).unwrap();
+        assert_eq!(share, 0);
+    }
+
+    #[test]
+    fn dividend_share_weighted_penalizes_late_entry() {
+        // Both buy the same weight, but p1 holds it for 10x as long —
+        // under weight-seconds p1 earns far more than an equal, late-buying p2.
+        let pool = 1_000_000_000u64;
+        let p1_seconds = 1_000u128; // weight 10, held 100s
+        let p2_seconds = 100u128; // weight 10, held 10s
+        let total_seconds = p1_seconds + p2_seconds;
+
+        let p1_share = calculate_dividend_share_weighted(p1_seconds, pool, total_seconds).unwrap();
+        let p2_share = calculate_dividend_share_weighted(p2_seconds, pool, total_seconds).unwrap();
+
+        assert!(p1_share > p2_share * 5);
+    }
+
+    // ===== calculate_timer_extension tests =====
+
+    #[test]
+    fn timer_extension_basic() {
+        let result = calculate_timer_extension(
+            1000, // current_time
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        // new_timer = 1030, max = 86400
+        // max(1030, 1020) = 1030, min(1030, 86400) = 1030
+        assert_eq!(result, 1030);
+    }
+
+    #[test]
+    fn timer_cannot_decrease() {
+        let result = calculate_timer_extension(
+            500,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1000,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        // new_timer = 530, but current is 1000
+        // max(530, 1000) = 1000 (timer doesn't decrease)
+        assert_eq!(result, 1000);
+    }
+
+    #[test]
+    fn timer_capped_at_max() {
+        let result = calculate_timer_extension(
+            86390,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 86400,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        // new_timer = 86420, max = 86400
+        // max(86420, 86400) = 86420, min(86420, 86400) = 86400
+        assert_eq!(result, 86400);
+    }
+
+    #[test]
+    fn timer_exactly_at_max() {
+        let result = calculate_timer_extension(
+            86370,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 86300,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 86400);
+    }
+
+    #[test]
+    fn timer_with_nonzero_round_start() {
+        let round_start = 1_000_000i64;
+        let result = calculate_timer_extension(
+            1_086_370,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1_086_300,
+                round_start,
+                max_timer_secs: 86_400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1_086_400);
+    }
+
+    #[test]
+    fn timer_sequential_purchases_monotonic() {
+        let round_start = 0i64;
+        let max_timer = 86_400i64;
+        let extension = 30i64;
+
+        let mut timer_end = max_timer;
+
+        for i in 0..100 {
+            let current_time = i * 100;
+            let new_end = calculate_timer_extension(
+                current_time,
+                TimerExtensionParams {
+                    extension_secs: extension,
+                    current_timer_end: timer_end,
+                    round_start,
+                    max_timer_secs: max_timer,
+                    final_hour_active: false,
+                    keys_since_final_hour_start: 0,
+                    final_hour_shrink_interval_keys: 0,
+                    min_remaining_secs: 0,
+                },
+            )
+            .unwrap();
+            assert!(new_end >= timer_end, "Timer decreased at purchase {}", i);
+            timer_end = new_end;
+        }
+    }
+
+    // ===== final-hour extension-shrink tests =====
+
+    #[test]
+    fn timer_final_hour_inactive_keeps_full_extension() {
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 1_000_000,
+                final_hour_shrink_interval_keys: 1,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        // Inactive: final_hour params are ignored entirely, even if they'd
+        // otherwise shrink the extension to near zero.
+        assert_eq!(result, 1030);
+    }
+
+    #[test]
+    fn timer_final_hour_active_no_keys_sold_yet_is_full_extension() {
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: true,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 10,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1030);
+    }
+
+    #[test]
+    fn timer_final_hour_active_halves_after_one_interval() {
+        // 10 keys sold since activation, interval 10 -> one halving: 30 -> 15.
+        // current_timer_end == current_time so the shrink is actually visible
+        // (not masked by the timer's own monotonic floor).
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1000,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: true,
+                keys_since_final_hour_start: 10,
+                final_hour_shrink_interval_keys: 10,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1015);
+    }
+
+    #[test]
+    fn timer_final_hour_active_halves_twice_after_two_intervals() {
+        // 20 keys sold since activation, interval 10 -> two halvings: 30 -> 7
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1000,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: true,
+                keys_since_final_hour_start: 20,
+                final_hour_shrink_interval_keys: 10,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1007);
+    }
+
+    #[test]
+    fn timer_final_hour_active_shrinks_to_zero_eventually() {
+        // Many intervals in -> extension shrinks to 0, timer stops moving
+        // forward (still can't decrease, so it holds at current_timer_end).
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: true,
+                keys_since_final_hour_start: 1000,
+                final_hour_shrink_interval_keys: 10,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1020);
+    }
+
+    #[test]
+    fn timer_final_hour_zero_shrink_interval_disables_shrink() {
+        // A shrink interval of 0 is the same "disabled" sentinel used
+        // elsewhere (e.g. early_bird_key_threshold) — full extension applies
+        // even with final_hour_active = true.
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: true,
+                keys_since_final_hour_start: 500,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1030);
+    }
+
+    // ===== min_remaining_secs floor tests =====
+
+    #[test]
+    fn timer_min_remaining_secs_floors_a_small_extension() {
+        // extension_secs=30 would only reach 1030, but a 300s floor wins.
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 300,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1300);
+    }
+
+    #[test]
+    fn timer_min_remaining_secs_is_a_no_op_when_extension_already_exceeds_it() {
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 300,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 30,
+            },
+        )
+        .unwrap();
+        // new_timer = 1300 already clears the 1030 floor.
+        assert_eq!(result, 1300);
+    }
+
+    #[test]
+    fn timer_min_remaining_secs_still_capped_at_max_timer() {
+        let result = calculate_timer_extension(
+            86_390,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 86_400,
+                round_start: 0,
+                max_timer_secs: 86_400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 300,
+            },
+        )
+        .unwrap();
+        // Floor would be 86_690, but the round's hard cap wins.
+        assert_eq!(result, 86_400);
+    }
+
+    #[test]
+    fn timer_min_remaining_secs_zero_disables_the_floor() {
+        // Same as timer_extension_basic with the floor explicitly disabled.
+        let result = calculate_timer_extension(
+            1000,
+            TimerExtensionParams {
+                extension_secs: 30,
+                current_timer_end: 1020,
+                round_start: 0,
+                max_timer_secs: 86400,
+                final_hour_active: false,
+                keys_since_final_hour_start: 0,
+                final_hour_shrink_interval_keys: 0,
+                min_remaining_secs: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result, 1030);
+    }
+
+    #[test]
+    fn timer_min_remaining_secs_sequential_purchases_monotonic() {
+        let round_start = 0i64;
+        let max_timer = 86_400i64;
+        let extension = 30i64;
+        let min_remaining_secs = 300i64;
+
+        let mut timer_end = max_timer;
+
+        for i in 0..100 {
+            let current_time = i * 100;
+            let new_end = calculate_timer_extension(
+                current_time,
+                TimerExtensionParams {
+                    extension_secs: extension,
+                    current_timer_end: timer_end,
+                    round_start,
+                    max_timer_secs: max_timer,
+                    final_hour_active: false,
+                    keys_since_final_hour_start: 0,
+                    final_hour_shrink_interval_keys: 0,
+                    min_remaining_secs,
+                },
+            )
+            .unwrap();
+            assert!(new_end >= timer_end, "Timer decreased at purchase {}", i);
+            assert!(
+                new_end >= (current_time + min_remaining_secs).min(round_start + max_timer),
+                "Timer didn't hold the min-remaining floor at purchase {}",
+                i
+            );
+            timer_end = new_end;
+        }
+    }
+
+    // ===== validate_bps_sum tests =====
+
+    #[test]
+    fn bps_sum_valid_default() {
+        // 4800 + 4500 + 700 = 10000
+        let result = validate_bps_sum(4800, 4500, 700);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn bps_sum_valid_equal_split() {
+        // Not exactly equal but sums to 10000
+        let result = validate_bps_sum(3334, 3333, 3333);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn bps_sum_invalid_under() {
+        let result = validate_bps_sum(4800, 4500, 600);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn bps_sum_invalid_over() {
+        let result = validate_bps_sum(5000, 4500, 700);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn bps_sum_all_winner() {
+        let result = validate_bps_sum(10_000, 0, 0);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn bps_sum_zero_all() {
+        let result = validate_bps_sum(0, 0, 0);
+        assert!(result.is_err());
+    }
+
+    // ===== Pot milestone tests =====
+
+    #[test]
+    fn milestones_crossed_disabled_when_interval_zero() {
+        assert_eq!(calculate_milestones_crossed(0, 1_000_000, 0), 0);
+    }
+
+    #[test]
+    fn milestones_crossed_none_within_same_interval() {
+        assert_eq!(calculate_milestones_crossed(80, 99, 100), 0);
+    }
+
+    #[test]
+    fn milestones_crossed_single() {
+        assert_eq!(calculate_milestones_crossed(80, 220, 100), 2);
+    }
+
+    #[test]
+    fn milestones_crossed_lands_exactly_on_boundary() {
+        assert_eq!(calculate_milestones_crossed(0, 100, 100), 1);
+    }
+
+    #[test]
+    fn milestones_crossed_large_jump_in_one_buy() {
+        assert_eq!(calculate_milestones_crossed(0, 1_050, 100), 10);
+    }
+
+    // ===== Pot cap tests =====
+
+    #[test]
+    fn pot_cap_disabled_when_zero() {
+        assert_eq!(apply_pot_cap(1_000, 0, 500, 300).unwrap(), (500, 300, 0));
+    }
+
+    #[test]
+    fn pot_cap_no_overflow_under_limit() {
+        assert_eq!(apply_pot_cap(1_000, 2_000, 500, 300).unwrap(), (500, 300, 0));
+    }
+
+    #[test]
+    fn pot_cap_lands_exactly_on_boundary() {
+        assert_eq!(apply_pot_cap(1_000, 1_800, 500, 300).unwrap(), (500, 300, 0));
+    }
+
+    #[test]
+    fn pot_cap_takes_from_dividend_first() {
+        // committed=1_000, cap=1_100 -> only 100 lamports of room left, but
+        // winner=500 + dividend=300 = 800 wants to land, so 700 must be cut.
+        // dividend (300) is fully absorbed first, then the remaining 400
+        // comes out of winner (500 -> 100).
+        assert_eq!(apply_pot_cap(1_000, 1_100, 500, 300).unwrap(), (100, 0, 700));
+    }
+
+    #[test]
+    fn pot_cap_spills_into_winner_once_dividend_exhausted() {
+        // committed=1_000, cap=1_050 -> 50 lamports of room left.
+        // dividend (300) is fully zeroed (absorbs 300), remaining 450
+        // overflow comes out of winner (500 -> 50).
+        assert_eq!(apply_pot_cap(1_000, 1_050, 500, 300).unwrap(), (50, 0, 750));
+    }
+
+    #[test]
+    fn pot_cap_already_exceeded_zeroes_both() {
+        assert_eq!(apply_pot_cap(2_000, 1_000, 500, 300).unwrap(), (0, 0, 800));
+    }
+
+    // ===== Economic invariant tests =====
+
+    #[test]
+    fn fee_ordering_conserves_funds() {
+        let cost = 1_000_000_000u64; // 1 SOL
+
+        // Step 1: House fee off the top
+        let house_fee = calculate_bps_split(cost, 200).unwrap(); // 2%
+        let after_fee = cost - house_fee;
+
+        // Step 2: Referral from remainder
+        let referral = calculate_bps_split(after_fee, 1000).unwrap(); // 10% of 98%
+        let pot_contribution = after_fee - referral;
+
+        // Step 3: Pot splits
+        let winner = calculate_bps_split(pot_contribution, 4800).unwrap();
+        let dividend = calculate_bps_split(pot_contribution, 4500).unwrap();
+        let next_round = calculate_bps_split(pot_contribution, 700).unwrap();
+
+        // All pieces should sum to original cost
+        let total = house_fee + referral + winner + dividend + next_round;
+        assert_eq!(total, cost);
+    }
+
+    #[test]
+    fn fee_ordering_no_referrer_conserves_funds() {
+        let cost = 1_000_000_000u64; // 1 SOL
+
+        let house_fee = calculate_bps_split(cost, 200).unwrap();
+        let pot_contribution = cost - house_fee; // full after_fee goes to pot
+
+        let winner = calculate_bps_split(pot_contribution, 4800).unwrap();
+        let dividend = calculate_bps_split(pot_contribution, 4500).unwrap();
+        let next_round = calculate_bps_split(pot_contribution, 700).unwrap();
+
+        let total = house_fee + winner + dividend + next_round;
+        assert_eq!(total, cost);
+    }
+
+    #[test]
+    fn fee_ordering_various_costs() {
+        let costs = [1u64, 100, 999, 10_000_000, 1_000_000_000, 10_000_000_000];
+        for cost in costs {
+            let house_fee = calculate_bps_split(cost, 200).unwrap();
+            let after_fee = cost - house_fee;
+            let referral = calculate_bps_split(after_fee, 1000).unwrap();
+            let pot = after_fee - referral;
+
+            let winner = calculate_bps_split(pot, 4800).unwrap();
+            let dividend = calculate_bps_split(pot, 4500).unwrap();
+            let next_round = calculate_bps_split(pot, 700).unwrap();
+
+            let accounted = house_fee + referral + winner + dividend + next_round;
+            // With rounding, accounted should be <= cost
+            assert!(
+                accounted <= cost,
+                "Overcount at cost {}: accounted {}",
+                cost,
+                accounted
+            );
+            // Rounding loss should be tiny (< 3 lamports from 3 division steps)
+            assert!(
+                cost - accounted <= 3,
+                "Too much dust at cost {}: lost {}",
+                cost,
+                cost - accounted
+            );
+        }
+    }
+
+    #[test]
+    fn dividend_distribution_fair_share() {
+        // With N equal key holders, each gets 1/N of dividends
+        let total_keys = 5u64;
+        let dividend_pool = 1_000_000_000u64; // 1 SOL
+
+        let per_holder = calculate_dividend_share(1, dividend_pool, total_keys).unwrap();
+        assert_eq!(per_holder, 200_000_000);
+
+        let total_claimed = calculate_dividend_share(total_keys, dividend_pool, total_keys).unwrap();
+        assert_eq!(total_claimed, dividend_pool);
+    }
+
+    #[test]
+    fn cost_increases_with_supply() {
+        let base_price = 10_000_000u64;
+        let increment = 1_000_000u64;
+
+        let cost_at_0 = calculate_cost(0, 1, base_price, increment).unwrap();
+        let cost_at_100 = calculate_cost(100, 1, base_price, increment).unwrap();
+        let cost_at_1000 = calculate_cost(1000, 1, base_price, increment).unwrap();
+
+        assert!(cost_at_0 < cost_at_100);
+        assert!(cost_at_100 < cost_at_1000);
+    }
+}
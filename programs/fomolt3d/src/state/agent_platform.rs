@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+/// Tracks a registered agent marketplace's accrued share of the protocol
+/// fees paid by agents it routed — scoped by `game_id` rather than any one
+/// round, same as `KeeperState`, since a platform's earnings accrue across
+/// `start_new_round` transitions. An agent attaches itself to a platform via
+/// `instructions::register_agent_platform`, which requires the platform's
+/// own signature (this codebase's usual "signed allowlist" idiom — see
+/// `GlobalConfig::approved_stake_vote_account` for the same single-pubkey-
+/// co-signer pattern elsewhere), then every subsequent `buy_keys` from that
+/// agent diverts `GameState::agent_platform_fee_share_bps` of its house fee
+/// here instead of `protocol_wallet`. See
+/// `instructions::claim_agent_platform_earnings` for the payout side.
+#[account]
+pub struct AgentPlatform {
+    /// Which game lineage this registration belongs to
+    pub game_id: u64,
+    /// The platform's own wallet address — signs `register_agent_platform`
+    /// for every agent it takes on, and is the only signer
+    /// `claim_agent_platform_earnings` accepts.
+    pub platform: Pubkey,
+    /// Accrued fee share not yet claimed
+    pub pending_earnings_lamports: u64,
+    /// Total fee share already claimed, all-time
+    pub claimed_earnings_lamports: u64,
+    /// Number of agents currently attributed to this platform. Purely
+    /// informational — nothing on-chain depends on it.
+    pub agent_count: u32,
+    /// When this platform was first registered (its first agent's
+    /// `register_agent_platform` call)
+    pub registered_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AgentPlatform {
+    // game_id(8) + platform(32) + pending_earnings_lamports(8)
+    // + claimed_earnings_lamports(8) + agent_count(4) + registered_at(8)
+    // + bump(1) = 69
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 4 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_platform_space() {
+        assert_eq!(AgentPlatform::SPACE, 69);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn agent_platform_space_matches_serialized_size() {
+        let platform = AgentPlatform {
+            game_id: 0,
+            platform: Pubkey::default(),
+            pending_earnings_lamports: 0,
+            claimed_earnings_lamports: 0,
+            agent_count: 0,
+            registered_at: 0,
+            bump: 0,
+        };
+        assert_eq!(platform.try_to_vec().unwrap().len(), AgentPlatform::SPACE);
+    }
+}
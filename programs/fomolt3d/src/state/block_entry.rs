@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+/// Marks a wallet as blocked from participating in a game. Presence of this
+/// PDA (seeds `[b"blocked", game_id, wallet]`) is itself the block. Callers
+/// pin the address with a `seeds`/`bump` constraint on a required (never
+/// `Option`) account and go through `BlockEntry::load` to find out whether
+/// it's actually initialized — unlike an `Option<Account<..>>`, a required
+/// account can't be skipped by a caller who'd rather the check not run.
+#[account]
+pub struct BlockEntry {
+    /// Which game lineage this block applies to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// The blocked wallet
+    pub wallet: Pubkey,
+    /// Unix timestamp the block was applied
+    pub blocked_at: i64,
+    /// Policy: if true, the wallet can still claim dividends/winnings already
+    /// owed to it from before the block — it just can't buy new keys. If
+    /// false, the block is total: no buys, no claims.
+    pub allow_claim: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BlockEntry {
+    // game_id(8) + wallet(32) + blocked_at(8) + allow_claim(1) + bump(1) = 50
+    pub const SPACE: usize = 8 + 32 + 8 + 1 + 1;
+
+    /// Loads the block status for a `[b"blocked", game_id, wallet]` PDA
+    /// pinned by the caller's `seeds` constraint. The PDA's address is valid
+    /// regardless of whether `add_to_blocklist` ever initialized it, so
+    /// ownership by this program (vs. the System Program's default
+    /// ownership of an empty account) is what actually signals "blocked" —
+    /// returns `None` for an uninitialized account rather than erroring.
+    pub fn load(account_info: &AccountInfo) -> Result<Option<Self>> {
+        if account_info.owner != &crate::ID {
+            return Ok(None);
+        }
+        let data = account_info.try_borrow_data()?;
+        Ok(Some(BlockEntry::try_deserialize(&mut data.as_ref())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_entry_space() {
+        assert_eq!(BlockEntry::SPACE, 50);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn block_entry_space_matches_serialized_size() {
+        let entry = BlockEntry {
+            game_id: 0,
+            wallet: Pubkey::default(),
+            blocked_at: 0,
+            allow_claim: false,
+            bump: 0,
+        };
+        assert_eq!(entry.try_to_vec().unwrap().len(), BlockEntry::SPACE);
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// A locked-in, not-yet-revealed key purchase. `commit_buy` escrows
+/// `budget_lamports` into a dedicated `commit_vault` PDA and records a hash
+/// of the real purchase (`keys_to_buy`, a `salt`, and the buyer) along with
+/// the round's `total_keys` at commit time. `reveal_buy` can only execute in
+/// a later slot, and always prices the purchase off `total_keys_at_commit`
+/// rather than the live curve position — so neither the buyer nor anyone
+/// watching the mempool can react to the reveal before it lands.
+#[account]
+pub struct BuyCommitment {
+    /// Game lineage this commitment belongs to
+    pub game_id: u64,
+    /// Round this commitment belongs to — reveal_buy requires the round to still match
+    pub round: u64,
+    /// The wallet that committed and must sign the matching reveal
+    pub buyer: Pubkey,
+    /// Hash of (keys_to_buy, salt, buyer) — checked against reveal_buy's arguments
+    pub commitment_hash: [u8; 32],
+    /// GameState.total_keys as of commit_buy — reveal_buy prices off this, not the live value
+    pub total_keys_at_commit: u64,
+    /// Lamports escrowed in commit_vault; reveal_buy's cost may not exceed this
+    pub budget_lamports: u64,
+    /// Slot commit_buy landed in — reveal_buy requires the current slot to be later
+    pub commit_slot: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BuyCommitment {
+    // game_id(8) + round(8) + buyer(32) + commitment_hash(32)
+    // + total_keys_at_commit(8) + budget_lamports(8) + commit_slot(8) + bump(1) = 105
+    pub const SPACE: usize = 8 + 8 + 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buy_commitment_space() {
+        assert_eq!(BuyCommitment::SPACE, 105);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn buy_commitment_space_matches_serialized_size() {
+        let commitment = BuyCommitment {
+            game_id: 0,
+            round: 0,
+            buyer: Pubkey::default(),
+            commitment_hash: [0u8; 32],
+            total_keys_at_commit: 0,
+            budget_lamports: 0,
+            commit_slot: 0,
+            bump: 0,
+        };
+        assert_eq!(
+            commitment.try_to_vec().unwrap().len(),
+            BuyCommitment::SPACE
+        );
+    }
+}
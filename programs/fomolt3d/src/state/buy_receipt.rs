@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+/// Opt-in, per-purchase proof of a single `buy_keys` call, created only when
+/// the caller supplies the account — see `BuyKeys::receipt`. Unlike
+/// `PlayerHistory` (a shared ring buffer that overwrites old entries),
+/// `receipt` is a fresh PDA per purchase that survives indefinitely, letting
+/// an agent or accounting system retrieve the exact fee/split breakdown of
+/// one transaction on-chain long after `GameState` has moved past that
+/// round. PDA seeds `[b"receipt", game_state, player, nonce]` — `nonce` is
+/// `GameState::purchase_count` as of this buy, which the caller can read
+/// off-chain before submitting the same way `HolderIndex::page` is derived
+/// from `GameState::total_players`.
+#[account]
+pub struct BuyReceipt {
+    /// Game lineage this purchase belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// Round this purchase landed in
+    pub round: u64,
+    /// The wallet that bought
+    pub player: Pubkey,
+    /// `GameState::purchase_count` at the time of this buy — the seed nonce
+    pub nonce: u64,
+    /// Keys bought in this single purchase
+    pub keys_bought: u64,
+    /// Total lamports charged for this purchase
+    pub cost_lamports: u64,
+    /// House fee taken from `cost_lamports`
+    pub house_fee_lamports: u64,
+    /// Frontend fee taken from `cost_lamports`, if a `frontend_wallet` was supplied
+    pub frontend_fee_lamports: u64,
+    /// Referral bonus taken from `cost_lamports`, if a referrer was credited
+    pub referral_bonus_lamports: u64,
+    /// Remainder that reached the pot split (winner/dividend/next-round)
+    pub pot_contribution_lamports: u64,
+    /// Unix timestamp this purchase was recorded at
+    pub timestamp: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BuyReceipt {
+    // game_id(8) + round(8) + player(32) + nonce(8) + keys_bought(8)
+    // + cost_lamports(8) + house_fee_lamports(8) + frontend_fee_lamports(8)
+    // + referral_bonus_lamports(8) + pot_contribution_lamports(8) + timestamp(8) + bump(1) = 113
+    pub const SPACE: usize = 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buy_receipt_space() {
+        assert_eq!(BuyReceipt::SPACE, 113);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn buy_receipt_space_matches_serialized_size() {
+        let receipt = BuyReceipt {
+            game_id: 0,
+            round: 0,
+            player: Pubkey::default(),
+            nonce: 0,
+            keys_bought: 0,
+            cost_lamports: 0,
+            house_fee_lamports: 0,
+            frontend_fee_lamports: 0,
+            referral_bonus_lamports: 0,
+            pot_contribution_lamports: 0,
+            timestamp: 0,
+            bump: 0,
+        };
+        assert_eq!(receipt.try_to_vec().unwrap().len(), BuyReceipt::SPACE);
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+/// A small, read-mostly mirror of the handful of `GameState` fields that
+/// change on every buy — pot size, key count, timer, last buyer, and next
+/// key price. Rewritten in full on every `buy_keys`/`buy_keys_via_session`
+/// call and re-created at the start of every round, so a Geyser plugin or
+/// websocket indexer can subscribe to this one ~90-byte account instead of
+/// decoding the full `GameState` (see `GameState::SPACE`) on every update.
+#[account]
+pub struct GameSnapshot {
+    /// Which game lineage this snapshot belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// Round number this snapshot mirrors
+    pub round: u64,
+    /// Mirrors `GameState::pot_lamports`
+    pub pot_lamports: u64,
+    /// Mirrors `GameState::total_keys`
+    pub total_keys: u64,
+    /// Mirrors `GameState::timer_end`
+    pub timer_end: i64,
+    /// Mirrors `GameState::last_buyer`
+    pub last_buyer: Pubkey,
+    /// Cost of the next single key on the bonding curve, i.e.
+    /// `calculate_cost(total_keys, 1, base_price, price_increment)`
+    pub next_key_price: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl GameSnapshot {
+    // game_id(8) + round(8) + pot_lamports(8) + total_keys(8) + timer_end(8)
+    // + last_buyer(32) + next_key_price(8) + bump(1) = 81
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 8 + 32 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_snapshot_space() {
+        assert_eq!(GameSnapshot::SPACE, 81);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn game_snapshot_space_matches_serialized_size() {
+        let snapshot = GameSnapshot {
+            game_id: 0,
+            round: 0,
+            pot_lamports: 0,
+            total_keys: 0,
+            timer_end: 0,
+            last_buyer: Pubkey::default(),
+            next_key_price: 0,
+            bump: 0,
+        };
+        assert_eq!(snapshot.try_to_vec().unwrap().len(), GameSnapshot::SPACE);
+    }
+}
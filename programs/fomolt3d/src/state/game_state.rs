@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::FomoltError;
+use crate::state::round_status::RoundStatus;
+use crate::state::rounding_beneficiary::RoundingBeneficiary;
+use crate::state::unclaimed_dividend_policy::UnclaimedDividendPolicy;
+
 #[account]
 pub struct GameState {
+    /// Snapshot: which game lineage this round belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
     /// Round number (0-indexed)
     pub round: u64,
     /// Total SOL deposited this round (lamports) — informational, tracks gross spend
@@ -14,10 +21,10 @@ pub struct GameState {
     pub total_keys: u64,
     /// Unix timestamp of round start
     pub round_start: i64,
-    /// Whether round is active
-    pub active: bool,
-    /// Whether winner has claimed prize
-    pub winner_claimed: bool,
+    /// Explicit round lifecycle state — see `RoundStatus`. Replaces the old
+    /// independent `active`/`winner_claimed` booleans with guarded
+    /// transitions via `transition_status`.
+    pub status: RoundStatus,
     /// Number of unique players in this round
     pub total_players: u32,
     /// Total lamports allocated to dividends this round (claimed proportionally at round end)
@@ -49,17 +56,613 @@ pub struct GameState {
     pub protocol_wallet: Pubkey,
     /// PDA bump seed
     pub bump: u8,
+    /// Outstanding (unclaimed) referral earnings owed against this round's vault
+    pub total_referral_obligations: u64,
+    /// Sum of every player's `dividend_weight` this round — the denominator
+    /// for weight-based dividend shares (see `state::player_state::PlayerState`)
+    pub total_weight: u64,
+    /// Snapshot: early-bird bonus window (see `GlobalConfig`)
+    pub early_bird_key_threshold: u64,
+    /// Snapshot: early-bird bonus multiplier in bps
+    pub early_bird_multiplier_bps: u64,
+    /// Snapshot: minimum buy_keys cost in lamports (see `GlobalConfig`)
+    pub min_purchase_lamports: u64,
+    /// Snapshot: winner claim window in seconds (see `GlobalConfig`)
+    pub winner_claim_window_secs: i64,
+    /// Snapshot: final-hour pot threshold in lamports (see `GlobalConfig`)
+    pub final_hour_pot_threshold_lamports: u64,
+    /// Snapshot: final-hour shrink interval in keys (see `GlobalConfig`)
+    pub final_hour_shrink_interval_keys: u64,
+    /// Whether `pot_lamports` has crossed `final_hour_pot_threshold_lamports`
+    /// this round. Sticky once set — never clears mid-round.
+    pub final_hour_active: bool,
+    /// `total_keys` at the moment final-hour mode activated — the timer
+    /// extension shrink is computed against keys sold since this point,
+    /// not since round start.
+    pub final_hour_start_keys: u64,
+    /// Snapshot: pot milestone interval in lamports (see `GlobalConfig`)
+    pub pot_milestone_interval_lamports: u64,
+    /// Snapshot: pot milestone bonus keys (see `GlobalConfig`)
+    pub pot_milestone_bonus_keys: u64,
+    /// Cumulative lamports ever deposited into this round's vault — buy
+    /// proceeds, carry-over forwarded in from the previous round, and any
+    /// rent-gap top-up. Lets an off-chain monitor diff
+    /// `vault_lamports_in - vault_lamports_out` against the vault's live
+    /// balance to catch a leak without replaying instruction history.
+    pub vault_lamports_in: u64,
+    /// Cumulative lamports ever withdrawn from this round's vault — claims,
+    /// referral payouts, and carry-over forwarded out to the next round.
+    pub vault_lamports_out: u64,
+    /// Snapshot: per-round cap on keys grantable via `grant_promo_keys` (see
+    /// `GlobalConfig::promo_keys_cap_per_round`). 0 disables promo grants.
+    pub promo_keys_cap_per_round: u64,
+    /// Running count of keys granted via `grant_promo_keys` this round,
+    /// checked against `promo_keys_cap_per_round`. Resets to 0 every round.
+    pub promo_keys_granted_this_round: u64,
+    /// Snapshot: whether `transfer_keys` is allowed this round (see
+    /// `GlobalConfig::transfers_enabled`).
+    pub transfers_enabled: bool,
+    /// Snapshot: whether wrapping keys into this round's SPL mint is
+    /// allowed (see `GlobalConfig::wrapped_keys_enabled`). Unwrapping is
+    /// always allowed regardless of this flag.
+    pub wrapped_keys_enabled: bool,
+    /// Running total of keys currently represented by outstanding wrapped
+    /// SPL tokens rather than an individual `PlayerState`. Resets to 0 every
+    /// round. Always `<= total_keys`; the gap is exactly the unwrapped,
+    /// individually-attributed key count.
+    pub wrapped_keys_total: u64,
+    /// Dividend weight backing `wrapped_keys_total`, carved out of the
+    /// wrapping player's `dividend_weight` and restored (pro-rata) on
+    /// unwrap. Part of `total_weight`'s denominator throughout — wrapping
+    /// only moves weight between a `PlayerState` and this pool, it never
+    /// enters or leaves `total_weight` itself.
+    pub wrapped_weight_total: u64,
+    /// Snapshot: flat lamport fee paid to whoever cranks `end_round` (see
+    /// `GlobalConfig::keeper_fee_lamports`).
+    pub keeper_fee_lamports: u64,
+    /// Snapshot: whether `init_player_history`/the ring-buffer write in
+    /// `buy_keys` are allowed this round (see
+    /// `GlobalConfig::purchase_history_enabled`).
+    pub purchase_history_enabled: bool,
+    /// Count of `buy_keys` calls this round that bought at least one key.
+    /// Resets to 0 every round.
+    pub purchase_count: u64,
+    /// Cumulative lamport cost of every purchase counted in
+    /// `purchase_count`. Unlike `pot_lamports`, this is never seeded with
+    /// the previous round's carry-over — it's buy volume only.
+    pub gross_volume_lamports: u64,
+    /// Largest single `buy_keys` lamport cost seen this round.
+    pub max_single_buy_lamports: u64,
+    /// Wallet that made `max_single_buy_lamports`.
+    pub max_single_buyer: Pubkey,
+    /// Snapshot: whether dividends are split by weight-seconds instead of
+    /// point-in-time weight this round (see
+    /// `GlobalConfig::time_weighted_dividends_enabled`).
+    pub time_weighted_dividends_enabled: bool,
+    /// Lazily-accrued sum of `total_weight * seconds_held` since round
+    /// start — the denominator `claim` uses for a time-weighted dividend
+    /// split. Kept current by `sync_dividend_seconds`, called before any
+    /// instruction changes `total_weight`. Resets to 0 every round.
+    pub dividend_weight_seconds_total: u128,
+    /// Unix timestamp `dividend_weight_seconds_total` was last synced up
+    /// to. Initialized to `round_start`.
+    pub dividend_seconds_last_update: i64,
+    /// Snapshot of `GlobalConfig::hook_program`. `Pubkey::default()` means no
+    /// partner hook is CPI-notified on purchase this round.
+    pub hook_program: Pubkey,
+    /// Snapshot: per-round cap (lamports) on referral earnings per referrer
+    /// — see `GlobalConfig::referral_earnings_cap_lamports_per_round`.
+    pub referral_earnings_cap_lamports_per_round: u64,
+    /// Snapshot: referral bonus decay threshold (lamports) — see
+    /// `GlobalConfig::referral_decay_threshold_lamports`.
+    pub referral_decay_threshold_lamports: u64,
+    /// Snapshot: minimum seconds between referrer changes — see
+    /// `GlobalConfig::referrer_change_cooldown_secs`.
+    pub referrer_change_cooldown_secs: i64,
+    /// Root of a Merkle tree over (player, dividend_amount) leaves, set by
+    /// `record_dividend_merkle_root` once the round has ended. `None` means
+    /// no root has been recorded — `claim_with_proof` is only accepted once
+    /// this is set, and it never affects the existing direct `claim` path.
+    pub dividend_merkle_root: Option<[u8; 32]>,
+    /// Snapshot: whether `buy_keys` requires a `KycCredential` this round —
+    /// see `GlobalConfig::kyc_required`.
+    pub kyc_required: bool,
+    /// Snapshot: authority allowed to call `issue_kyc_credential` for this
+    /// round — see `GlobalConfig::kyc_issuer`.
+    pub kyc_issuer: Pubkey,
+    /// Truncation remainder left over from `buy_keys`'s three-way pot split
+    /// (winner/dividend/next_round bps splits don't always sum exactly back
+    /// to `pot_contribution`). Accumulates here instead of being folded into
+    /// `next_round_pot`, so the vault's accounting is exact rather than
+    /// "bounded by a few lamports" — withdrawable only via
+    /// `instructions::sweep_dust_reserve`.
+    pub dust_reserve: u64,
+    /// Lazily-accrued sum of `key_price * seconds_held` since
+    /// `price_last_update` — the numerator of an on-chain TWAP of key price
+    /// (`price_cumulative / (now - round_start)`). Kept current by
+    /// `sync_price_cumulative`, called before every `buy_keys` purchase
+    /// changes `total_keys`, so the accrual always uses the marginal price
+    /// that was actually in effect over the elapsed interval. Resets to 0
+    /// every round.
+    pub price_cumulative: u128,
+    /// Unix timestamp `price_cumulative` was last synced up to. Initialized
+    /// to `round_start`.
+    pub price_last_update: i64,
+    /// Snapshot: what happens to this round's dividend pool if some of it is
+    /// still unclaimed once `dividend_claim_window_secs` has elapsed — see
+    /// `instructions::sweep_unclaimed_dividends` and `GlobalConfig`.
+    pub unclaimed_dividend_policy: UnclaimedDividendPolicy,
+    /// Snapshot: seconds after `timer_end` before
+    /// `instructions::sweep_unclaimed_dividends` may crank this round's
+    /// `unclaimed_dividend_policy` (see `GlobalConfig`).
+    pub dividend_claim_window_secs: i64,
+    /// Running total of dividends actually paid out so far via `claim`,
+    /// `claim_and_roll`, and `claim_with_proof`. Unlike `total_dividend_pool`
+    /// (which stays constant across claims — see `instructions::claim`),
+    /// this is the one field that does shrink the gap, letting
+    /// `sweep_unclaimed_dividends` compute `total_dividend_pool -
+    /// total_dividend_claimed_lamports` as the genuinely unclaimed remainder.
+    pub total_dividend_claimed_lamports: u64,
+    /// Snapshot: caps how many of a single wallet's buys may extend
+    /// `timer_end` within any `timer_extension_window_secs`-long rolling
+    /// window — see `PlayerState::timer_extensions_in_window` and
+    /// `instructions::buy_keys`. 0 disables the cap (unlimited extensions,
+    /// the pre-existing behavior).
+    pub max_timer_extensions_per_window: u32,
+    /// Snapshot: length of the rolling window
+    /// `max_timer_extensions_per_window` counts over. Meaningless while the
+    /// cap above is 0.
+    pub timer_extension_window_secs: i64,
+    /// Snapshot: basis points of `winner_pot` `end_round` carves out into
+    /// `top_referrer_bonus_pool` — see `GlobalConfig::top_referrer_bonus_bps`.
+    pub top_referrer_bonus_bps: u64,
+    /// Lamports owed to this round's leading referrer (see
+    /// `GameStateExt::top_referrers`), carved out of `winner_pot` by
+    /// `end_round` and claimable via
+    /// `instructions::claim_top_referrer_bonus`. Moving lamports here out of
+    /// `winner_pot` doesn't change `pending_obligations()`'s total, only
+    /// which bucket owes them. 0 once claimed (or if never allocated).
+    pub top_referrer_bonus_pool: u64,
+    /// Snapshot: basis points of each purchase's `pot_contribution` carved
+    /// into `raffle_pool_lamports` — see `GlobalConfig::raffle_bps`.
+    pub raffle_bps: u64,
+    /// Snapshot: basis points of `raffle_pool_lamports` moved into a day's
+    /// prize on `record_raffle_snapshot` — see
+    /// `GlobalConfig::raffle_daily_payout_bps`.
+    pub raffle_daily_payout_bps: u64,
+    /// Accumulated raffle funding not yet carved into a day's prize.
+    /// Incremented by `buy_keys`/`buy_keys_via_session`/`reveal_buy` out of
+    /// `pot_contribution`, decremented by `instructions::record_raffle_snapshot`
+    /// as it moves a day's cut into that day's `RaffleSnapshot::prize_lamports`
+    /// (tracked in `raffle_prize_pool_pending` instead, since a snapshot's
+    /// own prize amount lives on a separate account `pending_obligations()`
+    /// can't see). Moving lamports between these two fields never changes
+    /// `pending_obligations()`'s total, only which bucket owes them.
+    pub raffle_pool_lamports: u64,
+    /// Sum of `RaffleSnapshot::prize_lamports` across every day this round
+    /// whose prize hasn't been claimed yet. Kept in lockstep with the
+    /// individual snapshots' `prize_lamports` fields purely so
+    /// `pending_obligations()` has something on `GameState` itself to sum —
+    /// it never reads sibling `RaffleSnapshot` accounts.
+    pub raffle_prize_pool_pending: u64,
+    /// Set by `instructions::cancel_round` when an admin freezes a
+    /// misconfigured `Active` round: the sum of `winner_pot`,
+    /// `total_dividend_pool`, `next_round_pot`, `raffle_pool_lamports`, and
+    /// `dust_reserve` at cancellation time, with all five zeroed out.
+    /// Draining those buckets into this single one instead of leaving them
+    /// in place keeps `pending_obligations()`'s total unchanged (it's the
+    /// same lamports, just relabeled) while making it unambiguous which
+    /// bucket `instructions::refund` pays out of. 0 while the round hasn't
+    /// been cancelled.
+    pub refund_pool_lamports: u64,
+    /// Snapshot of `GlobalConfig::bridge_program`. `Pubkey::default()` means
+    /// no cross-chain attestation is configured — see `instructions::end_round`.
+    pub bridge_program: Pubkey,
+    /// Snapshot of `GlobalConfig::max_pot_lamports` taken at
+    /// `initialize_first_round`/`start_new_round` time, same as
+    /// `bridge_program`/`hook_program`, so a mid-round config change can't
+    /// retroactively cap a round that already promised players more. 0
+    /// disables the cap.
+    pub max_pot_lamports: u64,
+    /// Winner/dividend contributions diverted here by `buy_keys` once
+    /// `max_pot_lamports` would otherwise be exceeded — see
+    /// `math::apply_pot_cap`. Funded out of players' `pot_contribution`, same
+    /// as `next_round_pot`, so `start_new_round` folds it into the next
+    /// round's carry-over the same way. 0 while `max_pot_lamports` is
+    /// disabled or hasn't been hit yet.
+    pub pot_overflow_reserve_lamports: u64,
+    // --- Round-duration analytics, accumulated live by `buy_keys` and
+    // surfaced on `events::RoundConcluded` for an off-chain indexer's round
+    // archive. `round_duration_secs` (timer_end - round_start) and
+    // `average_seconds_between_buys` aren't stored — they're always fully
+    // derivable from fields already here, same reasoning as
+    // `pending_obligations`. Resets to 0/false every round. ---
+    /// Count of successful timer extensions this round (buys that moved
+    /// `timer_end` forward) — see `instructions::buy_keys`. Buys skipped by
+    /// `max_timer_extensions_per_window` don't count.
+    pub timer_extensions_triggered: u64,
+    /// Unix timestamp of the most recent purchase, the watermark
+    /// `buy_interval_seconds_total` accrues against. Initialized to
+    /// `round_start`.
+    pub last_buy_timestamp: i64,
+    /// Sum of the seconds between each purchase and the one before it,
+    /// accrued the same way `price_cumulative` accrues elapsed price. Divide
+    /// by `purchase_count` for the round's average time between buys.
+    pub buy_interval_seconds_total: i64,
+    /// `pot_lamports` the first time elapsed time since `round_start` reaches
+    /// 25% of `max_timer_secs`. 0 if never reached.
+    pub pot_checkpoint_25_lamports: u64,
+    /// See `pot_checkpoint_25_lamports` — same at the 50% mark.
+    pub pot_checkpoint_50_lamports: u64,
+    /// See `pot_checkpoint_25_lamports` — same at the 75% mark.
+    pub pot_checkpoint_75_lamports: u64,
+    /// Whether `pot_checkpoint_25_lamports` has been recorded yet — needed
+    /// because a legitimately-reached checkpoint can itself be 0.
+    pub pot_checkpoint_25_reached: bool,
+    /// See `pot_checkpoint_25_reached` — same at the 50% mark.
+    pub pot_checkpoint_50_reached: bool,
+    /// See `pot_checkpoint_25_reached` — same at the 75% mark.
+    pub pot_checkpoint_75_reached: bool,
+    /// Snapshot of `GlobalConfig::auto_payout_winner_enabled`. When true,
+    /// `end_round` pushes `winner_pot` straight to `last_buyer` itself; when
+    /// false (the default), the winner must call `claim` as before.
+    pub auto_payout_winner_enabled: bool,
+    /// Snapshot of `GlobalConfig::min_keys_for_timer_extension`. A buy for
+    /// fewer keys than this still gets keys and dividends as normal, it just
+    /// doesn't push out `timer_end` — see `instructions::buy_keys`. 0
+    /// disables the floor entirely (the default).
+    pub min_keys_for_timer_extension: u64,
+    /// Snapshot of `GlobalConfig::price_sample_interval_slots`. Gates how
+    /// often `buy_keys` (and the permissionless `record_sample` crank)
+    /// append a sample to this round's `PriceHistory` ring buffer. 0
+    /// disables sampling entirely (the default).
+    pub price_sample_interval_slots: u64,
+    /// Snapshot of `GlobalConfig::rounding_beneficiary`. Chooses where a
+    /// buy's leftover bps-split dust lands — see `instructions::buy_keys`.
+    /// Defaults to `Protocol`, matching the pre-existing `dust_reserve`
+    /// behavior of every round before this setting existed.
+    pub rounding_beneficiary: RoundingBeneficiary,
+    /// Snapshot of `GlobalConfig::season_length_rounds`. Number of
+    /// consecutive rounds (starting at round 1) that make up one `Season` —
+    /// see `current_season_id`. 0 disables the season meta-game entirely
+    /// (the default).
+    pub season_length_rounds: u64,
+    /// Snapshot of `GlobalConfig::season_fee_bps`. Slice of `protocol_fee_bps`
+    /// diverted into the active `Season`'s prize pool instead of
+    /// `protocol_wallet` on each buy — see `instructions::buy_keys`. 0
+    /// disables pool funding (the default).
+    pub season_fee_bps: u64,
+    /// Total keys bought this round by players with `is_agent = true` —
+    /// lets off-chain analysis split AI-agent behavior from human play
+    /// without heuristics. See `PlayerState::is_agent` and
+    /// `events::AgentAction`.
+    pub agent_keys_total: u64,
+    /// See `agent_keys_total` — same tally for `is_agent = false` buyers.
+    pub human_keys_total: u64,
+    /// Snapshot of `GlobalConfig::max_keys_per_round`. Once `total_keys`
+    /// reaches this, `buy_keys` ends the round immediately regardless of
+    /// `timer_end` — see `instructions::buy_keys`. 0 disables the cap
+    /// entirely (the default — unlimited supply).
+    pub max_keys_per_round: u64,
+    /// Snapshot of `GlobalConfig::referral_vesting_enabled` — see that field.
+    pub referral_vesting_enabled: bool,
+    /// Snapshot of `GlobalConfig::biggest_buyer_bonus_bps` — see that field.
+    pub biggest_buyer_bonus_bps: u64,
+    /// Lamports owed to whoever made `max_single_buy_lamports` this round,
+    /// carved out of `winner_pot` by `end_round` and claimable via
+    /// `instructions::claim_biggest_buyer_bonus`, same pattern as
+    /// `top_referrer_bonus_pool`. 0 once claimed (or if never allocated).
+    pub biggest_buyer_bonus_pool: u64,
+    /// Snapshot of `GlobalConfig::biggest_holder_bonus_bps` — see that field.
+    pub biggest_holder_bonus_bps: u64,
+    /// Lamports owed to `largest_holder` this round, carved out of
+    /// `winner_pot` by `end_round` and claimable via
+    /// `instructions::claim_biggest_holder_bonus`, same pattern as
+    /// `top_referrer_bonus_pool`. 0 once claimed (or if never allocated).
+    pub biggest_holder_bonus_pool: u64,
+    /// Player currently holding `largest_holder_keys` — the largest total
+    /// key balance seen this round, tracked live by `buy_keys` the same way
+    /// `max_single_buyer` tracks the largest single purchase.
+    /// `Pubkey::default()` until the first purchase.
+    pub largest_holder: Pubkey,
+    /// `PlayerState::keys` belonging to `largest_holder` at the time it was
+    /// last updated. 0 until the first purchase.
+    pub largest_holder_keys: u64,
+    /// Snapshot of `GlobalConfig::frontend_fee_bps` — see that field.
+    pub frontend_fee_bps: u64,
+    /// Snapshot of `GlobalConfig::dividend_apr_window_secs`. 0 disables the
+    /// trailing-yield estimator entirely (the default).
+    pub dividend_apr_window_secs: i64,
+    /// Start of the current rolling window `dividend_apr_window_dividend_lamports`
+    /// accrues over — see `maybe_reset_dividend_apr_window`. Initialized to
+    /// `round_start`.
+    pub dividend_apr_window_start: i64,
+    /// Dividends added to `total_dividend_pool` since
+    /// `dividend_apr_window_start` — lets an off-chain UI or a future quote
+    /// instruction derive "current key yield ~X%/day" as
+    /// `dividend_apr_window_dividend_lamports / total_keys` annualized over
+    /// `dividend_apr_window_secs`, without replaying every purchase event.
+    /// Reset to 0 whenever the window rolls over.
+    pub dividend_apr_window_dividend_lamports: u64,
+    /// Snapshot of `GlobalConfig::min_remaining_secs`. 0 disables the floor
+    /// entirely (the default). See `math::calculate_timer_extension`.
+    pub min_remaining_secs: i64,
+    /// Snapshot of `GlobalConfig::agent_platform_fee_share_bps`. 0 disables
+    /// the platform fee share entirely (the default). See
+    /// `instructions::buy_keys` and `instructions::register_agent_platform`.
+    pub agent_platform_fee_share_bps: u64,
+    /// Sum of every `AgentPlatform::pending_earnings_lamports` obligation
+    /// this round's vault currently backs — mirrors
+    /// `total_referral_obligations`'s role in `pending_obligations`. Not
+    /// round-scoped (a platform's earnings accrue across rounds, same as
+    /// referral earnings), so a claim against a different round's vault
+    /// than the one that credited it saturates rather than errors, exactly
+    /// like `total_referral_obligations` does in `claim_referral_earnings`.
+    pub total_agent_platform_obligations: u64,
+    /// `hashv` of this round's `GlobalConfig` snapshot, taken at round
+    /// creation (`initialize_first_round` / `start_new_round`) before any
+    /// further config edits can land. Lets an archived round be provably
+    /// matched to the exact parameters it ran under, even after
+    /// `GlobalConfig` has since been updated many times — see
+    /// `events::RoundConcluded`.
+    pub genesis_config_hash: [u8; 32],
 }
 
 impl GameState {
-    // round(8) + pot(8) + timer_end(8) + last_buyer(32) + total_keys(8) + round_start(8)
-    // + active(1) + winner_claimed(1) + total_players(4) + total_dividend_pool(8) + next_round_pot(8) + winner_pot(8)
+    // game_id(8) + round(8) + pot(8) + timer_end(8) + last_buyer(32) + total_keys(8) + round_start(8)
+    // + status(1) + total_players(4) + total_dividend_pool(8) + next_round_pot(8) + winner_pot(8)
     // + base_price(8) + price_inc(8) + timer_ext(8) + max_timer(8)
     // + winner_bps(8) + dividend_bps(8) + next_round_bps(8) + protocol_fee_bps(8) + referral_bps(8)
-    // + protocol_wallet(32) + bump(1) = 207
-    pub const SPACE: usize = 8 + 8 + 8 + 32 + 8 + 8 + 1 + 1 + 4 + 8 + 8 + 8
+    // + protocol_wallet(32) + bump(1) + total_referral_obligations(8)
+    // + total_weight(8) + early_bird_key_threshold(8) + early_bird_multiplier_bps(8)
+    // + min_purchase_lamports(8) + winner_claim_window_secs(8)
+    // + final_hour_pot_threshold_lamports(8) + final_hour_shrink_interval_keys(8)
+    // + final_hour_active(1) + final_hour_start_keys(8)
+    // + pot_milestone_interval_lamports(8) + pot_milestone_bonus_keys(8)
+    // + vault_lamports_in(8) + vault_lamports_out(8)
+    // + promo_keys_cap_per_round(8) + promo_keys_granted_this_round(8)
+    // + transfers_enabled(1)
+    // + wrapped_keys_enabled(1) + wrapped_keys_total(8) + wrapped_weight_total(8)
+    // + keeper_fee_lamports(8) + purchase_history_enabled(1)
+    // + purchase_count(8) + gross_volume_lamports(8) + max_single_buy_lamports(8) + max_single_buyer(32)
+    // + time_weighted_dividends_enabled(1) + dividend_weight_seconds_total(16)
+    // + dividend_seconds_last_update(8) + hook_program(32)
+    // + referral_earnings_cap_lamports_per_round(8) + referral_decay_threshold_lamports(8)
+    // + referrer_change_cooldown_secs(8)
+    // + dividend_merkle_root(1 + 32) + kyc_required(1) + kyc_issuer(32)
+    // + dust_reserve(8) + price_cumulative(16) + price_last_update(8)
+    // + unclaimed_dividend_policy(1) + dividend_claim_window_secs(8)
+    // + total_dividend_claimed_lamports(8)
+    // + max_timer_extensions_per_window(4) + timer_extension_window_secs(8)
+    // + top_referrer_bonus_bps(8) + top_referrer_bonus_pool(8)
+    // + raffle_bps(8) + raffle_daily_payout_bps(8) + raffle_pool_lamports(8)
+    // + raffle_prize_pool_pending(8) + refund_pool_lamports(8) + bridge_program(32)
+    // + max_pot_lamports(8) + pot_overflow_reserve_lamports(8) = 730
+    // + timer_extensions_triggered(8) + last_buy_timestamp(8)
+    // + buy_interval_seconds_total(8) + pot_checkpoint_25_lamports(8)
+    // + pot_checkpoint_50_lamports(8) + pot_checkpoint_75_lamports(8)
+    // + pot_checkpoint_25_reached(1) + pot_checkpoint_50_reached(1)
+    // + pot_checkpoint_75_reached(1) + auto_payout_winner_enabled(1)
+    // + min_keys_for_timer_extension(8) + price_sample_interval_slots(8)
+    // + rounding_beneficiary(1) + season_length_rounds(8) + season_fee_bps(8)
+    // + agent_keys_total(8) + human_keys_total(8) = 831
+    // + max_keys_per_round(8) = 839
+    // + referral_vesting_enabled(1) = 840
+    // + biggest_buyer_bonus_bps(8) + biggest_buyer_bonus_pool(8)
+    // + biggest_holder_bonus_bps(8) + biggest_holder_bonus_pool(8)
+    // + largest_holder(32) + largest_holder_keys(8) = 912
+    // + frontend_fee_bps(8) = 920
+    // + dividend_apr_window_secs(8) + dividend_apr_window_start(8)
+    // + dividend_apr_window_dividend_lamports(8) = 944
+    // + min_remaining_secs(8) = 952
+    // + agent_platform_fee_share_bps(8) + total_agent_platform_obligations(8) = 968
+    // + genesis_config_hash(32) = 1000
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 32 + 8 + 8 + 1 + 4 + 8 + 8 + 8
         + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8
-        + 32 + 1;
+        + 32 + 1 + 8
+        + 8 + 8 + 8
+        + 8 + 8
+        + 8 + 8 + 1 + 8
+        + 8 + 8
+        + 8 + 8
+        + 8 + 8
+        + 1
+        + 1 + 8 + 8
+        + 8
+        + 1
+        + 8 + 8 + 8 + 32
+        + 1 + 16 + 8
+        + 32
+        + 8 + 8
+        + 8
+        + 1 + 32
+        + 1 + 32
+        + 8
+        + 16 + 8
+        + 1 + 8
+        + 8
+        + 4 + 8
+        + 8 + 8
+        + 8 + 8 + 8 + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8 + 8
+        + 8 + 8 + 8 + 8
+        + 1 + 1 + 1
+        + 1
+        + 8
+        + 8
+        + 1 + 8 + 8
+        + 8 + 8
+        + 8
+        + 1
+        + 8 + 8 + 8 + 8 + 32 + 8
+        + 8
+        + 8 + 8 + 8
+        + 8
+        + 8 + 8
+        + 32;
+
+    /// Sum of every obligation this round's vault currently owes: unclaimed
+    /// winner prize, unclaimed dividends, next round's carry, unclaimed
+    /// referral earnings, the unclaimed top-referrer bonus, the raffle
+    /// pools, the pot-cap overflow reserve, and (once cancelled)
+    /// `refund_pool_lamports`. Computed from already-stored fields rather
+    /// than kept as its own duplicated counter, so there's nothing extra to
+    /// keep in sync as those fields change — see `instructions::assert_solvency`.
+    pub fn pending_obligations(&self) -> Result<u64> {
+        self.winner_pot
+            .checked_add(self.total_dividend_pool)
+            .and_then(|v| v.checked_add(self.next_round_pot))
+            .and_then(|v| v.checked_add(self.total_referral_obligations))
+            .and_then(|v| v.checked_add(self.total_agent_platform_obligations))
+            .and_then(|v| v.checked_add(self.top_referrer_bonus_pool))
+            .and_then(|v| v.checked_add(self.raffle_pool_lamports))
+            .and_then(|v| v.checked_add(self.raffle_prize_pool_pending))
+            .and_then(|v| v.checked_add(self.refund_pool_lamports))
+            .and_then(|v| v.checked_add(self.pot_overflow_reserve_lamports))
+            .ok_or_else(|| crate::errors::FomoltError::Overflow.into())
+    }
+
+    /// Whether the winner slot has been resolved — claimed, auto-settled for
+    /// an empty round, or forfeited forward. Equivalent to the old
+    /// `winner_claimed` boolean.
+    pub fn winner_claimed(&self) -> bool {
+        matches!(self.status, RoundStatus::Settled | RoundStatus::Archived)
+    }
+
+    /// 0-indexed season this round belongs to, per `season_length_rounds`
+    /// consecutive rounds per season. `0` when the season meta-game is
+    /// disabled (`season_length_rounds == 0`), so every round resolves to
+    /// the same `Season` PDA — harmless since `instructions::buy_keys` never
+    /// credits it in that case.
+    pub fn current_season_id(&self) -> u64 {
+        if self.season_length_rounds == 0 {
+            return 0;
+        }
+        (self.round.saturating_sub(1)) / self.season_length_rounds
+    }
+
+    /// First round (inclusive) of `current_season_id`.
+    pub fn current_season_start_round(&self) -> u64 {
+        self.current_season_id()
+            .saturating_mul(self.season_length_rounds)
+            .saturating_add(1)
+    }
+
+    /// Last round (inclusive) of `current_season_id`.
+    pub fn current_season_end_round(&self) -> u64 {
+        self.current_season_start_round()
+            .saturating_add(self.season_length_rounds)
+            .saturating_sub(1)
+    }
+
+    /// Accrues `total_weight * elapsed_seconds` since
+    /// `dividend_seconds_last_update` into `dividend_weight_seconds_total`,
+    /// then advances the watermark to `now`. Must be called (when
+    /// `time_weighted_dividends_enabled`) before any instruction changes
+    /// `total_weight`, so the accrual always uses the weight that was
+    /// actually in effect over the elapsed interval. Idempotent for a given
+    /// `now` — calling it again with the same or an earlier timestamp is a
+    /// no-op.
+    pub fn sync_dividend_seconds(&mut self, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.dividend_seconds_last_update).max(0) as u128;
+        self.dividend_weight_seconds_total = self
+            .dividend_weight_seconds_total
+            .checked_add(
+                (self.total_weight as u128)
+                    .checked_mul(elapsed)
+                    .ok_or(FomoltError::Overflow)?,
+            )
+            .ok_or(FomoltError::Overflow)?;
+        self.dividend_seconds_last_update = now;
+        Ok(())
+    }
+
+    /// Accrues `current_price * elapsed_seconds` since `price_last_update`
+    /// into `price_cumulative`, then advances the watermark to `now`. Called
+    /// before every `buy_keys` purchase changes `total_keys`, so the accrual
+    /// always uses the marginal price that was actually in effect over the
+    /// elapsed interval. Idempotent for a given `now` — calling it again
+    /// with the same or an earlier timestamp is a no-op.
+    pub fn sync_price_cumulative(&mut self, now: i64, current_price: u64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.price_last_update).max(0) as u128;
+        self.price_cumulative = self
+            .price_cumulative
+            .checked_add(
+                (current_price as u128)
+                    .checked_mul(elapsed)
+                    .ok_or(FomoltError::Overflow)?,
+            )
+            .ok_or(FomoltError::Overflow)?;
+        self.price_last_update = now;
+        Ok(())
+    }
+
+    /// Restarts the rolling dividend-APR window at `now` (zeroing
+    /// `dividend_apr_window_dividend_lamports`) if `dividend_apr_window_secs`
+    /// has elapsed since `dividend_apr_window_start`. Same reset pattern as
+    /// `PlayerState::maybe_reset_timer_extension_window`. A no-op while the
+    /// estimator is disabled (`dividend_apr_window_secs == 0`).
+    pub fn maybe_reset_dividend_apr_window(&mut self, now: i64) {
+        if self.dividend_apr_window_secs == 0 {
+            return;
+        }
+        if now >= self
+            .dividend_apr_window_start
+            .saturating_add(self.dividend_apr_window_secs)
+        {
+            self.dividend_apr_window_start = now;
+            self.dividend_apr_window_dividend_lamports = 0;
+        }
+    }
+
+    /// Rolls the dividend-APR window forward to `now` if it's elapsed, then
+    /// accrues `dividend_amount` into it — called by `buy_keys` alongside
+    /// the `total_dividend_pool` credit it mirrors. A no-op while the
+    /// estimator is disabled.
+    pub fn record_dividend_for_apr_window(&mut self, now: i64, dividend_amount: u64) -> Result<()> {
+        if self.dividend_apr_window_secs == 0 {
+            return Ok(());
+        }
+        self.maybe_reset_dividend_apr_window(now);
+        self.dividend_apr_window_dividend_lamports = self
+            .dividend_apr_window_dividend_lamports
+            .checked_add(dividend_amount)
+            .ok_or(FomoltError::Overflow)?;
+        Ok(())
+    }
+
+    /// `timer_end - round_start` — the round's actual duration once it's
+    /// over. Computed on demand rather than stored, same as
+    /// `pending_obligations`.
+    pub fn round_duration_secs(&self) -> i64 {
+        self.timer_end.saturating_sub(self.round_start)
+    }
+
+    /// `buy_interval_seconds_total / purchase_count` — the round's average
+    /// time between buys. 0 for a round with no purchases.
+    pub fn average_seconds_between_buys(&self) -> i64 {
+        if self.purchase_count == 0 {
+            0
+        } else {
+            self.buy_interval_seconds_total / self.purchase_count as i64
+        }
+    }
+
+    /// Moves `status` to `to`, rejecting the call if that's not a legal
+    /// transition per `RoundStatus::can_transition_to`. Callers are
+    /// responsible for emitting `events::RoundStatusChanged`.
+    pub fn transition_status(&mut self, to: RoundStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(to),
+            FomoltError::InvalidRoundStatusTransition
+        );
+        self.status = to;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +671,127 @@ mod tests {
 
     #[test]
     fn game_state_space() {
-        assert_eq!(GameState::SPACE, 207);
+        assert_eq!(GameState::SPACE, 1000);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized. `dividend_merkle_root: Some(..)`
+    /// is the worst case SPACE budgets for (Borsh's `None` serializes 1 byte).
+    #[test]
+    fn game_state_space_matches_serialized_size() {
+        let game = GameState {
+            game_id: 0,
+            round: 0,
+            pot_lamports: 0,
+            timer_end: 0,
+            last_buyer: Pubkey::default(),
+            total_keys: 0,
+            round_start: 0,
+            status: RoundStatus::Pending,
+            total_players: 0,
+            total_dividend_pool: 0,
+            next_round_pot: 0,
+            winner_pot: 0,
+            base_price_lamports: 0,
+            price_increment_lamports: 0,
+            timer_extension_secs: 0,
+            max_timer_secs: 0,
+            winner_bps: 0,
+            dividend_bps: 0,
+            next_round_bps: 0,
+            protocol_fee_bps: 0,
+            referral_bonus_bps: 0,
+            protocol_wallet: Pubkey::default(),
+            bump: 0,
+            total_referral_obligations: 0,
+            total_weight: 0,
+            early_bird_key_threshold: 0,
+            early_bird_multiplier_bps: 0,
+            min_purchase_lamports: 0,
+            winner_claim_window_secs: 0,
+            final_hour_pot_threshold_lamports: 0,
+            final_hour_shrink_interval_keys: 0,
+            final_hour_active: false,
+            final_hour_start_keys: 0,
+            pot_milestone_interval_lamports: 0,
+            pot_milestone_bonus_keys: 0,
+            vault_lamports_in: 0,
+            vault_lamports_out: 0,
+            promo_keys_cap_per_round: 0,
+            promo_keys_granted_this_round: 0,
+            transfers_enabled: false,
+            wrapped_keys_enabled: false,
+            wrapped_keys_total: 0,
+            wrapped_weight_total: 0,
+            keeper_fee_lamports: 0,
+            purchase_history_enabled: false,
+            purchase_count: 0,
+            gross_volume_lamports: 0,
+            max_single_buy_lamports: 0,
+            max_single_buyer: Pubkey::default(),
+            time_weighted_dividends_enabled: false,
+            dividend_weight_seconds_total: 0,
+            dividend_seconds_last_update: 0,
+            hook_program: Pubkey::default(),
+            referral_earnings_cap_lamports_per_round: 0,
+            referral_decay_threshold_lamports: 0,
+            referrer_change_cooldown_secs: 0,
+            dividend_merkle_root: Some([0u8; 32]),
+            kyc_required: false,
+            kyc_issuer: Pubkey::default(),
+            dust_reserve: 0,
+            price_cumulative: 0,
+            price_last_update: 0,
+            unclaimed_dividend_policy: UnclaimedDividendPolicy::Strand,
+            dividend_claim_window_secs: 0,
+            total_dividend_claimed_lamports: 0,
+            max_timer_extensions_per_window: 0,
+            timer_extension_window_secs: 0,
+            top_referrer_bonus_bps: 0,
+            top_referrer_bonus_pool: 0,
+            raffle_bps: 0,
+            raffle_daily_payout_bps: 0,
+            raffle_pool_lamports: 0,
+            raffle_prize_pool_pending: 0,
+            refund_pool_lamports: 0,
+            bridge_program: Pubkey::default(),
+            max_pot_lamports: 0,
+            pot_overflow_reserve_lamports: 0,
+            timer_extensions_triggered: 0,
+            last_buy_timestamp: 0,
+            buy_interval_seconds_total: 0,
+            pot_checkpoint_25_lamports: 0,
+            pot_checkpoint_50_lamports: 0,
+            pot_checkpoint_75_lamports: 0,
+            pot_checkpoint_25_reached: false,
+            pot_checkpoint_50_reached: false,
+            pot_checkpoint_75_reached: false,
+            auto_payout_winner_enabled: false,
+            min_keys_for_timer_extension: 0,
+            price_sample_interval_slots: 0,
+            rounding_beneficiary: RoundingBeneficiary::Protocol,
+            season_length_rounds: 0,
+            season_fee_bps: 0,
+            agent_keys_total: 0,
+            human_keys_total: 0,
+            max_keys_per_round: 0,
+            referral_vesting_enabled: false,
+            biggest_buyer_bonus_bps: 0,
+            biggest_buyer_bonus_pool: 0,
+            biggest_holder_bonus_bps: 0,
+            biggest_holder_bonus_pool: 0,
+            largest_holder: Pubkey::default(),
+            largest_holder_keys: 0,
+            frontend_fee_bps: 0,
+            dividend_apr_window_secs: 0,
+            dividend_apr_window_start: 0,
+            dividend_apr_window_dividend_lamports: 0,
+            min_remaining_secs: 0,
+            agent_platform_fee_share_bps: 0,
+            total_agent_platform_obligations: 0,
+            genesis_config_hash: [0u8; 32],
+        };
+        assert_eq!(game.try_to_vec().unwrap().len(), GameState::SPACE);
     }
 }
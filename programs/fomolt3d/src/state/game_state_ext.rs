@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::TOP_REFERRERS_LEADERBOARD_SIZE;
+use crate::errors::FomoltError;
+
+/// One slot of `GameStateExt::top_referrers` — a referrer's running
+/// referral earnings this round. `referrer == Pubkey::default()` marks an
+/// empty slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReferrerLeaderboardEntry {
+    pub referrer: Pubkey,
+    pub earned_lamports: u64,
+}
+
+/// Append-only companion to `GameState` for per-round data that doesn't
+/// belong in the hot, size-sensitive main account. Unlike `GameSnapshot`
+/// (eagerly created alongside every `GameState`), this account is created
+/// lazily via `init_if_needed` by whichever instruction first needs to
+/// write to it — most rounds may never touch it at all. New fields should
+/// be appended here rather than growing `GameState::SPACE` further.
+#[account]
+pub struct GameStateExt {
+    /// Which game lineage this extension belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// Round number this extension is paired with
+    pub round: u64,
+    /// Count of `MilestoneReached` events emitted so far this round (see
+    /// `buy_keys::handle_buy_keys`)
+    pub milestones_reached_this_round: u32,
+    /// Running principal currently CPI'd out into `GlobalConfig::yield_program`
+    /// via `deploy_vault_yield`, not yet reclaimed via `unwind_vault_yield`
+    /// (see `instructions::deploy_vault_yield`). 0 while the feature is
+    /// unused this round.
+    pub yield_deployed_lamports: u64,
+    /// This round's top `TOP_REFERRERS_LEADERBOARD_SIZE` referrers by
+    /// earned referral bonus, sorted descending — index 0 is the round's
+    /// leader and the only one payable via
+    /// `instructions::claim_top_referrer_bonus`. Maintained incrementally by
+    /// `credit_referrer` on every referred purchase; a referrer who never
+    /// makes the top slots simply doesn't appear here, though their real
+    /// earnings are still tracked in full by
+    /// `PlayerState::referral_earnings_lamports`.
+    pub top_referrers: [ReferrerLeaderboardEntry; TOP_REFERRERS_LEADERBOARD_SIZE],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl GameStateExt {
+    // game_id(8) + round(8) + milestones_reached_this_round(4)
+    // + yield_deployed_lamports(8) + top_referrers(5 * (32 + 8) = 200) + bump(1) = 229
+    pub const SPACE: usize =
+        8 + 8 + 4 + 8 + TOP_REFERRERS_LEADERBOARD_SIZE * (32 + 8) + 1;
+
+    /// Adds `bonus_lamports` to `referrer`'s running total in
+    /// `top_referrers` and re-sorts. The array is tiny and already sorted
+    /// going in, so a single bubble-up pass after the credit is cheaper
+    /// than resorting from scratch. A referrer outside the top
+    /// `TOP_REFERRERS_LEADERBOARD_SIZE` earners this round is silently
+    /// dropped — this is a round-long hype leaderboard, not the source of
+    /// truth for referral payouts.
+    pub fn credit_referrer(&mut self, referrer: Pubkey, bonus_lamports: u64) -> Result<()> {
+        let mut idx = match self.top_referrers.iter().position(|e| e.referrer == referrer) {
+            Some(i) => {
+                self.top_referrers[i].earned_lamports = self.top_referrers[i]
+                    .earned_lamports
+                    .checked_add(bonus_lamports)
+                    .ok_or(FomoltError::Overflow)?;
+                i
+            }
+            None => match self
+                .top_referrers
+                .iter()
+                .position(|e| e.referrer == Pubkey::default())
+            {
+                Some(i) => {
+                    self.top_referrers[i] = ReferrerLeaderboardEntry {
+                        referrer,
+                        earned_lamports: bonus_lamports,
+                    };
+                    i
+                }
+                None => {
+                    let last = TOP_REFERRERS_LEADERBOARD_SIZE - 1;
+                    if bonus_lamports <= self.top_referrers[last].earned_lamports {
+                        return Ok(());
+                    }
+                    self.top_referrers[last] = ReferrerLeaderboardEntry {
+                        referrer,
+                        earned_lamports: bonus_lamports,
+                    };
+                    last
+                }
+            },
+        };
+
+        while idx > 0
+            && self.top_referrers[idx].earned_lamports > self.top_referrers[idx - 1].earned_lamports
+        {
+            self.top_referrers.swap(idx, idx - 1);
+            idx -= 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_state_ext_space() {
+        assert_eq!(GameStateExt::SPACE, 229);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn game_state_ext_space_matches_serialized_size() {
+        let ext = GameStateExt {
+            game_id: 0,
+            round: 0,
+            milestones_reached_this_round: 0,
+            yield_deployed_lamports: 0,
+            top_referrers: [ReferrerLeaderboardEntry::default(); TOP_REFERRERS_LEADERBOARD_SIZE],
+            bump: 0,
+        };
+        assert_eq!(ext.try_to_vec().unwrap().len(), GameStateExt::SPACE);
+    }
+
+    #[test]
+    fn credit_referrer_maintains_descending_order() {
+        let mut ext = GameStateExt {
+            game_id: 0,
+            round: 0,
+            milestones_reached_this_round: 0,
+            yield_deployed_lamports: 0,
+            top_referrers: [ReferrerLeaderboardEntry::default(); TOP_REFERRERS_LEADERBOARD_SIZE],
+            bump: 0,
+        };
+
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+
+        ext.credit_referrer(a, 100).unwrap();
+        ext.credit_referrer(b, 300).unwrap();
+        ext.credit_referrer(c, 200).unwrap();
+        // b leads after its bigger single credit
+        assert_eq!(ext.top_referrers[0].referrer, b);
+        assert_eq!(ext.top_referrers[0].earned_lamports, 300);
+
+        // a catches up and overtakes b
+        ext.credit_referrer(a, 250).unwrap();
+        assert_eq!(ext.top_referrers[0].referrer, a);
+        assert_eq!(ext.top_referrers[0].earned_lamports, 350);
+        assert_eq!(ext.top_referrers[1].referrer, b);
+    }
+
+    #[test]
+    fn credit_referrer_drops_below_full_leaderboard() {
+        let mut ext = GameStateExt {
+            game_id: 0,
+            round: 0,
+            milestones_reached_this_round: 0,
+            yield_deployed_lamports: 0,
+            top_referrers: [ReferrerLeaderboardEntry::default(); TOP_REFERRERS_LEADERBOARD_SIZE],
+            bump: 0,
+        };
+
+        for i in 0..TOP_REFERRERS_LEADERBOARD_SIZE {
+            ext.credit_referrer(Pubkey::new_unique(), 1000 - i as u64).unwrap();
+        }
+        let smallest_leaderboard_amount = ext.top_referrers[TOP_REFERRERS_LEADERBOARD_SIZE - 1]
+            .earned_lamports;
+
+        let latecomer = Pubkey::new_unique();
+        ext.credit_referrer(latecomer, 1).unwrap();
+        assert!(ext.top_referrers.iter().all(|e| e.referrer != latecomer));
+        assert_eq!(
+            ext.top_referrers[TOP_REFERRERS_LEADERBOARD_SIZE - 1].earned_lamports,
+            smallest_leaderboard_amount
+        );
+
+        ext.credit_referrer(latecomer, 10_000).unwrap();
+        assert_eq!(ext.top_referrers[0].referrer, latecomer);
+    }
+}
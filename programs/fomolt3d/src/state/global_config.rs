@@ -1,8 +1,28 @@
 use anchor_lang::prelude::*;
 
+use crate::state::rounding_beneficiary::RoundingBeneficiary;
+use crate::state::unclaimed_dividend_policy::UnclaimedDividendPolicy;
+
 #[account]
 pub struct GlobalConfig {
-    /// Admin authority — only this signer can update config or start round 1
+    /// Which independent game lineage this config belongs to. Part of every
+    /// PDA seed derived from this config (directly or via a `GameState`
+    /// snapshot) so one deployment can run multiple concurrent games (e.g. a
+    /// high-roller game and a micro-stakes game) without their accounts
+    /// colliding. This is also what lets unrelated operators share one
+    /// program deployment instead of each needing their own: picking an
+    /// unclaimed `game_id` namespaces all of that operator's PDAs away from
+    /// everyone else's, and `admin` (set on first use of a `game_id`) is the
+    /// only signer who can touch that namespace afterward — see
+    /// `instructions::create_or_update_config`. Fixed at creation — there's
+    /// no instruction to move an existing config to a different game_id.
+    pub game_id: u64,
+    /// Admin authority — only this signer can update config or start round 1.
+    /// Admin-gated instructions check `admin.key() == config.admin` against a
+    /// plain `Signer<'info>`, which is satisfied by a PDA invoked via
+    /// `invoke_signed` just as well as by an EOA's Ed25519 signature — so
+    /// `admin` can safely be a Squads (or any other) multisig's vault PDA,
+    /// not only a single hot keypair.
     pub admin: Pubkey,
     /// Base price per key in lamports
     pub base_price_lamports: u64,
@@ -26,11 +46,410 @@ pub struct GlobalConfig {
     pub protocol_wallet: Pubkey,
     /// PDA bump seed
     pub bump: u8,
+    /// Early-bird bonus window: keys sold before this supply threshold earn
+    /// `early_bird_multiplier_bps` dividend weight instead of the standard
+    /// 10_000 (1x). 0 disables the bonus.
+    pub early_bird_key_threshold: u64,
+    /// Dividend weight (in bps, 10_000 = 1x) granted to keys within the
+    /// early-bird window
+    pub early_bird_multiplier_bps: u64,
+    /// Minimum cost (in lamports) a single buy_keys call must reach. Guards
+    /// against dust-only purchases whose bps splits would all truncate to
+    /// zero. 0 disables the check.
+    pub min_purchase_lamports: u64,
+    /// Seconds after a round's timer ends that the winner has to claim their
+    /// prize before it's forfeitable to the currently active round via
+    /// `forfeit_winner_pot`.
+    pub winner_claim_window_secs: i64,
+    /// Endgame convergence mode: once `GameState::pot_lamports` crosses this
+    /// many lamports, `final_hour_shrink_interval_keys` starts shrinking the
+    /// per-buy timer extension (see `math::calculate_timer_extension`) so
+    /// the round can't be extended forever by minimum buys. 0 disables it.
+    pub final_hour_pot_threshold_lamports: u64,
+    /// Once final-hour mode is active, the timer extension halves every
+    /// this many keys sold since activation. 0 disables the shrink even if
+    /// the pot threshold above is set.
+    pub final_hour_shrink_interval_keys: u64,
+    /// Pot hype milestones: every time `GameState::pot_lamports` crosses a
+    /// multiple of this many lamports (e.g. every 100 SOL), `buy_keys` emits
+    /// a `MilestoneReached` event for front ends/bots to broadcast. 0
+    /// disables milestones entirely.
+    pub pot_milestone_interval_lamports: u64,
+    /// Free bonus keys granted to the buyer whose purchase crosses a
+    /// milestone (see `pot_milestone_interval_lamports`). Minted directly —
+    /// no extra cost, no pot contribution. 0 means milestones still emit
+    /// their event but grant no bonus.
+    pub pot_milestone_bonus_keys: u64,
+    /// Per-round cap on keys grantable via `grant_promo_keys`. Snapshotted
+    /// into `GameState::promo_keys_cap_per_round` and checked against the
+    /// round's running `GameState::promo_keys_granted_this_round`. 0 disables
+    /// promo grants entirely for rounds snapshotting this config.
+    pub promo_keys_cap_per_round: u64,
+    /// Whether `transfer_keys` is allowed for rounds snapshotting this
+    /// config. Lets the admin shut down secondary OTC transfers (e.g. to
+    /// comply with a jurisdiction's rules) without touching anything else.
+    pub transfers_enabled: bool,
+    /// Whether `init_key_mint`/`wrap_keys`/`unwrap_keys` are allowed for
+    /// rounds snapshotting this config. When enabled, a player may wrap
+    /// keys into a transferable per-round SPL token (see
+    /// `instructions::wrap_keys`) composable with DEXes and lending, and
+    /// unwrap (burn) it back into a dividend-bearing `PlayerState` position
+    /// later. Disabling only blocks new wraps — existing wrapped supply can
+    /// always be unwrapped, so no one's funds get stranded.
+    pub wrapped_keys_enabled: bool,
+    /// Flat lamport reimbursement paid to whoever calls `end_round` from
+    /// the round's `KeeperBudget` PDA (see `instructions::end_round`),
+    /// funded by the admin via `fund_keeper_budget`. Snapshotted into
+    /// `GameState::keeper_fee_lamports`. 0 disables reimbursement — the
+    /// instruction stays permissionless either way. Lets off-chain
+    /// automation (a keeper bot, a Clockwork-style thread) end rounds at
+    /// `timer_end` without relying on an incidental buy/claim to do it.
+    pub keeper_fee_lamports: u64,
+    /// Whether `init_player_history` and the ring-buffer write in `buy_keys`
+    /// are allowed for rounds snapshotting this config. Snapshotted into
+    /// `GameState::purchase_history_enabled`. Off by default — maintaining a
+    /// `PlayerHistory` costs the player extra rent and every buy an extra
+    /// account write, so it's opt-in rather than automatic like `PlayerStats`.
+    pub purchase_history_enabled: bool,
+    /// Anti last-second-farming mode: when enabled, `GameState` snapshots
+    /// this and accrues a "weight-seconds" accumulator (weight held
+    /// multiplied by seconds held) for both the round and each player,
+    /// updated lazily on `buy_keys`/`claim`. `claim` then splits the
+    /// dividend pool by weight-seconds instead of the point-in-time
+    /// `dividend_weight`, so keys bought moments before round end earn
+    /// dividends in proportion to the tiny sliver of time they were
+    /// actually held rather than a full share. Off by default — it's a
+    /// strictly more expensive accounting path than the flat weight split.
+    pub time_weighted_dividends_enabled: bool,
+    /// Partner integration hook: when set to a non-default pubkey, `buy_keys`
+    /// CPI-notifies this program after a successful purchase with a minimal
+    /// `(round, buyer, keys, cost)` payload, so a loyalty program, quest
+    /// platform, or points system can react atomically in the same
+    /// transaction. This single stored pubkey IS the allowlist — the caller
+    /// must pass the matching program account or the purchase fails; there's
+    /// no way to CPI into an arbitrary program. `Pubkey::default()` disables
+    /// the hook entirely (the default).
+    pub hook_program: Pubkey,
+    /// Per-round cap (lamports) on how much referral bonus a single referrer
+    /// can earn in one round. Once a referrer's `PlayerState::referral_earnings_this_round_lamports`
+    /// reaches this, `buy_keys` stops crediting that referrer further
+    /// referral bonus for the rest of the round — the would-be bonus flows
+    /// into the pot instead, same as a purchase with no referrer at all.
+    /// 0 disables the cap.
+    pub referral_earnings_cap_lamports_per_round: u64,
+    /// Once a referrer's earnings in the current round cross this many
+    /// lamports, `referral_bonus_bps` is halved for the rest of that round —
+    /// a soft diminishing-returns curve that can apply before (or instead
+    /// of) the hard cap above. 0 disables the decay.
+    pub referral_decay_threshold_lamports: u64,
+    /// Minimum seconds that must elapse since `PlayerState::referrer_set_at`
+    /// before `set_referrer` can be called again to switch an already-set
+    /// referrer to a different one. 0 keeps referrer changes disabled
+    /// entirely (the default) — a player can still attach a referrer for the
+    /// first time via `set_referrer` regardless of this setting, only
+    /// *changing* an existing one is gated.
+    pub referrer_change_cooldown_secs: i64,
+    /// Whether `buy_keys` requires the buyer to present a `KycCredential`
+    /// PDA (issued via `issue_kyc_credential`) before purchasing. Off by
+    /// default — most rounds stay permissionless; a licensed operator
+    /// running a compliant round turns this on and sets `kyc_issuer`.
+    pub kyc_required: bool,
+    /// Authority allowed to call `issue_kyc_credential` for this game.
+    /// `Pubkey::default()` means no issuer is configured — `kyc_required`
+    /// must not be set to true while this is unset (see
+    /// `validate_config_params`).
+    pub kyc_issuer: Pubkey,
+    /// What happens to a round's dividend pool if some of it is still
+    /// unclaimed once `dividend_claim_window_secs` has elapsed past
+    /// `timer_end`. Snapshotted into `GameState::unclaimed_dividend_policy`.
+    /// Defaults to `Strand`, matching every round's behavior before this
+    /// policy existed.
+    pub unclaimed_dividend_policy: UnclaimedDividendPolicy,
+    /// Seconds after a round's timer ends before
+    /// `instructions::sweep_unclaimed_dividends` may act on
+    /// `unclaimed_dividend_policy` for that round. Snapshotted into
+    /// `GameState::dividend_claim_window_secs`.
+    pub dividend_claim_window_secs: i64,
+    /// Caps how many of a single wallet's buys may extend the timer within
+    /// any `timer_extension_window_secs`-long rolling window (tracked per
+    /// player, see `PlayerState::timer_extensions_in_window`) — past the
+    /// cap, further buys in the window still add keys as normal, they just
+    /// stop extending `GameState::timer_end`. Snapshotted into
+    /// `GameState::max_timer_extensions_per_window`. 0 disables the cap
+    /// (the default — unlimited, matching every round's behavior before
+    /// this setting existed).
+    pub max_timer_extensions_per_window: u32,
+    /// Length of the rolling window `max_timer_extensions_per_window`
+    /// counts over. Snapshotted into
+    /// `GameState::timer_extension_window_secs`. Meaningless while the cap
+    /// above is 0.
+    pub timer_extension_window_secs: i64,
+    /// Single-pubkey allowlist for `instructions::claim_to_stake`, same
+    /// pattern as `hook_program`/`kyc_issuer`: the only vote account players
+    /// may delegate their claimed dividends/winnings to instead of cashing
+    /// out. `Pubkey::default()` disables the instruction entirely — there's
+    /// no way to delegate to an arbitrary, unvetted validator.
+    pub approved_stake_vote_account: Pubkey,
+    /// Single-pubkey allowlist for `instructions::deploy_vault_yield` /
+    /// `instructions::unwind_vault_yield`, same pattern as `hook_program`:
+    /// the only program a round's idle vault lamports may be CPI'd into for
+    /// yield. `Pubkey::default()` disables both instructions entirely — there's
+    /// no way to deploy into an arbitrary, unvetted program.
+    pub yield_program: Pubkey,
+    /// Bounds how much of the vault's current balance a single
+    /// `deploy_vault_yield` call may move out, as basis points of that
+    /// balance evaluated at call time (see `math::calculate_bps_split`).
+    /// Deployment additionally always leaves enough behind to cover
+    /// `GameState::pending_obligations()`, so this cap only ever tightens
+    /// that floor, never loosens it. 0 disables new deployments — matching
+    /// `wrapped_keys_enabled`, this only blocks *new* deployments;
+    /// `unwind_vault_yield` can always reclaim whatever's already deployed.
+    pub max_yield_deployment_bps: u64,
+    /// Snapshotted into `GameState::top_referrer_bonus_bps` at round start.
+    /// Basis points of the round's `winner_pot` that `end_round` carves out
+    /// into `GameState::top_referrer_bonus_pool` for whoever leads
+    /// `GameStateExt::top_referrers` when the round concludes — see
+    /// `instructions::claim_top_referrer_bonus`. 0 disables the bonus
+    /// entirely; the full winner pot then goes to the winner as before this
+    /// setting existed.
+    pub top_referrer_bonus_bps: u64,
+    /// Snapshotted into `GameState::raffle_bps` at round start. Basis points
+    /// of each purchase's `pot_contribution` carved out into
+    /// `GameState::raffle_pool_lamports` to fund the daily key-holder raffle
+    /// (see `instructions::record_raffle_snapshot`). Carved before the
+    /// winner/dividend/next-round split, not from the must-sum-to-10_000
+    /// group. 0 disables the raffle entirely.
+    pub raffle_bps: u64,
+    /// Fraction of `GameState::raffle_pool_lamports` moved into a single
+    /// day's `RaffleSnapshot::prize_lamports` when
+    /// `instructions::record_raffle_snapshot` runs. Basis points of the pool
+    /// balance at that instant, so an idle pool with no purchases between
+    /// draws simply carries less forward each day. 0 disables new draws even
+    /// if `raffle_bps` is nonzero — funding still accrues, nothing is drawn.
+    pub raffle_daily_payout_bps: u64,
+    /// Single-pubkey allowlist for the cross-chain conclusion attestation,
+    /// same pattern as `hook_program`: the only program `end_round` may
+    /// CPI-notify with `(round, winner, winner_lamports, pot_lamports)` once
+    /// a round transitions to `Ended`, so a sister deployment on another
+    /// chain (relayed via Wormhole/LayerZero) or an EVM mirror contract can
+    /// react to the same conclusion. `Pubkey::default()` disables the
+    /// attestation entirely (the default) — there's no way to CPI into an
+    /// arbitrary, unvetted program.
+    pub bridge_program: Pubkey,
+    /// Optional cap on this round's combined `GameState::winner_pot` +
+    /// `GameState::total_dividend_pool` liability — lets an operator bound
+    /// how much SOL the program is ever on the hook to pay out in a single
+    /// round. Once a purchase's split would push that combined total past
+    /// this cap, the excess is diverted into
+    /// `GameState::pot_overflow_reserve_lamports` instead (dividends first,
+    /// then the winner pot — see `math::apply_pot_cap`), which
+    /// `start_new_round` folds into the next round's carry-over just like
+    /// `next_round_pot`. `0` disables the cap entirely (the default,
+    /// unbounded — the pre-existing behavior).
+    pub max_pot_lamports: u64,
+    /// Snapshotted into `GameState::auto_payout_winner_enabled` at round
+    /// start. When true, `end_round` pushes `winner_pot` straight to
+    /// `last_buyer` itself instead of leaving it for the winner to claim —
+    /// see `instructions::end_round`. Off by default, matching every
+    /// round's behavior before this setting existed; `claim` always remains
+    /// available as a fallback for whatever `end_round` didn't (or
+    /// couldn't) auto-pay.
+    pub auto_payout_winner_enabled: bool,
+    /// Snapshotted into `GameState::min_keys_for_timer_extension` at round
+    /// start. A buy for fewer keys than this still gets its keys and
+    /// dividends as normal, it just doesn't push out `GameState::timer_end`
+    /// — closes off 1-lamport-scale spam buys that would otherwise hold a
+    /// round hostage indefinitely while still letting genuine micro-buys
+    /// through. 0 disables the floor entirely (the default — every buy
+    /// extends the timer, matching every round's behavior before this
+    /// setting existed).
+    pub min_keys_for_timer_extension: u64,
+    /// Snapshotted into `GameState::price_sample_interval_slots` at round
+    /// start. On a buy that lands `price_sample_interval_slots` or more
+    /// slots after `PriceHistory::last_sampled_slot`, `buy_keys` appends a
+    /// `(slot, total_keys, price)` sample to that round's `PriceHistory` ring
+    /// buffer; the permissionless `record_sample` crank covers quiet periods
+    /// with no buys. `0` disables sampling entirely (the default — no
+    /// `PriceHistory` account is ever populated).
+    pub price_sample_interval_slots: u64,
+    /// Snapshotted into `GameState::rounding_beneficiary` at round start.
+    /// Chooses where a buy's leftover bps-split dust lands — see
+    /// `RoundingBeneficiary`. Defaults to `Protocol`, matching the
+    /// pre-existing `dust_reserve` behavior of every round before this
+    /// setting existed.
+    pub rounding_beneficiary: RoundingBeneficiary,
+    /// Snapshotted into `GameState::season_length_rounds` at round start.
+    /// Number of consecutive rounds that make up one `Season` — see
+    /// `state::season`. `0` disables the season meta-game entirely (the
+    /// default — no `Season` account is ever meaningfully populated).
+    pub season_length_rounds: u64,
+    /// Snapshotted into `GameState::season_fee_bps` at round start. Slice of
+    /// `protocol_fee_bps`'s own cut (not of the purchase cost) diverted into
+    /// the active `Season`'s prize pool instead of `protocol_wallet` — see
+    /// `instructions::buy_keys`. `0` disables pool funding even while a
+    /// season is active, so volume/wins can still be tracked with no prize
+    /// on the line (the default).
+    pub season_fee_bps: u64,
+    /// Bitmask letting the admin disable individual instructions without a
+    /// full pause — see the `GlobalConfig::FLAG_*` constants and
+    /// `is_instruction_disabled`. Useful for staged rollouts of a new
+    /// subsystem (e.g. shipping `claim_top_referrer_bonus` ahead of turning
+    /// it on) or shutting one down in isolation without touching every
+    /// other instruction. `0` disables nothing (the default).
+    pub disabled_instructions_bitmask: u64,
+    /// Highest round number that has actually been started for this game
+    /// lineage via `initialize_first_round`/`start_new_round` — program-
+    /// managed runtime state, not an admin-set config value, so it's never
+    /// threaded through `ConfigParams`/`ConfigUpdated` like the fields
+    /// above. `start_new_round` requires the new round to be exactly
+    /// `latest_round + 1`, closing off forked or skipped round chains.
+    pub latest_round: u64,
+    /// Optional supply cap: once `GameState::total_keys` reaches this,
+    /// `buy_keys` ends the round immediately regardless of `timer_end` —
+    /// the last buyer wins, same as a timer-driven conclusion. A purchase
+    /// that would cross the cap is partially filled (only the remaining
+    /// supply is sold and charged for) rather than rejected outright. `0`
+    /// disables the cap entirely (the default — unlimited supply, matching
+    /// every round's behavior before this setting existed).
+    pub max_keys_per_round: u64,
+    /// When true, a referrer's bonus from a buy credited during the
+    /// currently active round is held in
+    /// `PlayerState::pending_referral_earnings_lamports` instead of the
+    /// claimable `PlayerState::referral_earnings_lamports` bucket, and only
+    /// vests once that round has ended — see `instructions::buy_keys` and
+    /// `instructions::claim_referral_earnings`. Deters wash trading a
+    /// referral link against oneself right before a round concludes.
+    /// `false` (the default) credits instantly, as every round did before
+    /// this setting existed.
+    pub referral_vesting_enabled: bool,
+    /// Snapshotted into `GameState::biggest_buyer_bonus_bps` at round start.
+    /// Basis points of the round's `winner_pot` that `end_round` carves out
+    /// into `GameState::biggest_buyer_bonus_pool` for whoever made
+    /// `GameState::max_single_buy_lamports` this round — see
+    /// `instructions::claim_biggest_buyer_bonus`. 0 disables the bonus
+    /// entirely; the full winner pot then goes to the winner as before this
+    /// setting existed.
+    pub biggest_buyer_bonus_bps: u64,
+    /// Snapshotted into `GameState::biggest_holder_bonus_bps` at round
+    /// start. Basis points of the round's `winner_pot` that `end_round`
+    /// carves out into `GameState::biggest_holder_bonus_pool` for whoever
+    /// holds `GameState::largest_holder_keys` this round — see
+    /// `instructions::claim_biggest_holder_bonus`. 0 disables the bonus
+    /// entirely; the full winner pot then goes to the winner as before this
+    /// setting existed.
+    pub biggest_holder_bonus_bps: u64,
+    /// Snapshotted into `GameState::frontend_fee_bps` at round start. Basis
+    /// points of each purchase's cost paid to that buy's caller-supplied
+    /// `frontend_wallet`, off the top like `protocol_fee_bps` — see
+    /// `instructions::buy_keys`. 0 disables the affiliate cut entirely; a
+    /// buy that omits `frontend_wallet` pays nothing regardless of this bps.
+    pub frontend_fee_bps: u64,
+    /// Snapshotted into `GameState::dividend_apr_window_secs` at round
+    /// start. Length of the trailing window `buy_keys` rolls
+    /// `GameState::dividend_apr_window_dividend_lamports` over — see
+    /// `GameState::maybe_reset_dividend_apr_window`. 0 disables the
+    /// estimator entirely (the default); a UI or the client SDK divides the
+    /// rolling sum by `total_keys` and the window length itself to derive a
+    /// yield rate, so nothing here is expressed as a percentage.
+    pub dividend_apr_window_secs: i64,
+    /// Snapshotted into `GameState::min_remaining_secs` at round start.
+    /// Floors every timer-extending buy's new `timer_end` at
+    /// `now + min_remaining_secs`, on top of the usual `timer_extension_secs`
+    /// bump — see `math::calculate_timer_extension`. 0 disables the floor
+    /// entirely (the default, i.e. purely `timer_extension_secs`-driven).
+    pub min_remaining_secs: i64,
+    /// Snapshotted into `GameState::agent_platform_fee_share_bps` at round
+    /// start. Basis points of each purchase's `protocol_fee_bps` house fee
+    /// diverted into the buyer's `PlayerState::agent_platform` pending
+    /// earnings instead of `protocol_wallet`, when that field is set — see
+    /// `instructions::register_agent_platform` and
+    /// `instructions::claim_agent_platform_earnings`. 0 disables the share
+    /// entirely (the default); a buyer with no registered platform never
+    /// diverts anything regardless of this bps.
+    pub agent_platform_fee_share_bps: u64,
 }
 
 impl GlobalConfig {
-    // admin(32) + 9 x u64/i64(72) + protocol_wallet(32) + bump(1) = 137
-    pub const SPACE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 1;
+    // game_id(8) + admin(32) + 9 x u64/i64(72) + protocol_wallet(32) + bump(1)
+    // + early_bird_key_threshold(8) + early_bird_multiplier_bps(8)
+    // + min_purchase_lamports(8) + winner_claim_window_secs(8)
+    // + final_hour_pot_threshold_lamports(8) + final_hour_shrink_interval_keys(8)
+    // + pot_milestone_interval_lamports(8) + pot_milestone_bonus_keys(8)
+    // + promo_keys_cap_per_round(8) + transfers_enabled(1) + wrapped_keys_enabled(1)
+    // + keeper_fee_lamports(8) + purchase_history_enabled(1)
+    // + time_weighted_dividends_enabled(1) + hook_program(32)
+    // + referral_earnings_cap_lamports_per_round(8) + referral_decay_threshold_lamports(8)
+    // + referrer_change_cooldown_secs(8) + kyc_required(1) + kyc_issuer(32)
+    // + unclaimed_dividend_policy(1) + dividend_claim_window_secs(8)
+    // + max_timer_extensions_per_window(4) + timer_extension_window_secs(8)
+    // + approved_stake_vote_account(32) + yield_program(32)
+    // + max_yield_deployment_bps(8) + top_referrer_bonus_bps(8)
+    // + raffle_bps(8) + raffle_daily_payout_bps(8) + bridge_program(32)
+    // + max_pot_lamports(8) + auto_payout_winner_enabled(1)
+    // + min_keys_for_timer_extension(8) + price_sample_interval_slots(8)
+    // + rounding_beneficiary(1) + season_length_rounds(8) + season_fee_bps(8)
+    // + disabled_instructions_bitmask(8) + latest_round(8) = 525
+    // + max_keys_per_round(8) = 533
+    // + referral_vesting_enabled(1) = 534
+    // + biggest_buyer_bonus_bps(8) + biggest_holder_bonus_bps(8) = 550
+    // + frontend_fee_bps(8) = 558
+    // + dividend_apr_window_secs(8) = 566
+    // + min_remaining_secs(8) = 574
+    // + agent_platform_fee_share_bps(8) = 582
+    pub const SPACE: usize = 8 + 32
+        + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8
+        + 32 + 1
+        + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 1
+        + 1
+        + 32
+        + 8 + 8
+        + 8
+        + 1 + 32
+        + 1 + 8
+        + 4 + 8
+        + 32
+        + 32 + 8
+        + 8
+        + 8 + 8
+        + 32
+        + 8
+        + 1
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8 + 8
+        + 8
+        + 8
+        + 8
+        + 8;
+
+    /// `disabled_instructions_bitmask` bit gating `claim_referral_earnings`.
+    pub const FLAG_CLAIM_REFERRAL_EARNINGS: u64 = 1 << 0;
+    /// `disabled_instructions_bitmask` bit gating `consolidate_referral_earnings`.
+    pub const FLAG_CONSOLIDATE_REFERRAL_EARNINGS: u64 = 1 << 1;
+    /// `disabled_instructions_bitmask` bit gating `claim_top_referrer_bonus`.
+    pub const FLAG_CLAIM_TOP_REFERRER_BONUS: u64 = 1 << 2;
+    /// `disabled_instructions_bitmask` bit gating `claim_biggest_buyer_bonus`.
+    pub const FLAG_CLAIM_BIGGEST_BUYER_BONUS: u64 = 1 << 3;
+    /// `disabled_instructions_bitmask` bit gating `claim_biggest_holder_bonus`.
+    pub const FLAG_CLAIM_BIGGEST_HOLDER_BONUS: u64 = 1 << 4;
+
+    /// Whether `flag` (one of the `FLAG_*` constants above) is set in
+    /// `disabled_instructions_bitmask`.
+    pub fn is_instruction_disabled(&self, flag: u64) -> bool {
+        self.disabled_instructions_bitmask & flag != 0
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +458,77 @@ mod tests {
 
     #[test]
     fn global_config_space() {
-        assert_eq!(GlobalConfig::SPACE, 137);
+        assert_eq!(GlobalConfig::SPACE, 582);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn global_config_space_matches_serialized_size() {
+        let config = GlobalConfig {
+            game_id: 0,
+            admin: Pubkey::default(),
+            base_price_lamports: 0,
+            price_increment_lamports: 0,
+            timer_extension_secs: 0,
+            max_timer_secs: 0,
+            winner_bps: 0,
+            dividend_bps: 0,
+            next_round_bps: 0,
+            protocol_fee_bps: 0,
+            referral_bonus_bps: 0,
+            protocol_wallet: Pubkey::default(),
+            bump: 0,
+            early_bird_key_threshold: 0,
+            early_bird_multiplier_bps: 0,
+            min_purchase_lamports: 0,
+            winner_claim_window_secs: 0,
+            final_hour_pot_threshold_lamports: 0,
+            final_hour_shrink_interval_keys: 0,
+            pot_milestone_interval_lamports: 0,
+            pot_milestone_bonus_keys: 0,
+            promo_keys_cap_per_round: 0,
+            transfers_enabled: false,
+            wrapped_keys_enabled: false,
+            keeper_fee_lamports: 0,
+            purchase_history_enabled: false,
+            time_weighted_dividends_enabled: false,
+            hook_program: Pubkey::default(),
+            referral_earnings_cap_lamports_per_round: 0,
+            referral_decay_threshold_lamports: 0,
+            referrer_change_cooldown_secs: 0,
+            kyc_required: false,
+            kyc_issuer: Pubkey::default(),
+            unclaimed_dividend_policy: UnclaimedDividendPolicy::Strand,
+            dividend_claim_window_secs: 0,
+            max_timer_extensions_per_window: 0,
+            timer_extension_window_secs: 0,
+            approved_stake_vote_account: Pubkey::default(),
+            yield_program: Pubkey::default(),
+            max_yield_deployment_bps: 0,
+            top_referrer_bonus_bps: 0,
+            raffle_bps: 0,
+            raffle_daily_payout_bps: 0,
+            bridge_program: Pubkey::default(),
+            max_pot_lamports: 0,
+            auto_payout_winner_enabled: false,
+            min_keys_for_timer_extension: 0,
+            price_sample_interval_slots: 0,
+            rounding_beneficiary: RoundingBeneficiary::Protocol,
+            season_length_rounds: 0,
+            season_fee_bps: 0,
+            disabled_instructions_bitmask: 0,
+            latest_round: 0,
+            max_keys_per_round: 0,
+            referral_vesting_enabled: false,
+            biggest_buyer_bonus_bps: 0,
+            biggest_holder_bonus_bps: 0,
+            frontend_fee_bps: 0,
+            dividend_apr_window_secs: 0,
+            min_remaining_secs: 0,
+            agent_platform_fee_share_bps: 0,
+        };
+        assert_eq!(config.try_to_vec().unwrap().len(), GlobalConfig::SPACE);
     }
 }
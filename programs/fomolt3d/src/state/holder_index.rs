@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+/// Per-round page of `HolderIndex::holders` — see `HolderIndex`.
+pub const HOLDER_INDEX_PAGE_CAPACITY: usize = 64;
+
+/// Append-only page of the per-round holder registry, populated by `buy_keys`
+/// whenever a purchase registers a player as new to the round (see
+/// `GameState::total_players`). `page` is derived client-side from
+/// `game_state.total_players / HolderIndex::PAGE_CAPACITY` at the time of the
+/// buy, so a crank enumerating every holder for push-claims, sweeps, or
+/// Merkle root construction just walks pages `0..=(total_players /
+/// PAGE_CAPACITY)` for a round instead of relying on an external indexer.
+/// Created lazily via `init_if_needed`, same as `GameStateExt`/`PriceHistory`
+/// above it; unlike those, a full page is immutable once `len` reaches
+/// `PAGE_CAPACITY` and later holders land in the next page's account.
+#[account]
+pub struct HolderIndex {
+    /// Which game lineage this page belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// Round number this page is paired with
+    pub round: u64,
+    /// Page number within the round — see the seed derivation above
+    pub page: u32,
+    /// Holders registered on this page, in join order
+    pub holders: [Pubkey; HolderIndex::PAGE_CAPACITY],
+    /// Number of valid entries in `holders`; the rest are unused padding
+    pub len: u16,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl HolderIndex {
+    pub const PAGE_CAPACITY: usize = HOLDER_INDEX_PAGE_CAPACITY;
+
+    // game_id(8) + round(8) + page(4) + holders(64 * 32 = 2048) + len(2) + bump(1) = 2071
+    pub const SPACE: usize = 8 + 8 + 4 + (32 * Self::PAGE_CAPACITY) + 2 + 1;
+
+    /// Appends `holder` at the next free slot. Callers are responsible for
+    /// ensuring the page isn't already full — `buy_keys` only ever writes to
+    /// the page derived from the current `total_players` count, which is
+    /// guaranteed to have room by construction.
+    pub fn push(&mut self, holder: Pubkey) {
+        self.holders[self.len as usize] = holder;
+        self.len += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holder_index_space() {
+        assert_eq!(HolderIndex::SPACE, 2071);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn holder_index_space_matches_serialized_size() {
+        let index = HolderIndex {
+            game_id: 0,
+            round: 0,
+            page: 0,
+            holders: [Pubkey::default(); HolderIndex::PAGE_CAPACITY],
+            len: 0,
+            bump: 0,
+        };
+        assert_eq!(index.try_to_vec().unwrap().len(), HolderIndex::SPACE);
+    }
+
+    #[test]
+    fn push_appends_in_order() {
+        let mut index = HolderIndex {
+            game_id: 0,
+            round: 0,
+            page: 0,
+            holders: [Pubkey::default(); HolderIndex::PAGE_CAPACITY],
+            len: 0,
+            bump: 0,
+        };
+
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        index.push(a);
+        index.push(b);
+
+        assert_eq!(index.len, 2);
+        assert_eq!(index.holders[0], a);
+        assert_eq!(index.holders[1], b);
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+/// Registers `keeper` as a bonded crank operator for this game lineage,
+/// scoped by `config.game_id` rather than any one round so a keeper stays
+/// registered across `start_new_round` transitions. The bond itself lives in
+/// a separate `keeper_bond` vault PDA (same bare-lamport-vault shape as
+/// `keeper_budget`) — `bond_lamports` mirrors that vault's balance so reads
+/// don't need to fetch two accounts, but the vault is the source of truth
+/// and is what `slash_keeper`/`unregister_keeper` actually move lamports out
+/// of.
+///
+/// Registration itself confers no special permission — `end_round` (and any
+/// future crank) simply pays a richer bounty when the caller presents an
+/// `active` `KeeperState`, per `GlobalConfig::keeper_bounty_bps`. Admin can
+/// `slash_keeper` an account for misbehavior (e.g. exploiting a bug window
+/// to end a round early); repeated slashing is left to the admin's
+/// discretion rather than an automatic ban.
+#[account]
+pub struct KeeperState {
+    /// Which game lineage this registration belongs to
+    pub game_id: u64,
+    /// The registered keeper's wallet address
+    pub keeper: Pubkey,
+    /// Mirror of the `keeper_bond` vault's balance — see struct doc above
+    pub bond_lamports: u64,
+    /// When this keeper registered
+    pub registered_at: i64,
+    /// Whether this registration is in good standing. Never cleared by
+    /// slashing alone — only `unregister_keeper` sets this false, as part
+    /// of closing the account and returning the remaining bond.
+    pub active: bool,
+    /// Number of times admin has slashed this keeper's bond
+    pub slash_count: u32,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl KeeperState {
+    // game_id(8) + keeper(32) + bond_lamports(8) + registered_at(8)
+    // + active(1) + slash_count(4) + bump(1) = 62
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1 + 4 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeper_state_space() {
+        assert_eq!(KeeperState::SPACE, 62);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn keeper_state_space_matches_serialized_size() {
+        let keeper = KeeperState {
+            game_id: 0,
+            keeper: Pubkey::default(),
+            bond_lamports: 0,
+            registered_at: 0,
+            active: false,
+            slash_count: 0,
+            bump: 0,
+        };
+        assert_eq!(keeper.try_to_vec().unwrap().len(), KeeperState::SPACE);
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+/// Proof that a wallet has passed KYC for a game whose `GlobalConfig::kyc_required`
+/// is set. Presence of this PDA (seeds `[b"kyc", game_id, wallet]`) is itself
+/// the credential — `buy_keys` checks for it via an optional account, the
+/// same pattern `BlockEntry` uses for the blocklist. Scoped per `game_id` so
+/// a credential issued for one game doesn't carry over to an unrelated one.
+#[account]
+pub struct KycCredential {
+    /// Which game lineage this credential applies to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// The verified wallet
+    pub wallet: Pubkey,
+    /// The issuer that approved this credential — must match
+    /// `GlobalConfig::kyc_issuer` at issuance time, though the config's
+    /// issuer may rotate afterward without invalidating credentials already
+    /// issued under the old one.
+    pub issuer: Pubkey,
+    /// Unix timestamp the credential was issued
+    pub issued_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl KycCredential {
+    // game_id(8) + wallet(32) + issuer(32) + issued_at(8) + bump(1) = 81
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kyc_credential_space() {
+        assert_eq!(KycCredential::SPACE, 81);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn kyc_credential_space_matches_serialized_size() {
+        let credential = KycCredential {
+            game_id: 0,
+            wallet: Pubkey::default(),
+            issuer: Pubkey::default(),
+            issued_at: 0,
+            bump: 0,
+        };
+        assert_eq!(
+            credential.try_to_vec().unwrap().len(),
+            KycCredential::SPACE
+        );
+    }
+}
@@ -1,7 +1,53 @@
 pub mod global_config;
 pub mod game_state;
+pub mod round_status;
+pub mod game_snapshot;
 pub mod player_state;
+pub mod player_stats;
+pub mod block_entry;
+pub mod session_authority;
+pub mod buy_commitment;
+pub mod player_history;
+pub mod merkle_claim_receipt;
+pub mod kyc_credential;
+pub mod unclaimed_dividend_policy;
+pub mod sponsor_allocation;
+pub mod keeper_state;
+pub mod game_state_ext;
+pub mod raffle_snapshot;
+pub mod raffle_claim_receipt;
+pub mod price_history;
+pub mod rounding_beneficiary;
+pub mod season;
+pub mod season_claim_receipt;
+pub mod vault_flow;
+pub mod holder_index;
+pub mod buy_receipt;
+pub mod agent_platform;
 
 pub use global_config::*;
 pub use game_state::*;
+pub use round_status::*;
+pub use game_snapshot::*;
 pub use player_state::*;
+pub use player_stats::*;
+pub use block_entry::*;
+pub use session_authority::*;
+pub use buy_commitment::*;
+pub use player_history::*;
+pub use merkle_claim_receipt::*;
+pub use kyc_credential::*;
+pub use unclaimed_dividend_policy::*;
+pub use sponsor_allocation::*;
+pub use keeper_state::*;
+pub use game_state_ext::*;
+pub use raffle_snapshot::*;
+pub use raffle_claim_receipt::*;
+pub use price_history::*;
+pub use rounding_beneficiary::*;
+pub use season::*;
+pub use season_claim_receipt::*;
+pub use vault_flow::*;
+pub use holder_index::*;
+pub use buy_receipt::*;
+pub use agent_platform::*;
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+/// A single past purchase, as recorded by `PlayerHistory::record`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct PurchaseRecord {
+    pub timestamp: i64,
+    pub keys: u64,
+    pub cost_lamports: u64,
+}
+
+impl PurchaseRecord {
+    // timestamp(8) + keys(8) + cost_lamports(8) = 24
+    pub const SPACE: usize = 8 + 8 + 8;
+}
+
+/// Optional, opt-in per-player purchase log: a fixed-size ring buffer of the
+/// player's most recent `buy_keys` calls (see `PlayerHistory::CAPACITY`),
+/// gated by `GameState::purchase_history_enabled` since every write costs
+/// extra compute and every account costs extra rent. Created once via
+/// `init_player_history` and then reused across rounds, unlike `PlayerState`
+/// which resets on claim — the history itself is lifetime, round-agnostic
+/// activity, same as `PlayerStats`. Lets agents and analytics dashboards read
+/// recent activity directly off-chain without running an indexer.
+#[account]
+pub struct PlayerHistory {
+    /// Which game lineage this history belongs to (see `GlobalConfig::game_id`)
+    pub game_id: u64,
+    /// Player's wallet address
+    pub player: Pubkey,
+    /// Ring buffer of the most recent purchases, oldest entries overwritten
+    /// first once `len` reaches `CAPACITY`
+    pub entries: [PurchaseRecord; PlayerHistory::CAPACITY],
+    /// Index `record` will write to next, wrapping modulo `CAPACITY`
+    pub next_index: u8,
+    /// Number of live entries in `entries`, capped at `CAPACITY`
+    pub len: u8,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PlayerHistory {
+    /// How many recent purchases are retained before the oldest is overwritten
+    pub const CAPACITY: usize = 8;
+
+    // game_id(8) + player(32) + entries(8 * 24 = 192) + next_index(1) + len(1) + bump(1) = 235
+    pub const SPACE: usize = 8 + 32 + (PurchaseRecord::SPACE * Self::CAPACITY) + 1 + 1 + 1;
+
+    /// Appends a purchase, overwriting the oldest entry once the buffer is full.
+    pub fn record(&mut self, timestamp: i64, keys: u64, cost_lamports: u64) {
+        let idx = self.next_index as usize;
+        self.entries[idx] = PurchaseRecord {
+            timestamp,
+            keys,
+            cost_lamports,
+        };
+        self.next_index = ((idx + 1) % Self::CAPACITY) as u8;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_history_space() {
+        assert_eq!(PlayerHistory::SPACE, 235);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn player_history_space_matches_serialized_size() {
+        let history = PlayerHistory {
+            game_id: 0,
+            player: Pubkey::default(),
+            entries: [PurchaseRecord::default(); PlayerHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            bump: 0,
+        };
+        assert_eq!(history.try_to_vec().unwrap().len(), PlayerHistory::SPACE);
+    }
+
+    #[test]
+    fn record_fills_buffer_in_order() {
+        let mut history = PlayerHistory {
+            game_id: 0,
+            player: Pubkey::default(),
+            entries: [PurchaseRecord::default(); PlayerHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            bump: 0,
+        };
+
+        history.record(100, 5, 1_000);
+        history.record(200, 3, 2_000);
+
+        assert_eq!(history.len, 2);
+        assert_eq!(history.next_index, 2);
+        assert_eq!(history.entries[0].timestamp, 100);
+        assert_eq!(history.entries[1].keys, 3);
+    }
+
+    #[test]
+    fn record_wraps_after_capacity_reached() {
+        let mut history = PlayerHistory {
+            game_id: 0,
+            player: Pubkey::default(),
+            entries: [PurchaseRecord::default(); PlayerHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            bump: 0,
+        };
+
+        for i in 0..PlayerHistory::CAPACITY as u64 {
+            history.record(i as i64, i, i);
+        }
+        assert_eq!(history.len, PlayerHistory::CAPACITY as u8);
+        assert_eq!(history.next_index, 0);
+
+        // One more purchase overwrites the oldest entry (index 0) instead of growing.
+        history.record(999, 99, 99);
+        assert_eq!(history.len, PlayerHistory::CAPACITY as u8);
+        assert_eq!(history.next_index, 1);
+        assert_eq!(history.entries[0].timestamp, 999);
+        assert_eq!(history.entries[1].timestamp, 1);
+    }
+}
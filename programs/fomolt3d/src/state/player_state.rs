@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::FomoltError;
+
 #[account]
 pub struct PlayerState {
+    /// Which game lineage this PlayerState belongs to (see `GlobalConfig::game_id`).
+    /// Part of the PDA seed so the same wallet can hold an independent
+    /// PlayerState per concurrent game.
+    pub game_id: u64,
     /// Player's wallet address
     pub player: Pubkey,
     /// Keys held in this round
@@ -20,11 +26,285 @@ pub struct PlayerState {
     pub is_agent: bool,
     /// PDA bump seed
     pub bump: u8,
+    /// Accumulated dividend weight for this round, in bps (10_000 = 1 key
+    /// at 1x). Equals `keys * 10_000` unless an early-bird bonus applied.
+    pub dividend_weight: u64,
+    /// When true, `claim` reinvests this player's dividend share into keys
+    /// of the currently active round instead of paying it out as SOL. Set
+    /// via `set_preferences`. Never affects the winner prize, which always
+    /// pays out in SOL.
+    pub auto_compound: bool,
+    /// This player's weight-seconds accrual for the current round — see
+    /// `GameState::dividend_weight_seconds_total`. Reset to 0 whenever
+    /// `dividend_weight` is reset (registration, re-entry, or claim).
+    pub dividend_weight_seconds: u128,
+    /// Unix timestamp `dividend_weight_seconds` was last synced up to.
+    pub dividend_seconds_last_update: i64,
+    /// Round number `referral_earnings_this_round_lamports` applies to —
+    /// lets `buy_keys` detect the round has rolled over and reset the
+    /// counter lazily, the same pattern `current_round` uses for `keys`.
+    pub referral_earnings_round: u64,
+    /// Referral bonus this player has earned (as a referrer) so far in
+    /// `referral_earnings_round` — compared against
+    /// `GameState::referral_earnings_cap_lamports_per_round` and
+    /// `GameState::referral_decay_threshold_lamports` in `buy_keys`. Reset
+    /// to 0 whenever a buy is credited in a later round.
+    pub referral_earnings_this_round_lamports: u64,
+    /// Referral bonus credited during `referral_earnings_round` while
+    /// `GlobalConfig::referral_vesting_enabled` is on, not yet vested into
+    /// the claimable `referral_earnings_lamports` bucket. Vests (moves over)
+    /// once the round it was earned in has ended — see `instructions::buy_keys`
+    /// and `instructions::claim_referral_earnings`. Always 0 while vesting is
+    /// disabled, since earnings are credited straight to
+    /// `referral_earnings_lamports` in that case.
+    pub pending_referral_earnings_lamports: u64,
+    /// Unix timestamp `referrer` was last set — either on first registration
+    /// or via a later `set_referrer` change. Used to enforce
+    /// `GameState::referrer_change_cooldown_secs` against further changes.
+    pub referrer_set_at: i64,
+    /// Self-imposed cap on lamports spent per rolling 24h window, set via
+    /// `set_spend_limit`. 0 means no limit (the default — opt-in only).
+    /// Enforced by `buy_keys` against `spend_window_lamports`.
+    pub spend_limit_lamports_per_day: u64,
+    /// A higher `spend_limit_lamports_per_day` waiting to take effect at
+    /// `spend_limit_effective_at` — see `PlayerState::apply_pending_spend_limit`.
+    /// None once there's no raise in flight.
+    pub pending_spend_limit_lamports_per_day: Option<u64>,
+    /// Unix timestamp `pending_spend_limit_lamports_per_day` becomes active.
+    /// Meaningless while that field is None.
+    pub spend_limit_effective_at: i64,
+    /// Start of the current rolling 24h spend-tracking window. Reset lazily
+    /// by `PlayerState::maybe_reset_spend_window` once it's more than
+    /// `SPEND_WINDOW_SECS` old.
+    pub spend_window_start: i64,
+    /// Lamports this player has spent on key purchases within
+    /// `spend_window_start..spend_window_start + SPEND_WINDOW_SECS`.
+    pub spend_window_lamports: u64,
+    /// Start of the current rolling timer-extension-tracking window. Reset
+    /// lazily by `PlayerState::maybe_reset_timer_extension_window` once it's
+    /// more than `GameState::timer_extension_window_secs` old. Meaningless
+    /// while `GameState::max_timer_extensions_per_window` is 0.
+    pub timer_extension_window_start: i64,
+    /// Number of this player's buys that have extended the timer within
+    /// `timer_extension_window_start..timer_extension_window_start +
+    /// timer_extension_window_secs`. Enforced by `buy_keys` against
+    /// `GameState::max_timer_extensions_per_window` — once reached, further
+    /// buys in the window still add keys as normal, they just stop
+    /// extending `GameState::timer_end`.
+    pub timer_extensions_in_window: u32,
+    /// Optional payout beneficiary set via `set_preferences`. When set,
+    /// `claim` and `claim_referral_earnings` send cash payouts here instead
+    /// of to the signing wallet — lets a custodial agent or DAO play from a
+    /// hot wallet while routing proceeds to a separate treasury address.
+    /// None means pay the signer directly (the pre-existing behavior).
+    pub payout_address: Option<Pubkey>,
+    /// This player's cumulative `pot_contribution` (the post-fee,
+    /// post-referral amount that actually reached `GameState`'s pot
+    /// buckets) for `current_round`. Only meaningful for refunds:
+    /// `instructions::refund` pays this out 1:1 if the round is cancelled.
+    /// Reset to 0 at every site that resets `keys`/`dividend_weight` for a
+    /// round rollover — it shares their round scope, not a separate one.
+    pub contributed_lamports: u64,
+    /// This player's `pot_contribution` summed across every round they've
+    /// ever bought into — never reset, unlike `contributed_lamports`. Exists
+    /// purely for off-chain "amount in" analytics/ROI displays, so they don't
+    /// have to replay every `KeysPurchased` event to reconstruct it.
+    pub total_contributed_lamports: u64,
+    /// True once this `PlayerState` has been through a genuine `init` (i.e.
+    /// the `is_new_player`/`player.player == Pubkey::default()` branch has
+    /// run at least once). Every read-only instruction that trusts this
+    /// account's fields (`claim`, `claim_referral_earnings`, `refund`, ...)
+    /// requires this — belt-and-suspenders against `init_if_needed`
+    /// re-initializing a PDA whose lamports were drained by
+    /// `close_player_state` but whose account buffer wasn't actually
+    /// reclaimed by the runtime before a later instruction in the same
+    /// transaction reused it (a "revival attack" — see
+    /// `instructions::close_player_state`).
+    pub initialized: bool,
+    /// Monotonically increasing counter, bumped every time the
+    /// `is_new_player` init branch runs — including a legitimate
+    /// close-then-re-register cycle, and (crucially) a revival attack, since
+    /// only the 8-byte Anchor discriminator is guaranteed to be zeroed by
+    /// `close`, not this field. Never reset. Purely a forensic trail for
+    /// off-chain monitoring to flag a `PlayerState` that's been closed and
+    /// reopened more than once — the program itself doesn't need to compare
+    /// it against anything, since `initialized` alone is what claim paths gate on.
+    pub generation: u32,
+    /// A wallet awaiting `instructions::execute_player_migration` to take
+    /// over this `PlayerState`, set via `instructions::propose_player_migration`.
+    /// None while no migration is in flight.
+    pub pending_migration_wallet: Option<Pubkey>,
+    /// Unix timestamp `pending_migration_wallet` becomes executable —
+    /// `now + PLAYER_MIGRATION_TIMELOCK_SECS` at proposal time. Meaningless
+    /// while `pending_migration_wallet` is None. Gives the true owner a
+    /// window to notice and object (off-chain, e.g. by contacting the admin)
+    /// before an admin-assisted recovery actually moves funds.
+    pub migration_effective_at: i64,
+    /// Agent-supplied classifier for the strategy driving this player's
+    /// buys, set on every `buy_keys` call and echoed back on the `AgentAction`
+    /// event `buy_keys` and the claim instructions emit for agent players —
+    /// see `events::AgentAction`. Meaningless while `is_agent` is false;
+    /// humans never set it, so it stays 0.
+    pub strategy_tag: u32,
+    /// The agent marketplace this player registered through, if any — set
+    /// once, immutable after, via `instructions::register_agent_platform`
+    /// (requires the platform's own signature). While set,
+    /// `GameState::agent_platform_fee_share_bps` of this player's house fee
+    /// on every `buy_keys` is diverted into that platform's `AgentPlatform`
+    /// pending earnings instead of `protocol_wallet`. None means no
+    /// platform is attributed — the default, and the case for humans.
+    pub agent_platform: Option<Pubkey>,
+    /// Balance sitting in this player's `prepaid` vault PDA, funded via
+    /// `instructions::deposit_prepaid` and drawn down by
+    /// `instructions::execute_scheduled_buy`. Kept as a cached mirror of the
+    /// vault's actual lamports for the same reason `KeeperState::bond_lamports`
+    /// mirrors `keeper_bond`'s balance — cheap to read without a second
+    /// account lookup.
+    pub prepaid_balance_lamports: u64,
+    /// Keys bought per `execute_scheduled_buy` crank, set via
+    /// `instructions::set_scheduled_buy`. Meaningless while
+    /// `scheduled_buy_interval_secs` is 0.
+    pub scheduled_buy_keys: u64,
+    /// Minimum seconds between `execute_scheduled_buy` cranks for this
+    /// player. 0 disables the schedule entirely — the default.
+    pub scheduled_buy_interval_secs: i64,
+    /// Unix timestamp of the last successful `execute_scheduled_buy` crank.
+    /// 0 (never run) makes the very first crank due immediately once a
+    /// schedule is configured.
+    pub last_scheduled_buy_at: i64,
 }
 
+/// Length of the rolling window `set_spend_limit` caps spend over.
+pub const SPEND_WINDOW_SECS: i64 = 86_400;
+
+/// How long a raise to `spend_limit_lamports_per_day` (including removing the
+/// limit entirely, i.e. raising it to 0/unlimited) must wait before taking
+/// effect. Lowering the limit, or setting one for the first time, always
+/// applies immediately — only loosening an existing self-imposed cap is
+/// delayed, so a player mid-binge can't undo their own guardrail on the spot.
+pub const SPEND_LIMIT_INCREASE_DELAY_SECS: i64 = 86_400;
+
+/// How long `execute_player_migration` must wait after
+/// `propose_player_migration` before it can transfer a `PlayerState` to the
+/// new wallet — 72 hours. Longer than `SPEND_LIMIT_INCREASE_DELAY_SECS`
+/// since this moves the whole account, not just a self-imposed cap, giving
+/// the true owner more time to notice and dispute the recovery with the
+/// admin before it executes.
+pub const PLAYER_MIGRATION_TIMELOCK_SECS: i64 = 259_200;
+
 impl PlayerState {
-    // 32 + 8 + 8 + 8 + (1+32) + 8 + 8 + 1 + 1 = 107
-    pub const SPACE: usize = 32 + 8 + 8 + 8 + (1 + 32) + 8 + 8 + 1 + 1;
+    // game_id(8) + 32 + 8 + 8 + 8 + (1+32) + 8 + 8 + 1 + 1 + 8 + auto_compound(1)
+    // + dividend_weight_seconds(16) + dividend_seconds_last_update(8)
+    // + referral_earnings_round(8) + referral_earnings_this_round_lamports(8)
+    // + referrer_set_at(8) + spend_limit_lamports_per_day(8)
+    // + pending_spend_limit_lamports_per_day(1+8) + spend_limit_effective_at(8)
+    // + spend_window_start(8) + spend_window_lamports(8)
+    // + timer_extension_window_start(8) + timer_extensions_in_window(4)
+    // + payout_address(1+32) + contributed_lamports(8)
+    // + total_contributed_lamports(8) + initialized(1) + generation(4)
+    // + pending_migration_wallet(1+32) + migration_effective_at(8)
+    // + strategy_tag(4) = 324
+    // + pending_referral_earnings_lamports(8) = 332
+    // + agent_platform(1+32) = 365
+    // + prepaid_balance_lamports(8) + scheduled_buy_keys(8)
+    // + scheduled_buy_interval_secs(8) + last_scheduled_buy_at(8) = 397
+    pub const SPACE: usize = 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + (1 + 32)
+        + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 1
+        + 16
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + (1 + 8)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 4
+        + (1 + 32)
+        + 8
+        + 8
+        + 1
+        + 4
+        + (1 + 32)
+        + 8
+        + 4
+        + 8
+        + (1 + 32)
+        + 8
+        + 8
+        + 8
+        + 8;
+
+    /// Accrues `dividend_weight * elapsed_seconds` since
+    /// `dividend_seconds_last_update` into `dividend_weight_seconds`, then
+    /// advances the watermark to `now`. Mirrors
+    /// `GameState::sync_dividend_seconds` — see that doc comment.
+    pub fn sync_dividend_seconds(&mut self, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.dividend_seconds_last_update).max(0) as u128;
+        self.dividend_weight_seconds = self
+            .dividend_weight_seconds
+            .checked_add(
+                (self.dividend_weight as u128)
+                    .checked_mul(elapsed)
+                    .ok_or(FomoltError::Overflow)?,
+            )
+            .ok_or(FomoltError::Overflow)?;
+        self.dividend_seconds_last_update = now;
+        Ok(())
+    }
+
+    /// Promotes `pending_spend_limit_lamports_per_day` into
+    /// `spend_limit_lamports_per_day` once `spend_limit_effective_at` has
+    /// passed. No-op if there's no raise pending, or it isn't due yet.
+    pub fn apply_pending_spend_limit(&mut self, now: i64) {
+        if let Some(pending) = self.pending_spend_limit_lamports_per_day {
+            if now >= self.spend_limit_effective_at {
+                self.spend_limit_lamports_per_day = pending;
+                self.pending_spend_limit_lamports_per_day = None;
+            }
+        }
+    }
+
+    /// Restarts the rolling spend window at `now` (zeroing
+    /// `spend_window_lamports`) if `SPEND_WINDOW_SECS` has elapsed since
+    /// `spend_window_start`.
+    pub fn maybe_reset_spend_window(&mut self, now: i64) {
+        if now >= self.spend_window_start.saturating_add(SPEND_WINDOW_SECS) {
+            self.spend_window_start = now;
+            self.spend_window_lamports = 0;
+        }
+    }
+
+    /// Restarts the rolling timer-extension window at `now` (zeroing
+    /// `timer_extensions_in_window`) if `window_secs` has elapsed since
+    /// `timer_extension_window_start`. Unlike `maybe_reset_spend_window`,
+    /// the window length isn't a fixed constant — it's
+    /// `GameState::timer_extension_window_secs`, snapshotted per round.
+    pub fn maybe_reset_timer_extension_window(&mut self, now: i64, window_secs: i64) {
+        if now >= self.timer_extension_window_start.saturating_add(window_secs) {
+            self.timer_extension_window_start = now;
+            self.timer_extensions_in_window = 0;
+        }
+    }
+
+    /// True once `now` has reached `migration_effective_at` for a pending
+    /// migration. False (never executable) while `pending_migration_wallet`
+    /// is None.
+    pub fn migration_ready(&self, now: i64) -> bool {
+        self.pending_migration_wallet.is_some() && now >= self.migration_effective_at
+    }
 }
 
 #[cfg(test)]
@@ -33,6 +313,56 @@ mod tests {
 
     #[test]
     fn player_state_space() {
-        assert_eq!(PlayerState::SPACE, 107);
+        assert_eq!(PlayerState::SPACE, 397);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized. `referrer: Some(..)` is the
+    /// worst case SPACE budgets for (Borsh's `None` serializes 32 bytes
+    /// shorter).
+    #[test]
+    fn player_state_space_matches_serialized_size() {
+        let player = PlayerState {
+            game_id: 0,
+            player: Pubkey::default(),
+            keys: 0,
+            current_round: 0,
+            claimed_dividends_lamports: 0,
+            referrer: Some(Pubkey::default()),
+            referral_earnings_lamports: 0,
+            claimed_referral_earnings_lamports: 0,
+            is_agent: false,
+            bump: 0,
+            dividend_weight: 0,
+            auto_compound: false,
+            dividend_weight_seconds: 0,
+            dividend_seconds_last_update: 0,
+            referral_earnings_round: 0,
+            referral_earnings_this_round_lamports: 0,
+            pending_referral_earnings_lamports: 0,
+            referrer_set_at: 0,
+            spend_limit_lamports_per_day: 0,
+            pending_spend_limit_lamports_per_day: Some(0),
+            spend_limit_effective_at: 0,
+            spend_window_start: 0,
+            spend_window_lamports: 0,
+            timer_extension_window_start: 0,
+            timer_extensions_in_window: 0,
+            payout_address: Some(Pubkey::default()),
+            contributed_lamports: 0,
+            total_contributed_lamports: 0,
+            initialized: false,
+            generation: 0,
+            pending_migration_wallet: Some(Pubkey::default()),
+            migration_effective_at: 0,
+            strategy_tag: 0,
+            agent_platform: Some(Pubkey::default()),
+            prepaid_balance_lamports: 0,
+            scheduled_buy_keys: 0,
+            scheduled_buy_interval_secs: 0,
+            last_scheduled_buy_at: 0,
+        };
+        assert_eq!(player.try_to_vec().unwrap().len(), PlayerState::SPACE);
     }
 }
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+/// Global, round-agnostic player profile. Unlike `PlayerState` (which resets
+/// `keys`/`current_round` on claim so a player can re-enter the next round),
+/// these totals accumulate across every round the player has ever touched —
+/// front ends use this for a persistent lifetime profile.
+#[account]
+pub struct PlayerStats {
+    /// Which game lineage this lifetime profile belongs to (see
+    /// `GlobalConfig::game_id`). A player running in several concurrent
+    /// games gets a separate lifetime profile per game rather than one
+    /// profile conflating unrelated games' history.
+    pub game_id: u64,
+    /// Player's wallet address
+    pub player: Pubkey,
+    /// Total keys ever bought, across all rounds
+    pub lifetime_keys_bought: u64,
+    /// Total lamports ever spent buying keys
+    pub lifetime_lamports_spent: u64,
+    /// Total dividends ever claimed (lamports)
+    pub lifetime_dividends_earned: u64,
+    /// Total referral bonuses ever earned as a referrer (lamports)
+    pub lifetime_referral_earned: u64,
+    /// Number of rounds this player has won
+    pub rounds_won: u32,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    // game_id(8) + player(32) + keys_bought(8) + lamports_spent(8) + dividends(8) + referral(8) + rounds_won(4) + bump(1) = 77
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 4 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_stats_space() {
+        assert_eq!(PlayerStats::SPACE, 77);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn player_stats_space_matches_serialized_size() {
+        let stats = PlayerStats {
+            game_id: 0,
+            player: Pubkey::default(),
+            lifetime_keys_bought: 0,
+            lifetime_lamports_spent: 0,
+            lifetime_dividends_earned: 0,
+            lifetime_referral_earned: 0,
+            rounds_won: 0,
+            bump: 0,
+        };
+        assert_eq!(stats.try_to_vec().unwrap().len(), PlayerStats::SPACE);
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+/// One sample in `PriceHistory::samples` — the round's spot state at a given
+/// slot. `price_lamports` is the cost of the *next* key at that instant (the
+/// same value `GameSnapshot::next_key_price` and `GameUpdated::next_key_price`
+/// report), not the price actually paid by whichever buy triggered the sample.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct PriceSample {
+    pub slot: u64,
+    pub total_keys: u64,
+    pub price_lamports: u64,
+}
+
+impl PriceSample {
+    // slot(8) + total_keys(8) + price_lamports(8) = 24
+    pub const SPACE: usize = 8 + 8 + 8;
+}
+
+/// Per-round ring buffer PDA of recent `PriceSample`s, giving charting UIs
+/// and on-chain consumers a way to read price history without running an
+/// indexer. Populated two ways: `buy_keys` appends a sample whenever a buy
+/// lands `GameState::price_sample_interval_slots` or more slots after
+/// `last_sampled_slot` (see `GlobalConfig::price_sample_interval_slots`), and
+/// the permissionless `record_sample` crank covers rounds that go quiet for
+/// longer than that. Created lazily via `init_if_needed` by whichever of the
+/// two touches it first, matching `GameStateExt`.
+#[account]
+pub struct PriceHistory {
+    pub game_id: u64,
+    pub round: u64,
+    pub samples: [PriceSample; PriceHistory::CAPACITY],
+    pub next_index: u8,
+    pub len: u8,
+    pub last_sampled_slot: u64,
+    pub bump: u8,
+}
+
+impl PriceHistory {
+    pub const CAPACITY: usize = 32;
+
+    // game_id(8) + round(8) + samples(32 * 24 = 768) + next_index(1) + len(1)
+    // + last_sampled_slot(8) + bump(1) = 795
+    pub const SPACE: usize =
+        8 + 8 + (PriceSample::SPACE * Self::CAPACITY) + 1 + 1 + 8 + 1;
+
+    /// Appends a sample and advances `last_sampled_slot`, overwriting the
+    /// oldest entry once the buffer is full.
+    pub fn record(&mut self, slot: u64, total_keys: u64, price_lamports: u64) {
+        let idx = self.next_index as usize;
+        self.samples[idx] = PriceSample {
+            slot,
+            total_keys,
+            price_lamports,
+        };
+        self.next_index = ((idx + 1) % Self::CAPACITY) as u8;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+        self.last_sampled_slot = slot;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_history_space() {
+        assert_eq!(PriceHistory::SPACE, 795);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn price_history_space_matches_serialized_size() {
+        let history = PriceHistory {
+            game_id: 0,
+            round: 0,
+            samples: [PriceSample::default(); PriceHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            last_sampled_slot: 0,
+            bump: 0,
+        };
+        assert_eq!(history.try_to_vec().unwrap().len(), PriceHistory::SPACE);
+    }
+
+    #[test]
+    fn record_fills_buffer_in_order() {
+        let mut history = PriceHistory {
+            game_id: 0,
+            round: 0,
+            samples: [PriceSample::default(); PriceHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            last_sampled_slot: 0,
+            bump: 0,
+        };
+
+        history.record(100, 1, 10_000);
+        history.record(200, 2, 11_000);
+
+        assert_eq!(history.len, 2);
+        assert_eq!(history.next_index, 2);
+        assert_eq!(history.samples[0].slot, 100);
+        assert_eq!(history.samples[1].total_keys, 2);
+        assert_eq!(history.last_sampled_slot, 200);
+    }
+
+    #[test]
+    fn record_wraps_after_capacity_reached() {
+        let mut history = PriceHistory {
+            game_id: 0,
+            round: 0,
+            samples: [PriceSample::default(); PriceHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            last_sampled_slot: 0,
+            bump: 0,
+        };
+
+        for i in 0..PriceHistory::CAPACITY {
+            history.record(i as u64, i as u64, i as u64);
+        }
+        assert_eq!(history.len, PriceHistory::CAPACITY as u8);
+        assert_eq!(history.next_index, 0);
+
+        history.record(999, 999, 999);
+        assert_eq!(history.len, PriceHistory::CAPACITY as u8);
+        assert_eq!(history.next_index, 1);
+        assert_eq!(history.samples[0].slot, 999);
+        assert_eq!(history.samples[1].slot, 1);
+    }
+}
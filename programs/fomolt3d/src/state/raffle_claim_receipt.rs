@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+/// A one-time marker that a given day's `RaffleSnapshot::prize_lamports` has
+/// been paid out via `claim_raffle_prize`. Existence alone is the claim
+/// record, same as `MerkleClaimReceipt` — there's no further state to track,
+/// so the account is never written to again after `init`. PDA seeds
+/// `[b"raffle_claim", raffle_snapshot, player]` make a second
+/// `claim_raffle_prize` for the same (day, player) fail on account
+/// re-initialization rather than needing an explicit check.
+#[account]
+pub struct RaffleClaimReceipt {
+    /// Game lineage this claim belongs to
+    pub game_id: u64,
+    /// Round this claim belongs to
+    pub round: u64,
+    /// Day within the round this claim belongs to
+    pub day_index: u64,
+    /// The wallet the winning ticket's leaf was made out to
+    pub player: Pubkey,
+    /// Lamports paid out for this day's prize
+    pub lamports: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RaffleClaimReceipt {
+    // game_id(8) + round(8) + day_index(8) + player(32) + lamports(8) + bump(1) = 65
+    pub const SPACE: usize = 8 + 8 + 8 + 32 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raffle_claim_receipt_space() {
+        assert_eq!(RaffleClaimReceipt::SPACE, 65);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn raffle_claim_receipt_space_matches_serialized_size() {
+        let receipt = RaffleClaimReceipt {
+            game_id: 0,
+            round: 0,
+            day_index: 0,
+            player: Pubkey::default(),
+            lamports: 0,
+            bump: 0,
+        };
+        assert_eq!(
+            receipt.try_to_vec().unwrap().len(),
+            RaffleClaimReceipt::SPACE
+        );
+    }
+}
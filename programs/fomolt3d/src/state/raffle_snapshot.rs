@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+/// One day's daily key-holder raffle within a round (see
+/// `instructions::record_raffle_snapshot`, `instructions::draw_raffle_ticket`,
+/// `instructions::claim_raffle_prize`). PDA seeds
+/// `[b"raffle", game_state, day_index.to_le_bytes()]` — a fresh account per
+/// day, `init`'d by `record_raffle_snapshot`, so a round accumulates one of
+/// these every `constants::RAFFLE_INTERVAL_SECS`.
+///
+/// `merkle_root` commits (off-chain, at snapshot time) to every player's
+/// `(player, weight_range_start, weight_range_end)` leaf, where the ranges
+/// partition `0..total_weight` by each player's `PlayerState::dividend_weight`
+/// at that instant. `draw_raffle_ticket` then picks a `winning_ticket` in
+/// that same range, and `claim_raffle_prize` lets whoever's committed range
+/// contains it prove so with a Merkle proof — the same shape as
+/// `GameState::dividend_merkle_root` / `instructions::claim_with_proof`,
+/// adapted from flat amounts to weight ranges since the "winner" here is
+/// whoever held the ticket, not a fixed payout per leaf.
+#[account]
+pub struct RaffleSnapshot {
+    /// Game lineage this snapshot belongs to
+    pub game_id: u64,
+    /// Round this snapshot belongs to
+    pub round: u64,
+    /// 0-indexed day within the round this snapshot represents
+    pub day_index: u64,
+    /// Root of the weight-range Merkle tree computed at snapshot time
+    pub merkle_root: [u8; 32],
+    /// Sum of every leaf's weight range width — the exclusive upper bound
+    /// `draw_raffle_ticket`'s ticket is drawn under
+    pub total_weight: u64,
+    /// Ticket drawn by `draw_raffle_ticket`, in `0..total_weight`. `None`
+    /// until drawn — `claim_raffle_prize` requires this to be `Some`.
+    pub winning_ticket: Option<u64>,
+    /// Lamports carved out of `GameState::raffle_pool_lamports` for this
+    /// day, paid to whoever's weight range contains `winning_ticket`. Drained
+    /// to 0 by `claim_raffle_prize` — same zero-on-claim pattern as
+    /// `GameState::top_referrer_bonus_pool`, mirrored into
+    /// `GameState::raffle_prize_pool_pending` since solvency checks only
+    /// read `GameState`'s own fields.
+    pub prize_lamports: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RaffleSnapshot {
+    // game_id(8) + round(8) + day_index(8) + merkle_root(32) + total_weight(8)
+    // + winning_ticket(1 + 8) + prize_lamports(8) + bump(1) = 82
+    pub const SPACE: usize = 8 + 8 + 8 + 32 + 8 + 9 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raffle_snapshot_space() {
+        assert_eq!(RaffleSnapshot::SPACE, 82);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized. `winning_ticket: Some(..)` is the
+    /// worst case SPACE budgets for (Borsh's `None` serializes 1 byte).
+    #[test]
+    fn raffle_snapshot_space_matches_serialized_size() {
+        let snapshot = RaffleSnapshot {
+            game_id: 0,
+            round: 0,
+            day_index: 0,
+            merkle_root: [0u8; 32],
+            total_weight: 0,
+            winning_ticket: Some(0),
+            prize_lamports: 0,
+            bump: 0,
+        };
+        assert_eq!(snapshot.try_to_vec().unwrap().len(), RaffleSnapshot::SPACE);
+    }
+}
@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+
+/// Explicit round lifecycle state machine. Replaces what used to be two
+/// independently-toggled booleans on `GameState` (`active`, `winner_claimed`)
+/// with a single source of truth, so off-chain automation (Clockwork, custom
+/// keepers) can drive the lifecycle by polling one field instead of
+/// cross-referencing two, and illegal transitions (e.g. re-settling an
+/// already-archived round) fail loudly via `GameState::transition_status`
+/// instead of silently no-opping.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundStatus {
+    /// Account created but the round hasn't started yet. Only observed
+    /// transiently inside `initialize_first_round`/`start_new_round`, which
+    /// move it to `Active` before the instruction finishes.
+    Pending,
+    /// Timer running — keys can be bought.
+    Active,
+    /// Timer expired; winner prize and dividends not yet settled.
+    Ended,
+    /// The winner slot has been resolved — either claimed via `claim`/
+    /// `claim_and_roll`, or (for an empty round with no winner) settled
+    /// automatically by `start_new_round`. Dividends may still be
+    /// outstanding for other players.
+    Settled,
+    /// Fully wound down: an unclaimed winner pot was forfeited forward via
+    /// `forfeit_winner_pot`. No further vault activity is expected.
+    Archived,
+    /// Frozen by an admin via `cancel_round` before the timer expired,
+    /// typically because the round was started with a misconfigured
+    /// override. Buying, claiming, and ending are all disabled; the only
+    /// remaining vault activity is players draining
+    /// `GameState::refund_pool_lamports` via `instructions::refund`.
+    /// Terminal — there's no path back to `Active`.
+    Cancelled,
+}
+
+impl RoundStatus {
+    /// Whether moving from `self` to `to` is a legal lifecycle transition.
+    pub fn can_transition_to(&self, to: RoundStatus) -> bool {
+        matches!(
+            (self, to),
+            (RoundStatus::Pending, RoundStatus::Active)
+                | (RoundStatus::Active, RoundStatus::Ended)
+                | (RoundStatus::Ended, RoundStatus::Settled)
+                | (RoundStatus::Ended, RoundStatus::Archived)
+                | (RoundStatus::Settled, RoundStatus::Archived)
+                | (RoundStatus::Active, RoundStatus::Cancelled)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::GameState;
+
+    #[test]
+    fn legal_transitions_are_allowed() {
+        assert!(RoundStatus::Pending.can_transition_to(RoundStatus::Active));
+        assert!(RoundStatus::Active.can_transition_to(RoundStatus::Ended));
+        assert!(RoundStatus::Ended.can_transition_to(RoundStatus::Settled));
+        assert!(RoundStatus::Ended.can_transition_to(RoundStatus::Archived));
+        assert!(RoundStatus::Settled.can_transition_to(RoundStatus::Archived));
+        assert!(RoundStatus::Active.can_transition_to(RoundStatus::Cancelled));
+    }
+
+    #[test]
+    fn illegal_transitions_are_rejected() {
+        // Can't skip straight from Pending to Ended/Settled/Archived.
+        assert!(!RoundStatus::Pending.can_transition_to(RoundStatus::Ended));
+        assert!(!RoundStatus::Pending.can_transition_to(RoundStatus::Settled));
+        assert!(!RoundStatus::Pending.can_transition_to(RoundStatus::Archived));
+        // Can't re-activate a round that has already ended.
+        assert!(!RoundStatus::Ended.can_transition_to(RoundStatus::Active));
+        assert!(!RoundStatus::Settled.can_transition_to(RoundStatus::Active));
+        // Can't re-settle or re-archive a terminal round.
+        assert!(!RoundStatus::Settled.can_transition_to(RoundStatus::Settled));
+        assert!(!RoundStatus::Archived.can_transition_to(RoundStatus::Settled));
+        assert!(!RoundStatus::Archived.can_transition_to(RoundStatus::Active));
+        // No-ops aren't legal transitions either.
+        assert!(!RoundStatus::Active.can_transition_to(RoundStatus::Active));
+        // Cancellation is terminal and can't be reached once the round has
+        // already moved on to Ended/Settled/Archived.
+        assert!(!RoundStatus::Ended.can_transition_to(RoundStatus::Cancelled));
+        assert!(!RoundStatus::Cancelled.can_transition_to(RoundStatus::Active));
+    }
+
+    #[test]
+    fn game_state_transition_status_rejects_illegal_moves() {
+        let mut game = GameState {
+            game_id: 0,
+            round: 0,
+            pot_lamports: 0,
+            timer_end: 0,
+            last_buyer: Pubkey::default(),
+            total_keys: 0,
+            round_start: 0,
+            status: RoundStatus::Pending,
+            total_players: 0,
+            total_dividend_pool: 0,
+            next_round_pot: 0,
+            winner_pot: 0,
+            base_price_lamports: 0,
+            price_increment_lamports: 0,
+            timer_extension_secs: 0,
+            max_timer_secs: 0,
+            winner_bps: 0,
+            dividend_bps: 0,
+            next_round_bps: 0,
+            protocol_fee_bps: 0,
+            referral_bonus_bps: 0,
+            protocol_wallet: Pubkey::default(),
+            bump: 0,
+            total_referral_obligations: 0,
+            total_weight: 0,
+            early_bird_key_threshold: 0,
+            early_bird_multiplier_bps: 0,
+            min_purchase_lamports: 0,
+            winner_claim_window_secs: 0,
+            final_hour_pot_threshold_lamports: 0,
+            final_hour_shrink_interval_keys: 0,
+            final_hour_active: false,
+            final_hour_start_keys: 0,
+            pot_milestone_interval_lamports: 0,
+            pot_milestone_bonus_keys: 0,
+            vault_lamports_in: 0,
+            vault_lamports_out: 0,
+            promo_keys_cap_per_round: 0,
+            promo_keys_granted_this_round: 0,
+            transfers_enabled: false,
+            wrapped_keys_enabled: false,
+            wrapped_keys_total: 0,
+            wrapped_weight_total: 0,
+            keeper_fee_lamports: 0,
+            purchase_history_enabled: false,
+            purchase_count: 0,
+            gross_volume_lamports: 0,
+            max_single_buy_lamports: 0,
+            max_single_buyer: Pubkey::default(),
+            time_weighted_dividends_enabled: false,
+            dividend_weight_seconds_total: 0,
+            dividend_seconds_last_update: 0,
+            hook_program: Pubkey::default(),
+            referral_earnings_cap_lamports_per_round: 0,
+            referral_decay_threshold_lamports: 0,
+            referrer_change_cooldown_secs: 0,
+            dividend_merkle_root: None,
+            kyc_required: false,
+            kyc_issuer: Pubkey::default(),
+            dust_reserve: 0,
+            price_cumulative: 0,
+            price_last_update: 0,
+            unclaimed_dividend_policy: crate::state::UnclaimedDividendPolicy::Strand,
+            dividend_claim_window_secs: 0,
+            total_dividend_claimed_lamports: 0,
+            max_timer_extensions_per_window: 0,
+            timer_extension_window_secs: 0,
+            top_referrer_bonus_bps: 0,
+            top_referrer_bonus_pool: 0,
+            raffle_bps: 0,
+            raffle_daily_payout_bps: 0,
+            raffle_pool_lamports: 0,
+            raffle_prize_pool_pending: 0,
+            refund_pool_lamports: 0,
+            bridge_program: Pubkey::default(),
+            max_pot_lamports: 0,
+            pot_overflow_reserve_lamports: 0,
+            timer_extensions_triggered: 0,
+            last_buy_timestamp: 0,
+            buy_interval_seconds_total: 0,
+            pot_checkpoint_25_lamports: 0,
+            pot_checkpoint_50_lamports: 0,
+            pot_checkpoint_75_lamports: 0,
+            pot_checkpoint_25_reached: false,
+            pot_checkpoint_50_reached: false,
+            pot_checkpoint_75_reached: false,
+            auto_payout_winner_enabled: false,
+            min_keys_for_timer_extension: 0,
+            price_sample_interval_slots: 0,
+            rounding_beneficiary: crate::state::RoundingBeneficiary::Protocol,
+            season_length_rounds: 0,
+            season_fee_bps: 0,
+            agent_keys_total: 0,
+            human_keys_total: 0,
+            max_keys_per_round: 0,
+            referral_vesting_enabled: false,
+            biggest_buyer_bonus_bps: 0,
+            biggest_buyer_bonus_pool: 0,
+            biggest_holder_bonus_bps: 0,
+            biggest_holder_bonus_pool: 0,
+            largest_holder: Pubkey::default(),
+            largest_holder_keys: 0,
+            frontend_fee_bps: 0,
+            dividend_apr_window_secs: 0,
+            dividend_apr_window_start: 0,
+            dividend_apr_window_dividend_lamports: 0,
+            min_remaining_secs: 0,
+            agent_platform_fee_share_bps: 0,
+            total_agent_platform_obligations: 0,
+            genesis_config_hash: [0u8; 32],
+        };
+
+        assert!(game.transition_status(RoundStatus::Ended).is_err());
+        assert_eq!(game.status, RoundStatus::Pending);
+
+        assert!(game.transition_status(RoundStatus::Active).is_ok());
+        assert_eq!(game.status, RoundStatus::Active);
+        assert!(!game.winner_claimed());
+
+        assert!(game.transition_status(RoundStatus::Active).is_err());
+        assert!(game.transition_status(RoundStatus::Ended).is_ok());
+        assert!(!game.winner_claimed());
+
+        assert!(game.transition_status(RoundStatus::Settled).is_ok());
+        assert!(game.winner_claimed());
+
+        assert!(game.transition_status(RoundStatus::Ended).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Governs where a buy's leftover bps-split dust (the few lamports integer
+/// division can't assign to winner/dividend/next-round) ends up — see
+/// `instructions::buy_keys`. Snapshotted onto `GameState` from `GlobalConfig`
+/// at round creation, same as every other per-round economic knob.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingBeneficiary {
+    /// Route dust into `GameState::dust_reserve`, swept to the protocol
+    /// wallet via `instructions::sweep_dust_reserve` — the default. Matches
+    /// the pre-existing behavior of every round before this setting existed.
+    Protocol,
+    /// Add dust straight to `GameState::winner_pot`.
+    WinnerPot,
+    /// Add dust straight to `GameState::total_dividend_pool`.
+    DividendPool,
+    /// Add dust straight to `GameState::next_round_pot`.
+    NextRoundPot,
+}
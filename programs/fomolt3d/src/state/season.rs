@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SEASON_LEADERBOARD_SIZE;
+use crate::errors::FomoltError;
+
+/// One slot of `Season::leaderboard` — a player's running purchase volume
+/// and win count for the season. `player == Pubkey::default()` marks an
+/// empty slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeasonLeaderboardEntry {
+    pub player: Pubkey,
+    pub volume_lamports: u64,
+    pub wins: u32,
+}
+
+/// A season's lifecycle — mirrors the `Active` -> settled shape of
+/// `RaffleSnapshot::winning_ticket` and `GameState::status`, just with two
+/// states instead of many since there's nothing for a season to do besides
+/// accrue and then pay out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeasonStatus {
+    Active,
+    Settled,
+}
+
+/// Aggregates player stats across `GlobalConfig::season_length_rounds`
+/// consecutive rounds of a game lineage — see `instructions::buy_keys`,
+/// `instructions::settle_season`, `instructions::claim_season_prize`. PDA
+/// seeds `[b"season", game_id, season_id]`, where `season_id` is
+/// `GameState::current_season_id()`; every round belonging to the same
+/// season resolves to the same account, `init_if_needed` by whichever buy
+/// first lands in it.
+#[account]
+pub struct Season {
+    /// Game lineage this season belongs to
+    pub game_id: u64,
+    /// 0-indexed season number — see `GameState::current_season_id`
+    pub season_id: u64,
+    /// First round (inclusive) that counts toward this season
+    pub start_round: u64,
+    /// Last round (inclusive) that counts toward this season
+    pub end_round: u64,
+    /// Lamports carved out of `GameState::protocol_fee_bps` via
+    /// `GlobalConfig::season_fee_bps`, held in the `season_vault` PDA and
+    /// split across `leaderboard`'s top ranks by `settle_season`
+    pub pool_lamports: u64,
+    /// `Active` while `end_round` hasn't concluded; `Settled` once
+    /// `settle_season` has fixed the payable ranks
+    pub status: SeasonStatus,
+    /// Top `SEASON_LEADERBOARD_SIZE` players by total purchase volume this
+    /// season, sorted descending — see `credit_volume`/`credit_win`
+    pub leaderboard: [SeasonLeaderboardEntry; SEASON_LEADERBOARD_SIZE],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Season {
+    // game_id(8) + season_id(8) + start_round(8) + end_round(8) + pool_lamports(8)
+    // + status(1) + leaderboard(5 * (32 + 8 + 4) = 220) + bump(1) = 262
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 8 + 1 + SEASON_LEADERBOARD_SIZE * (32 + 8 + 4) + 1;
+
+    /// Adds `lamports` to `player`'s running volume in `leaderboard` and
+    /// re-sorts. Same incremental bubble-up-after-credit approach as
+    /// `GameStateExt::credit_referrer` — the array is tiny and already
+    /// sorted going in, so this is cheaper than a full resort. A player
+    /// outside the top `SEASON_LEADERBOARD_SIZE` spenders this season is
+    /// silently dropped from the board, though their actual spend is still
+    /// tracked in full by `PlayerStats`/`GameState::gross_volume_lamports`.
+    pub fn credit_volume(&mut self, player: Pubkey, lamports: u64) -> Result<()> {
+        let mut idx = match self.leaderboard.iter().position(|e| e.player == player) {
+            Some(i) => {
+                self.leaderboard[i].volume_lamports = self.leaderboard[i]
+                    .volume_lamports
+                    .checked_add(lamports)
+                    .ok_or(FomoltError::Overflow)?;
+                i
+            }
+            None => match self
+                .leaderboard
+                .iter()
+                .position(|e| e.player == Pubkey::default())
+            {
+                Some(i) => {
+                    self.leaderboard[i] = SeasonLeaderboardEntry {
+                        player,
+                        volume_lamports: lamports,
+                        wins: 0,
+                    };
+                    i
+                }
+                None => {
+                    let last = SEASON_LEADERBOARD_SIZE - 1;
+                    if lamports <= self.leaderboard[last].volume_lamports {
+                        return Ok(());
+                    }
+                    self.leaderboard[last] = SeasonLeaderboardEntry {
+                        player,
+                        volume_lamports: lamports,
+                        wins: 0,
+                    };
+                    last
+                }
+            },
+        };
+
+        while idx > 0
+            && self.leaderboard[idx].volume_lamports > self.leaderboard[idx - 1].volume_lamports
+        {
+            self.leaderboard.swap(idx, idx - 1);
+            idx -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Increments `player`'s win count if they already hold a leaderboard
+    /// slot. A round winner who never cracked the volume leaderboard simply
+    /// isn't tracked here — same "board leader only" tradeoff
+    /// `GameStateExt::top_referrers` makes for referral earnings.
+    pub fn credit_win(&mut self, player: Pubkey) -> Result<()> {
+        if let Some(entry) = self.leaderboard.iter_mut().find(|e| e.player == player) {
+            entry.wins = entry.wins.checked_add(1).ok_or(FomoltError::Overflow)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_season() -> Season {
+        Season {
+            game_id: 0,
+            season_id: 0,
+            start_round: 1,
+            end_round: 10,
+            pool_lamports: 0,
+            status: SeasonStatus::Active,
+            leaderboard: [SeasonLeaderboardEntry::default(); SEASON_LEADERBOARD_SIZE],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn season_space() {
+        assert_eq!(Season::SPACE, 262);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn season_space_matches_serialized_size() {
+        let season = empty_season();
+        assert_eq!(season.try_to_vec().unwrap().len(), Season::SPACE);
+    }
+
+    #[test]
+    fn credit_volume_maintains_descending_order() {
+        let mut season = empty_season();
+
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+
+        season.credit_volume(a, 100).unwrap();
+        season.credit_volume(b, 300).unwrap();
+        season.credit_volume(c, 200).unwrap();
+        assert_eq!(season.leaderboard[0].player, b);
+        assert_eq!(season.leaderboard[0].volume_lamports, 300);
+
+        season.credit_volume(a, 250).unwrap();
+        assert_eq!(season.leaderboard[0].player, a);
+        assert_eq!(season.leaderboard[0].volume_lamports, 350);
+        assert_eq!(season.leaderboard[1].player, b);
+    }
+
+    #[test]
+    fn credit_volume_drops_below_full_leaderboard() {
+        let mut season = empty_season();
+
+        for i in 0..SEASON_LEADERBOARD_SIZE {
+            season
+                .credit_volume(Pubkey::new_unique(), 1000 - i as u64)
+                .unwrap();
+        }
+        let smallest = season.leaderboard[SEASON_LEADERBOARD_SIZE - 1].volume_lamports;
+
+        let latecomer = Pubkey::new_unique();
+        season.credit_volume(latecomer, 1).unwrap();
+        assert!(season.leaderboard.iter().all(|e| e.player != latecomer));
+        assert_eq!(
+            season.leaderboard[SEASON_LEADERBOARD_SIZE - 1].volume_lamports,
+            smallest
+        );
+
+        season.credit_volume(latecomer, 10_000).unwrap();
+        assert_eq!(season.leaderboard[0].player, latecomer);
+    }
+
+    #[test]
+    fn credit_win_only_tracks_leaderboard_members() {
+        let mut season = empty_season();
+        let a = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+
+        season.credit_volume(a, 100).unwrap();
+        season.credit_win(a).unwrap();
+        season.credit_win(a).unwrap();
+        assert_eq!(season.leaderboard[0].wins, 2);
+
+        // A round winner who never bought a key this season isn't tracked.
+        season.credit_win(stranger).unwrap();
+        assert!(season.leaderboard.iter().all(|e| e.player != stranger));
+    }
+}
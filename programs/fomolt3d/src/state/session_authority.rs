@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Authorizes `delegate` to sign `buy_keys_via_session` on behalf of `owner`,
+/// up to `spend_limit_lamports` total and before `expiry_unix_ts`. Lets an
+/// agent hold its own hot wallet key instead of the principal's main keypair
+/// for every buy — the delegate still fronts the SOL from its own balance,
+/// this account just bounds how much of that spend the owner has authorized
+/// and to whom the resulting keys/dividends are attributed.
+///
+/// Deliberately not scoped by `game_id`: a session authorizes spend on an
+/// owner's behalf regardless of which concurrent game it's spent in, so one
+/// session covers an agent across every game rather than forcing the owner
+/// to mint a fresh delegation per game.
+#[account]
+pub struct SessionAuthority {
+    /// The principal whose keys/dividends purchases are attributed to
+    pub owner: Pubkey,
+    /// The wallet authorized to sign buys on the owner's behalf
+    pub delegate: Pubkey,
+    /// Cumulative lamport cost this session may attribute to `owner`
+    pub spend_limit_lamports: u64,
+    /// Cumulative lamport cost already attributed through this session
+    pub spent_lamports: u64,
+    /// Unix timestamp after which the session can no longer be used
+    pub expiry_unix_ts: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl SessionAuthority {
+    // owner(32) + delegate(32) + spend_limit_lamports(8) + spent_lamports(8)
+    // + expiry_unix_ts(8) + bump(1) = 89
+    pub const SPACE: usize = 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_authority_space() {
+        assert_eq!(SessionAuthority::SPACE, 89);
+    }
+
+    /// SPACE is a hand-summed literal above — this catches it drifting out
+    /// of sync with the struct's actual borsh-serialized size whenever a
+    /// field is added, removed, or resized.
+    #[test]
+    fn session_authority_space_matches_serialized_size() {
+        let session = SessionAuthority {
+            owner: Pubkey::default(),
+            delegate: Pubkey::default(),
+            spend_limit_lamports: 0,
+            spent_lamports: 0,
+            expiry_unix_ts: 0,
+            bump: 0,
+        };
+        assert_eq!(session.try_to_vec().unwrap().len(), SessionAuthority::SPACE);
+    }
+}
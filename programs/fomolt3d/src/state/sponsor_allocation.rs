@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Which pot a `sponsor_pot` deposit is credited to. No keys are issued
+/// either way — this is a pure top-up, distinct from a `buy_keys` purchase.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SponsorAllocation {
+    /// Adds straight to `GameState::winner_pot` — grows what the eventual
+    /// last buyer walks away with.
+    WinnerPot,
+    /// Adds to `GameState::total_dividend_pool` — shared out across current
+    /// keyholders at round end, same as a buy's dividend slice.
+    DividendPool,
+    /// Adds to `GameState::next_round_pot` — carried over to seed the round
+    /// after this one, same as a buy's next-round slice.
+    NextRoundPot,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Governs what happens to a round's dividend pool once
+/// `GameState::dividend_claim_window_secs` has elapsed past `timer_end` and
+/// some of it is still unclaimed — see `instructions::sweep_unclaimed_dividends`.
+/// Snapshotted onto `GameState` from `GlobalConfig` at round creation, same as
+/// every other per-round economic knob.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnclaimedDividendPolicy {
+    /// Leave unclaimed dividends sitting in the old round's vault
+    /// indefinitely — the default. Matches the pre-existing behavior of
+    /// every round before this policy existed.
+    Strand,
+    /// Forward unclaimed dividends into the currently active round's
+    /// `next_round_pot`, the same carry-over bucket a round's own leftover
+    /// pot contribution feeds.
+    RollToNextRound,
+    /// Sweep unclaimed dividends to the protocol wallet, same destination
+    /// `sweep_dust_reserve` already uses for rounding dust.
+    ToProtocol,
+}
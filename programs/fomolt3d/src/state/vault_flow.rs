@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Which way a `VaultFlow` event's lamports moved relative to the vault —
+/// see `events::VaultFlow`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultFlowDirection {
+    /// Lamports moved into the vault.
+    In,
+    /// Lamports moved out of the vault.
+    Out,
+}
+
+/// Why a `VaultFlow` event's lamports moved — lets auditors reconstruct the
+/// vault's full ledger from a single event stream instead of correlating
+/// heterogeneous events and balance diffs across every instruction that
+/// touches a vault. See `events::VaultFlow`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultFlowReason {
+    /// A `buy_keys`/`buy_keys_via_session`/`reveal_buy`/`execute_scheduled_buy` purchase.
+    Buy,
+    /// A `claim`/`claim_dividends`/`claim_with_proof` dividend or auto-claim payout.
+    Claim,
+    /// A `claim_winner`/`end_round` auto-payout of the round's winner pot.
+    ClaimWinner,
+    /// A `claim_referral_earnings`/`consolidate_referral_earnings`/`claim_top_referrer_bonus` payout.
+    Referral,
+    /// A `start_new_round`/`claim_and_roll` transfer of one round's carry-over into the next round's vault.
+    Carry,
+    /// A `sweep_dust_reserve`/`sweep_unclaimed_dividends` sweep of leftover lamports.
+    Sweep,
+    /// A `claim_biggest_buyer_bonus`/`claim_biggest_holder_bonus` payout.
+    RoundBonus,
+    /// A `claim_agent_platform_earnings` payout.
+    AgentPlatform,
+}
@@ -0,0 +1,62 @@
+// Integration tests: `PlayerState::strategy_tag`, `GameState::agent_keys_total`/
+// `human_keys_total`, and the `AgentAction` event — lets off-chain analysis
+// split AI-agent behavior from human play without heuristics.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_agent_buy_records_strategy_tag_and_agent_key_total() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let ix = buy_keys_ix_with_strategy_tag(&p1.pubkey(), 1, 5, 42, &pw);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert!(player.is_agent);
+    assert_eq!(player.strategy_tag, 42);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.agent_keys_total, 5);
+    assert_eq!(game.human_keys_total, 0);
+}
+
+#[test]
+fn test_human_buy_does_not_perturb_agent_key_total() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert!(!player.is_agent);
+    assert_eq!(player.strategy_tag, 0);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.agent_keys_total, 0);
+    assert_eq!(game.human_keys_total, 5);
+}
+
+#[test]
+fn test_agent_and_human_key_totals_sum_correctly_across_mixed_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let agent = Keypair::new();
+    let human = Keypair::new();
+    register(&mut svm, &agent, 1, false, None);
+    register(&mut svm, &human, 1, false, None);
+
+    let ix = buy_keys_ix_with_strategy_tag(&agent.pubkey(), 1, 3, 7, &pw);
+    send_tx(&mut svm, &[ix], &agent, &[&agent]).unwrap();
+    buy(&mut svm, &human, 1, 4, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.agent_keys_total, 3);
+    assert_eq!(game.human_keys_total, 4);
+    assert_eq!(game.total_keys, 7);
+}
@@ -0,0 +1,81 @@
+// Integration tests: set_preferences and dividend auto-compounding on claim
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_set_preferences_updates_auto_compound_flag() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 1, &pw, None);
+
+    assert!(!get_player(&svm, &p1.pubkey()).auto_compound);
+
+    let ix = set_preferences_ix(&p1.pubkey(), true);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert!(get_player(&svm, &p1.pubkey()).auto_compound);
+}
+
+#[test]
+fn test_claim_with_auto_compound_buys_keys_in_current_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None);
+
+    let ix = set_preferences_ix(&p1.pubkey(), true);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    expire_round(&mut svm, 1);
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let balance_before = get_balance(&svm, &p1.pubkey());
+
+    let ix = claim_ix_with_options(&p1.pubkey(), 1, false, Some(2));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert_eq!(player.current_round, 2);
+    assert!(player.keys > 0);
+    assert!(player.dividend_weight > 0);
+
+    let round_2 = get_game(&svm, 2);
+    assert_eq!(round_2.total_keys, player.keys);
+    assert!(round_2.pot_lamports > 0);
+
+    // Any leftover dust that didn't buy a whole key is still cashed out.
+    let balance_after = get_balance(&svm, &p1.pubkey());
+    assert!(balance_after >= balance_before);
+}
+
+#[test]
+fn test_claim_with_auto_compound_requires_current_game_state() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = set_preferences_ix(&p1.pubkey(), true);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    expire_round(&mut svm, 1);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // auto_compound is enabled but the current-round accounts are omitted.
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+}
@@ -0,0 +1,182 @@
+// Integration tests: admin-managed blocklist enforcement in buy_keys and claim
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_admin_can_add_and_remove_from_blocklist() {
+    let (mut svm, admin, _pw) = setup_game();
+    let wallet = Keypair::new().pubkey();
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &wallet, false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = remove_from_blocklist_ix(&admin.pubkey(), &wallet);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+}
+
+#[test]
+fn test_non_admin_cannot_add_to_blocklist() {
+    let (mut svm, _admin, _pw) = setup_game();
+    let attacker = Keypair::new();
+    airdrop(&mut svm, &attacker.pubkey(), 10_000_000_000);
+    let wallet = Keypair::new().pubkey();
+
+    let ix = add_to_blocklist_ix(&attacker.pubkey(), &wallet, false);
+    let err = send_tx_expect_err(&mut svm, &[ix], &attacker, &[&attacker]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_non_admin_cannot_remove_from_blocklist() {
+    let (mut svm, admin, _pw) = setup_game();
+    let attacker = Keypair::new();
+    airdrop(&mut svm, &attacker.pubkey(), 10_000_000_000);
+    let wallet = Keypair::new().pubkey();
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &wallet, false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = remove_from_blocklist_ix(&attacker.pubkey(), &wallet);
+    let err = send_tx_expect_err(&mut svm, &[ix], &attacker, &[&attacker]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_wallet_cannot_buy_keys() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_ix_with_block_entry(&p1.pubkey(), 1, 5, false, &pw, None, true);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_wallet_cannot_claim_when_claims_disallowed() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_ix_with_block_entry(&p1.pubkey(), 1, true);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_wallet_can_claim_when_claims_allowed() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    // allow_claim = true: can still withdraw winnings already owed, just can't buy
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), true);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_ix_with_block_entry(&p1.pubkey(), 1, true);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+}
+
+#[test]
+fn test_blocked_wallet_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Pre-fix, `block_entry` was an `Option<Account>` — a blocked wallet
+    // could substitute the program-ID "None" sentinel to skip the blocklist
+    // check entirely. `block_entry` is now required and seeds-constrained,
+    // so the substitution must be rejected before the handler ever runs.
+    let ix =
+        buy_keys_ix_with_raw_block_entry(&p1.pubkey(), 1, 5, false, &pw, None, PROGRAM_ID);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_wallet_cannot_bypass_claim_block_entry_with_wrong_pda() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Substituting another wallet's (uninitialized) block_entry PDA must be
+    // rejected the same way as the program-ID sentinel — the seeds
+    // constraint ties `block_entry` to `player`, not to whatever key the
+    // caller happens to supply.
+    let decoy = Keypair::new().pubkey();
+    let (decoy_block_entry, _) = blocked_entry_pda(&decoy);
+    let ix = claim_ix_with_raw_block_entry(&p1.pubkey(), 1, decoy_block_entry);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_unblocking_restores_normal_buy_behavior() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = remove_from_blocklist_ix(&admin.pubkey(), &p1.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Now unblocked — normal buy with the None sentinel succeeds
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 5);
+}
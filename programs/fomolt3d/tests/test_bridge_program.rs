@@ -0,0 +1,133 @@
+// Integration tests: cross-chain conclusion attestation CPI fired from `end_round`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+#[test]
+fn disabled_by_default_end_round_succeeds_without_bridge_account() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = end_round_ix(&player.pubkey(), 1, &player.pubkey());
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.status, RoundStatusData::Ended);
+    let _ = admin;
+}
+
+#[test]
+fn missing_bridge_account_rejected_when_configured() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let bridge_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        bridge_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+    expire_round(&mut svm, 1);
+
+    // No bridge account supplied (sentinel) even though one is configured.
+    let ix = end_round_ix(&player.pubkey(), 1, &player.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("MissingBridgeProgram") || err.contains("custom program error"),
+        "Expected MissingBridgeProgram error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn wrong_bridge_account_rejected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let bridge_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        bridge_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+    expire_round(&mut svm, 1);
+
+    let wrong_program = Pubkey::new_unique();
+    let ix = end_round_ix_with_bridge(&player.pubkey(), 1, None, false, &wrong_program, &[], &player.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("BridgeProgramMismatch") || err.contains("custom program error"),
+        "Expected BridgeProgramMismatch error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn too_many_bridge_accounts_rejected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let bridge_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        bridge_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+    expire_round(&mut svm, 1);
+
+    let extra: Vec<AccountMeta> = (0..5)
+        .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+        .collect();
+    let ix = end_round_ix_with_bridge(
+        &player.pubkey(),
+        1,
+        None,
+        false,
+        &bridge_program,
+        &extra,
+        &player.pubkey(),
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("TooManyBridgeAccounts") || err.contains("custom program error"),
+        "Expected TooManyBridgeAccounts error, got: {}",
+        err
+    );
+}
@@ -0,0 +1,129 @@
+// Integration tests: buying after `timer_end` either redirects into an
+// already-started next round or is rejected with `BuyRejectedRoundEnded`,
+// instead of the old silent no-op
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_buy_after_expiry_with_no_next_round_is_rejected() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    expire_round(&mut svm, 1);
+
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    assert!(!game_after.active, "Round should still auto-end");
+    assert_eq!(game_after.total_keys, 0, "No keys bought on round 1");
+}
+
+#[test]
+fn test_buy_after_expiry_redirects_into_active_next_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    svm.expire_blockhash();
+
+    // Start round 2 before the redirected buy lands — start_new_round's own
+    // auto-end check cranks round 1's Active -> Ended transition.
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let buyer = Keypair::new();
+    airdrop(&mut svm, &buyer.pubkey(), 10_000_000_000);
+
+    let ix = buy_keys_ix_with_next_round(DEFAULT_GAME_ID, &buyer.pubkey(), 1, 2, 3, false, &pw);
+    send_tx(&mut svm, &[ix], &buyer, &[&buyer]).unwrap();
+
+    let round2 = get_game(&svm, 2);
+    assert_eq!(round2.total_keys, 3, "Redirected buy should land in round 2");
+    assert_eq!(round2.last_buyer, buyer.pubkey().to_bytes());
+
+    let player_after = get_player(&svm, &buyer.pubkey());
+    assert_eq!(player_after.keys, 3);
+    assert_eq!(player_after.current_round, 2);
+}
+
+#[test]
+fn test_buy_after_expiry_redirect_ineligible_without_matching_round_number() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Buyer targets round 1 as the "next round" even though round 2 is the
+    // real successor — should be treated the same as no next round at all.
+    let buyer = Keypair::new();
+    airdrop(&mut svm, &buyer.pubkey(), 10_000_000_000);
+    let ix = buy_keys_ix_with_next_round(DEFAULT_GAME_ID, &buyer.pubkey(), 1, 1, 3, false, &pw);
+    send_tx(&mut svm, &[ix], &buyer, &[&buyer]).unwrap();
+
+    let round2 = get_game(&svm, 2);
+    assert_eq!(round2.total_keys, 0, "Mismatched next round must not receive the buy");
+}
+
+#[test]
+fn test_buy_after_expiry_still_pays_keeper_bounty_on_redirect() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        keeper_fee_lamports: 5_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = fund_keeper_budget_ix(&admin.pubkey(), 50_000_000);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let buyer = Keypair::new();
+    airdrop(&mut svm, &buyer.pubkey(), 10_000_000_000);
+    let balance_before = get_balance(&svm, &buyer.pubkey());
+
+    let ix = buy_keys_ix_with_next_round(DEFAULT_GAME_ID, &buyer.pubkey(), 1, 2, 3, false, &pw);
+    send_tx(&mut svm, &[ix], &buyer, &[&buyer]).unwrap();
+
+    let round2 = get_game(&svm, 2);
+    assert_eq!(round2.total_keys, 3, "Buy should still redirect into round 2");
+
+    // Buyer paid for their own purchase into round 2 but was reimbursed the
+    // keeper bounty for cranking round 1's expiry in the same transaction.
+    let balance_after = get_balance(&svm, &buyer.pubkey());
+    let cost = round2.pot_lamports; // only purchase in this round so far
+    assert!(
+        balance_after > balance_before - cost,
+        "Expected keeper bounty to offset the redirected purchase cost: before={}, after={}, cost={}",
+        balance_before,
+        balance_after,
+        cost
+    );
+}
@@ -2,7 +2,7 @@
 mod helpers;
 
 use helpers::*;
-use solana_sdk::{signature::Keypair, signer::Signer};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
 
 #[test]
 fn test_first_buy_creates_player_state() {
@@ -231,3 +231,100 @@ fn test_total_players_increments() {
     let game = get_game(&svm, 1);
     assert_eq!(game.total_players, 2);
 }
+
+#[test]
+fn test_buy_below_min_purchase_lamports_rejected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    // Single key costs base_price_lamports (10_000_000); set the minimum
+    // above that so the cheapest possible buy is rejected.
+    let params = ConfigParamsData {
+        protocol_wallet,
+        min_purchase_lamports: 20_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    airdrop(&mut svm, &player.pubkey(), 100_000_000_000);
+
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &protocol_wallet, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("BelowMinimumPurchase") || err.contains("custom program error"),
+        "Expected BelowMinimumPurchase error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_buy_dust_from_bps_truncation_routes_to_next_round_pot() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    // A tiny base price (3 lamports) makes the 48/45/7 bps splits truncate:
+    // winner = 1, dividend = 1, next_round = 0, leaving 1 lamport of dust.
+    let params = ConfigParamsData {
+        protocol_wallet,
+        base_price_lamports: 3,
+        price_increment_lamports: 1,
+        min_purchase_lamports: 0,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    airdrop(&mut svm, &player.pubkey(), 100_000_000_000);
+
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &protocol_wallet, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let game = get_game(&svm, 1);
+    // Cost = 3, no protocol fee or referral at this size, so pot_contribution
+    // = 3. Every lamport of it must land in exactly one of the three pools.
+    assert_eq!(
+        game.winner_pot + game.total_dividend_pool + game.next_round_pot,
+        3
+    );
+    assert_eq!(game.next_round_pot, 1, "truncation dust should land here");
+}
+
+#[test]
+fn test_buy_against_round_two_behind_current_is_rejected() {
+    // `game_state` must be config.latest_round or the round just behind it
+    // (the just-ended round, allowed for the auto-end redirect path) — see
+    // `GlobalConfig::latest_round` and `FomoltError::StaleRound`.
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    let new_round = complete_round_and_start_next(&mut svm, &admin, 1, &p1);
+    assert_eq!(new_round, 2);
+    svm.expire_blockhash();
+
+    // Round 2 is empty — start round 3 directly, no claim needed.
+    let game2 = get_game(&svm, 2);
+    set_clock(&mut svm, game2.timer_end + 1);
+    airdrop(&mut svm, &admin.pubkey(), 10_000_000_000);
+    let ix = start_new_round_ix(&admin.pubkey(), 2);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Round 1 is now two rounds behind config.latest_round (3) — rejected.
+    let ix = buy_keys_ix(&p1.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(!err.is_empty(), "a buy against a two-rounds-stale game_state must be rejected");
+}
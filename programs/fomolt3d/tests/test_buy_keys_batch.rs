@@ -0,0 +1,120 @@
+// Integration tests: `buy_keys_batch` collapsing several tranches into one instruction.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn batch_matches_single_buy_of_the_summed_amount() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let batch_player = Keypair::new();
+    register(&mut svm, &batch_player, 1, false, None);
+    let ix = buy_keys_batch_ix(&batch_player.pubkey(), 1, &[2, 3, 5], false, &pw, None);
+    send_tx(&mut svm, &[ix], &batch_player, &[&batch_player]).unwrap();
+
+    let single_player = Keypair::new();
+    register(&mut svm, &single_player, 1, false, None);
+    buy(&mut svm, &single_player, 1, 10, &pw, None);
+
+    let batch_state = get_player(&svm, &batch_player.pubkey());
+    let single_state = get_player(&svm, &single_player.pubkey());
+    assert_eq!(batch_state.keys, single_state.keys);
+    assert_eq!(batch_state.dividend_weight, single_state.dividend_weight);
+}
+
+#[test]
+fn batch_crossing_early_bird_threshold_splits_weight_correctly() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        early_bird_key_threshold: 10,
+        early_bird_multiplier_bps: 20_000, // 2x
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    // Batch buyer starts at supply 8, buys 2+4 (straddles the threshold of 10).
+    let batch_player = Keypair::new();
+    register(&mut svm, &batch_player, 1, false, None);
+    let seed_buyer = Keypair::new();
+    register(&mut svm, &seed_buyer, 1, false, None);
+    buy(&mut svm, &seed_buyer, 1, 8, &protocol_wallet, None);
+
+    let ix = buy_keys_batch_ix(
+        &batch_player.pubkey(),
+        1,
+        &[2, 4],
+        false,
+        &protocol_wallet,
+        None,
+    );
+    send_tx(&mut svm, &[ix], &batch_player, &[&batch_player]).unwrap();
+
+    let batch_state = get_player(&svm, &batch_player.pubkey());
+    // 2 keys in-window at 2x (10_000 * 2 bps each) + 4 keys out-of-window at 1x.
+    let expected_weight = 2 * 20_000 + 4 * 10_000;
+    assert_eq!(batch_state.dividend_weight, expected_weight);
+}
+
+#[test]
+fn empty_batch_rejected() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    let ix = buy_keys_batch_ix(&player.pubkey(), 1, &[], false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("EmptyBatch") || err.contains("custom program error"),
+        "Expected EmptyBatch error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn oversized_batch_rejected() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    let amounts = vec![1u64; 33];
+    let ix = buy_keys_batch_ix(&player.pubkey(), 1, &amounts, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("TooManyBatchPurchases") || err.contains("custom program error"),
+        "Expected TooManyBatchPurchases error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn batch_pays_referral_bonus_once_on_the_summed_cost() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    buy(&mut svm, &referrer, 1, 1, &pw, None);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    let ix = buy_keys_batch_ix(
+        &player.pubkey(),
+        1,
+        &[1, 1, 1],
+        false,
+        &pw,
+        Some(&referrer.pubkey()),
+    );
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let referrer_state = get_player(&svm, &referrer.pubkey());
+    assert!(referrer_state.referral_earnings_lamports > 0);
+}
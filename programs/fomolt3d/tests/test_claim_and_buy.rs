@@ -0,0 +1,115 @@
+// Integration tests: `buy_keys`'s optional `prior_game_state`/`prior_vault`
+// accounts, which auto-settle a returning player's already-concluded prior
+// round before the new purchase proceeds — sparing the two-transaction
+// `claim_dividends`/`claim_winner` + `buy_keys` sequence normally forced by
+// `FomoltError::MustClaimPreviousRound`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_buy_auto_claims_prior_round_dividends_and_buys_into_current() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None); // p2 = last buyer / winner of round 1
+
+    expire_round(&mut svm, 1);
+
+    // Start round 2 directly — p1 never claims round 1's dividends first.
+    airdrop(&mut svm, &admin.pubkey(), 10_000_000_000);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let round1 = get_game(&svm, 1);
+    let p1_before = get_player(&svm, &p1.pubkey());
+    let expected_dividend =
+        expected_dividend_share(p1_before.dividend_weight, round1.total_dividend_pool, round1.total_weight);
+    assert!(expected_dividend > 0, "p1 must have a real dividend share owed from round 1");
+
+    let sol_before = svm.get_balance(&p1.pubkey()).unwrap();
+
+    let ix = buy_keys_ix_with_prior_round(DEFAULT_GAME_ID, &p1.pubkey(), 2, 1, 3, false, &pw);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    // p1 is now a fresh participant in round 2, holding the keys just bought.
+    let p1_after = get_player(&svm, &p1.pubkey());
+    assert_eq!(p1_after.current_round, 2);
+    assert_eq!(p1_after.keys, 3);
+    assert_eq!(p1_after.claimed_dividends_lamports, expected_dividend);
+
+    // Round 1's claimed total advanced and the payout landed in p1's wallet
+    // (net of the cost of the round-2 purchase they just made).
+    let round1_after = get_game(&svm, 1);
+    assert_eq!(round1_after.total_dividend_claimed_lamports, expected_dividend);
+
+    let sol_after = svm.get_balance(&p1.pubkey()).unwrap();
+    assert!(
+        sol_after + expected_dividend > sol_before,
+        "p1's wallet must net the round-1 payout minus the round-2 purchase cost"
+    );
+
+    // Re-claiming round 1 again is now a no-op path — current_round no longer
+    // points at it, so a second auto-claim attempt has nothing to settle.
+    let round2 = get_game(&svm, 2);
+    assert_eq!(round2.total_players, 2, "p2 registered fresh, p1 re-entered");
+}
+
+#[test]
+fn test_buy_auto_claims_prior_round_winner_prize() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None); // p1 = last buyer / winner of round 1
+
+    expire_round(&mut svm, 1);
+
+    airdrop(&mut svm, &admin.pubkey(), 10_000_000_000);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let round1 = get_game(&svm, 1);
+    assert!(round1.winner_pot > 0);
+
+    let ix = buy_keys_ix_with_prior_round(DEFAULT_GAME_ID, &p1.pubkey(), 2, 1, 1, false, &pw);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let p1_after = get_player(&svm, &p1.pubkey());
+    assert_eq!(p1_after.current_round, 2);
+    assert_eq!(p1_after.keys, 1);
+
+    let stats = get_player_stats(&svm, &p1.pubkey());
+    assert_eq!(stats.rounds_won, 1);
+}
+
+#[test]
+fn test_buy_without_prior_round_still_rejects_stale_player() {
+    // Baseline: omitting `prior_game_state`/`prior_vault` must still hit
+    // `MustClaimPreviousRound`, exactly as before this instruction gained
+    // the auto-claim accounts.
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    airdrop(&mut svm, &admin.pubkey(), 10_000_000_000);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_ix(&p1.pubkey(), 2, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(!err.is_empty(), "a stale player buying with no prior-round accounts must still be rejected");
+}
@@ -0,0 +1,167 @@
+// Integration tests: `claim_dividends` and `claim_winner`, the split-out
+// replacements for the combined `claim` — independent double-claim guards,
+// either one claimable without forcing the other.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_claim_winner_alone_does_not_touch_dividends() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None); // p2 = last buyer / winner
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_winner_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+
+    // The winner's dividend share is still sitting there, unclaimed.
+    let player = get_player(&svm, &p2.pubkey());
+    assert_eq!(player.claimed_dividends_lamports, 0);
+    assert!(player.current_round == 1, "claim_winner must not reset current_round");
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_dividend_claimed_lamports, 0);
+}
+
+#[test]
+fn test_claim_dividends_alone_leaves_winner_prize_claimable() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None); // p2 = winner
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_dividends_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert!(!game.winner_claimed, "claim_dividends must not settle the round");
+
+    // The winner prize is still claimable via claim_winner afterwards.
+    let ix = claim_winner_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert!(game.winner_claimed);
+}
+
+#[test]
+fn test_claim_dividends_twice_fails() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_dividends_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = claim_dividends_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("PlayerNotInRound") || err.contains("custom program error"),
+        "a second claim_dividends should hit the current_round guard: {err}"
+    );
+}
+
+#[test]
+fn test_claim_winner_twice_fails() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None); // p2 = winner
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_winner_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = claim_winner_ix(&p2.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p2, &[&p2]);
+    assert!(
+        err.contains("WinnerAlreadyClaimed") || err.contains("custom program error"),
+        "a second claim_winner should hit the winner_claimed() guard: {err}"
+    );
+}
+
+#[test]
+fn test_claim_winner_rejects_non_winner() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None); // p2 = winner, p1 is not
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_winner_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("NotWinner") || err.contains("custom program error"),
+        "a non-winner calling claim_winner should be rejected: {err}"
+    );
+}
+
+#[test]
+fn test_claim_dividends_and_claim_winner_together_match_combined_claim() {
+    // Split totals should equal what the old combined `claim` would have paid.
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None); // p2 = winner
+
+    let game = expire_round(&mut svm, 1);
+    let expected_winner_pot = game.winner_pot;
+
+    let before = get_balance(&svm, &p2.pubkey());
+    let ix = claim_dividends_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+    svm.expire_blockhash();
+    let ix = claim_winner_ix(&p2.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p2, &[&p2]).unwrap();
+    let after = get_balance(&svm, &p2.pubkey());
+
+    let total_paid = after + 10_000 - before; // add back both tx fees
+    assert_eq!(
+        total_paid,
+        expected_winner_pot + (get_player_stats(&svm, &p2.pubkey()).lifetime_dividends_earned),
+        "split claims should sum to the same payout the combined claim would have made"
+    );
+}
@@ -0,0 +1,157 @@
+// Integration tests: `claim_to_stake` — delegating a claim payout to the
+// admin-approved stake vote account instead of cashing it out.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn rejects_unapproved_vote_account_when_none_configured() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    let stake_account = Keypair::new();
+    let vote_account = Pubkey::new_unique();
+    let ix = claim_to_stake_ix(&p1.pubkey(), 1, &stake_account.pubkey(), &vote_account);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1, &stake_account]);
+    assert!(
+        err.contains("StakeVoteAccountNotApproved") || err.contains("custom program error"),
+        "Expected StakeVoteAccountNotApproved, got: {}",
+        err
+    );
+}
+
+#[test]
+fn rejects_vote_account_that_does_not_match_config() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let approved_stake_vote_account = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        approved_stake_vote_account,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &protocol_wallet, None);
+    buy(&mut svm, &p2, 1, 10, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    let stake_account = Keypair::new();
+    let wrong_vote_account = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = claim_to_stake_ix(&p1.pubkey(), 1, &stake_account.pubkey(), &wrong_vote_account);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1, &stake_account]);
+    assert!(
+        err.contains("StakeVoteAccountNotApproved") || err.contains("custom program error"),
+        "Expected StakeVoteAccountNotApproved, got: {}",
+        err
+    );
+}
+
+#[test]
+fn rejects_wrong_stake_program_account() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let approved_stake_vote_account = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        approved_stake_vote_account,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &protocol_wallet, None);
+    buy(&mut svm, &p2, 1, 10, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    let stake_account = Keypair::new();
+    let bogus_stake_program = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = claim_to_stake_ix_with_stake_program(
+        &p1.pubkey(),
+        1,
+        &stake_account.pubkey(),
+        &approved_stake_vote_account,
+        &bogus_stake_program,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1, &stake_account]);
+    assert!(
+        err.contains("InvalidStakeProgramAccount") || err.contains("custom program error"),
+        "Expected InvalidStakeProgramAccount, got: {}",
+        err
+    );
+}
+
+#[test]
+fn rejects_claim_to_stake_while_round_still_active() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let approved_stake_vote_account = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        approved_stake_vote_account,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &protocol_wallet, None);
+
+    let stake_account = Keypair::new();
+    let ix = claim_to_stake_ix(
+        &p1.pubkey(),
+        1,
+        &stake_account.pubkey(),
+        &approved_stake_vote_account,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1, &stake_account]);
+    assert!(
+        err.contains("GameStillActive") || err.contains("custom program error"),
+        "Expected GameStillActive, got: {}",
+        err
+    );
+}
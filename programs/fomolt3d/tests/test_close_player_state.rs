@@ -0,0 +1,90 @@
+// Integration tests: closing and reclaiming rent for PlayerState accounts
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_close_player_state_refunds_rent_after_claim() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let balance_before = get_balance(&svm, &p1.pubkey());
+    let rent = get_balance(&svm, &player_pda(&p1.pubkey()).0);
+
+    let ix = close_player_state_ix(&p1.pubkey());
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_balance(&svm, &player_pda(&p1.pubkey()).0), 0);
+    assert_eq!(get_balance(&svm, &p1.pubkey()), balance_before + rent);
+}
+
+#[test]
+fn test_close_player_state_fails_with_keys_held() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = close_player_state_ix(&p1.pubkey());
+    send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+}
+
+#[test]
+fn test_close_player_state_fails_mid_round() {
+    // current_round != 0 while the round is still active blocks the close,
+    // even though a second player holds the keys weight.
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 1, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = close_player_state_ix(&p2.pubkey());
+    send_tx_expect_err(&mut svm, &[ix], &p2, &[&p2]);
+}
+
+#[test]
+fn test_player_state_reinitializable_after_close() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = close_player_state_ix(&p1.pubkey());
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    // Permissionless crank starts round 2, then buying again re-creates
+    // PlayerState from scratch via init_if_needed, with no stale fields.
+    let ix = start_new_round_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    buy(&mut svm, &p1, 2, 3, &pw, None);
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert_eq!(player.keys, 3);
+    assert_eq!(player.current_round, 2);
+    assert_eq!(player.claimed_dividends_lamports, 0);
+    assert_eq!(player.referral_earnings_lamports, 0);
+}
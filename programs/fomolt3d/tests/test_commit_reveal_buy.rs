@@ -0,0 +1,136 @@
+// Integration tests: commit_buy / reveal_buy anti-sniping purchase flow.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn salt(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+#[test]
+fn reveal_matches_direct_buy() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let game_before = get_game(&svm, 1);
+    let budget = expected_cost(game_before.total_keys, 5) * 2;
+    let hash = compute_commitment_hash(5, &salt(1), &p1.pubkey());
+
+    let commit_ix = commit_buy_ix(&p1.pubkey(), 1, hash, budget);
+    send_tx(&mut svm, &[commit_ix], &p1, &[&p1]).unwrap();
+
+    let commitment = get_buy_commitment(&svm, 1, &p1.pubkey());
+    assert_eq!(commitment.buyer_pubkey(), p1.pubkey());
+    assert_eq!(commitment.total_keys_at_commit, game_before.total_keys);
+    assert_eq!(commitment.budget_lamports, budget);
+
+    advance_slot(&mut svm, 2);
+
+    let buyer_balance_before = get_balance(&svm, &p1.pubkey());
+    let reveal_ix = reveal_buy_ix(&p1.pubkey(), 1, 5, salt(1), false, &pw, None);
+    send_tx(&mut svm, &[reveal_ix], &p1, &[&p1]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    let player = get_player(&svm, &p1.pubkey());
+    let expected = expected_cost(game_before.total_keys, 5);
+
+    assert_eq!(player.keys, 5);
+    assert_eq!(game_after.total_keys, 5);
+    assert_eq!(game_after.pot_lamports, expected);
+
+    // Excess budget (minus the cost and tx fees) comes back to the buyer.
+    let buyer_balance_after = get_balance(&svm, &p1.pubkey());
+    assert!(buyer_balance_after > buyer_balance_before + budget - expected - 1_000_000);
+
+    // The commitment account is closed by reveal_buy.
+    assert!(svm
+        .get_account(&commitment_pda(&game_pda(1).0, &p1.pubkey()).0)
+        .is_none());
+}
+
+#[test]
+fn reveal_rejected_on_hash_mismatch() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let hash = compute_commitment_hash(5, &salt(1), &p1.pubkey());
+    let commit_ix = commit_buy_ix(&p1.pubkey(), 1, hash, 1_000_000_000);
+    send_tx(&mut svm, &[commit_ix], &p1, &[&p1]).unwrap();
+
+    advance_slot(&mut svm, 2);
+
+    // Wrong salt produces a different hash than what was committed.
+    let reveal_ix = reveal_buy_ix(&p1.pubkey(), 1, 5, salt(2), false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[reveal_ix], &p1, &[&p1]);
+    assert!(err.contains("CommitmentHashMismatch") || err.contains("6"));
+}
+
+#[test]
+fn reveal_rejected_in_same_slot_as_commit() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let hash = compute_commitment_hash(5, &salt(1), &p1.pubkey());
+    let commit_ix = commit_buy_ix(&p1.pubkey(), 1, hash, 1_000_000_000);
+    let reveal_ix = reveal_buy_ix(&p1.pubkey(), 1, 5, salt(1), false, &pw, None);
+
+    // Both instructions land in the same transaction, hence the same slot.
+    let err = send_tx_expect_err(&mut svm, &[commit_ix, reveal_ix], &p1, &[&p1]);
+    assert!(err.contains("RevealTooSoon") || err.contains("Error"));
+}
+
+#[test]
+fn reveal_prices_off_commit_time_supply_not_live_supply() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    let game_at_commit = get_game(&svm, 1);
+    let budget = expected_cost(game_at_commit.total_keys, 5) * 2;
+    let hash = compute_commitment_hash(5, &salt(1), &p1.pubkey());
+    let commit_ix = commit_buy_ix(&p1.pubkey(), 1, hash, budget);
+    send_tx(&mut svm, &[commit_ix], &p1, &[&p1]).unwrap();
+
+    // p2 buys in between commit and reveal, moving the live curve position —
+    // reveal_buy must still price p1's 5 keys off the supply as of commit.
+    buy(&mut svm, &p2, 1, 20, &pw, None);
+
+    advance_slot(&mut svm, 2);
+
+    let reveal_ix = reveal_buy_ix(&p1.pubkey(), 1, 5, salt(1), false, &pw, None);
+    send_tx(&mut svm, &[reveal_ix], &p1, &[&p1]).unwrap();
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert_eq!(player.keys, 5);
+
+    let expected = expected_cost(game_at_commit.total_keys, 5);
+    let game_after = get_game(&svm, 1);
+    // 20 (p2) + 5 (p1) keys total; p1's slice of pot_lamports reflects the
+    // frozen, not live, price.
+    assert_eq!(game_after.total_keys, 25);
+    let p2_cost = expected_cost(game_at_commit.total_keys, 20);
+    assert_eq!(game_after.pot_lamports, p2_cost + expected);
+}
+
+#[test]
+fn commit_buy_rejects_zero_budget() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let hash = compute_commitment_hash(5, &salt(1), &p1.pubkey());
+    let commit_ix = commit_buy_ix(&p1.pubkey(), 1, hash, 0);
+    let err = send_tx_expect_err(&mut svm, &[commit_ix], &p1, &[&p1]);
+    assert!(err.contains("InvalidCommitBudget") || err.contains("Error"));
+}
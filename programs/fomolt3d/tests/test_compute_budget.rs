@@ -0,0 +1,101 @@
+// Compute budget regression tests: record compute units consumed by each
+// instruction and assert they stay under a generous ceiling. These aren't
+// meant to pin exact CU counts (those shift with every Anchor/solana-program
+// point release) — they exist to catch a logic change that blows past
+// Solana's per-transaction compute limit (200_000 CU default, 1.4M max with
+// a compute budget request) long before it reaches mainnet.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// Generous ceiling for a single buy_keys call (no referrer). Well under
+/// Solana's default 200_000 CU/instruction budget with headroom for future
+/// fields.
+const BUY_KEYS_CU_CEILING: u64 = 120_000;
+
+/// buy_keys with a referrer does more work (extra account loads, an extra
+/// CPI transfer, referral bookkeeping) so gets a higher ceiling.
+const BUY_KEYS_WITH_REFERRER_CU_CEILING: u64 = 150_000;
+
+const CLAIM_CU_CEILING: u64 = 100_000;
+
+const START_NEW_ROUND_CU_CEILING: u64 = 100_000;
+
+#[test]
+fn test_buy_keys_compute_units_under_ceiling() {
+    let (mut svm, _admin, pw) = setup_game();
+    let buyer = Keypair::new();
+    register(&mut svm, &buyer, 1, false, None);
+
+    let ix = buy_keys_ix(&buyer.pubkey(), 1, 5, false, &pw, None);
+    let cu = send_tx_compute_units(&mut svm, &[ix], &buyer, &[&buyer]);
+    assert!(
+        cu <= BUY_KEYS_CU_CEILING,
+        "buy_keys consumed {} CU, expected <= {}",
+        cu,
+        BUY_KEYS_CU_CEILING
+    );
+}
+
+#[test]
+fn test_buy_keys_with_referrer_compute_units_under_ceiling() {
+    let (mut svm, _admin, pw) = setup_game();
+    let referrer = Keypair::new();
+    let buyer = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &buyer, 1, false, None);
+    buy(&mut svm, &referrer, 1, 1, &pw, None);
+    svm.expire_blockhash();
+
+    let ix = buy_keys_ix(&buyer.pubkey(), 1, 5, false, &pw, Some(&referrer.pubkey()));
+    let cu = send_tx_compute_units(&mut svm, &[ix], &buyer, &[&buyer]);
+    assert!(
+        cu <= BUY_KEYS_WITH_REFERRER_CU_CEILING,
+        "buy_keys with referrer consumed {} CU, expected <= {}",
+        cu,
+        BUY_KEYS_WITH_REFERRER_CU_CEILING
+    );
+}
+
+#[test]
+fn test_claim_compute_units_under_ceiling() {
+    let (mut svm, _admin, pw) = setup_game();
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 1, false, None);
+    buy(&mut svm, &winner, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    svm.expire_blockhash();
+
+    let ix = claim_ix(&winner.pubkey(), 1);
+    let cu = send_tx_compute_units(&mut svm, &[ix], &winner, &[&winner]);
+    assert!(
+        cu <= CLAIM_CU_CEILING,
+        "claim consumed {} CU, expected <= {}",
+        cu,
+        CLAIM_CU_CEILING
+    );
+}
+
+#[test]
+fn test_start_new_round_compute_units_under_ceiling() {
+    let (mut svm, admin, pw) = setup_game();
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 1, false, None);
+    buy(&mut svm, &winner, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    svm.expire_blockhash();
+
+    let claim = claim_ix(&winner.pubkey(), 1);
+    send_tx(&mut svm, &[claim], &winner, &[&winner]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    let cu = send_tx_compute_units(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        cu <= START_NEW_ROUND_CU_CEILING,
+        "start_new_round consumed {} CU, expected <= {}",
+        cu,
+        START_NEW_ROUND_CU_CEILING
+    );
+}
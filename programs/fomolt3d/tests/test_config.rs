@@ -60,6 +60,7 @@ fn test_update_config_by_admin() {
         protocol_fee_bps: 500,
         referral_bonus_bps: 500,
         protocol_wallet,
+        ..Default::default()
     };
 
     let ix = create_or_update_config_ix(&admin.pubkey(), &new_params);
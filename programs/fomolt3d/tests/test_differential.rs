@@ -0,0 +1,131 @@
+// Integration tests: differential testing harness
+//
+// Replays a fixed action sequence (registrations, buys, a round expiry, and
+// claims) through a local reference model built from the `expected_*` math
+// mirrors in `helpers.rs`, and checks it against the real on-chain program
+// state after every step. This catches divergence between the math this
+// test file assumes and what the live handlers actually do that the
+// handler-local unit tests in `test_buy_keys.rs` et al. would miss, since
+// those only assert against the same constants they were written against.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::collections::HashMap;
+
+/// Local mirror of on-chain GameState, updated after each buy using the
+/// same `expected_*` helpers the rest of the suite already trusts.
+#[derive(Default)]
+struct RefGame {
+    total_keys: u64,
+    winner_pot: u64,
+    total_dividend_pool: u64,
+    next_round_pot: u64,
+    last_buyer: usize, // index into `players`, usize::MAX if none yet
+}
+
+fn assert_game_matches(game: &GameStateData, reference: &RefGame, players: &[Keypair], step: usize) {
+    assert_eq!(game.total_keys, reference.total_keys, "total_keys diverged at step {}", step);
+    assert_eq!(game.winner_pot, reference.winner_pot, "winner_pot diverged at step {}", step);
+    assert_eq!(
+        game.total_dividend_pool, reference.total_dividend_pool,
+        "total_dividend_pool diverged at step {}",
+        step
+    );
+    assert_eq!(
+        game.next_round_pot, reference.next_round_pot,
+        "next_round_pot diverged at step {}",
+        step
+    );
+    if reference.last_buyer != usize::MAX {
+        assert!(
+            pubkey_eq(&game.last_buyer, &players[reference.last_buyer].pubkey()),
+            "last_buyer diverged at step {}",
+            step
+        );
+    }
+}
+
+#[test]
+fn differential_buy_sequence_matches_reference_model() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let players: Vec<Keypair> = (0..4).map(|_| Keypair::new()).collect();
+    for p in &players {
+        register(&mut svm, p, 1, false, None);
+    }
+
+    // (player_index, keys_to_buy) — deterministic, no referrers, so the
+    // reference model only needs to track the core bonding-curve pot splits.
+    let actions: [(usize, u64); 8] = [
+        (0, 1),
+        (1, 2),
+        (2, 1),
+        (0, 3),
+        (3, 1),
+        (1, 1),
+        (2, 2),
+        (3, 4),
+    ];
+
+    let mut reference = RefGame::default();
+    let mut ref_keys: HashMap<usize, u64> = HashMap::new();
+
+    for (step, (player_idx, keys)) in actions.iter().enumerate() {
+        svm.expire_blockhash();
+        let supply_before = reference.total_keys;
+
+        let cost = expected_cost(supply_before, *keys);
+        let after_fee = expected_after_fee(cost);
+        let pot_contribution = expected_pot_contribution(after_fee, false);
+
+        reference.total_keys += keys;
+        reference.winner_pot += expected_winner_amount(pot_contribution);
+        reference.total_dividend_pool += expected_dividend_amount(pot_contribution);
+        reference.next_round_pot += expected_next_round_amount(pot_contribution);
+        reference.last_buyer = *player_idx;
+        *ref_keys.entry(*player_idx).or_insert(0) += keys;
+
+        buy(&mut svm, &players[*player_idx], 1, *keys, &pw, None);
+
+        let game = get_game(&svm, 1);
+        assert_game_matches(&game, &reference, &players, step);
+
+        let player_state = get_player(&svm, &players[*player_idx].pubkey());
+        assert_eq!(
+            player_state.keys, ref_keys[player_idx],
+            "player {} keys diverged at step {}",
+            player_idx, step
+        );
+    }
+
+    // --- Round end: dividend shares and winner payout must match the
+    // reference model's closed-form expectations, not just "something > 0" ---
+    expire_round(&mut svm, 1);
+
+    for (idx, player) in players.iter().enumerate() {
+        let expected_share = expected_dividend_share(
+            ref_keys[&idx],
+            reference.total_dividend_pool,
+            reference.total_keys,
+        );
+        let balance_before = get_balance(&svm, &player.pubkey());
+
+        svm.expire_blockhash();
+        let ix = claim_ix(&player.pubkey(), 1);
+        send_tx(&mut svm, &[ix], player, &[player]).unwrap();
+
+        let is_winner = idx == reference.last_buyer;
+        let expected_payout = expected_share + if is_winner { reference.winner_pot } else { 0 };
+        let balance_after = get_balance(&svm, &player.pubkey());
+
+        assert_eq!(
+            balance_after - balance_before,
+            expected_payout,
+            "claim payout diverged from reference model for player {}",
+            idx
+        );
+    }
+
+    let _ = admin; // kept for setup_game's return shape / future round rollover extension
+}
@@ -0,0 +1,94 @@
+// Integration tests: `GameState::dust_reserve` accumulation in `buy_keys`
+// and the admin-only `sweep_dust_reserve` instruction that withdraws it.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_dust_reserve_accumulates_from_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let vault_bal = get_vault_balance(&svm, 1);
+
+    // No claims have happened yet, so every lamport in the vault must be
+    // accounted for across the three pot buckets plus dust_reserve.
+    let accounting_sum =
+        game.winner_pot + game.total_dividend_pool + game.next_round_pot + game.dust_reserve;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "winner_pot + total_dividend_pool + next_round_pot + dust_reserve must equal vault balance"
+    );
+}
+
+#[test]
+fn test_sweep_dust_reserve_pays_protocol_wallet_and_resets() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game_before = get_game(&svm, 1);
+    assert!(
+        game_before.dust_reserve > 0,
+        "test setup should have produced some dust to sweep"
+    );
+
+    let pw_balance_before = get_balance(&svm, &pw);
+
+    let ix = sweep_dust_reserve_ix(&admin.pubkey(), 1, &pw);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let pw_balance_after = get_balance(&svm, &pw);
+    assert_eq!(
+        pw_balance_after - pw_balance_before,
+        game_before.dust_reserve,
+        "protocol wallet should receive exactly the swept dust_reserve"
+    );
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(
+        game_after.dust_reserve, 0,
+        "dust_reserve should reset to zero after a sweep"
+    );
+}
+
+#[test]
+fn test_sweep_dust_reserve_requires_admin() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &impostor.pubkey(), 10_000_000_000);
+
+    let ix = sweep_dust_reserve_ix(&impostor.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_sweep_dust_reserve_rejects_when_empty() {
+    let (mut svm, admin, pw) = setup_game();
+
+    // No buys have happened yet, so dust_reserve is still zero.
+    let ix = sweep_dust_reserve_ix(&admin.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("NothingToSweep") || err.contains("custom program error"),
+        "Expected NothingToSweep error, got: {}",
+        err
+    );
+}
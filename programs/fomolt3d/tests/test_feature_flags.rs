@@ -0,0 +1,92 @@
+// Integration tests: `GlobalConfig::disabled_instructions_bitmask` and the
+// `FomoltError::FeatureDisabled` gate it enforces — lets the admin pause an
+// individual instruction (e.g. for a staged subsystem rollout) without a
+// full round pause.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_disabled_flag_blocks_claim_referral_earnings() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        disabled_instructions_bitmask: FLAG_CLAIM_REFERRAL_EARNINGS,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = claim_referral_earnings_ix(&player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("FeatureDisabled") || err.contains("custom program error"),
+        "Expected FeatureDisabled error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_disabled_flag_does_not_block_unrelated_instructions() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        disabled_instructions_bitmask: FLAG_CLAIM_REFERRAL_EARNINGS,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let player_state = get_player(&svm, &player.pubkey());
+    assert_eq!(player_state.keys, 5);
+}
+
+#[test]
+fn test_reenabling_flag_allows_instruction_again() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let disabled_params = ConfigParamsData {
+        protocol_wallet: pw,
+        disabled_instructions_bitmask: FLAG_CLAIM_REFERRAL_EARNINGS,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &disabled_params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = claim_referral_earnings_ix(&player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("FeatureDisabled") || err.contains("custom program error"));
+
+    let reenabled_params = ConfigParamsData {
+        protocol_wallet: pw,
+        disabled_instructions_bitmask: 0,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &reenabled_params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    // No referral earnings were actually accrued, so the call still fails —
+    // just no longer on FeatureDisabled.
+    let ix = claim_referral_earnings_ix(&player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("NoReferralEarnings") || err.contains("custom program error"),
+        "Expected NoReferralEarnings error, got: {}",
+        err
+    );
+}
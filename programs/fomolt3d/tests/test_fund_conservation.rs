@@ -52,12 +52,13 @@ fn test_full_drain_3_players_no_referrals() {
     );
 
     // With the no-deduction model, each player gets their full proportional share.
-    // Stranded funds = only integer division rounding dust.
-    let stranded = vault_after - game_after.next_round_pot;
+    // Stranded funds = the tracked, admin-sweepable dust_reserve plus only
+    // integer division rounding dust from the claim-time dividend split.
+    let stranded = vault_after - game_after.next_round_pot - game_after.dust_reserve;
     assert!(
         stranded <= 10, // at most total_keys lamports of rounding dust
-        "Stranded funds should be minimal rounding dust: stranded={}, vault={}, nrp={}",
-        stranded, vault_after, game_after.next_round_pot
+        "Stranded funds should be minimal rounding dust: stranded={}, vault={}, nrp={}, dust_reserve={}",
+        stranded, vault_after, game_after.next_round_pot, game_after.dust_reserve
     );
 }
 
@@ -214,8 +215,10 @@ fn test_vault_receives_exactly_after_fee() {
 
 #[test]
 fn test_accounting_sum_leq_vault() {
-    // After N buys, winner_pot + dividend_pool + next_round_pot + referral_earnings <= vault_balance.
-    // Difference is rounding dust, bounded by 4 * num_buys.
+    // After N buys with no claims, winner_pot + dividend_pool + next_round_pot
+    // + dust_reserve accounts for every lamport in the vault exactly — the
+    // three-way bps split's truncation remainder lands in dust_reserve
+    // instead of silently vanishing.
     let (mut svm, _admin, pw) = setup_game();
 
     let p1 = Keypair::new();
@@ -233,21 +236,13 @@ fn test_accounting_sum_leq_vault() {
     let game = get_game(&svm, 1);
     let vault_bal = get_vault_balance(&svm, 1);
 
-    let accounting_sum = game.winner_pot + game.total_dividend_pool + game.next_round_pot;
+    let accounting_sum =
+        game.winner_pot + game.total_dividend_pool + game.next_round_pot + game.dust_reserve;
 
-    assert!(
-        accounting_sum <= vault_bal,
-        "Accounting sum {} exceeds vault balance {}",
-        accounting_sum,
-        vault_bal
-    );
-
-    let dust = vault_bal - accounting_sum;
-    assert!(
-        dust <= 4 * num_buys as u64,
-        "Too much dust: {} (max expected {})",
-        dust,
-        4 * num_buys
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "Accounting sum {} must exactly equal vault balance {}",
+        accounting_sum, vault_bal
     );
 }
 
@@ -281,14 +276,15 @@ fn test_rounding_dust_bounded_100_buys() {
     let vault_after = get_vault_balance(&svm, 1);
     let game_final = get_game(&svm, 1);
 
-    // With no-deduction model, stranded is only integer division rounding dust.
+    // With no-deduction model, stranded is only integer division rounding dust
+    // from claim-time dividend splits, on top of the tracked dust_reserve.
     // 10 equal players with 10 keys each = 100 total keys.
     // Each gets pool * 10 / 100 = pool / 10 (exact division).
-    let stranded = vault_after - next_round_pot;
+    let stranded = vault_after - next_round_pot - game_final.dust_reserve;
     assert!(
         stranded <= 100, // generous bound for 100-key rounding dust
-        "Stranded should be minimal rounding dust: stranded={}, vault={}, nrp={}",
-        stranded, vault_after, next_round_pot
+        "Stranded should be minimal rounding dust: stranded={}, vault={}, nrp={}, dust_reserve={}",
+        stranded, vault_after, next_round_pot, game_final.dust_reserve
     );
 }
 
@@ -409,6 +405,7 @@ fn test_single_player_gets_everything() {
     let game = get_game(&svm, 1);
     let expected_payout = game.winner_pot + game.total_dividend_pool;
     let next_round_pot = game.next_round_pot;
+    let dust_reserve = game.dust_reserve;
 
     expire_round(&mut svm, 1);
 
@@ -426,8 +423,8 @@ fn test_single_player_gets_everything() {
 
     let vault_after = get_vault_balance(&svm, 1);
     assert_eq!(
-        vault_after, next_round_pot,
-        "Vault should hold exactly next_round_pot: vault={}, nrp={}",
-        vault_after, next_round_pot
+        vault_after, next_round_pot + dust_reserve,
+        "Vault should hold exactly next_round_pot + dust_reserve: vault={}, nrp={}, dust_reserve={}",
+        vault_after, next_round_pot, dust_reserve
     );
 }
@@ -0,0 +1,66 @@
+// Integration tests: GameSnapshot mirrors GameState's hot fields across
+// round creation, buys, and rollover.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn snapshot_created_with_first_round() {
+    let (svm, _admin, _pw) = setup_game();
+
+    let game = get_game(&svm, 1);
+    let snapshot = get_game_snapshot(&svm, 1);
+
+    assert_eq!(snapshot.game_id, game.game_id);
+    assert_eq!(snapshot.round, game.round);
+    assert_eq!(snapshot.pot_lamports, game.pot_lamports);
+    assert_eq!(snapshot.total_keys, game.total_keys);
+    assert_eq!(snapshot.timer_end, game.timer_end);
+    assert_eq!(snapshot.last_buyer_pubkey(), game.last_buyer_pubkey());
+    assert_eq!(snapshot.next_key_price, game.base_price_lamports);
+}
+
+#[test]
+fn snapshot_tracks_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let snapshot = get_game_snapshot(&svm, 1);
+
+    assert_eq!(snapshot.pot_lamports, game.pot_lamports);
+    assert_eq!(snapshot.total_keys, game.total_keys);
+    assert_eq!(snapshot.timer_end, game.timer_end);
+    assert_eq!(snapshot.last_buyer_pubkey(), p1.pubkey());
+    assert!(
+        snapshot.next_key_price > game.base_price_lamports,
+        "price should have climbed off the bonding curve after keys were bought",
+    );
+}
+
+#[test]
+fn snapshot_survives_rollover() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None); // p1 is the only buyer = winner
+
+    expire_round(&mut svm, 1);
+
+    let new_round = complete_round_and_start_next(&mut svm, &admin, 1, &p1);
+    assert_eq!(new_round, 2);
+
+    let new_game = get_game(&svm, 2);
+    let new_snapshot = get_game_snapshot(&svm, 2);
+
+    assert_eq!(new_snapshot.game_id, new_game.game_id);
+    assert_eq!(new_snapshot.round, new_game.round);
+    assert_eq!(new_snapshot.pot_lamports, new_game.pot_lamports);
+    assert_eq!(new_snapshot.total_keys, 0);
+    assert_eq!(new_snapshot.last_buyer_pubkey(), new_game.last_buyer_pubkey());
+}
@@ -0,0 +1,59 @@
+// Integration tests: GameStateExt is created lazily (not alongside GameState
+// itself) and only once a per-round event that needs it actually occurs.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn game_state_ext_does_not_exist_until_a_milestone_is_crossed() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    assert!(
+        get_game_state_ext(&svm, 1).is_none(),
+        "GameStateExt should not be created by initialize_first_round"
+    );
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 1, &pw, None);
+
+    // Default config has pot_milestone_interval_lamports = 0, which disables
+    // milestones entirely, so a plain buy still shouldn't create the account.
+    assert!(get_game_state_ext(&svm, 1).is_none());
+}
+
+#[test]
+fn game_state_ext_is_created_and_tracks_milestone_crossings() {
+    let (mut svm, admin, pw) = setup_game_for_game(999);
+
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        pot_milestone_interval_lamports: 1_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix_for_game(999, &admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy_for_game(&mut svm, 999, &p1, 1, 1, &pw, None);
+
+    let ext = get_game_state_ext_for_game(&svm, 999, 1).expect("GameStateExt not created");
+    assert_eq!(ext.game_id, 999);
+    assert_eq!(ext.round, 1);
+    assert!(
+        ext.milestones_reached_this_round > 0,
+        "a single key buy should have crossed at least one 1_000_000-lamport milestone"
+    );
+
+    let reached_after_first_buy = ext.milestones_reached_this_round;
+    buy_for_game(&mut svm, 999, &p1, 1, 1, &pw, None);
+
+    let ext = get_game_state_ext_for_game(&svm, 999, 1).unwrap();
+    assert!(
+        ext.milestones_reached_this_round > reached_after_first_buy,
+        "a second buy crossing further milestones should keep incrementing the counter"
+    );
+}
@@ -0,0 +1,130 @@
+// Integration tests: partner-integration CPI hook fired from `buy_keys`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+#[test]
+fn disabled_by_default_buy_succeeds_without_hook_account() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let player_state = get_player(&svm, &player.pubkey());
+    assert_eq!(player_state.keys, 1);
+
+    let _ = admin;
+}
+
+#[test]
+fn missing_hook_account_rejected_when_configured() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let hook_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        hook_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    // No hook account supplied (sentinel) even though one is configured.
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &protocol_wallet, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("MissingHookProgram") || err.contains("custom program error"),
+        "Expected MissingHookProgram error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn wrong_hook_account_rejected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let hook_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        hook_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let wrong_program = Pubkey::new_unique();
+    let ix = buy_keys_ix_with_hook(
+        &player.pubkey(),
+        1,
+        1,
+        &protocol_wallet,
+        &wrong_program,
+        &[],
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("HookProgramMismatch") || err.contains("custom program error"),
+        "Expected HookProgramMismatch error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn too_many_hook_accounts_rejected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let hook_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        hook_program,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let extra: Vec<AccountMeta> = (0..5)
+        .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+        .collect();
+    let ix = buy_keys_ix_with_hook(
+        &player.pubkey(),
+        1,
+        1,
+        &protocol_wallet,
+        &hook_program,
+        &extra,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("TooManyHookAccounts") || err.contains("custom program error"),
+        "Expected TooManyHookAccounts error, got: {}",
+        err
+    );
+}
@@ -0,0 +1,201 @@
+// Integration tests for bonded keeper registration, slashing, and the
+// end_round priority bounty.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_register_keeper_creates_bonded_registration() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 2_000_000_000);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+
+    let state = get_keeper_state(&svm, &keeper.pubkey()).expect("KeeperState not found");
+    assert_eq!(state.keeper, keeper.pubkey().to_bytes());
+    assert_eq!(state.bond_lamports, 2_000_000_000);
+    assert!(state.active);
+    assert_eq!(state.slash_count, 0);
+}
+
+#[test]
+fn test_register_keeper_rejects_zero_bond() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+    assert!(
+        err.contains("InvalidFundAmount") || err.contains("custom program error"),
+        "Expected InvalidFundAmount, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_unregister_keeper_returns_bond_and_closes_account() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let balance_before = get_balance(&svm, &keeper.pubkey());
+    let ix = unregister_keeper_ix(&keeper.pubkey());
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+    let balance_after = get_balance(&svm, &keeper.pubkey());
+
+    assert!(
+        balance_after > balance_before,
+        "Expected the bond to be returned: before={}, after={}",
+        balance_before,
+        balance_after
+    );
+    assert!(get_keeper_state(&svm, &keeper.pubkey()).is_none());
+}
+
+#[test]
+fn test_slash_keeper_forfeits_bond_to_protocol_wallet() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let protocol_balance_before = get_balance(&svm, &pw);
+    let ix = slash_keeper_ix(&admin.pubkey(), &keeper.pubkey(), 300_000_000, &pw);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let protocol_balance_after = get_balance(&svm, &pw);
+
+    assert_eq!(protocol_balance_after, protocol_balance_before + 300_000_000);
+
+    let state = get_keeper_state(&svm, &keeper.pubkey()).unwrap();
+    assert_eq!(state.bond_lamports, 700_000_000);
+    assert_eq!(state.slash_count, 1);
+    assert!(state.active, "slashing alone should not deregister the keeper");
+}
+
+#[test]
+fn test_slash_keeper_rejects_non_admin() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let keeper = Keypair::new();
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &impostor.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = slash_keeper_ix(&impostor.pubkey(), &keeper.pubkey(), 100_000_000, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_slash_keeper_rejects_amount_exceeding_bond() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 10_000_000_000);
+
+    let ix = register_keeper_ix(&keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = slash_keeper_ix(&admin.pubkey(), &keeper.pubkey(), 5_000_000_000, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("InsufficientBond") || err.contains("custom program error"),
+        "Expected InsufficientBond, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_end_round_pays_bonded_keeper_a_bounty_over_flat_fee() {
+    let (mut svm, admin, pw) = setup_game();
+
+    // Configure a flat keeper fee before round 1 is ever created, so the
+    // fee is baked into round 1's GameState snapshot.
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        keeper_fee_lamports: 5_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = fund_keeper_budget_ix(&admin.pubkey(), 100_000_000);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let bonded_keeper = Keypair::new();
+    airdrop(&mut svm, &bonded_keeper.pubkey(), 10_000_000_000);
+    let ix = register_keeper_ix(&bonded_keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &bonded_keeper, &[&bonded_keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let balance_before = get_balance(&svm, &bonded_keeper.pubkey());
+    let ix =
+        end_round_ix_with_keeper(&bonded_keeper.pubkey(), 1, Some(bonded_keeper.pubkey()), &p1.pubkey());
+    send_tx(&mut svm, &[ix], &bonded_keeper, &[&bonded_keeper]).unwrap();
+    let balance_after = get_balance(&svm, &bonded_keeper.pubkey());
+
+    // 5_000_000 flat fee + 20% bounty = 6_000_000
+    assert_eq!(balance_after, balance_before + 6_000_000);
+}
+
+#[test]
+fn test_end_round_rejects_keeper_state_belonging_to_someone_else() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let other_keeper = Keypair::new();
+    airdrop(&mut svm, &other_keeper.pubkey(), 10_000_000_000);
+    let ix = register_keeper_ix(&other_keeper.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[ix], &other_keeper, &[&other_keeper]).unwrap();
+    svm.expire_blockhash();
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = end_round_ix_with_keeper(&cranker.pubkey(), 1, Some(other_keeper.pubkey()), &p1.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &cranker, &[&cranker]);
+    assert!(
+        err.contains("KeeperMismatch")
+            || err.contains("ConstraintSeeds")
+            || err.contains("custom program error"),
+        "Expected KeeperMismatch/ConstraintSeeds, got: {}",
+        err
+    );
+}
@@ -0,0 +1,153 @@
+// Integration tests: permissionless `end_round` cranking and the
+// `KeeperBudget` vault that reimburses whoever calls it
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn test_end_round_rejected_before_timer_expiry() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let ix = end_round_ix(&admin.pubkey(), 1, &Pubkey::default());
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("TimerNotExpired") || err.contains("custom program error"),
+        "Expected TimerNotExpired error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_end_round_rejected_when_round_not_active() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    // Crank it once — round moves to Ended.
+    let ix = end_round_ix(&admin.pubkey(), 1, &p1.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // A second crank against the same (no longer Active) round must fail.
+    let ix = end_round_ix(&admin.pubkey(), 1, &p1.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("GameNotActive") || err.contains("custom program error"),
+        "Expected GameNotActive error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_anyone_can_crank_end_round() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = end_round_ix(&cranker.pubkey(), 1, &p1.pubkey());
+    send_tx(&mut svm, &[ix], &cranker, &[&cranker]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert!(!game.active);
+}
+
+#[test]
+fn test_fund_keeper_budget_requires_admin() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &impostor.pubkey(), 10_000_000_000);
+    let ix = fund_keeper_budget_ix(&impostor.pubkey(), 1_000_000);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_fund_keeper_budget_rejects_zero_amount() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let ix = fund_keeper_budget_ix(&admin.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("InvalidFundAmount") || err.contains("custom program error"),
+        "Expected InvalidFundAmount error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_end_round_reimburses_keeper_from_budget() {
+    let (mut svm, admin, pw) = setup_game();
+
+    // Configure a flat keeper fee and fund the budget ahead of round 1.
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        keeper_fee_lamports: 5_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = fund_keeper_budget_ix(&admin.pubkey(), 50_000_000);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let balance_before = get_balance(&svm, &cranker.pubkey());
+
+    let ix = end_round_ix(&cranker.pubkey(), 1, &p1.pubkey());
+    send_tx(&mut svm, &[ix], &cranker, &[&cranker]).unwrap();
+
+    let balance_after = get_balance(&svm, &cranker.pubkey());
+    assert!(
+        balance_after > balance_before,
+        "Expected cranker to be reimbursed: before={}, after={}",
+        balance_before,
+        balance_after
+    );
+}
+
+#[test]
+fn test_end_round_caps_reimbursement_to_available_budget() {
+    let (mut svm, admin, pw) = setup_game();
+
+    // Keeper fee is configured, but the budget is never funded.
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        keeper_fee_lamports: 5_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    // An empty KeeperBudget vault still lets end_round succeed — it just
+    // pays out nothing.
+    let ix = end_round_ix(&admin.pubkey(), 1, &p1.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert!(!game.active);
+}
@@ -0,0 +1,111 @@
+// Integration tests: permissioned KYC-gated rounds via `KycCredential` PDAs.
+mod helpers;
+
+use helpers::*;
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+fn setup_kyc_game(issuer: &Pubkey) -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        kyc_required: true,
+        kyc_issuer: *issuer,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn rejects_config_with_kyc_required_but_no_issuer() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        kyc_required: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("InvalidConfig") || err.contains("custom program error"),
+        "Expected InvalidConfig error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn buy_keys_rejected_without_credential_when_kyc_required() {
+    let issuer = Keypair::new();
+    let (mut svm, _admin, pw) = setup_kyc_game(&issuer.pubkey());
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("KycCredentialRequired") || err.contains("custom program error"),
+        "Expected KycCredentialRequired error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn buy_keys_succeeds_with_valid_credential() {
+    let issuer = Keypair::new();
+    let (mut svm, _admin, pw) = setup_kyc_game(&issuer.pubkey());
+    airdrop(&mut svm, &issuer.pubkey(), 10_000_000_000);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = issue_kyc_credential_ix(&issuer.pubkey(), &player.pubkey());
+    send_tx(&mut svm, &[ix], &issuer, &[&issuer]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_ix_with_kyc_credential(&player.pubkey(), 1, 5, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 5);
+}
+
+#[test]
+fn non_issuer_cannot_issue_credential() {
+    let issuer = Keypair::new();
+    let (mut svm, _admin, _pw) = setup_kyc_game(&issuer.pubkey());
+    let attacker = Keypair::new();
+    airdrop(&mut svm, &attacker.pubkey(), 10_000_000_000);
+
+    let wallet = Keypair::new().pubkey();
+    let ix = issue_kyc_credential_ix(&attacker.pubkey(), &wallet);
+    let err = send_tx_expect_err(&mut svm, &[ix], &attacker, &[&attacker]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn kyc_not_required_by_default() {
+    let (mut svm, _admin, pw) = setup_game();
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 5, &pw, None);
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 5);
+}
@@ -0,0 +1,184 @@
+// Integration tests: admin-recorded dividend Merkle root and the
+// permissionless claim_with_proof path for compressed mass distribution
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_record_merkle_root_rejected_while_round_active() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let root = [7u8; 32];
+    let ix = record_dividend_merkle_root_ix(&admin.pubkey(), 1, &root);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("RoundStillActiveForMerkleRoot") || err.contains("custom program error"),
+        "Expected RoundStillActiveForMerkleRoot error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_record_merkle_root_rejected_for_non_admin() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p1.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let attacker = Keypair::new();
+    airdrop(&mut svm, &attacker.pubkey(), 10_000_000_000);
+
+    let root = [7u8; 32];
+    let ix = record_dividend_merkle_root_ix(&attacker.pubkey(), 1, &root);
+    let err = send_tx_expect_err(&mut svm, &[ix], &attacker, &[&attacker]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_with_proof_rejected_before_root_recorded() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p1.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let leaf = compute_merkle_leaf(&p1.pubkey(), 1_000_000);
+    let ix = claim_with_proof_ix(&p1.pubkey(), &p1.pubkey(), 1, 1_000_000, &[leaf]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("MerkleRootNotSet") || err.contains("custom program error"),
+        "Expected MerkleRootNotSet error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_with_proof_pays_out_and_updates_vault_accounting() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p2.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let amount1 = 1_000_000u64;
+    let amount2 = 2_000_000u64;
+    let leaf1 = compute_merkle_leaf(&p1.pubkey(), amount1);
+    let leaf2 = compute_merkle_leaf(&p2.pubkey(), amount2);
+    let (root, proofs) = build_merkle_tree(&[leaf1, leaf2]);
+
+    let ix = record_dividend_merkle_root_ix(&admin.pubkey(), 1, &root);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let vault_before = get_vault_balance(&svm, 1);
+    let balance_before = get_balance(&svm, &p1.pubkey());
+
+    let ix = claim_with_proof_ix(&p1.pubkey(), &p1.pubkey(), 1, amount1, &proofs[0]);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_balance(&svm, &p1.pubkey()), balance_before + amount1);
+    assert_eq!(get_vault_balance(&svm, 1), vault_before - amount1);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.vault_lamports_out, amount1);
+}
+
+#[test]
+fn test_claim_with_proof_rejected_with_invalid_proof() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p2.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let amount1 = 1_000_000u64;
+    let amount2 = 2_000_000u64;
+    let leaf1 = compute_merkle_leaf(&p1.pubkey(), amount1);
+    let leaf2 = compute_merkle_leaf(&p2.pubkey(), amount2);
+    let (root, _proofs) = build_merkle_tree(&[leaf1, leaf2]);
+
+    let ix = record_dividend_merkle_root_ix(&admin.pubkey(), 1, &root);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Tamper: claim p1's leaf using p2's sibling proof — doesn't verify.
+    let wrong_proof = [leaf1];
+    let ix = claim_with_proof_ix(&p1.pubkey(), &p1.pubkey(), 1, amount1, &wrong_proof);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("InvalidMerkleProof") || err.contains("custom program error"),
+        "Expected InvalidMerkleProof error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_with_proof_rejected_on_double_claim() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p1.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let amount = 1_000_000u64;
+    let leaf = compute_merkle_leaf(&p1.pubkey(), amount);
+    let (root, proofs) = build_merkle_tree(&[leaf]);
+
+    let ix = record_dividend_merkle_root_ix(&admin.pubkey(), 1, &root);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_with_proof_ix(&p1.pubkey(), &p1.pubkey(), 1, amount, &proofs[0]);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_with_proof_ix(&p1.pubkey(), &p1.pubkey(), 1, amount, &proofs[0]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("already in use") || err.contains("custom program error"),
+        "Expected a re-initialization failure, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_direct_claim_path_still_works_alongside_merkle_root() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+    send_tx(&mut svm, &[end_round_ix(&admin.pubkey(), 1, &p1.pubkey())], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let root = [9u8; 32];
+    let ix = record_dividend_merkle_root_ix(&admin.pubkey(), 1, &root);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // Recording a root doesn't disturb the existing direct claim path.
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+}
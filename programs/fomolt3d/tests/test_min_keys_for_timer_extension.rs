@@ -0,0 +1,105 @@
+// Integration tests: `GlobalConfig::min_keys_for_timer_extension` stopping
+// tiny buys from extending `GameState::timer_end`, while still crediting
+// them keys and dividends as normal.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_default_config_lets_every_buy_extend_timer() {
+    let (mut svm, _admin, pw) = setup_game();
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let after = get_game(&svm, 1).timer_end;
+    assert!(after > before, "expected a single-key buy to extend the timer by default");
+}
+
+#[test]
+fn test_buy_below_minimum_still_grants_keys_but_skips_extension() {
+    let params = ConfigParamsData {
+        min_keys_for_timer_extension: 5,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 4, &pw, None);
+    let after = get_game(&svm, 1);
+    assert_eq!(after.timer_end, before, "a below-threshold buy must not extend the timer");
+    assert_eq!(after.total_keys, 4, "keys and dividends still accrue for a small buy");
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.keys, 4);
+}
+
+#[test]
+fn test_buy_at_or_above_minimum_extends_timer() {
+    let params = ConfigParamsData {
+        min_keys_for_timer_extension: 5,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 5, &pw, None);
+    let after = get_game(&svm, 1);
+    assert!(after.timer_end > before, "a buy meeting the threshold should extend the timer");
+}
+
+#[test]
+fn test_below_minimum_buy_does_not_count_against_extension_window_cap() {
+    let params = ConfigParamsData {
+        min_keys_for_timer_extension: 5,
+        max_timer_extensions_per_window: 1,
+        timer_extension_window_secs: 86_400,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    // Several sub-threshold buys never touch the extension counter...
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.timer_extensions_in_window, 0);
+
+    // ...so the wallet's single allowed extension is still available.
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 5, &pw, None);
+    let after = get_game(&svm, 1);
+    assert!(after.timer_end > before, "the first qualifying buy should still extend the timer");
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.timer_extensions_in_window, 1);
+}
@@ -0,0 +1,120 @@
+// Integration tests for running multiple concurrent game lineages
+// (distinct `game_id`s) against a single deployed program.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn test_two_games_have_independent_config_and_state() {
+    let (svm_a, admin_a, protocol_wallet_a) = setup_game_for_game(1);
+    let (svm_b, admin_b, protocol_wallet_b) = setup_game_for_game(2);
+
+    let config_a = get_config_for_game(&svm_a, 1);
+    let config_b = get_config_for_game(&svm_b, 2);
+    assert_eq!(config_a.game_id, 1);
+    assert_eq!(config_b.game_id, 2);
+    assert!(pubkey_eq(&config_a.admin, &admin_a.pubkey()));
+    assert!(pubkey_eq(&config_b.admin, &admin_b.pubkey()));
+    assert!(pubkey_eq(&config_a.protocol_wallet, &protocol_wallet_a));
+    assert!(pubkey_eq(&config_b.protocol_wallet, &protocol_wallet_b));
+
+    let game_a = get_game_for_game(&svm_a, 1, 1);
+    let game_b = get_game_for_game(&svm_b, 2, 1);
+    assert_eq!(game_a.game_id, 1);
+    assert_eq!(game_b.game_id, 2);
+}
+
+#[test]
+fn test_same_game_id_in_two_deployments_does_not_collide_across_games() {
+    // Two distinct game lineages (3 and 4) created in the same SVM instance —
+    // their config/game/player/stats PDAs must not alias each other even
+    // though they share an admin and a buyer.
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet_3 = Pubkey::new_unique();
+    let params_3 = ConfigParamsData {
+        protocol_wallet: protocol_wallet_3,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix_for_game(3, &admin.pubkey(), &params_3);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix_for_game(3, &admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let protocol_wallet_4 = Pubkey::new_unique();
+    let params_4 = ConfigParamsData {
+        protocol_wallet: protocol_wallet_4,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix_for_game(4, &admin.pubkey(), &params_4);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix_for_game(4, &admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let buyer = Keypair::new();
+    airdrop(&mut svm, &buyer.pubkey(), 100_000_000_000);
+
+    buy_for_game(&mut svm, 3, &buyer, 1, 5, &protocol_wallet_3, None);
+    buy_for_game(&mut svm, 4, &buyer, 1, 2, &protocol_wallet_4, None);
+
+    let (player_pda_3, _) = player_pda_for_game(3, &buyer.pubkey());
+    let (player_pda_4, _) = player_pda_for_game(4, &buyer.pubkey());
+    assert_ne!(player_pda_3, player_pda_4);
+
+    let player_3 = PlayerStateData::from_account_data(&svm.get_account(&player_pda_3).unwrap().data);
+    let player_4 = PlayerStateData::from_account_data(&svm.get_account(&player_pda_4).unwrap().data);
+    assert_eq!(player_3.game_id, 3);
+    assert_eq!(player_3.keys, 5);
+    assert_eq!(player_4.game_id, 4);
+    assert_eq!(player_4.keys, 2);
+
+    let game_3 = get_game_for_game(&svm, 3, 1);
+    let game_4 = get_game_for_game(&svm, 4, 1);
+    assert_eq!(game_3.total_keys, 5);
+    assert_eq!(game_4.total_keys, 2);
+}
+
+#[test]
+fn test_admin_of_one_game_cannot_administer_another_games_config() {
+    // game_id is the namespace seed every PDA in the program derives from
+    // (see `GlobalConfig::game_id`), so two independent operators can run
+    // their own games against the same deployed program. That only holds if
+    // one operator's admin key can't reach into another operator's config.
+    let mut svm = setup_svm();
+    let admin_a = Keypair::new();
+    let admin_b = Keypair::new();
+    airdrop(&mut svm, &admin_a.pubkey(), 100_000_000_000);
+    airdrop(&mut svm, &admin_b.pubkey(), 100_000_000_000);
+
+    let params_a = ConfigParamsData {
+        protocol_wallet: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix_for_game(5, &admin_a.pubkey(), &params_a);
+    send_tx(&mut svm, &[ix], &admin_a, &[&admin_a]).unwrap();
+
+    let params_b = ConfigParamsData {
+        protocol_wallet: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix_for_game(6, &admin_b.pubkey(), &params_b);
+    send_tx(&mut svm, &[ix], &admin_b, &[&admin_b]).unwrap();
+    svm.expire_blockhash();
+
+    // admin_a tries to update game 6's config, which admin_b already owns.
+    let ix = create_or_update_config_ix_for_game(6, &admin_a.pubkey(), &params_a);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin_a, &[&admin_a]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+
+    // game 6's config is untouched.
+    let config_b = get_config_for_game(&svm, 6);
+    assert!(pubkey_eq(&config_b.admin, &admin_b.pubkey()));
+    assert!(pubkey_eq(&config_b.protocol_wallet, &params_b.protocol_wallet));
+}
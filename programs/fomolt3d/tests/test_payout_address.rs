@@ -0,0 +1,172 @@
+// Integration tests for PlayerState::payout_address — routing claim and
+// claim_referral_earnings cash payouts to a designated beneficiary instead
+// of the signer.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn test_claim_pays_signer_when_payout_address_unset() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    let bal_before = get_balance(&svm, &p1.pubkey());
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    let bal_after = get_balance(&svm, &p1.pubkey());
+    assert!(
+        bal_after > bal_before,
+        "funds should go to the signer when no payout_address is set"
+    );
+}
+
+#[test]
+fn test_claim_routes_dividends_to_payout_address() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    let treasury = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = set_preferences_ix_with_payout_address(&p1.pubkey(), false, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    let signer_bal_before = get_balance(&svm, &p1.pubkey());
+    let treasury_bal_before = get_balance(&svm, &treasury);
+
+    svm.expire_blockhash();
+    let ix = claim_ix_with_payout_destination(&p1.pubkey(), 1, false, None, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let signer_bal_after = get_balance(&svm, &p1.pubkey());
+    let treasury_bal_after = get_balance(&svm, &treasury);
+
+    assert_eq!(
+        signer_bal_after, signer_bal_before,
+        "signer's own balance should be untouched by the claim itself"
+    );
+    assert!(
+        treasury_bal_after > treasury_bal_before,
+        "dividends should have been paid to the designated treasury wallet"
+    );
+}
+
+#[test]
+fn test_claim_fails_when_payout_address_set_but_destination_missing() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    let treasury = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = set_preferences_ix_with_payout_address(&p1.pubkey(), false, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    svm.expire_blockhash();
+    let ix = claim_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("MissingPayoutDestination") || err.contains("custom program error"),
+        "Expected MissingPayoutDestination, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_fails_when_payout_destination_does_not_match() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    buy(&mut svm, &p2, 1, 10, &pw, None);
+
+    let treasury = Pubkey::new_unique();
+    let wrong = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = set_preferences_ix_with_payout_address(&p1.pubkey(), false, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    svm.expire_blockhash();
+    let ix = claim_ix_with_payout_destination(&p1.pubkey(), 1, false, None, Some(wrong));
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("PayoutDestinationMismatch") || err.contains("custom program error"),
+        "Expected PayoutDestinationMismatch, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_referral_earnings_routes_to_payout_address() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &player, 1, 10, &pw, Some(&referrer.pubkey()));
+
+    let ps = get_player(&svm, &referrer.pubkey());
+    assert!(
+        ps.referral_earnings_lamports > 0,
+        "referrer should have accrued earnings from the referred buy"
+    );
+
+    let treasury = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = set_preferences_ix_with_payout_address(&referrer.pubkey(), false, Some(treasury));
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).unwrap();
+
+    let treasury_bal_before = get_balance(&svm, &treasury);
+    svm.expire_blockhash();
+    let ix = claim_referral_earnings_ix_with_payout_destination(
+        &referrer.pubkey(),
+        1,
+        Some(treasury),
+    );
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).unwrap();
+    let treasury_bal_after = get_balance(&svm, &treasury);
+
+    assert!(
+        treasury_bal_after > treasury_bal_before,
+        "referral earnings should have been paid to the designated treasury wallet"
+    );
+}
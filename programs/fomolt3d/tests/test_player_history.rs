@@ -0,0 +1,165 @@
+// Integration tests: opt-in per-player purchase history ring buffer.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn init_player_history_rejected_when_disabled() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = init_player_history_ix(&player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("PurchaseHistoryDisabled") || err.contains("custom program error"),
+        "Expected PurchaseHistoryDisabled error, got: {}",
+        err
+    );
+
+    let _ = pw;
+}
+
+#[test]
+fn init_player_history_creates_empty_buffer_when_enabled() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        purchase_history_enabled: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = init_player_history_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let history = get_player_history(&svm, &player.pubkey());
+    assert_eq!(history.len, 0);
+    assert_eq!(history.next_index, 0);
+    assert!(pubkey_eq(&history.player, &player.pubkey()));
+}
+
+#[test]
+fn buy_keys_records_into_history_when_enabled() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        purchase_history_enabled: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = init_player_history_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_ix_with_history(
+        &player.pubkey(),
+        1,
+        3,
+        false,
+        &protocol_wallet,
+        None,
+    );
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let history = get_player_history(&svm, &player.pubkey());
+    assert_eq!(history.len, 1);
+    assert_eq!(history.next_index, 1);
+    assert_eq!(history.entries[0].keys, 3);
+    assert!(history.entries[0].cost_lamports > 0);
+}
+
+#[test]
+fn history_wraps_after_capacity_reached() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        purchase_history_enabled: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    let ix = init_player_history_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    svm.expire_blockhash();
+
+    let rounds = PlayerHistoryData::CAPACITY + 3;
+    for i in 0..rounds {
+        let ix = buy_keys_ix_with_history(
+            &player.pubkey(),
+            1,
+            1,
+            false,
+            &protocol_wallet,
+            None,
+        );
+        send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+        svm.expire_blockhash();
+        let _ = i;
+    }
+
+    let history = get_player_history(&svm, &player.pubkey());
+    assert_eq!(history.len as usize, PlayerHistoryData::CAPACITY);
+    assert_eq!(history.next_index as usize, rounds % PlayerHistoryData::CAPACITY);
+}
+
+#[test]
+fn buy_keys_ignores_history_when_account_omitted() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        purchase_history_enabled: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    // No init_player_history call — buy_keys must still succeed with the
+    // Option<Account> sentinel since purchase history is opt-in per player.
+    buy(&mut svm, &player, 1, 5, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 5);
+}
@@ -0,0 +1,133 @@
+// Integration tests: admin-assisted self-custody recovery via
+// `propose_player_migration` / `execute_player_migration` — see
+// `PlayerState::pending_migration_wallet` / `migration_effective_at`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[test]
+fn propose_sets_pending_migration_with_timelock() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 3, &pw, None);
+
+    let new_wallet = Keypair::new();
+    let before = get_player(&svm, &old_wallet.pubkey());
+    assert!(before.pending_migration_wallet.is_none());
+
+    let ix = propose_player_migration_ix(&admin.pubkey(), &old_wallet.pubkey(), &new_wallet.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).expect("propose should succeed");
+
+    let after = get_player(&svm, &old_wallet.pubkey());
+    assert_eq!(after.pending_migration_wallet, Some(new_wallet.pubkey().to_bytes()));
+    assert!(after.migration_effective_at > 0);
+}
+
+#[test]
+fn propose_rejects_non_admin() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 1, &pw, None);
+
+    let impostor = Keypair::new();
+    svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+    let new_wallet = Keypair::new();
+
+    let ix = propose_player_migration_ix(&impostor.pubkey(), &old_wallet.pubkey(), &new_wallet.pubkey());
+    send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+}
+
+#[test]
+fn propose_rejects_same_wallet() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 1, &pw, None);
+
+    let ix = propose_player_migration_ix(&admin.pubkey(), &old_wallet.pubkey(), &old_wallet.pubkey());
+    send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+}
+
+#[test]
+fn execute_rejects_before_timelock_elapses() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 2, &pw, None);
+
+    let new_wallet = Keypair::new();
+    svm.airdrop(&new_wallet.pubkey(), 1_000_000_000).unwrap();
+
+    let propose = propose_player_migration_ix(&admin.pubkey(), &old_wallet.pubkey(), &new_wallet.pubkey());
+    send_tx(&mut svm, &[propose], &admin, &[&admin]).unwrap();
+
+    let execute = execute_player_migration_ix(&new_wallet.pubkey(), &old_wallet.pubkey(), None);
+    send_tx_expect_err(&mut svm, &[execute], &new_wallet, &[&new_wallet]);
+}
+
+#[test]
+fn execute_transfers_keys_and_closes_old_state_after_timelock() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 4, &pw, None);
+
+    let old_before = get_player(&svm, &old_wallet.pubkey());
+    assert_eq!(old_before.keys, 4);
+
+    let new_wallet = Keypair::new();
+    svm.airdrop(&new_wallet.pubkey(), 1_000_000_000).unwrap();
+
+    let propose = propose_player_migration_ix(&admin.pubkey(), &old_wallet.pubkey(), &new_wallet.pubkey());
+    send_tx(&mut svm, &[propose], &admin, &[&admin]).unwrap();
+
+    advance_clock(&mut svm, 259_200 + 1);
+
+    let (game_key, _) = game_pda(1);
+    let execute = execute_player_migration_ix(&new_wallet.pubkey(), &old_wallet.pubkey(), Some(game_key));
+    send_tx(&mut svm, &[execute], &new_wallet, &[&new_wallet]).expect("execute should succeed");
+
+    let new_state = get_player(&svm, &new_wallet.pubkey());
+    assert_eq!(new_state.keys, 4);
+    assert_eq!(new_state.dividend_weight, old_before.dividend_weight);
+    assert!(new_state.initialized);
+    assert!(new_state.pending_migration_wallet.is_none());
+
+    let old_account = svm.get_account(&player_pda(&old_wallet.pubkey()).0);
+    assert!(old_account.is_none() || old_account.unwrap().lamports == 0);
+}
+
+#[test]
+fn execute_repoints_last_buyer_when_migrating_players_holds_it() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let old_wallet = Keypair::new();
+    register(&mut svm, &old_wallet, 1, false, None);
+    buy(&mut svm, &old_wallet, 1, 1, &pw, None);
+
+    let game_before = get_game(&svm, 1);
+    assert_eq!(game_before.last_buyer_pubkey(), old_wallet.pubkey());
+
+    let new_wallet = Keypair::new();
+    svm.airdrop(&new_wallet.pubkey(), 1_000_000_000).unwrap();
+
+    let propose = propose_player_migration_ix(&admin.pubkey(), &old_wallet.pubkey(), &new_wallet.pubkey());
+    send_tx(&mut svm, &[propose], &admin, &[&admin]).unwrap();
+
+    advance_clock(&mut svm, 259_200 + 1);
+
+    let (game_key, _) = game_pda(1);
+    let execute = execute_player_migration_ix(&new_wallet.pubkey(), &old_wallet.pubkey(), Some(game_key));
+    send_tx(&mut svm, &[execute], &new_wallet, &[&new_wallet]).expect("execute should succeed");
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.last_buyer_pubkey(), new_wallet.pubkey());
+}
@@ -0,0 +1,131 @@
+// Integration tests: `max_pot_lamports` cap on winner_pot + total_dividend_pool,
+// with excess routed into `pot_overflow_reserve_lamports` (see `math::apply_pot_cap`).
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn disabled_by_default_buy_unaffected() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let cost = expected_cost(0, 1);
+    let pot_contribution = expected_pot_contribution(expected_after_fee(cost), false);
+    assert_eq!(game.winner_pot, expected_winner_amount(pot_contribution));
+    assert_eq!(
+        game.total_dividend_pool,
+        expected_dividend_amount(pot_contribution)
+    );
+    assert_eq!(game.pot_overflow_reserve_lamports, 0);
+}
+
+#[test]
+fn purchase_under_cap_unaffected() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        max_pot_lamports: 1_000_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    let cost = expected_cost(0, 1);
+    let pot_contribution = expected_pot_contribution(expected_after_fee(cost), false);
+    assert_eq!(game.winner_pot, expected_winner_amount(pot_contribution));
+    assert_eq!(
+        game.total_dividend_pool,
+        expected_dividend_amount(pot_contribution)
+    );
+    assert_eq!(game.pot_overflow_reserve_lamports, 0);
+}
+
+#[test]
+fn purchase_over_cap_routes_excess_to_overflow_reserve() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    // 1_000_000 lamports of pot room, far less than what a single key buy's
+    // winner+dividend split contributes, so the whole excess must be diverted.
+    let params = ConfigParamsData {
+        protocol_wallet,
+        max_pot_lamports: 1_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    let cost = expected_cost(0, 1);
+    let pot_contribution = expected_pot_contribution(expected_after_fee(cost), false);
+    let uncapped_winner = expected_winner_amount(pot_contribution);
+    let uncapped_dividend = expected_dividend_amount(pot_contribution);
+
+    // Dividend is drained first, then winner absorbs the rest of the overflow.
+    assert_eq!(game.total_dividend_pool, 0);
+    assert_eq!(game.winner_pot, 1_000_000);
+    assert_eq!(game.winner_pot + game.total_dividend_pool, 1_000_000);
+    assert_eq!(
+        game.pot_overflow_reserve_lamports,
+        (uncapped_winner + uncapped_dividend) - 1_000_000
+    );
+}
+
+#[test]
+fn overflow_reserve_carries_into_next_round_pot() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        max_pot_lamports: 1_000_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &protocol_wallet, None);
+
+    let game = get_game(&svm, 1);
+    let overflow = game.pot_overflow_reserve_lamports;
+    let next_round_pot = game.next_round_pot;
+    assert!(overflow > 0);
+
+    expire_round(&mut svm, 1);
+    let round = complete_round_and_start_next(&mut svm, &admin, 1, &player);
+
+    let new_game = get_game(&svm, round);
+    assert_eq!(new_game.pot_overflow_reserve_lamports, 0);
+    assert_eq!(new_game.winner_pot, next_round_pot + overflow);
+    assert_eq!(new_game.max_pot_lamports, 1_000_000);
+}
@@ -0,0 +1,268 @@
+// Integration tests: deposit_prepaid, set_scheduled_buy, execute_scheduled_buy, withdraw_prepaid
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_deposit_prepaid_increases_balance() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_player(&svm, &p1.pubkey()).prepaid_balance_lamports, 5_000_000_000);
+    assert_eq!(get_balance(&svm, &prepaid_vault_pda(&p1.pubkey()).0), 5_000_000_000);
+}
+
+#[test]
+fn test_set_scheduled_buy_configures_and_disables() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let ix = set_scheduled_buy_ix(&p1.pubkey(), 3, 3600);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert_eq!(player.scheduled_buy_keys, 3);
+    assert_eq!(player.scheduled_buy_interval_secs, 3600);
+
+    // interval_secs == 0 disables the schedule
+    let ix = set_scheduled_buy_ix(&p1.pubkey(), 3, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_player(&svm, &p1.pubkey()).scheduled_buy_interval_secs, 0);
+}
+
+#[test]
+fn test_execute_scheduled_buy_crank_succeeds() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 2, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    advance_clock(&mut svm, 3601);
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    let ix = execute_scheduled_buy_ix(&keeper.pubkey(), &p1.pubkey(), 1, &pw);
+    send_tx(&mut svm, &[ix], &keeper, &[&keeper]).unwrap();
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert_eq!(player.keys, 2);
+    assert!(player.prepaid_balance_lamports < 5_000_000_000);
+    assert_eq!(get_game(&svm, 1).total_keys, 2);
+}
+
+#[test]
+fn test_execute_scheduled_buy_fails_with_insufficient_balance() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 5, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    advance_clock(&mut svm, 3601);
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    let ix = execute_scheduled_buy_ix(&keeper.pubkey(), &p1.pubkey(), 1, &pw);
+    send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+}
+
+#[test]
+fn test_execute_scheduled_buy_fails_when_not_due() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 2, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    let ix = execute_scheduled_buy_ix(&keeper.pubkey(), &p1.pubkey(), 1, &pw);
+    send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+}
+
+#[test]
+fn test_execute_scheduled_buy_rejects_blocked_player() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 2, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    advance_clock(&mut svm, 3601);
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    let ix = execute_scheduled_buy_ix(&keeper.pubkey(), &p1.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_execute_scheduled_buy_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 2, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    advance_clock(&mut svm, 3601);
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    // Pre-fix, `execute_scheduled_buy` had no `block_entry` account at all —
+    // a blocked/self-excluded player's standing schedule kept cranking
+    // forever. `block_entry` is now required and seeds-constrained, so
+    // substituting the program-ID "None" sentinel must be rejected before
+    // the handler ever runs.
+    let ix = execute_scheduled_buy_ix_with_raw_block_entry(
+        &keeper.pubkey(),
+        &p1.pubkey(),
+        1,
+        &pw,
+        PROGRAM_ID,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_execute_scheduled_buy_rejects_purchase_exceeding_spend_limit() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+    let schedule_ix = set_scheduled_buy_ix(&p1.pubkey(), 2, 3600);
+    send_tx(&mut svm, &[schedule_ix], &p1, &[&p1]).unwrap();
+
+    // Cap the player's own daily spend below the cost of the scheduled buy.
+    let limit_ix = set_spend_limit_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[limit_ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    advance_clock(&mut svm, 3601);
+
+    let keeper = Keypair::new();
+    airdrop(&mut svm, &keeper.pubkey(), 1_000_000_000);
+    let ix = execute_scheduled_buy_ix(&keeper.pubkey(), &p1.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &keeper, &[&keeper]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_withdraw_prepaid_returns_lamports() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 5_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+
+    let balance_before = get_balance(&svm, &p1.pubkey());
+    let withdraw_ix = withdraw_prepaid_ix(&p1.pubkey(), 2_000_000_000);
+    send_tx(&mut svm, &[withdraw_ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_player(&svm, &p1.pubkey()).prepaid_balance_lamports, 3_000_000_000);
+    assert_eq!(get_balance(&svm, &p1.pubkey()), balance_before + 2_000_000_000);
+}
+
+#[test]
+fn test_withdraw_prepaid_fails_when_exceeding_balance() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 0, &pw, None);
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+
+    let withdraw_ix = withdraw_prepaid_ix(&p1.pubkey(), 2_000_000_000);
+    send_tx_expect_err(&mut svm, &[withdraw_ix], &p1, &[&p1]);
+}
+
+#[test]
+fn test_close_player_state_fails_with_nonzero_prepaid_balance() {
+    // Isolate the new prepaid_balance_lamports guard: settle everything else
+    // ClosePlayerState already checks (keys, current_round, contributed) via
+    // the normal claim flow, then confirm a leftover prepaid balance alone
+    // still blocks the close.
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let claim_ix_ = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[claim_ix_], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let deposit_ix = deposit_prepaid_ix(&p1.pubkey(), 1_000_000_000);
+    send_tx(&mut svm, &[deposit_ix], &p1, &[&p1]).unwrap();
+
+    let ix = close_player_state_ix(&p1.pubkey());
+    send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+}
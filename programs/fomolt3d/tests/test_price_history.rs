@@ -0,0 +1,171 @@
+// Integration tests: `GlobalConfig::price_sample_interval_slots` and the
+// `PriceHistory` ring buffer it gates — samples appended by qualifying buys
+// in `buy_keys`, and by the permissionless `record_sample` crank during
+// quiet periods.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, solana_sdk::pubkey::Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = solana_sdk::pubkey::Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_default_config_never_populates_price_history() {
+    let (mut svm, _admin, pw) = setup_game();
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    assert!(
+        get_price_history(&svm, 1).is_none(),
+        "sampling is disabled by default (price_sample_interval_slots == 0)"
+    );
+}
+
+#[test]
+fn test_first_qualifying_buy_records_a_sample_immediately() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 10,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let history = get_price_history(&svm, 1).expect("first qualifying buy should create PriceHistory");
+    assert_eq!(history.len, 1, "the very first buy should always sample, regardless of interval");
+    assert_eq!(history.samples[0].total_keys, 1);
+}
+
+#[test]
+fn test_buy_before_interval_elapses_does_not_resample() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let history = get_price_history(&svm, 1).unwrap();
+    assert_eq!(history.len, 1, "a second buy within the interval must not append another sample");
+}
+
+#[test]
+fn test_buy_after_interval_elapses_records_another_sample() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 5,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    advance_slot(&mut svm, 5);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let history = get_price_history(&svm, 1).unwrap();
+    assert_eq!(history.len, 2, "a buy landing at or after the interval should append a new sample");
+    assert_eq!(history.samples[1].total_keys, 2);
+}
+
+#[test]
+fn test_ring_buffer_wraps_after_capacity_reached() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 1,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    for _ in 0..(PRICE_HISTORY_CAPACITY as u64 + 3) {
+        buy(&mut svm, &player, 1, 1, &pw, None);
+        advance_slot(&mut svm, 1);
+    }
+
+    let history = get_price_history(&svm, 1).unwrap();
+    assert_eq!(history.len, PRICE_HISTORY_CAPACITY as u8, "len should cap at CAPACITY once the buffer wraps");
+    assert_eq!(
+        history.samples[history.next_index as usize].total_keys, 4,
+        "the oldest surviving sample should be the 4th recorded, since 3 wrapped past it"
+    );
+}
+
+#[test]
+fn test_record_sample_crank_covers_a_quiet_period() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 5,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    // No buys happen here; the crank should still be able to sample once the
+    // interval has elapsed.
+    advance_slot(&mut svm, 5);
+    let ix = record_sample_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let history = get_price_history(&svm, 1).unwrap();
+    assert_eq!(history.len, 2, "the crank should append a sample once the interval elapses with no buys");
+}
+
+#[test]
+fn test_record_sample_crank_rejects_early_call() {
+    let params = ConfigParamsData {
+        price_sample_interval_slots: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let ix = record_sample_ix(&admin.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("PriceSampleIntervalNotElapsed") || err.contains("custom program error"),
+        "calling the crank before the interval elapses should fail: {err}"
+    );
+}
+
+#[test]
+fn test_record_sample_crank_rejects_when_sampling_disabled() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let ix = record_sample_ix(&admin.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("PriceSamplingDisabled") || err.contains("custom program error"),
+        "calling the crank with sampling disabled should fail: {err}"
+    );
+}
@@ -0,0 +1,90 @@
+// Integration tests: `GameState::price_cumulative` TWAP accumulator
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_price_cumulative_zero_before_any_elapsed_time() {
+    let (svm, _admin, _pw) = setup_game();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.price_cumulative, 0);
+    assert_eq!(game.price_last_update, game.round_start);
+}
+
+#[test]
+fn test_price_cumulative_accrues_base_price_before_first_buy() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let game_before = get_game(&svm, 1);
+    let base_price = game_before.base_price_lamports;
+
+    advance_clock(&mut svm, 100);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let game_after = get_game(&svm, 1);
+    // No keys were sold yet when the 100s elapsed, so the marginal price in
+    // effect the whole time was still base_price_lamports.
+    assert_eq!(
+        game_after.price_cumulative,
+        (base_price as u128) * 100,
+        "price_cumulative should accrue base_price over the pre-buy interval"
+    );
+    assert_eq!(game_after.price_last_update, game_after.round_start + 100);
+}
+
+#[test]
+fn test_price_cumulative_uses_marginal_price_between_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let game_after_first_buy = get_game(&svm, 1);
+    let price_after_first_buy = game_after_first_buy.base_price_lamports
+        + game_after_first_buy.price_increment_lamports * game_after_first_buy.total_keys;
+
+    advance_clock(&mut svm, 50);
+
+    let cumulative_before_second_buy = game_after_first_buy.price_cumulative;
+
+    let p2 = Keypair::new();
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p2, 1, 1, &pw, None);
+
+    let game_after_second_buy = get_game(&svm, 1);
+    let expected_delta = (price_after_first_buy as u128) * 50;
+    assert_eq!(
+        game_after_second_buy.price_cumulative,
+        cumulative_before_second_buy + expected_delta,
+        "price_cumulative should accrue the marginal price in effect since the prior buy"
+    );
+}
+
+#[test]
+fn test_price_cumulative_resets_on_new_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+    advance_clock(&mut svm, 100);
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let new_game = get_game(&svm, 2);
+    assert_eq!(new_game.price_cumulative, 0);
+    assert_eq!(new_game.price_last_update, new_game.round_start);
+}
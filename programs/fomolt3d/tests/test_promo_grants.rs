@@ -0,0 +1,118 @@
+// Integration tests: admin-only treasury-funded promotional key grants.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn grant_mints_dividend_bearing_keys_funded_by_admin() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let winner = Keypair::new();
+    let admin_balance_before = get_balance(&svm, &admin.pubkey());
+
+    let game_before = get_game(&svm, 1);
+    let cost = expected_cost(game_before.total_keys, 10);
+
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 10);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = get_player(&svm, &winner.pubkey());
+    assert_eq!(player.keys, 10);
+    assert_eq!(player.dividend_weight, 10 * 10_000);
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.total_keys, 10);
+    assert_eq!(game_after.pot_lamports, cost);
+    assert_eq!(game_after.promo_keys_granted_this_round, 10);
+
+    // The grant never touches last_buyer or the timer — it isn't a
+    // competitive buy and shouldn't extend the round or steal the win.
+    assert_eq!(game_after.last_buyer_pubkey(), Pubkey::default());
+    assert_eq!(game_after.timer_end, game_before.timer_end);
+
+    // Admin paid the cost out of pocket (plus a tx fee).
+    let admin_balance_after = get_balance(&svm, &admin.pubkey());
+    assert!(admin_balance_after <= admin_balance_before - cost);
+
+    let vault_balance = get_vault_balance(&svm, 1);
+    assert!(vault_balance >= cost);
+}
+
+#[test]
+fn grant_rejected_from_non_admin() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &impostor.pubkey(), 100_000_000_000);
+    let winner = Keypair::new();
+
+    let ix = grant_promo_keys_ix(&impostor.pubkey(), 1, &winner.pubkey(), 5);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(err.contains("Unauthorized") || err.contains("Error"));
+}
+
+#[test]
+fn grant_rejects_zero_keys() {
+    let (mut svm, admin, _pw) = setup_game();
+    let winner = Keypair::new();
+
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(err.contains("NoPromoKeysToGrant") || err.contains("Error"));
+}
+
+#[test]
+fn grant_enforces_per_round_cap() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        promo_keys_cap_per_round: 15,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let winner = Keypair::new();
+
+    // 10 keys grants fine, cumulative 10 <= 15.
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 10);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    // A further 10 would bring the cumulative to 20 > 15 — rejected.
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 10);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(err.contains("PromoCapExceeded") || err.contains("Error"));
+
+    // Exactly the remaining 5 still fits.
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 5);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.promo_keys_granted_this_round, 15);
+}
+
+#[test]
+fn grant_preserves_vault_solvency_alongside_real_buys() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 20, &pw, None);
+
+    let winner = Keypair::new();
+    let ix = grant_promo_keys_ix(&admin.pubkey(), 1, &winner.pubkey(), 7);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    let vault_balance = get_vault_balance(&svm, 1);
+    let obligations = game.winner_pot + game.total_dividend_pool + game.next_round_pot;
+    assert!(vault_balance >= obligations);
+    assert_eq!(game.vault_lamports_in, vault_balance);
+}
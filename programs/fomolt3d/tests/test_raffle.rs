@@ -0,0 +1,418 @@
+// Integration tests: the daily key-holder raffle — pot-time funding via
+// `raffle_bps`, `record_raffle_snapshot`, `draw_raffle_ticket`, and the
+// Merkle-proof `claim_raffle_prize` payout.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_zero_raffle_bps_allocates_nothing() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.raffle_pool_lamports, 0, "0 bps should carve nothing");
+}
+
+#[test]
+fn test_raffle_pool_accumulates_and_matches_vault_accounting() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000, // 10%
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert!(game.raffle_pool_lamports > 0, "10% raffle_bps should carve something");
+
+    let vault_bal = get_vault_balance(&svm, 1);
+    let accounting_sum = game.winner_pot
+        + game.total_dividend_pool
+        + game.next_round_pot
+        + game.dust_reserve
+        + game.raffle_pool_lamports;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "raffle_pool_lamports must be tracked alongside the other pot buckets"
+    );
+}
+
+#[test]
+fn test_record_raffle_snapshot_moves_pool_into_prize() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,             // 10% of pot_contribution funds the pool
+        raffle_daily_payout_bps: 5_000, // half the accumulated pool is drawn each day
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let pool_before = get_game(&svm, 1).raffle_pool_lamports;
+    assert!(pool_before > 0);
+
+    let root = [1u8; 32];
+    let total_weight = 100u64;
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    let snapshot = get_raffle_snapshot(&svm, 1, 0);
+
+    assert_eq!(snapshot.total_weight, total_weight);
+    assert_eq!(snapshot.merkle_root, root);
+    assert!(snapshot.winning_ticket.is_none());
+    assert!(snapshot.prize_lamports > 0, "50% daily payout should carve a nonzero prize");
+    assert_eq!(game.raffle_prize_pool_pending, snapshot.prize_lamports);
+    assert_eq!(game.raffle_pool_lamports, pool_before - snapshot.prize_lamports);
+}
+
+#[test]
+fn test_record_raffle_snapshot_rejected_before_day_elapsed() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    // Day 1's window (RAFFLE_INTERVAL_SECS after round start) hasn't opened yet.
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 1, &[2u8; 32], 100);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("RaffleDayNotElapsed") || err.contains("custom program error"),
+        "Expected RaffleDayNotElapsed error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_record_raffle_snapshot_rejected_with_zero_total_weight() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &[3u8; 32], 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("RaffleTotalWeightZero") || err.contains("custom program error"),
+        "Expected RaffleTotalWeightZero error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_draw_raffle_ticket_sets_winning_ticket_in_range() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let total_weight = 100u64;
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &[4u8; 32], total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let snapshot = get_raffle_snapshot(&svm, 1, 0);
+    let ticket = snapshot.winning_ticket.expect("winning_ticket should be set");
+    assert!(ticket < total_weight);
+}
+
+#[test]
+fn test_draw_raffle_ticket_rejected_when_already_drawn() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &[5u8; 32], 100);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("RaffleAlreadyDrawn") || err.contains("custom program error"),
+        "Expected RaffleAlreadyDrawn error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_raffle_prize_pays_out_and_updates_accounting() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let total_weight = 100u64;
+    // A single leaf covering the whole [0, total_weight) range always contains
+    // whatever ticket ends up drawn.
+    let leaf = compute_raffle_leaf(&p1.pubkey(), 0, total_weight);
+    let (root, proofs) = build_merkle_tree(&[leaf]);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let prize_lamports = get_raffle_snapshot(&svm, 1, 0).prize_lamports;
+    assert!(prize_lamports > 0);
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let vault_before = get_vault_balance(&svm, 1);
+    let balance_before = get_balance(&svm, &p1.pubkey());
+
+    let ix = claim_raffle_prize_ix(&p1.pubkey(), &p1.pubkey(), 1, 0, 0, total_weight, &proofs[0]);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    assert_eq!(get_balance(&svm, &p1.pubkey()), balance_before + prize_lamports);
+    assert_eq!(get_vault_balance(&svm, 1), vault_before - prize_lamports);
+    assert_eq!(get_game(&svm, 1).raffle_prize_pool_pending, 0);
+    assert_eq!(get_raffle_snapshot(&svm, 1, 0).prize_lamports, 0);
+}
+
+#[test]
+fn test_claim_raffle_prize_rejects_blocked_player() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let total_weight = 100u64;
+    let leaf = compute_raffle_leaf(&p1.pubkey(), 0, total_weight);
+    let (root, proofs) = build_merkle_tree(&[leaf]);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_raffle_prize_ix(&p1.pubkey(), &p1.pubkey(), 1, 0, 0, total_weight, &proofs[0]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_raffle_prize_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let total_weight = 100u64;
+    let leaf = compute_raffle_leaf(&p1.pubkey(), 0, total_weight);
+    let (root, proofs) = build_merkle_tree(&[leaf]);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_raffle_prize_ix_with_raw_block_entry(
+        &p1.pubkey(),
+        &p1.pubkey(),
+        1,
+        0,
+        0,
+        total_weight,
+        &proofs[0],
+        PROGRAM_ID,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_raffle_prize_rejected_with_ticket_out_of_range() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    // Split the weight range evenly between two committed leaves.
+    let total_weight = 100u64;
+    let leaf1 = compute_raffle_leaf(&p1.pubkey(), 0, 50);
+    let leaf2 = compute_raffle_leaf(&p2.pubkey(), 50, total_weight);
+    let (root, proofs) = build_merkle_tree(&[leaf1, leaf2]);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ticket = get_raffle_snapshot(&svm, 1, 0).winning_ticket.unwrap();
+    // Whichever player's range does NOT contain the ticket tries to claim
+    // with their own validly-proven (but non-winning) range.
+    let (claimant, range_start, range_end, proof) = if ticket < 50 {
+        (&p2, 50u64, total_weight, &proofs[1])
+    } else {
+        (&p1, 0u64, 50u64, &proofs[0])
+    };
+
+    let ix = claim_raffle_prize_ix(
+        &claimant.pubkey(),
+        &claimant.pubkey(),
+        1,
+        0,
+        range_start,
+        range_end,
+        proof,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], claimant, &[claimant]);
+    assert!(
+        err.contains("RaffleTicketOutOfRange") || err.contains("custom program error"),
+        "Expected RaffleTicketOutOfRange error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_raffle_prize_rejected_on_double_claim() {
+    let params = ConfigParamsData {
+        raffle_bps: 1_000,
+        raffle_daily_payout_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let total_weight = 100u64;
+    let leaf = compute_raffle_leaf(&p1.pubkey(), 0, total_weight);
+    let (root, proofs) = build_merkle_tree(&[leaf]);
+
+    let ix = record_raffle_snapshot_ix(&admin.pubkey(), 1, 0, &root, total_weight);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = draw_raffle_ticket_ix(&p1.pubkey(), 1, 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_raffle_prize_ix(&p1.pubkey(), &p1.pubkey(), 1, 0, 0, total_weight, &proofs[0]);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_raffle_prize_ix(&p1.pubkey(), &p1.pubkey(), 1, 0, 0, total_weight, &proofs[0]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        !err.is_empty(),
+        "second claim for the same (day, player) must fail on receipt re-init"
+    );
+}
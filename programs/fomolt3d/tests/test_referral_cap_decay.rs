@@ -0,0 +1,220 @@
+// Integration tests for per-round referral earnings caps and decay
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Mirrors `math::calculate_bps_split` — floor(amount * bps / 10_000).
+fn bps_split(amount: u64, bps: u64) -> u64 {
+    ((amount as u128) * (bps as u128) / 10_000) as u64
+}
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_referral_cap_and_decay_disabled_by_default_matches_uncapped_bonus() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+
+    let bal_before = get_balance(&svm, &player.pubkey());
+    buy(&mut svm, &player, 1, 50, &pw, Some(&referrer.pubkey()));
+    let cost = bal_before - get_balance(&svm, &player.pubkey());
+
+    let game = get_game(&svm, 1);
+    let after_fee = cost - bps_split(cost, game.protocol_fee_bps);
+    let expected_bonus = bps_split(after_fee, game.referral_bonus_bps);
+
+    let ref_state = get_player(&svm, &referrer.pubkey());
+    assert_eq!(
+        ref_state.referral_earnings_lamports, expected_bonus,
+        "0 cap/decay should behave exactly like the pre-existing uncapped referral path"
+    );
+}
+
+#[test]
+fn test_referral_cap_clamps_earnings_and_excess_flows_to_pot() {
+    let cap = 50_000u64;
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referral_earnings_cap_lamports_per_round: cap,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+
+    let game_before = get_game(&svm, 1);
+    let pot_before =
+        game_before.total_dividend_pool + game_before.winner_pot + game_before.next_round_pot;
+
+    // Large enough purchase that the raw 10% referral bonus vastly exceeds the cap.
+    buy(&mut svm, &player, 1, 200, &pw, Some(&referrer.pubkey()));
+
+    let ref_state = get_player(&svm, &referrer.pubkey());
+    assert_eq!(
+        ref_state.referral_earnings_lamports, cap,
+        "Referral earnings should plateau exactly at the configured cap"
+    );
+
+    let game_after = get_game(&svm, 1);
+    let pot_after =
+        game_after.total_dividend_pool + game_after.winner_pot + game_after.next_round_pot;
+    assert!(
+        pot_after > pot_before,
+        "Clamped-off referral bonus should flow into the pot splits instead of vanishing"
+    );
+}
+
+#[test]
+fn test_referral_cap_stops_crediting_further_once_reached() {
+    let cap = 10_000u64;
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referral_earnings_cap_lamports_per_round: cap,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+
+    // First buy alone already blows through the cap.
+    buy(&mut svm, &player, 1, 100, &pw, Some(&referrer.pubkey()));
+    let after_first = get_player(&svm, &referrer.pubkey()).referral_earnings_lamports;
+    assert_eq!(after_first, cap);
+
+    // A second buy in the same round must not push earnings past the cap.
+    svm.expire_blockhash();
+    buy(&mut svm, &player, 1, 100, &pw, Some(&referrer.pubkey()));
+    let after_second = get_player(&svm, &referrer.pubkey()).referral_earnings_lamports;
+    assert_eq!(
+        after_second, cap,
+        "Once the cap is reached, further buys in the round must not add more"
+    );
+}
+
+#[test]
+fn test_referral_decay_halves_bonus_past_threshold() {
+    let threshold = 20_000u64;
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referral_decay_threshold_lamports: threshold,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+
+    // First buy: referrer starts this round at 0 lamports earned, so the
+    // full bps applies regardless of whether this single buy's bonus
+    // crosses the threshold on its own.
+    let bal_before_1 = get_balance(&svm, &player.pubkey());
+    buy(&mut svm, &player, 1, 60, &pw, Some(&referrer.pubkey()));
+    let cost_1 = bal_before_1 - get_balance(&svm, &player.pubkey());
+
+    let game = get_game(&svm, 1);
+    let after_fee_1 = cost_1 - bps_split(cost_1, game.protocol_fee_bps);
+    let expected_bonus_1 = bps_split(after_fee_1, game.referral_bonus_bps);
+
+    let earnings_after_1 = get_player(&svm, &referrer.pubkey()).referral_earnings_lamports;
+    assert_eq!(earnings_after_1, expected_bonus_1);
+    assert!(
+        earnings_after_1 >= threshold,
+        "Test setup expects the first buy alone to cross the decay threshold"
+    );
+
+    // Second buy: earnings_this_round_lamports is now >= threshold, so the
+    // effective bps is halved for this purchase.
+    svm.expire_blockhash();
+    let bal_before_2 = get_balance(&svm, &player.pubkey());
+    buy(&mut svm, &player, 1, 60, &pw, Some(&referrer.pubkey()));
+    let cost_2 = bal_before_2 - get_balance(&svm, &player.pubkey());
+
+    let game = get_game(&svm, 1);
+    let after_fee_2 = cost_2 - bps_split(cost_2, game.protocol_fee_bps);
+    let expected_bonus_2 = bps_split(after_fee_2, game.referral_bonus_bps / 2);
+
+    let earnings_after_2 = get_player(&svm, &referrer.pubkey()).referral_earnings_lamports;
+    assert_eq!(
+        earnings_after_2 - earnings_after_1,
+        expected_bonus_2,
+        "Bonus on the second buy should use half the configured referral_bonus_bps"
+    );
+}
+
+#[test]
+fn test_referral_cap_and_decay_reset_each_round() {
+    let cap = 10_000u64;
+    let (mut svm, admin, pw) = setup_game_with_config(ConfigParamsData {
+        referral_earnings_cap_lamports_per_round: cap,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 100, &pw, Some(&referrer.pubkey()));
+    assert_eq!(
+        get_player(&svm, &referrer.pubkey()).referral_earnings_lamports,
+        cap
+    );
+
+    // Roll over to a new round. Referrer is the last buyer so it wins; both
+    // players claim (reinstating current_round = 0) so they can re-enter.
+    expire_round(&mut svm, 1);
+    let new_round = complete_round_and_start_next(&mut svm, &admin, 1, &player);
+    assert_eq!(new_round, 2);
+    svm.expire_blockhash();
+    let ix = claim_ix(&referrer.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).unwrap();
+    svm.expire_blockhash();
+
+    // Referrer and player both re-enter round 2 via a 0-key buy, then the
+    // player buys again with the same referrer — the cap counter should
+    // have reset for the new round.
+    buy(&mut svm, &referrer, 2, 0, &pw, None);
+    buy(&mut svm, &player, 2, 0, &pw, None);
+    svm.expire_blockhash();
+    buy(&mut svm, &player, 2, 100, &pw, Some(&referrer.pubkey()));
+
+    let ref_state = get_player(&svm, &referrer.pubkey());
+    assert_eq!(
+        ref_state.referral_earnings_this_round_lamports, cap,
+        "New round should allow earning back up to the cap again"
+    );
+}
@@ -0,0 +1,102 @@
+// Integration tests: forwarding a player's unclaimed referral-earnings
+// backing from a stale round's vault into the currently active round's vault
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_consolidate_moves_vault_and_obligation_bookkeeping() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let earnings = get_player(&svm, &referrer.pubkey()).referral_earnings_lamports;
+    assert!(earnings > 0);
+
+    expire_round(&mut svm, 1);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let old_before = get_game(&svm, 1);
+    let current_before = get_game(&svm, 2);
+    assert_eq!(old_before.total_referral_obligations, earnings);
+
+    let ix = consolidate_referral_earnings_ix(&admin.pubkey(), 1, 2, &referrer.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).expect("consolidation should succeed");
+
+    let old_after = get_game(&svm, 1);
+    let current_after = get_game(&svm, 2);
+    assert_eq!(old_after.total_referral_obligations, 0);
+    assert_eq!(
+        current_after.total_referral_obligations,
+        current_before.total_referral_obligations + earnings
+    );
+
+    let vault1 = get_vault_balance(&svm, 1);
+    let vault2 = get_vault_balance(&svm, 2);
+    assert_eq!(old_after.vault_lamports_in - old_after.vault_lamports_out, vault1);
+    assert_eq!(
+        current_after.vault_lamports_in - current_after.vault_lamports_out,
+        vault2
+    );
+
+    // The forwarded amount is now claimable against the current round.
+    let bal_before = get_balance(&svm, &referrer.pubkey());
+    let ix = claim_referral_earnings_ix(&referrer.pubkey(), 2);
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).expect("claim against new round should succeed");
+    let bal_after = get_balance(&svm, &referrer.pubkey());
+    assert!(bal_after > bal_before);
+}
+
+#[test]
+fn test_consolidate_rejected_when_nothing_to_consolidate() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // `player` never earned any referral bonus — nothing to forward.
+    let ix = consolidate_referral_earnings_ix(&admin.pubkey(), 1, 2, &player.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("NoReferralEarnings") || err.contains("custom program error"),
+        "Expected NoReferralEarnings, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_consolidate_rejected_when_current_round_not_active() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    // Round 1 is still active — "current" round can't be itself.
+    let ix = consolidate_referral_earnings_ix(&admin.pubkey(), 1, 1, &referrer.pubkey());
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("GameStillActive") || err.contains("custom program error"),
+        "Expected GameStillActive, got: {}",
+        err
+    );
+}
@@ -87,6 +87,57 @@ fn test_claim_referral_earnings() {
     assert!(ref_state.claimed_referral_earnings_lamports > 0);
 }
 
+#[test]
+fn test_blocked_referrer_cannot_claim_referral_earnings() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &referrer.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_referral_earnings_ix(&referrer.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_referrer_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &referrer.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix =
+        claim_referral_earnings_ix_with_raw_block_entry(&referrer.pubkey(), 1, PROGRAM_ID);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
 #[test]
 fn test_claim_zero_referral_earnings_fails() {
     let (mut svm, _admin, pw) = setup_game();
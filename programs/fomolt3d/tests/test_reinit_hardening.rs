@@ -0,0 +1,111 @@
+// Integration tests for PlayerState::initialized / PlayerState::generation —
+// the belt-and-suspenders guard against a `close_player_state`d PDA being
+// treated as trustworthy again without a genuine `is_new_player` init run.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_generation_increments_across_close_and_reopen() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert!(player.initialized);
+    assert_eq!(player.generation, 1);
+
+    expire_round(&mut svm, 1);
+    let ix = claim_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = close_player_state_ix(&p1.pubkey());
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    buy(&mut svm, &p1, 2, 3, &pw, None);
+
+    let player = get_player(&svm, &p1.pubkey());
+    assert!(player.initialized);
+    assert_eq!(player.generation, 2);
+}
+
+// A genuine same-transaction "revival" of a closed PDA (an attacker CPIing
+// into a hostile program that re-funds the account before this program's own
+// `init_if_needed` runs in the same transaction) can't be expressed through
+// the public instruction set without deploying a second on-chain program —
+// not something this test suite does anywhere. `set_player_state` (see
+// helpers.rs) is the closest feasible stand-in: it writes a `PlayerState`
+// buffer directly into the SVM's account store with `initialized: false`,
+// simulating an account whose lamports and discriminator were resurrected
+// but which never ran the real init branch.
+#[test]
+fn test_claim_rejects_revived_uninitialized_player_state() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+
+    let (_, bump) = player_pda(&p1.pubkey());
+    set_player_state(
+        &mut svm,
+        &PlayerStateData {
+            game_id: DEFAULT_GAME_ID,
+            player: p1.pubkey().to_bytes(),
+            keys: 5,
+            current_round: 1,
+            claimed_dividends_lamports: 0,
+            referrer: None,
+            referral_earnings_lamports: 0,
+            claimed_referral_earnings_lamports: 0,
+            is_agent: false,
+            bump,
+            dividend_weight: 50_000,
+            auto_compound: false,
+            dividend_weight_seconds: 0,
+            dividend_seconds_last_update: 0,
+            referral_earnings_round: 0,
+            referral_earnings_this_round_lamports: 0,
+            pending_referral_earnings_lamports: 0,
+            referrer_set_at: 0,
+            spend_limit_lamports_per_day: 0,
+            pending_spend_limit_lamports_per_day: None,
+            spend_limit_effective_at: 0,
+            spend_window_start: 0,
+            spend_window_lamports: 0,
+            timer_extension_window_start: 0,
+            timer_extensions_in_window: 0,
+            payout_address: None,
+            contributed_lamports: 0,
+            total_contributed_lamports: 0,
+            initialized: false,
+            generation: 3,
+            pending_migration_wallet: None,
+            migration_effective_at: 0,
+            strategy_tag: 0,
+            agent_platform: None,
+            prepaid_balance_lamports: 0,
+            scheduled_buy_keys: 0,
+            scheduled_buy_interval_secs: 0,
+            last_scheduled_buy_at: 0,
+        },
+    );
+
+    expire_round(&mut svm, 1);
+
+    let ix = claim_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(err.contains("PlayerStateNotInitialized") || err.contains("custom program error"));
+
+    let ix = refund_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(err.contains("PlayerStateNotInitialized") || err.contains("custom program error"));
+}
@@ -0,0 +1,194 @@
+// Integration tests for admin round cancellation and the resulting refund
+// path: cancel_round freezes an Active round and folds its pot buckets into
+// GameState::refund_pool_lamports, and refund pays PlayerState::contributed_lamports
+// back 1:1.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn test_admin_can_cancel_active_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let game_before = get_game(&svm, 1);
+    let expected_refund_pool = game_before.winner_pot
+        + game_before.total_dividend_pool
+        + game_before.next_round_pot
+        + game_before.raffle_pool_lamports
+        + game_before.dust_reserve;
+
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.status, RoundStatusData::Cancelled);
+    assert_eq!(game.refund_pool_lamports, expected_refund_pool);
+    assert_eq!(game.winner_pot, 0);
+    assert_eq!(game.total_dividend_pool, 0);
+    assert_eq!(game.next_round_pot, 0);
+    assert_eq!(game.raffle_pool_lamports, 0);
+    assert_eq!(game.dust_reserve, 0);
+}
+
+#[test]
+fn test_non_admin_cannot_cancel_round() {
+    let (mut svm, _admin, _pw) = setup_game();
+    let attacker = Keypair::new();
+    airdrop(&mut svm, &attacker.pubkey(), 10_000_000_000);
+
+    let ix = cancel_round_ix(&attacker.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &attacker, &[&attacker]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_cannot_cancel_a_non_active_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix(&admin.pubkey(), 1, &p1.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("RoundNotCancellable") || err.contains("custom program error"),
+        "Expected RoundNotCancellable, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_refund_pays_out_contributed_lamports_and_zeroes_it() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let contributed = get_player(&svm, &p1.pubkey()).contributed_lamports;
+    assert!(contributed > 0);
+
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let bal_before = get_balance(&svm, &p1.pubkey());
+    let ix = refund_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    let bal_after = get_balance(&svm, &p1.pubkey());
+
+    assert_eq!(bal_after - bal_before, contributed);
+    assert_eq!(get_player(&svm, &p1.pubkey()).contributed_lamports, 0);
+    assert_eq!(get_game(&svm, 1).refund_pool_lamports, 0);
+}
+
+#[test]
+fn test_double_refund_fails() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = refund_ix(&p1.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = refund_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("NothingToRefund") || err.contains("custom program error"),
+        "Expected NothingToRefund, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_refund_before_cancellation_fails() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let ix = refund_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("RoundNotCancelled") || err.contains("custom program error"),
+        "Expected RoundNotCancelled, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_fails_on_cancelled_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = claim_ix(&p1.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("RoundCancelled") || err.contains("custom program error"),
+        "Expected RoundCancelled, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_refund_routes_to_payout_address() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 10, &pw, None);
+
+    let treasury = Pubkey::new_unique();
+    svm.expire_blockhash();
+    let ix = set_preferences_ix_with_payout_address(&p1.pubkey(), false, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let contributed = get_player(&svm, &p1.pubkey()).contributed_lamports;
+
+    svm.expire_blockhash();
+    let ix = cancel_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let signer_bal_before = get_balance(&svm, &p1.pubkey());
+    let treasury_bal_before = get_balance(&svm, &treasury);
+
+    let ix = refund_ix_with_payout_destination(&p1.pubkey(), 1, Some(treasury));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let signer_bal_after = get_balance(&svm, &p1.pubkey());
+    let treasury_bal_after = get_balance(&svm, &treasury);
+
+    assert_eq!(signer_bal_after, signer_bal_before);
+    assert_eq!(treasury_bal_after - treasury_bal_before, contributed);
+}
@@ -0,0 +1,89 @@
+// Integration tests: round-duration analytics accumulated on `GameState` and
+// surfaced on `events::RoundConcluded` (see `GameState::round_duration_secs` /
+// `average_seconds_between_buys` and the `pot_checkpoint_*` fields).
+mod helpers;
+
+use helpers::*;
+use solana_sdk::signature::Keypair;
+
+#[test]
+fn fresh_round_has_zeroed_analytics() {
+    let (svm, _admin, _pw) = setup_game();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.timer_extensions_triggered, 0);
+    assert_eq!(game.buy_interval_seconds_total, 0);
+    assert_eq!(game.last_buy_timestamp, game.round_start);
+    assert_eq!(game.pot_checkpoint_25_lamports, 0);
+    assert_eq!(game.pot_checkpoint_50_lamports, 0);
+    assert_eq!(game.pot_checkpoint_75_lamports, 0);
+    assert!(!game.pot_checkpoint_25_reached);
+    assert!(!game.pot_checkpoint_50_reached);
+    assert!(!game.pot_checkpoint_75_reached);
+}
+
+#[test]
+fn buy_interval_accrues_across_purchases() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    advance_clock(&mut svm, 30);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    advance_clock(&mut svm, 70);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.buy_interval_seconds_total, 100);
+    assert_eq!(game.purchase_count, 3);
+    assert_eq!(
+        game.buy_interval_seconds_total / game.purchase_count as i64,
+        33
+    );
+}
+
+#[test]
+fn timer_extensions_triggered_counts_extending_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let after_first = get_game(&svm, 1).timer_extensions_triggered;
+    assert_eq!(after_first, 1);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let game = get_game(&svm, 1);
+    assert_eq!(game.timer_extensions_triggered, 2);
+}
+
+#[test]
+fn pot_checkpoints_recorded_once_elapsed_time_crosses_thresholds() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert!(!game.pot_checkpoint_25_reached);
+    let max_timer_secs = game.max_timer_secs;
+
+    // Default max_timer_secs is 24h; jump straight to just past the 25% mark.
+    advance_clock(&mut svm, max_timer_secs / 4 + 1);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let game = get_game(&svm, 1);
+    assert!(game.pot_checkpoint_25_reached);
+    assert_eq!(game.pot_checkpoint_25_lamports, game.pot_lamports);
+    assert!(!game.pot_checkpoint_50_reached);
+
+    // Jump to just past the 75% mark — 50% and 75% should both land on this buy.
+    advance_clock(&mut svm, max_timer_secs / 2);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let game = get_game(&svm, 1);
+    assert!(game.pot_checkpoint_50_reached);
+    assert!(game.pot_checkpoint_75_reached);
+    assert_eq!(game.pot_checkpoint_50_lamports, game.pot_lamports);
+    assert_eq!(game.pot_checkpoint_75_lamports, game.pot_lamports);
+}
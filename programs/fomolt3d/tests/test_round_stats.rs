@@ -0,0 +1,75 @@
+// Integration tests: round-level purchase statistics (purchase_count,
+// gross_volume_lamports, max_single_buy_lamports/max_single_buyer).
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn stats_start_at_zero_for_a_fresh_round() {
+    let (svm, _admin, _pw) = setup_game();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.purchase_count, 0);
+    assert_eq!(game.gross_volume_lamports, 0);
+    assert_eq!(game.max_single_buy_lamports, 0);
+    assert!(game.max_single_buyer_pubkey() == solana_sdk::pubkey::Pubkey::default());
+}
+
+#[test]
+fn stats_accumulate_across_buys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let p2 = Keypair::new();
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p2, 1, 2, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.purchase_count, 2);
+    assert_eq!(game.gross_volume_lamports, game.pot_lamports);
+}
+
+#[test]
+fn max_single_buy_tracks_largest_buyer() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 2, &pw, None);
+
+    let p2 = Keypair::new();
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p2, 1, 20, &pw, None);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.max_single_buyer_pubkey(), p2.pubkey());
+    assert!(game.max_single_buy_lamports > 0);
+
+    // A smaller follow-up buy from p1 doesn't overwrite the record.
+    buy(&mut svm, &p1, 1, 1, &pw, None);
+    let game = get_game(&svm, 1);
+    assert_eq!(game.max_single_buyer_pubkey(), p2.pubkey());
+}
+
+#[test]
+fn gross_volume_excludes_carry_over_into_next_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    complete_round_and_start_next(&mut svm, &admin, 1, &p1);
+
+    let new_round = get_game(&svm, 2);
+    // pot_lamports is seeded with the carried-over winner pot, but the
+    // fresh-round purchase stats always start clean.
+    assert_eq!(new_round.purchase_count, 0);
+    assert_eq!(new_round.gross_volume_lamports, 0);
+    assert_eq!(new_round.max_single_buy_lamports, 0);
+}
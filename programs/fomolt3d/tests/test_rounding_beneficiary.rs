@@ -0,0 +1,115 @@
+// Integration tests: `GlobalConfig::rounding_beneficiary` / `GameState::rounding_beneficiary`
+// choosing where a buy's leftover bps-split dust lands — see `instructions::buy_keys`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, solana_sdk::pubkey::Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = solana_sdk::pubkey::Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_default_config_routes_dust_to_protocol() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let vault_bal = get_vault_balance(&svm, 1);
+    assert!(game.dust_reserve > 0, "test setup should have produced some dust");
+
+    let accounting_sum =
+        game.winner_pot + game.total_dividend_pool + game.next_round_pot + game.dust_reserve;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "winner_pot + total_dividend_pool + next_round_pot + dust_reserve must equal vault balance"
+    );
+}
+
+#[test]
+fn test_winner_pot_beneficiary_routes_dust_to_winner_pot() {
+    let params = ConfigParamsData {
+        rounding_beneficiary: RoundingBeneficiaryData::WinnerPot,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let vault_bal = get_vault_balance(&svm, 1);
+
+    assert_eq!(game.dust_reserve, 0, "dust must not land in dust_reserve for this setting");
+    let accounting_sum = game.winner_pot + game.total_dividend_pool + game.next_round_pot;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "winner_pot + total_dividend_pool + next_round_pot must equal vault balance with no dust_reserve"
+    );
+}
+
+#[test]
+fn test_dividend_pool_beneficiary_routes_dust_to_dividend_pool() {
+    let params = ConfigParamsData {
+        rounding_beneficiary: RoundingBeneficiaryData::DividendPool,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let vault_bal = get_vault_balance(&svm, 1);
+
+    assert_eq!(game.dust_reserve, 0, "dust must not land in dust_reserve for this setting");
+    let accounting_sum = game.winner_pot + game.total_dividend_pool + game.next_round_pot;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "winner_pot + total_dividend_pool + next_round_pot must equal vault balance with no dust_reserve"
+    );
+}
+
+#[test]
+fn test_next_round_pot_beneficiary_routes_dust_to_next_round_pot() {
+    let params = ConfigParamsData {
+        rounding_beneficiary: RoundingBeneficiaryData::NextRoundPot,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let game = get_game(&svm, 1);
+    let vault_bal = get_vault_balance(&svm, 1);
+
+    assert_eq!(game.dust_reserve, 0, "dust must not land in dust_reserve for this setting");
+    let accounting_sum = game.winner_pot + game.total_dividend_pool + game.next_round_pot;
+    assert_eq!(
+        accounting_sum, vault_bal,
+        "winner_pot + total_dividend_pool + next_round_pot must equal vault balance with no dust_reserve"
+    );
+}
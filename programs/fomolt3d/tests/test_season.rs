@@ -0,0 +1,286 @@
+// Integration tests for the season meta-game: `Season` volume/win accrual
+// across `GlobalConfig::season_length_rounds` consecutive rounds, funded by
+// a slice of the house fee (`GlobalConfig::season_fee_bps`), settled by
+// `settle_season` and paid out by `claim_season_prize`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Mirrors `math::calculate_bps_split` — floor(amount * bps / 10_000).
+fn bps_split(amount: u64, bps: u64) -> u64 {
+    ((amount as u128) * (bps as u128) / 10_000) as u64
+}
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_disabled_season_leaves_pool_at_zero() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let season = get_season(&svm, DEFAULT_GAME_ID, 0);
+    assert_eq!(season.pool_lamports, 0, "season_fee_bps defaults to 0");
+}
+
+#[test]
+fn test_buy_funds_season_pool_and_credits_volume() {
+    let params = ConfigParamsData {
+        season_length_rounds: 10,
+        season_fee_bps: 2_000, // 20% of the house fee
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    let cost = expected_cost(0, 7);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    let expected_season_cut = bps_split(expected_protocol_fee(cost), 2_000);
+    assert!(expected_season_cut > 0, "test setup should produce a nonzero season cut");
+
+    let season = get_season(&svm, DEFAULT_GAME_ID, 0);
+    assert_eq!(season.pool_lamports, expected_season_cut);
+    assert_eq!(season.start_round, 1);
+    assert_eq!(season.end_round, 10);
+    assert_eq!(season.leaderboard[0].player, p1.pubkey().to_bytes());
+    assert_eq!(season.leaderboard[0].volume_lamports, cost);
+}
+
+#[test]
+fn test_season_pool_matches_vault_across_multiple_buys() {
+    let params = ConfigParamsData {
+        season_length_rounds: 10,
+        season_fee_bps: 1_500,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p1, 1, 3, &pw, None);
+    buy(&mut svm, &p2, 1, 5, &pw, None);
+
+    let season = get_season(&svm, DEFAULT_GAME_ID, 0);
+    let (season_key, _) = season_pda(DEFAULT_GAME_ID, 0);
+    let (season_vault_key, _) = season_vault_pda(&season_key);
+    let vault_balance = svm.get_balance(&season_vault_key).unwrap();
+    assert_eq!(season.pool_lamports, vault_balance);
+}
+
+#[test]
+fn test_credit_win_requires_matching_season_pda() {
+    let params = ConfigParamsData {
+        season_length_rounds: 10,
+        season_fee_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let ix = claim_winner_ix_with_season(&p1.pubkey(), 1, false, None, Some(admin.pubkey()));
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(!err.is_empty(), "wrong season PDA must be rejected");
+}
+
+#[test]
+fn test_claim_winner_credits_season_win() {
+    let params = ConfigParamsData {
+        season_length_rounds: 10,
+        season_fee_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let (season_key, _) = season_pda(DEFAULT_GAME_ID, 0);
+    let ix = claim_winner_ix_with_season(&p1.pubkey(), 1, false, None, Some(season_key));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let season = get_season(&svm, DEFAULT_GAME_ID, 0);
+    assert_eq!(season.leaderboard[0].wins, 1);
+}
+
+#[test]
+fn test_settle_season_rejected_before_end_round_concludes() {
+    let params = ConfigParamsData {
+        season_length_rounds: 1,
+        season_fee_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = settle_season_ix(&p1.pubkey(), 0, 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(!err.is_empty(), "an active end_round must reject settlement");
+}
+
+#[test]
+fn test_settle_season_then_claim_season_prize_pays_out_and_rejects_double_claim() {
+    let params = ConfigParamsData {
+        season_length_rounds: 1,
+        season_fee_bps: 5_000, // 50% of the house fee, to keep the payout comfortably nonzero
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let (season_key, _) = season_pda(DEFAULT_GAME_ID, 0);
+    let ix = claim_winner_ix_with_season(&p1.pubkey(), 1, false, None, Some(season_key));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let pool_lamports = get_season(&svm, DEFAULT_GAME_ID, 0).pool_lamports;
+    assert!(pool_lamports > 0, "test setup should have funded the season pool");
+
+    let ix = settle_season_ix(&p1.pubkey(), 0, 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let balance_before = svm.get_balance(&p1.pubkey()).unwrap();
+    let ix = claim_season_prize_ix(&p1.pubkey(), 0);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let expected_rank0_amount = bps_split(pool_lamports, 5_000); // SEASON_PAYOUT_BPS[0]
+    let balance_after = svm.get_balance(&p1.pubkey()).unwrap();
+    assert!(
+        balance_after > balance_before,
+        "sole leaderboard entry should be paid rank 0's share"
+    );
+    assert_eq!(balance_after - balance_before, expected_rank0_amount);
+
+    let ix = claim_season_prize_ix(&p1.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        !err.is_empty(),
+        "second claim for the same (season, player) must fail on receipt re-init"
+    );
+}
+
+#[test]
+fn test_claim_season_prize_rejects_blocked_player() {
+    let params = ConfigParamsData {
+        season_length_rounds: 1,
+        season_fee_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let (season_key, _) = season_pda(DEFAULT_GAME_ID, 0);
+    let ix = claim_winner_ix_with_season(&p1.pubkey(), 1, false, None, Some(season_key));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let ix = settle_season_ix(&p1.pubkey(), 0, 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_season_prize_ix(&p1.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_season_prize_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let params = ConfigParamsData {
+        season_length_rounds: 1,
+        season_fee_bps: 5_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 7, &pw, None);
+
+    expire_round(&mut svm, 1);
+    let (season_key, _) = season_pda(DEFAULT_GAME_ID, 0);
+    let ix = claim_winner_ix_with_season(&p1.pubkey(), 1, false, None, Some(season_key));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+
+    let ix = settle_season_ix(&p1.pubkey(), 0, 1);
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+    svm.expire_blockhash();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &p1.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_season_prize_ix_with_raw_block_entry(&p1.pubkey(), 0, PROGRAM_ID);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_season_prize_rejected_before_settlement() {
+    let params = ConfigParamsData {
+        season_length_rounds: 10,
+        season_fee_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = claim_season_prize_ix(&p1.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &p1, &[&p1]);
+    assert!(!err.is_empty(), "an unsettled season must reject claims");
+}
@@ -0,0 +1,176 @@
+// Integration tests: delegated buying via SessionAuthority (create_session / buy_keys_via_session)
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_delegate_buy_attributes_keys_to_owner() {
+    let (mut svm, _admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &delegate.pubkey(), 10_000_000_000);
+
+    let now = get_clock(&svm).unix_timestamp;
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1_000_000_000, now + 3600);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_via_session_ix(&delegate.pubkey(), &owner.pubkey(), 1, 5, false, &pw, None);
+    send_tx(&mut svm, &[ix], &delegate, &[&delegate]).unwrap();
+
+    let player = get_player(&svm, &owner.pubkey());
+    assert_eq!(player.keys, 5);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 5);
+    assert_eq!(game.last_buyer_pubkey(), owner.pubkey());
+}
+
+#[test]
+fn test_session_spend_limit_enforced() {
+    let (mut svm, _admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &delegate.pubkey(), 10_000_000_000);
+
+    let now = get_clock(&svm).unix_timestamp;
+    // Spend limit too small for even a single key at round 1's base price.
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1, now + 3600);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_via_session_ix(&delegate.pubkey(), &owner.pubkey(), 1, 5, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &delegate, &[&delegate]);
+    assert!(
+        err.contains("SessionSpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SessionSpendLimitExceeded error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_expired_session_rejected() {
+    let (mut svm, _admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &delegate.pubkey(), 10_000_000_000);
+
+    let now = get_clock(&svm).unix_timestamp;
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1_000_000_000, now + 100);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    advance_clock(&mut svm, 200);
+    svm.expire_blockhash();
+
+    let ix = buy_keys_via_session_ix(&delegate.pubkey(), &owner.pubkey(), 1, 5, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &delegate, &[&delegate]);
+    assert!(
+        err.contains("SessionExpired") || err.contains("custom program error"),
+        "Expected SessionExpired error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_unauthorized_delegate_rejected() {
+    let (mut svm, _admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &impostor.pubkey(), 10_000_000_000);
+
+    let now = get_clock(&svm).unix_timestamp;
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1_000_000_000, now + 3600);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    // impostor was never delegated — no SessionAuthority PDA exists for them.
+    let ix = buy_keys_via_session_ix(&impostor.pubkey(), &owner.pubkey(), 1, 5, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(
+        err.contains("AccountNotInitialized") || err.contains("custom program error") || err.contains("uninitialized"),
+        "Expected account-not-found style error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_owner_cannot_buy_via_session() {
+    let (mut svm, admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &delegate.pubkey(), 10_000_000_000);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &owner.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let now = get_clock(&svm).unix_timestamp;
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1_000_000_000, now + 3600);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = buy_keys_via_session_ix_with_block_entry(
+        &delegate.pubkey(),
+        &owner.pubkey(),
+        1,
+        5,
+        false,
+        &pw,
+        None,
+        true,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &delegate, &[&delegate]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_owner_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let (mut svm, admin, pw) = setup_game();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    airdrop(&mut svm, &owner.pubkey(), 10_000_000_000);
+    airdrop(&mut svm, &delegate.pubkey(), 10_000_000_000);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &owner.pubkey(), false);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let now = get_clock(&svm).unix_timestamp;
+    let ix = create_session_ix(&owner.pubkey(), &delegate.pubkey(), 1_000_000_000, now + 3600);
+    send_tx(&mut svm, &[ix], &owner, &[&owner]).unwrap();
+    svm.expire_blockhash();
+
+    // Pre-fix, `block_entry` was an `Option<Account>` — a blocked owner's
+    // delegate could substitute the program-ID "None" sentinel to skip the
+    // blocklist check entirely. `block_entry` is now required and
+    // seeds-constrained, so the substitution must be rejected before the
+    // handler ever runs.
+    let ix = buy_keys_via_session_ix_with_raw_block_entry(
+        &delegate.pubkey(),
+        &owner.pubkey(),
+        1,
+        5,
+        false,
+        &pw,
+        None,
+        PROGRAM_ID,
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &delegate, &[&delegate]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
@@ -0,0 +1,329 @@
+// Integration tests for the set_referrer instruction
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_set_referrer_attaches_to_unreferred_player() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    // Both register via 0-key buys without a referrer.
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &referrer.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert!(ps.referrer.is_some());
+    assert!(pubkey_eq(&ps.referrer.unwrap(), &referrer.pubkey()));
+}
+
+#[test]
+fn test_set_referrer_then_buy_earns_referral_bonus() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &referrer.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    svm.expire_blockhash();
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let ref_state = get_player(&svm, &referrer.pubkey());
+    assert!(
+        ref_state.referral_earnings_lamports > 0,
+        "Referrer attached via set_referrer should still earn referral bonus on later buys"
+    );
+}
+
+#[test]
+fn test_set_referrer_fails_if_already_set_and_changes_disabled() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let other = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &other, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &other, 1, 0, &pw, None);
+    // First buy with a referrer sets it.
+    buy(&mut svm, &player, 1, 0, &pw, Some(&referrer.pubkey()));
+
+    svm.expire_blockhash();
+    // Default config leaves referrer_change_cooldown_secs at 0, i.e. changes disabled.
+    let ix = set_referrer_ix(&player.pubkey(), &other.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("ReferrerAlreadySet") || err.contains("custom program error"),
+        "Expected ReferrerAlreadySet, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_fails_after_a_buy_this_round() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    // Player buys real keys with no referrer first.
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &referrer.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("ReferrerWindowClosed") || err.contains("custom program error"),
+        "Expected ReferrerWindowClosed, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_self_referral_fails() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("CannotReferSelf") || err.contains("custom program error"),
+        "Expected CannotReferSelf, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_direct_cycle_fails() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let a = Keypair::new();
+    let b = Keypair::new();
+    register(&mut svm, &a, 1, false, None);
+    register(&mut svm, &b, 1, false, None);
+
+    buy(&mut svm, &a, 1, 0, &pw, None);
+    buy(&mut svm, &b, 1, 0, &pw, None);
+
+    // B sets A as its referrer first.
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&b.pubkey(), &a.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &b, &[&b]).unwrap();
+
+    // A now tries to set B as its referrer, which would form a 2-cycle.
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&a.pubkey(), &b.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &a, &[&a]);
+    assert!(
+        err.contains("ReferralCycleDetected") || err.contains("custom program error"),
+        "Expected ReferralCycleDetected, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_change_fails_before_cooldown_elapses() {
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referrer_change_cooldown_secs: 3600,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let other = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &other, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &other, 1, 0, &pw, None);
+    buy(&mut svm, &player, 1, 0, &pw, Some(&referrer.pubkey()));
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &other.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("ReferrerChangeCooldownActive") || err.contains("custom program error"),
+        "Expected ReferrerChangeCooldownActive, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_change_succeeds_after_cooldown_and_emits_referrer_changed() {
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referrer_change_cooldown_secs: 3600,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let other = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &other, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &other, 1, 0, &pw, None);
+    buy(&mut svm, &player, 1, 0, &pw, Some(&referrer.pubkey()));
+
+    advance_clock(&mut svm, 3601);
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &other.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert!(pubkey_eq(&ps.referrer.unwrap(), &other.pubkey()));
+}
+
+#[test]
+fn test_set_referrer_change_still_blocked_after_buying_keys_this_round() {
+    let (mut svm, _admin, pw) = setup_game_with_config(ConfigParamsData {
+        referrer_change_cooldown_secs: 3600,
+        ..Default::default()
+    });
+
+    let referrer = Keypair::new();
+    let other = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &other, 1, false, None);
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &referrer, 1, 0, &pw, None);
+    buy(&mut svm, &other, 1, 0, &pw, None);
+    // Player registers with a referrer and immediately buys real keys this round.
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    advance_clock(&mut svm, 3601);
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&player.pubkey(), &other.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("ReferrerWindowClosed") || err.contains("custom program error"),
+        "A referrer change should still be blocked once keys were bought this round, \
+         even with the cooldown elapsed (prevents churn-based bonus farming). Got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_rejects_multi_level_ring() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let a = Keypair::new();
+    let b = Keypair::new();
+    let c = Keypair::new();
+    register(&mut svm, &a, 1, false, None);
+    register(&mut svm, &b, 1, false, None);
+    register(&mut svm, &c, 1, false, None);
+
+    buy(&mut svm, &a, 1, 0, &pw, None);
+    buy(&mut svm, &b, 1, 0, &pw, None);
+    buy(&mut svm, &c, 1, 0, &pw, None);
+
+    // Build the chain A <- B <- C (B refers to A, C refers to B).
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&b.pubkey(), &a.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &b, &[&b]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&c.pubkey(), &b.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &c, &[&c]).unwrap();
+
+    // A now tries to set C as its referrer. The direct pair (A, C) isn't a
+    // cycle by itself, but walking C's ancestry (C -> B -> A) reveals A would
+    // be referring into its own downline three levels deep.
+    svm.expire_blockhash();
+    let ix = set_referrer_ix_with_chain(&a.pubkey(), &c.pubkey(), 1, &[b.pubkey(), a.pubkey()]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &a, &[&a]);
+    assert!(
+        err.contains("ReferralCycleDetected") || err.contains("custom program error"),
+        "Expected ReferralCycleDetected from the deeper-ring walk, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_referrer_allows_legitimate_multi_level_chain() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let a = Keypair::new();
+    let b = Keypair::new();
+    let c = Keypair::new();
+    let d = Keypair::new();
+    register(&mut svm, &a, 1, false, None);
+    register(&mut svm, &b, 1, false, None);
+    register(&mut svm, &c, 1, false, None);
+    register(&mut svm, &d, 1, false, None);
+
+    buy(&mut svm, &a, 1, 0, &pw, None);
+    buy(&mut svm, &b, 1, 0, &pw, None);
+    buy(&mut svm, &c, 1, 0, &pw, None);
+    buy(&mut svm, &d, 1, 0, &pw, None);
+
+    // Chain A <- B <- C, same as the ring test, but D (unrelated to the
+    // chain) sets C as its referrer — not a cycle, should succeed even
+    // though D supplies C's full ancestry as remaining_accounts.
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&b.pubkey(), &a.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &b, &[&b]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix(&c.pubkey(), &b.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &c, &[&c]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = set_referrer_ix_with_chain(&d.pubkey(), &c.pubkey(), 1, &[b.pubkey(), a.pubkey()]);
+    send_tx(&mut svm, &[ix], &d, &[&d]).unwrap();
+
+    let ds = get_player(&svm, &d.pubkey());
+    assert!(pubkey_eq(&ds.referrer.unwrap(), &c.pubkey()));
+}
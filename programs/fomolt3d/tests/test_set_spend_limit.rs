@@ -0,0 +1,170 @@
+// Integration tests for the set_spend_limit instruction
+mod helpers;
+
+use helpers::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+/// Cost of the first key under the default bonding curve
+/// (base_price_lamports = 10_000_000).
+const FIRST_KEY_COST_LAMPORTS: u64 = 10_000_000;
+
+#[test]
+fn test_buy_keys_rejects_purchase_exceeding_spend_limit() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS - 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_buy_keys_allows_purchase_within_spend_limit_and_tracks_window() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS * 2);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.spend_window_lamports, FIRST_KEY_COST_LAMPORTS);
+
+    // A second key would push cumulative spend over the cap.
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_spend_limit_lowering_applies_immediately() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS * 10);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    // Lowering the cap below the next purchase's cost takes effect right away.
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS - 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.spend_limit_lamports_per_day, FIRST_KEY_COST_LAMPORTS - 1);
+    assert!(ps.pending_spend_limit_lamports_per_day.is_none());
+
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_set_spend_limit_raise_is_delayed_until_increase_delay_elapses() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS - 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    // Raising the cap doesn't apply yet — old (lower) cap still governs.
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS * 10);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.spend_limit_lamports_per_day, FIRST_KEY_COST_LAMPORTS - 1);
+    assert_eq!(
+        ps.pending_spend_limit_lamports_per_day,
+        Some(FIRST_KEY_COST_LAMPORTS * 10)
+    );
+
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded before the raise takes effect, got: {}",
+        err
+    );
+
+    // Once the delay has elapsed, the raise is promoted and the purchase succeeds.
+    advance_clock(&mut svm, 86_401);
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.spend_limit_lamports_per_day, FIRST_KEY_COST_LAMPORTS * 10);
+    assert!(ps.pending_spend_limit_lamports_per_day.is_none());
+}
+
+#[test]
+fn test_spend_limit_window_resets_after_24_hours() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 0, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = set_spend_limit_ix(&player.pubkey(), FIRST_KEY_COST_LAMPORTS);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    // Spent the full cap for this window — a further purchase is rejected.
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(
+        err.contains("SpendLimitExceeded") || err.contains("custom program error"),
+        "Expected SpendLimitExceeded, got: {}",
+        err
+    );
+
+    // Once the rolling window has elapsed, spend tracking starts fresh.
+    advance_clock(&mut svm, 86_401);
+    svm.expire_blockhash();
+    let ix = buy_keys_ix(&player.pubkey(), 1, 1, false, &pw, None);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+}
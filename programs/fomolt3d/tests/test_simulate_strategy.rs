@@ -0,0 +1,68 @@
+// Integration tests: the read-only `simulate_strategy` planner primitive —
+// projects cost/timer/dividend outcomes for a keys_schedule without
+// mutating any account.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_simulate_does_not_mutate_game_state() {
+    let (mut svm, _admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let game_before = get_game(&svm, 1);
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = simulate_strategy_ix(1, &[3, 2, 1], None);
+    send_tx(&mut svm, &[ix], &cranker, &[&cranker]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_before.total_keys, game_after.total_keys);
+    assert_eq!(game_before.total_dividend_pool, game_after.total_dividend_pool);
+    assert_eq!(game_before.timer_end, game_after.timer_end);
+}
+
+#[test]
+fn test_simulate_rejects_empty_schedule() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = simulate_strategy_ix(1, &[], None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &cranker, &[&cranker]);
+    assert!(
+        err.contains("EmptyBatch") || err.contains("custom program error"),
+        "Expected EmptyBatch error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_simulate_rejects_zero_step() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = simulate_strategy_ix(1, &[5, 0, 1], None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &cranker, &[&cranker]);
+    assert!(
+        err.contains("NoKeysToBuy") || err.contains("custom program error"),
+        "Expected NoKeysToBuy error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_simulate_accepts_existing_player_state() {
+    let (mut svm, _admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = simulate_strategy_ix(1, &[10], Some(&p1.pubkey()));
+    send_tx(&mut svm, &[ix], &p1, &[&p1]).unwrap();
+}
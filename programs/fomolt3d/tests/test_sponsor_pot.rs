@@ -0,0 +1,128 @@
+// Integration tests for the permissionless sponsor_pot instruction.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_sponsor_pot_tops_up_winner_pot_without_issuing_keys() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let sponsor = Keypair::new();
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    let game_before = get_game(&svm, 1);
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 5_000_000, SponsorAllocationData::WinnerPot);
+    send_tx(&mut svm, &[ix], &sponsor, &[&sponsor]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.winner_pot, game_before.winner_pot + 5_000_000);
+    assert_eq!(game_after.total_dividend_pool, game_before.total_dividend_pool);
+    assert_eq!(game_after.next_round_pot, game_before.next_round_pot);
+    assert_eq!(game_after.total_keys, 0, "sponsoring should not issue keys");
+}
+
+#[test]
+fn test_sponsor_pot_tops_up_dividend_pool() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let sponsor = Keypair::new();
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    let game_before = get_game(&svm, 1);
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 3_000_000, SponsorAllocationData::DividendPool);
+    send_tx(&mut svm, &[ix], &sponsor, &[&sponsor]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(
+        game_after.total_dividend_pool,
+        game_before.total_dividend_pool + 3_000_000
+    );
+    assert_eq!(game_after.pot_lamports, game_before.pot_lamports + 3_000_000);
+}
+
+#[test]
+fn test_sponsor_pot_tops_up_next_round_pot() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let sponsor = Keypair::new();
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    let game_before = get_game(&svm, 1);
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 2_000_000, SponsorAllocationData::NextRoundPot);
+    send_tx(&mut svm, &[ix], &sponsor, &[&sponsor]).unwrap();
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.next_round_pot, game_before.next_round_pot + 2_000_000);
+}
+
+#[test]
+fn test_sponsored_winner_pot_pays_out_to_the_winner() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let winner = Keypair::new();
+    let sponsor = Keypair::new();
+    register(&mut svm, &winner, 1, false, None);
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    buy(&mut svm, &winner, 1, 1, &pw, None);
+
+    svm.expire_blockhash();
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 7_000_000, SponsorAllocationData::WinnerPot);
+    send_tx(&mut svm, &[ix], &sponsor, &[&sponsor]).unwrap();
+
+    let game = get_game(&svm, 1);
+    set_clock(&mut svm, game.timer_end + 1);
+
+    svm.expire_blockhash();
+    let bal_before = get_balance(&svm, &winner.pubkey());
+    let ix = claim_ix(&winner.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &winner, &[&winner]).unwrap();
+    let bal_after = get_balance(&svm, &winner.pubkey());
+
+    assert!(
+        bal_after >= bal_before + 7_000_000,
+        "winner's payout should include the sponsored top-up"
+    );
+}
+
+#[test]
+fn test_sponsor_pot_rejects_zero_amount() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let sponsor = Keypair::new();
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 0, SponsorAllocationData::WinnerPot);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sponsor, &[&sponsor]);
+    assert!(
+        err.contains("InvalidFundAmount") || err.contains("custom program error"),
+        "Expected InvalidFundAmount, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_sponsor_pot_rejects_inactive_round() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    let sponsor = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    airdrop(&mut svm, &sponsor.pubkey(), 100_000_000_000);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = end_round_ix(&admin.pubkey(), 1, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = sponsor_pot_ix(&sponsor.pubkey(), 1, 1_000_000, SponsorAllocationData::WinnerPot);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sponsor, &[&sponsor]);
+    assert!(
+        err.contains("GameNotActive") || err.contains("custom program error"),
+        "Expected GameNotActive, got: {}",
+        err
+    );
+}
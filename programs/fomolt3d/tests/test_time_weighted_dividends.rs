@@ -0,0 +1,147 @@
+// Integration tests: time-weighted dividend split (anti last-second farming).
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+fn setup_with_flag(enabled: bool) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        time_weighted_dividends_enabled: enabled,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn disabled_by_default_splits_by_raw_weight() {
+    let (mut svm, admin, pw) = setup_with_flag(false);
+
+    let early = Keypair::new();
+    register(&mut svm, &early, 1, false, None);
+    buy(&mut svm, &early, 1, 10, &pw, None);
+    advance_clock(&mut svm, 23_000);
+
+    let late = Keypair::new();
+    register(&mut svm, &late, 1, false, None);
+    buy(&mut svm, &late, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    send_tx(&mut svm, &[claim_ix(&early.pubkey(), 1)], &early, &[&early]).unwrap();
+    send_tx(&mut svm, &[claim_ix(&late.pubkey(), 1)], &late, &[&late]).unwrap();
+
+    let early_player = get_player(&svm, &early.pubkey());
+    let late_player = get_player(&svm, &late.pubkey());
+    // Equal raw key counts earn equal dividends regardless of how long
+    // either of them was actually held.
+    assert_eq!(
+        early_player.claimed_dividends_lamports,
+        late_player.claimed_dividends_lamports
+    );
+
+    let _ = admin;
+}
+
+#[test]
+fn enabled_penalizes_late_entry() {
+    let (mut svm, admin, pw) = setup_with_flag(true);
+
+    let early = Keypair::new();
+    register(&mut svm, &early, 1, false, None);
+    buy(&mut svm, &early, 1, 10, &pw, None);
+    // Early buyer holds their weight for most of the round before the late
+    // buyer shows up in its final seconds.
+    advance_clock(&mut svm, 23_000);
+
+    let late = Keypair::new();
+    register(&mut svm, &late, 1, false, None);
+    buy(&mut svm, &late, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    send_tx(&mut svm, &[claim_ix(&early.pubkey(), 1)], &early, &[&early]).unwrap();
+    send_tx(&mut svm, &[claim_ix(&late.pubkey(), 1)], &late, &[&late]).unwrap();
+
+    let early_player = get_player(&svm, &early.pubkey());
+    let late_player = get_player(&svm, &late.pubkey());
+    assert!(
+        early_player.claimed_dividends_lamports > late_player.claimed_dividends_lamports * 5,
+        "early buyer ({}) should earn far more than the last-second buyer ({})",
+        early_player.claimed_dividends_lamports,
+        late_player.claimed_dividends_lamports,
+    );
+
+    let _ = admin;
+}
+
+#[test]
+fn enabled_conserves_pool_across_multiple_buys() {
+    let (mut svm, _admin, pw) = setup_with_flag(true);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    advance_clock(&mut svm, 1_000);
+
+    let p2 = Keypair::new();
+    register(&mut svm, &p2, 1, false, None);
+    buy(&mut svm, &p2, 1, 7, &pw, None);
+    advance_clock(&mut svm, 2_000);
+
+    buy(&mut svm, &p1, 1, 3, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    let pool = get_game(&svm, 1).total_dividend_pool;
+
+    send_tx(&mut svm, &[claim_ix(&p1.pubkey(), 1)], &p1, &[&p1]).unwrap();
+    send_tx(&mut svm, &[claim_ix(&p2.pubkey(), 1)], &p2, &[&p2]).unwrap();
+
+    let p1_share = get_player(&svm, &p1.pubkey()).claimed_dividends_lamports;
+    let p2_share = get_player(&svm, &p2.pubkey()).claimed_dividends_lamports;
+
+    // Integer-division dust aside, the two shares should account for
+    // essentially the whole pool.
+    assert!(p1_share + p2_share <= pool);
+    assert!(p1_share + p2_share >= pool - 10);
+}
+
+#[test]
+fn claiming_long_after_round_end_does_not_keep_accruing() {
+    let (mut svm, _admin, pw) = setup_with_flag(true);
+
+    let early = Keypair::new();
+    register(&mut svm, &early, 1, false, None);
+    buy(&mut svm, &early, 1, 10, &pw, None);
+    advance_clock(&mut svm, 100);
+
+    let late = Keypair::new();
+    register(&mut svm, &late, 1, false, None);
+    buy(&mut svm, &late, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+
+    // Claim the early buyer right away...
+    send_tx(&mut svm, &[claim_ix(&early.pubkey(), 1)], &early, &[&early]).unwrap();
+    let early_share_immediate = get_player(&svm, &early.pubkey()).claimed_dividends_lamports;
+
+    // ...and let a huge amount of wall-clock time pass before the late
+    // buyer claims. Weight-seconds must be capped at round end, not the
+    // claim timestamp, or this claim would unfairly dilute the early buyer.
+    advance_clock(&mut svm, 10_000_000);
+    send_tx(&mut svm, &[claim_ix(&late.pubkey(), 1)], &late, &[&late]).unwrap();
+    let late_share = get_player(&svm, &late.pubkey()).claimed_dividends_lamports;
+
+    assert!(early_share_immediate > late_share * 5);
+}
@@ -0,0 +1,124 @@
+// Integration tests: `GlobalConfig::max_timer_extensions_per_window` capping
+// how many of a single wallet's buys may extend `GameState::timer_end`
+// within a rolling `timer_extension_window_secs` window.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_default_config_leaves_timer_extensions_unlimited() {
+    let (mut svm, _admin, pw) = setup_game();
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    for _ in 0..5 {
+        let before = get_game(&svm, 1).timer_end;
+        buy(&mut svm, &player, 1, 1, &pw, None);
+        let after = get_game(&svm, 1).timer_end;
+        assert!(after > before, "expected timer to extend with the cap disabled");
+    }
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.timer_extensions_in_window, 5);
+}
+
+#[test]
+fn test_buys_past_cap_still_add_keys_but_stop_extending_timer() {
+    let params = ConfigParamsData {
+        max_timer_extensions_per_window: 2,
+        timer_extension_window_secs: 86_400,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.timer_extensions_in_window, 2);
+
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let after = get_game(&svm, 1);
+    assert_eq!(after.timer_end, before, "timer should not extend past the cap");
+    assert_eq!(after.total_keys, 3, "keys still count even once the cap is hit");
+
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(
+        ps.timer_extensions_in_window, 2,
+        "the capped buy shouldn't increment the counter further"
+    );
+}
+
+#[test]
+fn test_timer_extension_cap_resets_after_window_elapses() {
+    let params = ConfigParamsData {
+        max_timer_extensions_per_window: 1,
+        timer_extension_window_secs: 3_600,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    assert_eq!(get_game(&svm, 1).timer_end, before, "second buy in-window should not extend");
+
+    advance_clock(&mut svm, 3_601);
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &player, 1, 1, &pw, None);
+    assert!(
+        get_game(&svm, 1).timer_end > before,
+        "a buy after the window elapses should extend again"
+    );
+    let ps = get_player(&svm, &player.pubkey());
+    assert_eq!(ps.timer_extensions_in_window, 1);
+}
+
+#[test]
+fn test_timer_extension_cap_is_per_wallet() {
+    let params = ConfigParamsData {
+        max_timer_extensions_per_window: 1,
+        timer_extension_window_secs: 86_400,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    let p2 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    register(&mut svm, &p2, 1, false, None);
+
+    buy(&mut svm, &p1, 1, 1, &pw, None);
+    let before = get_game(&svm, 1).timer_end;
+    buy(&mut svm, &p2, 1, 1, &pw, None);
+    assert!(
+        get_game(&svm, 1).timer_end > before,
+        "a different wallet's first buy should still extend the timer"
+    );
+}
@@ -0,0 +1,292 @@
+// Integration tests for the per-round top-referrer leaderboard bonus
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Mirrors `math::calculate_bps_split` — floor(amount * bps / 10_000).
+fn bps_split(amount: u64, bps: u64) -> u64 {
+    ((amount as u128) * (bps as u128) / 10_000) as u64
+}
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_zero_bps_allocates_nothing() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.top_referrer_bonus_pool, 0, "0 bps should allocate nothing");
+}
+
+#[test]
+fn test_leading_referrer_bonus_carved_from_winner_pot() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000, // 10%
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let winner_pot_before = get_game(&svm, 1).winner_pot;
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    let expected_bonus = bps_split(winner_pot_before, 1_000);
+    assert_eq!(game.top_referrer_bonus_pool, expected_bonus);
+    assert_eq!(game.winner_pot, winner_pot_before - expected_bonus);
+
+    let ext = get_game_ext(&svm, 1);
+    assert!(pubkey_eq(&ext.top_referrers[0].referrer, &referrer.pubkey()));
+}
+
+#[test]
+fn test_no_referral_activity_allocates_nothing() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 10, &pw, None);
+
+    expire_round(&mut svm, 1);
+    // No referred buys this round means GameStateExt was never created —
+    // end_round must still succeed without it presented.
+    let ix = end_round_ix(&admin.pubkey(), 1, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.top_referrer_bonus_pool, 0);
+}
+
+#[test]
+fn test_leading_referrer_can_claim_bonus() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let bonus = get_game(&svm, 1).top_referrer_bonus_pool;
+    assert!(bonus > 0);
+
+    let bal_before = get_balance(&svm, &referrer.pubkey());
+    let ix = claim_top_referrer_bonus_ix(&referrer.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).unwrap();
+    let bal_after = get_balance(&svm, &referrer.pubkey());
+
+    assert_eq!(bal_after - bal_before, bonus);
+    assert_eq!(get_game(&svm, 1).top_referrer_bonus_pool, 0);
+}
+
+#[test]
+fn test_blocked_leader_cannot_claim_bonus() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &referrer.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = claim_top_referrer_bonus_ix(&referrer.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("WalletBlocked") || err.contains("custom program error"),
+        "Expected WalletBlocked error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_blocked_leader_cannot_bypass_block_entry_with_program_id_sentinel() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let block_ix = add_to_blocklist_ix(&admin.pubkey(), &referrer.pubkey(), false);
+    send_tx(&mut svm, &[block_ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix =
+        claim_top_referrer_bonus_ix_with_raw_block_entry(&referrer.pubkey(), 1, PROGRAM_ID);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("ConstraintSeeds") || err.contains("custom program error"),
+        "Expected ConstraintSeeds error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_non_leader_cannot_claim_bonus() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let other = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &other, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    airdrop(&mut svm, &other.pubkey(), 100_000_000_000);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = claim_top_referrer_bonus_ix(&other.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &other, &[&other]);
+    assert!(
+        err.contains("NotTopReferrer") || err.contains("custom program error"),
+        "Expected NotTopReferrer, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_double_claim_fails() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    expire_round(&mut svm, 1);
+    let ix = end_round_ix_with_keeper_and_ext(&admin.pubkey(), 1, None, true, &player.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = claim_top_referrer_bonus_ix(&referrer.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &referrer, &[&referrer]).unwrap();
+
+    svm.expire_blockhash();
+    let ix = claim_top_referrer_bonus_ix(&referrer.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("NoTopReferrerBonus") || err.contains("custom program error"),
+        "Expected NoTopReferrerBonus, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_claim_before_round_ends_fails() {
+    let params = ConfigParamsData {
+        top_referrer_bonus_bps: 1_000,
+        ..Default::default()
+    };
+    let (mut svm, _admin, pw) = setup_game_with_config(params);
+
+    let referrer = Keypair::new();
+    let player = Keypair::new();
+    register(&mut svm, &referrer, 1, false, None);
+    register(&mut svm, &player, 1, false, Some(&referrer.pubkey()));
+
+    buy(&mut svm, &referrer, 1, 10, &pw, None);
+    buy(&mut svm, &player, 1, 5, &pw, Some(&referrer.pubkey()));
+
+    let ix = claim_top_referrer_bonus_ix(&referrer.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &referrer, &[&referrer]);
+    assert!(
+        err.contains("GameStillActive") || err.contains("custom program error"),
+        "Expected GameStillActive, got: {}",
+        err
+    );
+}
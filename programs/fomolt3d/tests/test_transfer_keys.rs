@@ -0,0 +1,128 @@
+// Integration tests: secondary OTC key transfers between players.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn transfer_moves_keys_and_proportional_weight() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 10, &pw, None);
+
+    let recipient = Keypair::new();
+
+    let sender_before = get_player(&svm, &sender.pubkey());
+    assert_eq!(sender_before.keys, 10);
+    assert_eq!(sender_before.dividend_weight, 10 * 10_000);
+
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &recipient.pubkey(), 4);
+    send_tx(&mut svm, &[ix], &sender, &[&sender]).unwrap();
+
+    let sender_after = get_player(&svm, &sender.pubkey());
+    assert_eq!(sender_after.keys, 6);
+    assert_eq!(sender_after.dividend_weight, 6 * 10_000);
+
+    let recipient_after = get_player(&svm, &recipient.pubkey());
+    assert_eq!(recipient_after.keys, 4);
+    assert_eq!(recipient_after.dividend_weight, 4 * 10_000);
+
+    // Total weight is exactly conserved across the transfer.
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_weight, 10 * 10_000);
+    assert_eq!(game.total_keys, 10);
+}
+
+#[test]
+fn transfer_rejects_when_disabled() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        transfers_enabled: false,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 5, &protocol_wallet, None);
+
+    let recipient = Keypair::new();
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &recipient.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sender, &[&sender]);
+    assert!(err.contains("TransfersDisabled") || err.contains("Error"));
+}
+
+#[test]
+fn transfer_rejects_zero_amount() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 5, &pw, None);
+
+    let recipient = Keypair::new();
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &recipient.pubkey(), 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sender, &[&sender]);
+    assert!(err.contains("NoKeysToTransfer") || err.contains("Error"));
+}
+
+#[test]
+fn transfer_rejects_insufficient_keys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 5, &pw, None);
+
+    let recipient = Keypair::new();
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &recipient.pubkey(), 6);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sender, &[&sender]);
+    assert!(err.contains("InsufficientKeys") || err.contains("Error"));
+}
+
+#[test]
+fn transfer_rejects_self_transfer() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 5, &pw, None);
+
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &sender.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &sender, &[&sender]);
+    assert!(err.contains("CannotTransferToSelf") || err.contains("Error"));
+}
+
+#[test]
+fn transfer_to_existing_player_accumulates_onto_their_position() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let sender = Keypair::new();
+    register(&mut svm, &sender, 1, false, None);
+    buy(&mut svm, &sender, 1, 10, &pw, None);
+
+    let recipient = Keypair::new();
+    register(&mut svm, &recipient, 1, false, None);
+    buy(&mut svm, &recipient, 1, 3, &pw, None);
+
+    let ix = transfer_keys_ix(&sender.pubkey(), 1, &recipient.pubkey(), 5);
+    send_tx(&mut svm, &[ix], &sender, &[&sender]).unwrap();
+
+    let recipient_after = get_player(&svm, &recipient.pubkey());
+    assert_eq!(recipient_after.keys, 8);
+    assert_eq!(recipient_after.dividend_weight, 8 * 10_000);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.total_keys, 13);
+    assert_eq!(game.total_weight, 13 * 10_000);
+}
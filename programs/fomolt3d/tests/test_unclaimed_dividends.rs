@@ -0,0 +1,207 @@
+// Integration tests: `GameState::unclaimed_dividend_policy` and the
+// permissionless post-deadline crank `sweep_unclaimed_dividends` that acts on
+// whatever's left of a round's `total_dividend_pool` once
+// `dividend_claim_window_secs` has elapsed past `timer_end`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Spin up a fresh game whose config is `params` (with `protocol_wallet`
+/// filled in), rather than `setup_game`'s all-defaults config. Returns
+/// (svm, admin, protocol_wallet) like `setup_game` does.
+fn setup_game_with_config(mut params: ConfigParamsData) -> (litesvm::LiteSVM, Keypair, Pubkey) {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let protocol_wallet = Pubkey::new_unique();
+    params.protocol_wallet = protocol_wallet;
+
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    (svm, admin, protocol_wallet)
+}
+
+#[test]
+fn test_sweep_rejected_before_claim_window_expiry() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = sweep_unclaimed_dividends_ix(&admin.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("ClaimWindowNotExpired") || err.contains("custom program error"),
+        "Expected ClaimWindowNotExpired error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_sweep_rejected_when_round_still_active() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+
+    let ix = sweep_unclaimed_dividends_ix(&admin.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("GameStillActive") || err.contains("custom program error"),
+        "Expected GameStillActive error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_sweep_rejected_when_nothing_unclaimed() {
+    let (mut svm, admin, pw) = setup_game();
+    let game = expire_round(&mut svm, 1);
+    advance_clock(&mut svm, game.dividend_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    // No buys happened at all, so total_dividend_pool is zero.
+    let ix = sweep_unclaimed_dividends_ix(&admin.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("NothingToSweep") || err.contains("custom program error"),
+        "Expected NothingToSweep error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_strand_policy_leaves_vault_untouched_but_marks_swept() {
+    let (mut svm, _admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    let game = expire_round(&mut svm, 1);
+    advance_clock(&mut svm, game.dividend_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    assert_eq!(game.unclaimed_dividend_policy, UnclaimedDividendPolicyData::Strand);
+    let vault_before = get_vault_balance(&svm, 1);
+
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = sweep_unclaimed_dividends_ix(&cranker.pubkey(), 1, &pw);
+    send_tx(&mut svm, &[ix], &cranker, &[&cranker]).unwrap();
+
+    let vault_after = get_vault_balance(&svm, 1);
+    assert_eq!(vault_before, vault_after, "Strand must not move any lamports");
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.total_dividend_claimed_lamports, game_after.total_dividend_pool);
+
+    // A second sweep has nothing left to report.
+    let ix = sweep_unclaimed_dividends_ix(&cranker.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &cranker, &[&cranker]);
+    assert!(
+        err.contains("NothingToSweep") || err.contains("custom program error"),
+        "Expected NothingToSweep error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_to_protocol_policy_pays_protocol_wallet() {
+    let params = ConfigParamsData {
+        unclaimed_dividend_policy: UnclaimedDividendPolicyData::ToProtocol,
+        dividend_claim_window_secs: 3600,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    let game = expire_round(&mut svm, 1);
+    advance_clock(&mut svm, game.dividend_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    let unclaimed = game.total_dividend_pool - game.total_dividend_claimed_lamports;
+    assert!(unclaimed > 0);
+    let pw_balance_before = get_balance(&svm, &pw);
+
+    let ix = sweep_unclaimed_dividends_ix(&admin.pubkey(), 1, &pw);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let pw_balance_after = get_balance(&svm, &pw);
+    assert_eq!(pw_balance_after - pw_balance_before, unclaimed);
+
+    let game_after = get_game(&svm, 1);
+    assert_eq!(game_after.total_dividend_claimed_lamports, game_after.total_dividend_pool);
+}
+
+#[test]
+fn test_roll_to_next_round_policy_requires_destination_accounts() {
+    let params = ConfigParamsData {
+        unclaimed_dividend_policy: UnclaimedDividendPolicyData::RollToNextRound,
+        dividend_claim_window_secs: 3600,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    let game = expire_round(&mut svm, 1);
+    advance_clock(&mut svm, game.dividend_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    // No next_round supplied — must fail.
+    let ix = sweep_unclaimed_dividends_ix(&admin.pubkey(), 1, &pw);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("MissingRolloverTarget") || err.contains("custom program error"),
+        "Expected MissingRolloverTarget error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_roll_to_next_round_policy_moves_funds_into_next_round_pot() {
+    let params = ConfigParamsData {
+        unclaimed_dividend_policy: UnclaimedDividendPolicyData::RollToNextRound,
+        dividend_claim_window_secs: 3600,
+        ..Default::default()
+    };
+    let (mut svm, admin, pw) = setup_game_with_config(params);
+
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    let game = expire_round(&mut svm, 1);
+
+    airdrop(&mut svm, &admin.pubkey(), 10_000_000_000);
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    advance_clock(&mut svm, game.dividend_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    let unclaimed = game.total_dividend_pool - game.total_dividend_claimed_lamports;
+    assert!(unclaimed > 0);
+    let next_before = get_game(&svm, 2);
+
+    let ix = sweep_unclaimed_dividends_ix_with_next_round(&admin.pubkey(), 1, &pw, Some(2));
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let next_after = get_game(&svm, 2);
+    assert_eq!(
+        next_after.next_round_pot,
+        next_before.next_round_pot + unclaimed
+    );
+
+    let old_after = get_game(&svm, 1);
+    assert_eq!(old_after.total_dividend_claimed_lamports, old_after.total_dividend_pool);
+}
@@ -0,0 +1,234 @@
+// Integration tests: `deploy_vault_yield` / `unwind_vault_yield` — CPI'ing a
+// bounded slice of a round's idle vault balance out to (and back from) the
+// admin-approved `GlobalConfig::yield_program`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+#[test]
+fn deploy_rejected_when_kill_switch_disabled() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let yield_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        yield_program,
+        max_yield_deployment_bps: 0,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let yield_vault = Pubkey::new_unique();
+    let ix = deploy_vault_yield_ix(&admin.pubkey(), 1, 1_000_000, &yield_program, &yield_vault, &[]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("YieldDeploymentDisabled") || err.contains("custom program error"),
+        "Expected YieldDeploymentDisabled, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_unapproved_yield_program() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    let unapproved_yield_program = Pubkey::new_unique();
+    let yield_vault = Pubkey::new_unique();
+    let ix = deploy_vault_yield_ix(
+        &admin.pubkey(),
+        1,
+        1_000_000,
+        &unapproved_yield_program,
+        &yield_vault,
+        &[],
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("YieldProgramNotApproved") || err.contains("custom program error"),
+        "Expected YieldProgramNotApproved, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_when_amount_exceeds_bps_cap() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let yield_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        yield_program,
+        max_yield_deployment_bps: 1, // 0.01% of the vault
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 100, &protocol_wallet, None);
+
+    let vault_balance = get_vault_balance(&svm, 1);
+    let yield_vault = Pubkey::new_unique();
+    // Well above 1 bp of the vault's balance.
+    let ix = deploy_vault_yield_ix(
+        &admin.pubkey(),
+        1,
+        vault_balance,
+        &yield_program,
+        &yield_vault,
+        &[],
+    );
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("YieldDeploymentCapExceeded") || err.contains("custom program error"),
+        "Expected YieldDeploymentCapExceeded, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_when_it_would_breach_pending_obligations() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let yield_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        yield_program,
+        max_yield_deployment_bps: 10_000, // no cap beyond solvency itself
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 100, &protocol_wallet, None);
+
+    // A fresh round's vault balance always exactly equals its pending
+    // obligations (see `GameState::pending_obligations`) — there's no idle
+    // margin yet, so even a modest deployment must be rejected.
+    let yield_vault = Pubkey::new_unique();
+    let ix = deploy_vault_yield_ix(&admin.pubkey(), 1, 1_000_000, &yield_program, &yield_vault, &[]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("VaultInsolvent") || err.contains("custom program error"),
+        "Expected VaultInsolvent, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_zero_lamports() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let yield_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        yield_program,
+        max_yield_deployment_bps: 10_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let yield_vault = Pubkey::new_unique();
+    let ix = deploy_vault_yield_ix(&admin.pubkey(), 1, 0, &yield_program, &yield_vault, &[]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("InvalidFundAmount") || err.contains("custom program error"),
+        "Expected InvalidFundAmount, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_too_many_yield_accounts() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = Pubkey::new_unique();
+    let yield_program = Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        yield_program,
+        max_yield_deployment_bps: 10_000,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let yield_vault = Pubkey::new_unique();
+    let extra: Vec<AccountMeta> = (0..5)
+        .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+        .collect();
+    let ix = deploy_vault_yield_ix(&admin.pubkey(), 1, 1_000_000, &yield_program, &yield_vault, &extra);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("TooManyYieldAccounts") || err.contains("custom program error"),
+        "Expected TooManyYieldAccounts, got: {}",
+        err
+    );
+}
+
+#[test]
+fn unwind_rejected_before_any_deploy_this_round() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    // `game_state_ext` has never been created this round — only
+    // `deploy_vault_yield` creates it — so there's nothing to unwind.
+    let yield_program = Pubkey::new_unique();
+    let yield_vault = Pubkey::new_unique();
+    let ix = unwind_vault_yield_ix(&admin.pubkey(), 1, 1_000_000, &yield_program, &yield_vault, &[]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("AccountNotInitialized") || err.contains("custom program error"),
+        "Expected AccountNotInitialized, got: {}",
+        err
+    );
+}
+
+#[test]
+fn deploy_rejected_for_non_admin() {
+    let (mut svm, _admin, _pw) = setup_game();
+
+    let impostor = Keypair::new();
+    airdrop(&mut svm, &impostor.pubkey(), 100_000_000_000);
+
+    let yield_program = Pubkey::new_unique();
+    let yield_vault = Pubkey::new_unique();
+    let ix = deploy_vault_yield_ix(&impostor.pubkey(), 1, 1_000_000, &yield_program, &yield_vault, &[]);
+    let err = send_tx_expect_err(&mut svm, &[ix], &impostor, &[&impostor]);
+    assert!(
+        err.contains("Unauthorized") || err.contains("custom program error"),
+        "Expected Unauthorized, got: {}",
+        err
+    );
+}
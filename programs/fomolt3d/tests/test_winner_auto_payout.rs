@@ -0,0 +1,150 @@
+// Integration tests: end_round pushing winner_pot straight to last_buyer
+// when GlobalConfig::auto_payout_winner_enabled is set, instead of leaving
+// it stranded until the winner calls `claim`.
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Ends round 1 as-is (auto-payout off, per `setup_game`'s defaults), turns
+/// `auto_payout_winner_enabled` on, then starts round 2 so the new round
+/// snapshots the enabled setting. Returns the winner of round 1.
+fn setup_round_with_auto_payout_enabled() -> (litesvm::LiteSVM, Keypair, Pubkey, Keypair) {
+    let (mut svm, admin, pw) = setup_game();
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 1, false, None);
+    buy(&mut svm, &winner, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    // Winner claims round 1 the old way so round 2 starts from a clean slate.
+    let ix = claim_ix(&winner.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &winner, &[&winner]).unwrap();
+    svm.expire_blockhash();
+
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        auto_payout_winner_enabled: true,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    assert!(get_config(&svm).auto_payout_winner_enabled);
+    (svm, admin, pw, winner)
+}
+
+#[test]
+fn test_auto_payout_pays_winner_directly_on_end_round() {
+    let (mut svm, admin, pw, _round1_winner) = setup_round_with_auto_payout_enabled();
+
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 2, false, None);
+    buy(&mut svm, &winner, 2, 5, &pw, None);
+    expire_round(&mut svm, 2);
+
+    let game_before = get_game(&svm, 2);
+    assert!(game_before.winner_pot > 0);
+    let winner_pot = game_before.winner_pot;
+    let balance_before = get_balance(&svm, &winner.pubkey());
+
+    let ix = end_round_ix_with_auto_payout(&admin.pubkey(), 2, &winner.pubkey(), &winner.pubkey(), None);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let game_after = get_game(&svm, 2);
+    assert_eq!(game_after.winner_pot, 0);
+    assert!(game_after.winner_claimed);
+    assert_eq!(get_balance(&svm, &winner.pubkey()), balance_before + winner_pot);
+
+    // Already settled — a manual claim afterwards must be rejected.
+    let ix = claim_ix(&winner.pubkey(), 2);
+    let err = send_tx_expect_err(&mut svm, &[ix], &winner, &[&winner]);
+    assert!(
+        err.contains("WinnerAlreadyClaimed") || err.contains("custom program error"),
+        "Expected WinnerAlreadyClaimed error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_auto_payout_skipped_without_winner_account_leaves_claimable() {
+    let (mut svm, admin, pw, _round1_winner) = setup_round_with_auto_payout_enabled();
+
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 2, false, None);
+    buy(&mut svm, &winner, 2, 5, &pw, None);
+    expire_round(&mut svm, 2);
+
+    let game_before = get_game(&svm, 2);
+    let winner_pot = game_before.winner_pot;
+
+    // No winner_account presented — end_round must still succeed, and the
+    // round simply stays claimable.
+    let ix = end_round_ix_with_keeper(&admin.pubkey(), 2, None, &winner.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let game_after = get_game(&svm, 2);
+    assert_eq!(game_after.winner_pot, winner_pot);
+    assert!(!game_after.winner_claimed);
+
+    let ix = claim_ix(&winner.pubkey(), 2);
+    send_tx(&mut svm, &[ix], &winner, &[&winner]).unwrap();
+    assert_eq!(get_game(&svm, 2).winner_pot, 0);
+}
+
+#[test]
+fn test_auto_payout_rejects_mismatched_winner_account() {
+    let (mut svm, admin, pw, _round1_winner) = setup_round_with_auto_payout_enabled();
+
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 2, false, None);
+    buy(&mut svm, &winner, 2, 5, &pw, None);
+    expire_round(&mut svm, 2);
+
+    let impostor = Keypair::new();
+    let ix = end_round_ix_with_auto_payout(&admin.pubkey(), 2, &winner.pubkey(), &impostor.pubkey(), None);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("WinnerAccountMismatch") || err.contains("custom program error"),
+        "Expected WinnerAccountMismatch error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_auto_payout_skipped_when_winner_blocked_leaves_claimable() {
+    let (mut svm, admin, pw, _round1_winner) = setup_round_with_auto_payout_enabled();
+
+    let winner = Keypair::new();
+    register(&mut svm, &winner, 2, false, None);
+    buy(&mut svm, &winner, 2, 5, &pw, None);
+
+    let ix = add_to_blocklist_ix(&admin.pubkey(), &winner.pubkey(), true);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    expire_round(&mut svm, 2);
+
+    let game_before = get_game(&svm, 2);
+    let winner_pot = game_before.winner_pot;
+    let (block_entry, _) = blocked_entry_pda(&winner.pubkey());
+
+    let ix =
+        end_round_ix_with_auto_payout(&admin.pubkey(), 2, &winner.pubkey(), &winner.pubkey(), Some(block_entry));
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let game_after = get_game(&svm, 2);
+    assert_eq!(game_after.winner_pot, winner_pot);
+    assert!(!game_after.winner_claimed);
+
+    // allow_claim was true, so the blocked winner can still claim manually.
+    let ix = claim_ix_with_block_entry(&winner.pubkey(), 2, true);
+    send_tx(&mut svm, &[ix], &winner, &[&winner]).unwrap();
+    assert_eq!(get_game(&svm, 2).winner_pot, 0);
+}
@@ -0,0 +1,168 @@
+// Integration tests: forfeiting an unclaimed winner_pot to the current round
+// once the winner_claim_window_secs grace period has elapsed
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn test_forfeit_after_window_expiry_rolls_pot_into_current_round() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    // Start round 2 without the winner ever claiming round 1.
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let old_game_before = get_game(&svm, 1);
+    assert!(old_game_before.winner_pot > 0);
+    assert!(!old_game_before.winner_claimed);
+
+    // Window hasn't expired yet — forfeiture must be rejected.
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("ClaimWindowNotExpired") || err.contains("custom program error"),
+        "Expected ClaimWindowNotExpired error, got: {}",
+        err
+    );
+
+    // Advance past timer_end + winner_claim_window_secs.
+    advance_clock(&mut svm, old_game_before.winner_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    let current_before = get_game(&svm, 2);
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let old_game_after = get_game(&svm, 1);
+    let current_after = get_game(&svm, 2);
+    assert!(old_game_after.winner_claimed);
+    assert_eq!(old_game_after.winner_pot, 0);
+    assert_eq!(
+        current_after.winner_pot,
+        current_before.winner_pot + old_game_before.winner_pot
+    );
+}
+
+#[test]
+fn test_forfeit_rejected_before_window_expiry() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("ClaimWindowNotExpired") || err.contains("custom program error"),
+        "Expected ClaimWindowNotExpired error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_forfeit_rejected_when_old_round_still_active() {
+    let (mut svm, admin, _pw) = setup_game();
+
+    // Round 1 is still active — forfeiting against itself must fail.
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("GameStillActive") || err.contains("custom program error"),
+        "Expected GameStillActive error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_forfeit_rejected_when_current_round_not_active() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    // End round 2 as well, without starting round 3.
+    expire_round(&mut svm, 2);
+
+    let old_game = get_game(&svm, 1);
+    advance_clock(&mut svm, old_game.winner_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("GameNotActive") || err.contains("custom program error"),
+        "Expected GameNotActive error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_double_forfeiture_rejected() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let old_game = get_game(&svm, 1);
+    advance_clock(&mut svm, old_game.winner_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let ix = forfeit_winner_pot_ix(&admin.pubkey(), 1, 2);
+    let err = send_tx_expect_err(&mut svm, &[ix], &admin, &[&admin]);
+    assert!(
+        err.contains("WinnerAlreadyClaimed") || err.contains("custom program error"),
+        "Expected WinnerAlreadyClaimed error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_anyone_can_crank_forfeiture() {
+    let (mut svm, admin, pw) = setup_game();
+    let p1 = Keypair::new();
+    register(&mut svm, &p1, 1, false, None);
+    buy(&mut svm, &p1, 1, 5, &pw, None);
+    expire_round(&mut svm, 1);
+
+    let ix = start_new_round_ix(&admin.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    svm.expire_blockhash();
+
+    let old_game = get_game(&svm, 1);
+    advance_clock(&mut svm, old_game.winner_claim_window_secs + 1);
+    svm.expire_blockhash();
+
+    // A third party with no stake in either round can crank the forfeiture.
+    let cranker = Keypair::new();
+    airdrop(&mut svm, &cranker.pubkey(), 10_000_000_000);
+    let ix = forfeit_winner_pot_ix(&cranker.pubkey(), 1, 2);
+    send_tx(&mut svm, &[ix], &cranker, &[&cranker]).unwrap();
+
+    let old_game_after = get_game(&svm, 1);
+    assert!(old_game_after.winner_claimed);
+}
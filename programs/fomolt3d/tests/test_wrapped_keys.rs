@@ -0,0 +1,232 @@
+// Integration tests: per-round wrapped-key SPL mint (init_key_mint / wrap_keys / unwrap_keys).
+mod helpers;
+
+use helpers::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[test]
+fn wrap_moves_keys_into_pool_and_mints_tokens() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 10, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 4);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let player_after = get_player(&svm, &player.pubkey());
+    assert_eq!(player_after.keys, 6);
+    assert_eq!(player_after.dividend_weight, 6 * 10_000);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.wrapped_keys_total, 4);
+    assert_eq!(game.wrapped_weight_total, 4 * 10_000);
+    // Total weight is conserved — wrapping never touches it.
+    assert_eq!(game.total_weight, 10 * 10_000);
+    assert_eq!(game.total_keys, 10);
+
+    let (game_key, _) = game_pda(1);
+    let (key_mint, _) = key_mint_pda(&game_key);
+    let (token_account, _) = associated_token_pda(&player.pubkey(), &key_mint);
+    assert_eq!(get_token_balance(&svm, &token_account), 4);
+}
+
+#[test]
+fn unwrap_burns_tokens_and_restores_position() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 10, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 4);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = unwrap_keys_ix(&player.pubkey(), 1, 4);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let player_after = get_player(&svm, &player.pubkey());
+    assert_eq!(player_after.keys, 10);
+    assert_eq!(player_after.dividend_weight, 10 * 10_000);
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.wrapped_keys_total, 0);
+    assert_eq!(game.wrapped_weight_total, 0);
+    assert_eq!(game.total_weight, 10 * 10_000);
+
+    let (game_key, _) = game_pda(1);
+    let (key_mint, _) = key_mint_pda(&game_key);
+    let (token_account, _) = associated_token_pda(&player.pubkey(), &key_mint);
+    assert_eq!(get_token_balance(&svm, &token_account), 0);
+}
+
+#[test]
+fn unwrap_sweeps_rounding_dust_when_pool_fully_drained() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let a = Keypair::new();
+    register(&mut svm, &a, 1, false, None);
+    buy(&mut svm, &a, 1, 3, &pw, None);
+
+    let b = Keypair::new();
+    register(&mut svm, &b, 1, false, None);
+    buy(&mut svm, &b, 1, 7, &pw, None);
+
+    let ix = init_key_mint_ix(&a.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &a, &[&a]).unwrap();
+
+    // Wrap from both players so the pool's weight/keys ratio isn't 1:1,
+    // creating floor-rounding remainders on partial unwraps.
+    let ix = wrap_keys_ix(&a.pubkey(), 1, 3);
+    send_tx(&mut svm, &[ix], &a, &[&a]).unwrap();
+    let ix = wrap_keys_ix(&b.pubkey(), 1, 7);
+    send_tx(&mut svm, &[ix], &b, &[&b]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.wrapped_keys_total, 10);
+    assert_eq!(game.wrapped_weight_total, 10 * 10_000);
+
+    // Unwrap everything in one shot — the pool should drain to exactly zero
+    // on both sides, with any dust swept into this final unwrap.
+    let ix = unwrap_keys_ix(&a.pubkey(), 1, 10);
+    send_tx(&mut svm, &[ix], &a, &[&a]).unwrap();
+
+    let game = get_game(&svm, 1);
+    assert_eq!(game.wrapped_keys_total, 0);
+    assert_eq!(game.wrapped_weight_total, 0);
+
+    let player_after = get_player(&svm, &a.pubkey());
+    assert_eq!(player_after.keys, 10);
+    assert_eq!(player_after.dividend_weight, 10 * 10_000);
+}
+
+#[test]
+fn wrap_rejects_when_disabled() {
+    let mut svm = setup_svm();
+    let admin = Keypair::new();
+    let protocol_wallet = solana_sdk::pubkey::Pubkey::new_unique();
+    airdrop(&mut svm, &admin.pubkey(), 100_000_000_000);
+
+    let params = ConfigParamsData {
+        protocol_wallet,
+        wrapped_keys_enabled: false,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+    let ix = initialize_first_round_ix(&admin.pubkey());
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &protocol_wallet, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("WrappedKeysDisabled") || err.contains("Error"));
+}
+
+#[test]
+fn wrap_rejects_zero_amount() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("NoKeysToWrap") || err.contains("Error"));
+}
+
+#[test]
+fn wrap_rejects_insufficient_keys() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 6);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("InsufficientKeysToWrap") || err.contains("Error"));
+}
+
+#[test]
+fn unwrap_rejects_zero_amount() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 3);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = unwrap_keys_ix(&player.pubkey(), 1, 0);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("NoKeysToUnwrap") || err.contains("Error"));
+}
+
+#[test]
+fn unwrap_rejects_amount_exceeding_pool() {
+    let (mut svm, _admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 3);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let ix = unwrap_keys_ix(&player.pubkey(), 1, 4);
+    let err = send_tx_expect_err(&mut svm, &[ix], &player, &[&player]);
+    assert!(err.contains("InsufficientWrappedSupply") || err.contains("Error"));
+}
+
+#[test]
+fn unwrap_works_even_after_wraps_are_disabled() {
+    let (mut svm, admin, pw) = setup_game();
+
+    let player = Keypair::new();
+    register(&mut svm, &player, 1, false, None);
+    buy(&mut svm, &player, 1, 5, &pw, None);
+
+    let ix = init_key_mint_ix(&player.pubkey(), 1);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+    let ix = wrap_keys_ix(&player.pubkey(), 1, 3);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    // Admin disables new wraps for future rounds — already-wrapped supply in
+    // this round must still be unwrappable, since GameState snapshots the
+    // flag at round start and unwrap never checks it anyway.
+    let params = ConfigParamsData {
+        protocol_wallet: pw,
+        wrapped_keys_enabled: false,
+        ..Default::default()
+    };
+    let ix = create_or_update_config_ix(&admin.pubkey(), &params);
+    send_tx(&mut svm, &[ix], &admin, &[&admin]).unwrap();
+
+    let ix = unwrap_keys_ix(&player.pubkey(), 1, 3);
+    send_tx(&mut svm, &[ix], &player, &[&player]).unwrap();
+
+    let player_after = get_player(&svm, &player.pubkey());
+    assert_eq!(player_after.keys, 5);
+}
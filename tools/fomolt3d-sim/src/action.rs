@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// One state transition a simulated player (or the crank) can take.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Action {
+    /// Registers a new player with zero keys. A no-op if already registered
+    /// — see `GameSim::apply`.
+    Register { player: String },
+    /// Buys `keys` keys for `player`, who must already be registered.
+    Buy {
+        player: String,
+        keys: u64,
+        timestamp: i64,
+    },
+    /// Claims `player`'s dividend share and, if they're the last buyer after
+    /// the timer has lapsed, the winner pot.
+    Claim { player: String, timestamp: i64 },
+    /// Ends the current round and starts the next one, carrying
+    /// `next_round_pot` forward into the new round's pot.
+    StartRound { timestamp: i64 },
+}
+
+/// What actually changed as a result of one [`Action`] — named and shaped
+/// after the on-chain events in `programs/fomolt3d/src/events.rs` that the
+/// corresponding instruction would emit, since that's the closest existing
+/// vocabulary for describing a FOMolt3D state transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StateDelta {
+    PlayerRegistered {
+        player: String,
+    },
+    KeysPurchased {
+        player: String,
+        keys_bought: u64,
+        total_player_keys: u64,
+        lamports_spent: u64,
+        pot_contribution: u64,
+        timer_end: i64,
+    },
+    Claimed {
+        player: String,
+        dividend_lamports: u64,
+        winner_lamports: u64,
+        total_lamports: u64,
+    },
+    RoundStarted {
+        round: u64,
+        carry_over_lamports: u64,
+        timer_end: i64,
+    },
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Mirrors the subset of `fomolt3d::errors::FomoltError` that the pure
+/// economic transitions in this crate can actually hit — account/signer/PDA
+/// variants don't apply here since this crate never models accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimError {
+    /// Checked arithmetic overflowed.
+    Overflow,
+    /// `keys_to_buy == 0` in a `Buy` action.
+    NoKeysToBuy,
+    /// A `Buy`/`Claim` action named a player that was never `Register`ed.
+    UnknownPlayer,
+    /// `Register` action for a player that already has state this round.
+    PlayerAlreadyRegistered,
+    /// `Buy`/`Claim` action against a round that isn't the active one.
+    RoundNotActive,
+    /// `Claim` action with nothing to pay out (no dividends, not the winner).
+    NothingToClaim,
+    /// `Claim` action from a player who already claimed this round.
+    AlreadyClaimed,
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            SimError::Overflow => "arithmetic overflow",
+            SimError::NoKeysToBuy => "must buy at least one key",
+            SimError::UnknownPlayer => "player has not registered this round",
+            SimError::PlayerAlreadyRegistered => "player already registered this round",
+            SimError::RoundNotActive => "round is not active",
+            SimError::NothingToClaim => "nothing to claim",
+            SimError::AlreadyClaimed => "player already claimed this round",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for SimError {}
+
+pub type SimResult<T> = Result<T, SimError>;
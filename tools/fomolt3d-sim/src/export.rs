@@ -0,0 +1,41 @@
+//! Snapshot and export helpers — how a simulation run's state leaves this
+//! crate for consumption by strategy researchers (JSON) or the frontend's
+//! charting pipeline (CSV), per the same pattern as Recharts-facing data in
+//! the dashboard (see `plans/WS2-human-dapp.md`).
+
+use std::io::{self, Write};
+
+use serde_json;
+
+use crate::state::GameState;
+
+/// Serializes a [`GameState`] snapshot to pretty JSON — round-trips with
+/// [`load_snapshot`].
+pub fn save_snapshot(state: &GameState) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(state)
+}
+
+/// Deserializes a snapshot produced by [`save_snapshot`].
+pub fn load_snapshot(json: &str) -> serde_json::Result<GameState> {
+    serde_json::from_str(json)
+}
+
+/// Writes one CSV row per player — `player,keys,claimed` — to `writer`.
+/// Column order matches `GameState::players`' iteration order (sorted by
+/// player name, since it's a `BTreeMap`).
+pub fn write_players_csv<W: Write>(state: &GameState, writer: W) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    wtr.write_record(["player", "keys", "claimed"])?;
+    for (name, player) in &state.players {
+        wtr.write_record([name.as_str(), &player.keys.to_string(), &player.claimed.to_string()])?;
+    }
+    wtr.flush().map_err(csv::Error::from)?;
+    Ok(())
+}
+
+/// Convenience wrapper that renders [`write_players_csv`] to a `String`.
+pub fn players_csv_string(state: &GameState) -> io::Result<String> {
+    let mut buf = Vec::new();
+    write_players_csv(state, &mut buf).map_err(io::Error::other)?;
+    String::from_utf8(buf).map_err(io::Error::other)
+}
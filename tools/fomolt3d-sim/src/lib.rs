@@ -0,0 +1,28 @@
+//! Deterministic, offline simulator for FOMolt3D's bonding-curve economy.
+//!
+//! This is a standalone port of the core state transitions exercised by
+//! `programs/fomolt3d/src/test_scenarios.rs`'s internal `GameSim` — same
+//! bonding-curve/fee/dividend math, repackaged behind a small `apply`
+//! surface with `serde` snapshots and CSV/JSON export, so strategy
+//! researchers and the frontend dashboard can run the economy outside
+//! `#[cfg(test)]` without paying for a LiteSVM harness or touching a live
+//! program build.
+//!
+//! It does not model accounts, PDAs, lamport transfers, referral bonuses,
+//! KYC/blocklist gating, or partner-hook CPIs — the same scope boundary the
+//! on-chain `simulate_strategy` dry-run instruction uses, for the same
+//! reason: those all depend on state a pure economic model has no reason to
+//! carry around. Treat this crate's numbers as "what the curve and the pot
+//! splits alone would produce," not a full transaction replay.
+
+pub mod action;
+pub mod error;
+pub mod export;
+pub mod math;
+pub mod sim;
+pub mod state;
+
+pub use action::{Action, StateDelta};
+pub use error::{SimError, SimResult};
+pub use sim::GameSim;
+pub use state::{GameConfig, GameState, PlayerState};
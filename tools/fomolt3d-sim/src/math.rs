@@ -0,0 +1,114 @@
+//! Pure bonding-curve/split arithmetic, ported from
+//! `programs/fomolt3d/src/math/mod.rs` — same formulas, same checked-math
+//! discipline, just returning [`SimError`] instead of an Anchor `Result`
+//! since this crate has no on-chain error codes to report.
+
+use crate::error::{SimError, SimResult};
+
+/// cost = n * base_price + price_increment * n * (2k + n - 1) / 2
+pub fn calculate_cost(
+    current_supply: u64,
+    keys_to_buy: u64,
+    base_price: u64,
+    price_increment: u64,
+) -> SimResult<u64> {
+    let n = keys_to_buy as u128;
+    let k = current_supply as u128;
+    let base = base_price as u128;
+    let inc = price_increment as u128;
+
+    let base_cost = n.checked_mul(base).ok_or(SimError::Overflow)?;
+
+    let series_numerator = n
+        .checked_mul(
+            k.checked_mul(2)
+                .ok_or(SimError::Overflow)?
+                .checked_add(n)
+                .ok_or(SimError::Overflow)?
+                .checked_sub(1)
+                .ok_or(SimError::Overflow)?,
+        )
+        .ok_or(SimError::Overflow)?;
+
+    let series_cost = inc
+        .checked_mul(series_numerator)
+        .ok_or(SimError::Overflow)?
+        .checked_div(2)
+        .ok_or(SimError::Overflow)?;
+
+    let total = base_cost.checked_add(series_cost).ok_or(SimError::Overflow)?;
+
+    u64::try_from(total).map_err(|_| SimError::Overflow)
+}
+
+/// amount * bps / 10_000, rounded down.
+pub fn calculate_bps_split(amount: u64, bps: u64) -> SimResult<u64> {
+    let product = (amount as u128).checked_mul(bps as u128).ok_or(SimError::Overflow)?;
+    u64::try_from(product.checked_div(10_000).ok_or(SimError::Overflow)?)
+        .map_err(|_| SimError::Overflow)
+}
+
+/// (player_keys * total_dividend_pool) / total_keys
+pub fn calculate_dividend_share(
+    player_keys: u64,
+    total_dividend_pool: u64,
+    total_keys: u64,
+) -> SimResult<u64> {
+    if total_keys == 0 || player_keys == 0 {
+        return Ok(0);
+    }
+    let product = (player_keys as u128)
+        .checked_mul(total_dividend_pool as u128)
+        .ok_or(SimError::Overflow)?;
+    u64::try_from(product.checked_div(total_keys as u128).ok_or(SimError::Overflow)?)
+        .map_err(|_| SimError::Overflow)
+}
+
+/// New `timer_end` after a purchase — monotonic, capped at
+/// `round_start + max_timer_secs`. Final-hour extension-shrink is not
+/// modeled: callers that need it should hold `final_hour_active` false and
+/// treat extension_secs as already-effective if they want to approximate it.
+pub fn calculate_timer_extension(
+    current_time: i64,
+    extension_secs: i64,
+    current_timer_end: i64,
+    round_start: i64,
+    max_timer_secs: i64,
+) -> SimResult<i64> {
+    let new_timer = current_time.checked_add(extension_secs).ok_or(SimError::Overflow)?;
+    let max_timer = round_start.checked_add(max_timer_secs).ok_or(SimError::Overflow)?;
+    Ok(new_timer.max(current_timer_end).min(max_timer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_matches_known_value() {
+        let cost = calculate_cost(0, 10, 10_000_000, 1_000_000).unwrap();
+        assert_eq!(cost, 145_000_000);
+    }
+
+    #[test]
+    fn bps_split_matches_known_value() {
+        assert_eq!(calculate_bps_split(1_000_000_000, 4800).unwrap(), 480_000_000);
+    }
+
+    #[test]
+    fn dividend_share_proportional() {
+        assert_eq!(calculate_dividend_share(30, 1_000_000_000, 100).unwrap(), 300_000_000);
+    }
+
+    #[test]
+    fn timer_cannot_decrease() {
+        let result = calculate_timer_extension(500, 30, 1000, 0, 86400).unwrap();
+        assert_eq!(result, 1000);
+    }
+
+    #[test]
+    fn timer_capped_at_max() {
+        let result = calculate_timer_extension(86390, 30, 86400, 0, 86400).unwrap();
+        assert_eq!(result, 86400);
+    }
+}
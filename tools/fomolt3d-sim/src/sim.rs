@@ -0,0 +1,192 @@
+use crate::action::{Action, StateDelta};
+use crate::error::{SimError, SimResult};
+use crate::math;
+use crate::state::{GameConfig, GameState, PlayerState};
+
+/// Deterministic FOMolt3D economy simulator. Wraps a [`GameState`] and
+/// exposes a single `apply` entry point — the same surface an external
+/// consumer (a strategy researcher's search loop, or the frontend dashboard
+/// previewing "what if") drives one action at a time, recording the
+/// [`StateDelta`] each one produces.
+///
+/// Referral bonuses, KYC/blocklist gating, and partner-hook CPIs from the
+/// real `buy_keys` instruction are not modeled — same scope boundary as
+/// `instructions::simulate_strategy`'s on-chain dry run, for the same
+/// reason: they depend on state this standalone crate has no reason to
+/// carry around.
+pub struct GameSim {
+    pub state: GameState,
+    pub history: Vec<StateDelta>,
+}
+
+impl GameSim {
+    pub fn new(config: GameConfig, round_start: i64) -> Self {
+        Self {
+            state: GameState::new(config, round_start, 0),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn from_state(state: GameState) -> Self {
+        Self { state, history: Vec::new() }
+    }
+
+    /// Applies one [`Action`], mutating `self.state` and returning (and
+    /// recording in `self.history`) the resulting [`StateDelta`].
+    pub fn apply(&mut self, action: Action) -> SimResult<StateDelta> {
+        let delta = match action {
+            Action::Register { player } => self.apply_register(player)?,
+            Action::Buy { player, keys, timestamp } => self.apply_buy(player, keys, timestamp)?,
+            Action::Claim { player, timestamp } => self.apply_claim(player, timestamp)?,
+            Action::StartRound { timestamp } => self.apply_start_round(timestamp)?,
+        };
+        self.history.push(delta.clone());
+        Ok(delta)
+    }
+
+    fn apply_register(&mut self, player: String) -> SimResult<StateDelta> {
+        if self.state.players.contains_key(&player) {
+            return Err(SimError::PlayerAlreadyRegistered);
+        }
+        self.state.players.insert(player.clone(), PlayerState::default());
+        Ok(StateDelta::PlayerRegistered { player })
+    }
+
+    fn apply_buy(&mut self, player: String, keys: u64, timestamp: i64) -> SimResult<StateDelta> {
+        if !self.state.active || timestamp >= self.state.timer_end {
+            return Err(SimError::RoundNotActive);
+        }
+        if keys == 0 {
+            return Err(SimError::NoKeysToBuy);
+        }
+        if !self.state.players.contains_key(&player) {
+            return Err(SimError::UnknownPlayer);
+        }
+
+        let config = &self.state.config;
+        let cost = math::calculate_cost(
+            self.state.total_keys,
+            keys,
+            config.base_price_lamports,
+            config.price_increment_lamports,
+        )?;
+
+        let house_fee = math::calculate_bps_split(cost, config.protocol_fee_bps)?;
+        let pot_contribution = cost.checked_sub(house_fee).ok_or(SimError::Overflow)?;
+
+        let winner_amount = math::calculate_bps_split(pot_contribution, config.winner_bps)?;
+        let dividend_amount = math::calculate_bps_split(pot_contribution, config.dividend_bps)?;
+        let next_round_amount = math::calculate_bps_split(pot_contribution, config.next_round_bps)?;
+
+        let timer_end = math::calculate_timer_extension(
+            timestamp,
+            config.timer_extension_secs,
+            self.state.timer_end,
+            self.state.round_start,
+            config.max_timer_secs,
+        )?;
+
+        self.state.protocol_fees_collected = self
+            .state
+            .protocol_fees_collected
+            .checked_add(house_fee)
+            .ok_or(SimError::Overflow)?;
+        self.state.total_dividend_pool = self
+            .state
+            .total_dividend_pool
+            .checked_add(dividend_amount)
+            .ok_or(SimError::Overflow)?;
+        self.state.winner_pot = self.state.winner_pot.checked_add(winner_amount).ok_or(SimError::Overflow)?;
+        self.state.next_round_pot = self
+            .state
+            .next_round_pot
+            .checked_add(next_round_amount)
+            .ok_or(SimError::Overflow)?;
+        self.state.pot_lamports = self.state.pot_lamports.checked_add(cost).ok_or(SimError::Overflow)?;
+        self.state.total_keys = self.state.total_keys.checked_add(keys).ok_or(SimError::Overflow)?;
+        self.state.timer_end = timer_end;
+        self.state.last_buyer = Some(player.clone());
+
+        let total_player_keys = {
+            let p = self.state.players.get_mut(&player).ok_or(SimError::UnknownPlayer)?;
+            p.keys = p.keys.checked_add(keys).ok_or(SimError::Overflow)?;
+            p.keys
+        };
+
+        Ok(StateDelta::KeysPurchased {
+            player,
+            keys_bought: keys,
+            total_player_keys,
+            lamports_spent: cost,
+            pot_contribution,
+            timer_end,
+        })
+    }
+
+    fn apply_claim(&mut self, player: String, timestamp: i64) -> SimResult<StateDelta> {
+        if self.state.active && timestamp < self.state.timer_end {
+            return Err(SimError::RoundNotActive);
+        }
+        let player_keys = {
+            let p = self.state.players.get(&player).ok_or(SimError::UnknownPlayer)?;
+            if p.claimed {
+                return Err(SimError::AlreadyClaimed);
+            }
+            p.keys
+        };
+
+        let dividend_share =
+            math::calculate_dividend_share(player_keys, self.state.total_dividend_pool, self.state.total_keys)?;
+        let is_winner = self.state.last_buyer.as_deref() == Some(player.as_str());
+        let winner_lamports = if is_winner { self.state.winner_pot } else { 0 };
+        let total_lamports = dividend_share.checked_add(winner_lamports).ok_or(SimError::Overflow)?;
+
+        if total_lamports == 0 {
+            return Err(SimError::NothingToClaim);
+        }
+
+        self.state.total_dividend_pool =
+            self.state.total_dividend_pool.checked_sub(dividend_share).ok_or(SimError::Overflow)?;
+        self.state.total_keys = self.state.total_keys.checked_sub(player_keys).ok_or(SimError::Overflow)?;
+        if is_winner {
+            self.state.winner_pot = 0;
+        }
+
+        let p = self.state.players.get_mut(&player).ok_or(SimError::UnknownPlayer)?;
+        p.claimed = true;
+        p.keys = 0;
+        self.state.active = false;
+
+        Ok(StateDelta::Claimed {
+            player,
+            dividend_lamports: dividend_share,
+            winner_lamports,
+            total_lamports,
+        })
+    }
+
+    fn apply_start_round(&mut self, timestamp: i64) -> SimResult<StateDelta> {
+        let carry_over = self.state.next_round_pot;
+        let round = self.state.round.checked_add(1).ok_or(SimError::Overflow)?;
+        let config = self.state.config.clone();
+        let timer_end = timestamp.checked_add(config.max_timer_secs).ok_or(SimError::Overflow)?;
+
+        self.state = GameState {
+            round,
+            total_keys: 0,
+            pot_lamports: carry_over,
+            winner_pot: carry_over,
+            next_round_pot: 0,
+            total_dividend_pool: 0,
+            protocol_fees_collected: 0,
+            round_start: timestamp,
+            timer_end,
+            last_buyer: None,
+            active: true,
+            players: Default::default(),
+            config,
+        };
+
+        Ok(StateDelta::RoundStarted { round, carry_over_lamports: carry_over, timer_end })
+    }
+}
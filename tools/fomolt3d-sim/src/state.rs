@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Economic knobs for a round — mirrors the subset of `GlobalConfig`/
+/// `GameState` that this crate's transitions actually read. Defaults match
+/// `programs/fomolt3d/src/constants.rs`'s `DEFAULT_*` values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub base_price_lamports: u64,
+    pub price_increment_lamports: u64,
+    pub winner_bps: u64,
+    pub dividend_bps: u64,
+    pub next_round_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub timer_extension_secs: i64,
+    pub max_timer_secs: i64,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            base_price_lamports: 10_000_000,
+            price_increment_lamports: 1_000_000,
+            winner_bps: 4800,
+            dividend_bps: 4500,
+            next_round_bps: 700,
+            protocol_fee_bps: 200,
+            timer_extension_secs: 30,
+            max_timer_secs: 86_400,
+        }
+    }
+}
+
+/// A single player's state within one round — mirrors the on-chain
+/// `PlayerState` account, minus fields (referral, session, PDA bookkeeping)
+/// this crate doesn't model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub keys: u64,
+    pub claimed: bool,
+}
+
+/// The full state of one round, plus the player ledger — the thing a
+/// simulation run snapshots to/from JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameState {
+    pub config: GameConfig,
+    pub round: u64,
+    pub total_keys: u64,
+    pub pot_lamports: u64,
+    pub winner_pot: u64,
+    pub next_round_pot: u64,
+    pub total_dividend_pool: u64,
+    pub protocol_fees_collected: u64,
+    pub round_start: i64,
+    pub timer_end: i64,
+    pub last_buyer: Option<String>,
+    pub active: bool,
+    pub players: BTreeMap<String, PlayerState>,
+}
+
+impl GameState {
+    /// Starts round 1 at `round_start`, optionally seeded with a carried-over
+    /// pot from a prior round (see `Action::StartRound`).
+    pub fn new(config: GameConfig, round_start: i64, carry_over_lamports: u64) -> Self {
+        let timer_end = round_start + config.max_timer_secs;
+        Self {
+            round: 1,
+            total_keys: 0,
+            pot_lamports: carry_over_lamports,
+            winner_pot: carry_over_lamports,
+            next_round_pot: 0,
+            total_dividend_pool: 0,
+            protocol_fees_collected: 0,
+            round_start,
+            timer_end,
+            last_buyer: None,
+            active: true,
+            players: BTreeMap::new(),
+            config,
+        }
+    }
+}
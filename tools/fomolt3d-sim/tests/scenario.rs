@@ -0,0 +1,81 @@
+use fomolt3d_sim::{Action, GameConfig, GameSim, SimError, StateDelta};
+
+#[test]
+fn single_player_buys_and_claims_winner() {
+    let mut sim = GameSim::new(GameConfig::default(), 1000);
+
+    sim.apply(Action::Register { player: "alice".into() }).unwrap();
+    let delta = sim
+        .apply(Action::Buy { player: "alice".into(), keys: 5, timestamp: 1001 })
+        .unwrap();
+    match delta {
+        StateDelta::KeysPurchased { total_player_keys, .. } => assert_eq!(total_player_keys, 5),
+        other => panic!("unexpected delta: {:?}", other),
+    }
+
+    let timer_end = sim.state.timer_end;
+    let delta = sim
+        .apply(Action::Claim { player: "alice".into(), timestamp: timer_end })
+        .unwrap();
+    match delta {
+        StateDelta::Claimed { total_lamports, winner_lamports, .. } => {
+            assert!(total_lamports > 0);
+            assert!(winner_lamports > 0);
+        }
+        other => panic!("unexpected delta: {:?}", other),
+    }
+}
+
+#[test]
+fn two_players_split_dividends_proportionally() {
+    let mut sim = GameSim::new(GameConfig::default(), 1000);
+
+    sim.apply(Action::Register { player: "alice".into() }).unwrap();
+    sim.apply(Action::Register { player: "bob".into() }).unwrap();
+    sim.apply(Action::Buy { player: "alice".into(), keys: 50, timestamp: 1001 }).unwrap();
+    sim.apply(Action::Buy { player: "bob".into(), keys: 50, timestamp: 1002 }).unwrap();
+
+    let timer_end = sim.state.timer_end;
+    let alice = sim.apply(Action::Claim { player: "alice".into(), timestamp: timer_end }).unwrap();
+    let bob = sim.apply(Action::Claim { player: "bob".into(), timestamp: timer_end }).unwrap();
+
+    let alice_dividends = match alice {
+        StateDelta::Claimed { dividend_lamports, .. } => dividend_lamports,
+        other => panic!("unexpected delta: {:?}", other),
+    };
+    let bob_dividends = match bob {
+        StateDelta::Claimed { dividend_lamports, .. } => dividend_lamports,
+        other => panic!("unexpected delta: {:?}", other),
+    };
+    assert_eq!(alice_dividends, bob_dividends);
+}
+
+#[test]
+fn buying_before_registering_is_rejected() {
+    let mut sim = GameSim::new(GameConfig::default(), 1000);
+    let err = sim
+        .apply(Action::Buy { player: "alice".into(), keys: 1, timestamp: 1001 })
+        .unwrap_err();
+    assert_eq!(err, SimError::UnknownPlayer);
+}
+
+#[test]
+fn round_rolls_over_next_round_pot_as_carry() {
+    let mut sim = GameSim::new(GameConfig::default(), 1000);
+    sim.apply(Action::Register { player: "alice".into() }).unwrap();
+    sim.apply(Action::Buy { player: "alice".into(), keys: 10, timestamp: 1001 }).unwrap();
+
+    let carry = sim.state.next_round_pot;
+    let timer_end = sim.state.timer_end;
+    sim.apply(Action::Claim { player: "alice".into(), timestamp: timer_end }).unwrap();
+
+    let delta = sim.apply(Action::StartRound { timestamp: timer_end + 1 }).unwrap();
+    match delta {
+        StateDelta::RoundStarted { round, carry_over_lamports, .. } => {
+            assert_eq!(round, 2);
+            assert_eq!(carry_over_lamports, carry);
+        }
+        other => panic!("unexpected delta: {:?}", other),
+    }
+    assert_eq!(sim.state.winner_pot, carry);
+}
@@ -0,0 +1,377 @@
+//! Off-chain solvency watchdog. Polls a single game/round's `GameState` and
+//! vault over RPC, independently recomputes what the vault should hold, and
+//! alerts (webhook + nonzero exit code) the moment either drifts.
+//!
+//! This mirrors `instructions::assert_solvency` rather than replacing it —
+//! that instruction is the authoritative on-chain check (and is itself
+//! permissionless, so a crank can call it directly), but it must be sent as
+//! a transaction to run. This binary is meant to sit in prod polling many
+//! rounds without spending any SOL on fees, and to additionally cross-check
+//! `GameState::total_dividend_pool` against what `PlayerState` accounts for
+//! the round actually add up to, which `assert_solvency` doesn't do.
+//!
+//! Decodes account bytes by hand instead of depending on the `fomolt3d`
+//! program crate: anchor-lang 0.32.1 pins an older solana-program than
+//! solana-client/solana-sdk 3.x, so the two crates' `Pubkey` types aren't
+//! the same type. See `programs/fomolt3d/tests/helpers.rs` for the same
+//! workaround on the test side.
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// Program ID matching `declare_id!` in `programs/fomolt3d/src/lib.rs`.
+const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("EebbWtjHyocWPwZaQ4k2L61mSdW6y175knsEwppTpdWw");
+
+/// Anchor account discriminator: sha256("account:GameState")[..8]
+const GAME_STATE_DISCRIMINATOR: [u8; 8] = [144, 94, 208, 172, 248, 99, 134, 120];
+/// Anchor account discriminator: sha256("account:PlayerState")[..8]
+const PLAYER_STATE_DISCRIMINATOR: [u8; 8] = [56, 3, 60, 86, 174, 16, 244, 195];
+
+/// How far `total_dividend_pool` may drift from the sum of outstanding
+/// per-player shares before it's treated as accounting drift rather than
+/// ordinary floor-division dust (one lamport per holder, worst case).
+const DRIFT_TOLERANCE_LAMPORTS: u64 = 64;
+
+#[derive(Parser)]
+#[command(about = "Polls a FOMolt3D game/round and alerts on insolvency or accounting drift")]
+struct Args {
+    /// Solana RPC endpoint to poll
+    #[arg(long, env = "SOLANA_RPC_URL")]
+    rpc_url: String,
+
+    /// `GlobalConfig::game_id` of the round to watch
+    #[arg(long, default_value_t = 0)]
+    game_id: u64,
+
+    /// Round number to watch. Bump this when the operator rolls to a new
+    /// round — the monitor has no way to discover it on its own, since the
+    /// round number is part of the `GameState` PDA's seeds.
+    #[arg(long)]
+    round: u64,
+
+    /// Seconds between polls
+    #[arg(long, default_value_t = 10)]
+    interval_secs: u64,
+
+    /// Webhook URL to POST a JSON alert to on any insolvency or drift
+    #[arg(long, env = "MONITOR_WEBHOOK_URL")]
+    webhook_url: Option<String>,
+
+    /// Check once and exit (0 = solvent, 1 = insolvent/drift, 2 = error)
+    /// instead of polling forever. For cron/CI use.
+    #[arg(long)]
+    once: bool,
+}
+
+/// The subset of `GameState` this monitor needs, parsed by hand from raw
+/// account bytes (field order and sizes must match `state::game_state::GameState`
+/// exactly, since offsets are positional).
+struct GameStateView {
+    total_keys: u64,
+    winner_pot: u64,
+    total_dividend_pool: u64,
+    next_round_pot: u64,
+    total_referral_obligations: u64,
+    vault_lamports_in: u64,
+    vault_lamports_out: u64,
+    time_weighted_dividends_enabled: bool,
+}
+
+impl GameStateView {
+    fn parse(data: &[u8]) -> Self {
+        let mut o = 8; // skip discriminator
+        let _game_id = read_u64(data, &mut o);
+        let _round = read_u64(data, &mut o);
+        let _pot_lamports = read_u64(data, &mut o);
+        let _timer_end = read_i64(data, &mut o);
+        let _last_buyer = read_pubkey(data, &mut o);
+        let total_keys = read_u64(data, &mut o);
+        let _round_start = read_i64(data, &mut o);
+        let _status = read_u8(data, &mut o);
+        let _total_players = read_u32(data, &mut o);
+        let total_dividend_pool = read_u64(data, &mut o);
+        let next_round_pot = read_u64(data, &mut o);
+        let winner_pot = read_u64(data, &mut o);
+        let _base_price_lamports = read_u64(data, &mut o);
+        let _price_increment_lamports = read_u64(data, &mut o);
+        let _timer_extension_secs = read_i64(data, &mut o);
+        let _max_timer_secs = read_i64(data, &mut o);
+        let _winner_bps = read_u64(data, &mut o);
+        let _dividend_bps = read_u64(data, &mut o);
+        let _next_round_bps = read_u64(data, &mut o);
+        let _protocol_fee_bps = read_u64(data, &mut o);
+        let _referral_bonus_bps = read_u64(data, &mut o);
+        let _protocol_wallet = read_pubkey(data, &mut o);
+        let _bump = read_u8(data, &mut o);
+        let total_referral_obligations = read_u64(data, &mut o);
+        let _total_weight = read_u64(data, &mut o);
+        let _early_bird_key_threshold = read_u64(data, &mut o);
+        let _early_bird_multiplier_bps = read_u64(data, &mut o);
+        let _min_purchase_lamports = read_u64(data, &mut o);
+        let _winner_claim_window_secs = read_i64(data, &mut o);
+        let _final_hour_pot_threshold_lamports = read_u64(data, &mut o);
+        let _final_hour_shrink_interval_keys = read_u64(data, &mut o);
+        let _final_hour_active = read_bool(data, &mut o);
+        let _final_hour_start_keys = read_u64(data, &mut o);
+        let _pot_milestone_interval_lamports = read_u64(data, &mut o);
+        let _pot_milestone_bonus_keys = read_u64(data, &mut o);
+        let vault_lamports_in = read_u64(data, &mut o);
+        let vault_lamports_out = read_u64(data, &mut o);
+        let _promo_keys_cap_per_round = read_u64(data, &mut o);
+        let _promo_keys_granted_this_round = read_u64(data, &mut o);
+        let _transfers_enabled = read_bool(data, &mut o);
+        let _wrapped_keys_enabled = read_bool(data, &mut o);
+        let _wrapped_keys_total = read_u64(data, &mut o);
+        let _wrapped_weight_total = read_u64(data, &mut o);
+        let _keeper_fee_lamports = read_u64(data, &mut o);
+        let _purchase_history_enabled = read_bool(data, &mut o);
+        let _purchase_count = read_u64(data, &mut o);
+        let _gross_volume_lamports = read_u64(data, &mut o);
+        let _max_single_buy_lamports = read_u64(data, &mut o);
+        let _max_single_buyer = read_pubkey(data, &mut o);
+        let time_weighted_dividends_enabled = read_bool(data, &mut o);
+
+        Self {
+            total_keys,
+            winner_pot,
+            total_dividend_pool,
+            next_round_pot,
+            total_referral_obligations,
+            vault_lamports_in,
+            vault_lamports_out,
+            time_weighted_dividends_enabled,
+        }
+    }
+
+    /// Mirrors `GameState::pending_obligations`.
+    fn pending_obligations(&self) -> Option<u64> {
+        self.winner_pot
+            .checked_add(self.total_dividend_pool)
+            .and_then(|v| v.checked_add(self.next_round_pot))
+            .and_then(|v| v.checked_add(self.total_referral_obligations))
+    }
+}
+
+/// The subset of `PlayerState` this monitor needs.
+struct PlayerStateView {
+    keys: u64,
+    claimed_dividends_lamports: u64,
+}
+
+impl PlayerStateView {
+    fn parse(data: &[u8]) -> Self {
+        let mut o = 8; // skip discriminator
+        let _game_id = read_u64(data, &mut o);
+        let _player = read_pubkey(data, &mut o);
+        let keys = read_u64(data, &mut o);
+        let _current_round = read_u64(data, &mut o);
+        let claimed_dividends_lamports = read_u64(data, &mut o);
+        Self {
+            keys,
+            claimed_dividends_lamports,
+        }
+    }
+}
+
+fn read_u64(data: &[u8], offset: &mut usize) -> u64 {
+    let val = u64::from_le_bytes(data[*offset..*offset + 8].try_into().unwrap());
+    *offset += 8;
+    val
+}
+
+fn read_i64(data: &[u8], offset: &mut usize) -> i64 {
+    let val = i64::from_le_bytes(data[*offset..*offset + 8].try_into().unwrap());
+    *offset += 8;
+    val
+}
+
+fn read_u32(data: &[u8], offset: &mut usize) -> u32 {
+    let val = u32::from_le_bytes(data[*offset..*offset + 4].try_into().unwrap());
+    *offset += 4;
+    val
+}
+
+fn read_bool(data: &[u8], offset: &mut usize) -> bool {
+    let val = data[*offset] != 0;
+    *offset += 1;
+    val
+}
+
+fn read_u8(data: &[u8], offset: &mut usize) -> u8 {
+    let val = data[*offset];
+    *offset += 1;
+    val
+}
+
+fn read_pubkey(data: &[u8], offset: &mut usize) -> [u8; 32] {
+    let mut pk = [0u8; 32];
+    pk.copy_from_slice(&data[*offset..*offset + 32]);
+    *offset += 32;
+    pk
+}
+
+/// Mirrors `math::calculate_dividend_share`.
+fn calculate_dividend_share(player_keys: u64, total_dividend_pool: u64, total_keys: u64) -> u64 {
+    if total_keys == 0 || player_keys == 0 {
+        return 0;
+    }
+    ((player_keys as u128) * (total_dividend_pool as u128) / (total_keys as u128)) as u64
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+
+    loop {
+        match check_once(&rpc, &args) {
+            Ok(true) => {
+                println!("game_id={} round={}: solvent", args.game_id, args.round);
+                if args.once {
+                    return ExitCode::SUCCESS;
+                }
+            }
+            Ok(false) => {
+                if args.once {
+                    return ExitCode::from(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("monitor error: {err}");
+                if args.once {
+                    return ExitCode::from(2);
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_secs(args.interval_secs));
+    }
+}
+
+/// Runs one poll. Returns `Ok(true)` if solvent and in sync, `Ok(false)` if
+/// an alert was raised (and sent to the webhook, if configured).
+fn check_once(rpc: &RpcClient, args: &Args) -> Result<bool, Box<dyn std::error::Error>> {
+    let game_key = game_pda(args.game_id, args.round);
+    let vault_key = vault_pda(&game_key);
+
+    let game_data = rpc.get_account_data(&game_key)?;
+    if game_data.get(..8) != Some(GAME_STATE_DISCRIMINATOR.as_slice()) {
+        return Err("account at the derived game PDA is not a GameState".into());
+    }
+    let game = GameStateView::parse(&game_data);
+    let vault_lamports = rpc.get_balance(&vault_key)?;
+
+    let mut problems = Vec::new();
+
+    let obligations = game
+        .pending_obligations()
+        .ok_or("GameState obligations overflowed u64")?;
+    if vault_lamports < obligations {
+        problems.push(format!(
+            "vault underfunded: {vault_lamports} lamports held vs {obligations} owed"
+        ));
+    }
+
+    let expected_balance = game
+        .vault_lamports_in
+        .checked_sub(game.vault_lamports_out)
+        .ok_or("vault_lamports_in/out overflow")?;
+    if vault_lamports != expected_balance {
+        problems.push(format!(
+            "vault balance {vault_lamports} lamports != tracked in/out counters ({expected_balance} lamports) — leak or untracked transfer"
+        ));
+    }
+
+    if let Some(drift) = dividend_pool_drift(rpc, &game, args.game_id, args.round)? {
+        if drift > DRIFT_TOLERANCE_LAMPORTS {
+            problems.push(format!(
+                "total_dividend_pool drifted from summed player shares by {drift} lamports"
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(true);
+    }
+
+    let message = format!(
+        "FOMolt3D solvency alert (game_id={}, round={}): {}",
+        args.game_id,
+        args.round,
+        problems.join("; ")
+    );
+    eprintln!("{message}");
+    if let Some(url) = &args.webhook_url {
+        send_webhook(url, &message)?;
+    }
+    Ok(false)
+}
+
+/// Independently sums every `PlayerState` for this round's outstanding
+/// dividend share and compares it against `GameState::total_dividend_pool`.
+/// Returns `None` if the round uses time-weighted dividends, which this
+/// monitor doesn't recompute (weight-seconds accrual requires replaying the
+/// same clock-driven updates the program does, not just a point-in-time
+/// read).
+fn dividend_pool_drift(
+    rpc: &RpcClient,
+    game: &GameStateView,
+    game_id: u64,
+    round: u64,
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    if game.time_weighted_dividends_enabled {
+        return Ok(None);
+    }
+
+    let filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &PLAYER_STATE_DISCRIMINATOR)),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, &game_id.to_le_bytes())),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(56, &round.to_le_bytes())),
+    ];
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc.get_program_ui_accounts_with_config(&PROGRAM_ID, config)?;
+
+    let mut total_outstanding: u64 = 0;
+    for (_pubkey, account) in &accounts {
+        let data = account
+            .data
+            .decode()
+            .ok_or("could not decode PlayerState account data")?;
+        let player = PlayerStateView::parse(&data);
+        let share = calculate_dividend_share(player.keys, game.total_dividend_pool, game.total_keys);
+        total_outstanding =
+            total_outstanding.saturating_add(share.saturating_sub(player.claimed_dividends_lamports));
+    }
+
+    Ok(Some(game.total_dividend_pool.abs_diff(total_outstanding)))
+}
+
+fn game_pda(game_id: u64, round: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"game", &game_id.to_le_bytes(), &round.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+fn vault_pda(game_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", game_key.as_ref()], &PROGRAM_ID).0
+}
+
+fn send_webhook(url: &str, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    ureq::post(url).send_json(ureq::json!({ "text": message }))?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Operator CLI for projecting a FOMolt3D round's outcome ahead of a config
+//! change. Reads a [`GameConfig`]-shaped TOML file and a scenario script
+//! describing which players buy how many keys and when, replays it through
+//! [`fomolt3d_sim::GameSim`], and reports the numbers an operator tuning bps
+//! values before a `create_or_update_config` call would want: projected pot
+//! sizes, dividend APY per key, protocol revenue, and the end-of-round
+//! per-player distribution.
+//!
+//! Only models what `fomolt3d-sim` itself models — see that crate's
+//! `lib.rs` for the scope boundary (no referrals, no KYC/blocklist, no
+//! partner CPIs).
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use fomolt3d_sim::{Action, GameConfig, GameSim, StateDelta};
+use serde::Deserialize;
+
+/// Used to annualize the dividend yield observed over one simulated round —
+/// there's no on-chain notion of a "year", this is purely a reporting aid.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+#[derive(Parser)]
+#[command(about = "Projects a FOMolt3D round's pot, dividends, and revenue from a config + buy schedule")]
+struct Args {
+    /// Path to a TOML file with the `GameConfig` fields to simulate against
+    #[arg(long)]
+    config: PathBuf,
+
+    /// Path to a TOML scenario script: `round_start` plus a list of players
+    /// and their timestamped buys
+    #[arg(long)]
+    scenario: PathBuf,
+}
+
+/// A scenario script's top level: when the round starts, and who buys what
+/// and when. Players are auto-registered in file order before any buy runs.
+#[derive(Deserialize)]
+struct Scenario {
+    round_start: i64,
+    players: Vec<PlayerScript>,
+}
+
+#[derive(Deserialize)]
+struct PlayerScript {
+    name: String,
+    #[serde(default)]
+    buys: Vec<BuyScript>,
+}
+
+#[derive(Deserialize)]
+struct BuyScript {
+    timestamp: i64,
+    keys: u64,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let config = match read_toml::<GameConfig>(&args.config) {
+        Ok(c) => c,
+        Err(err) => {
+            eprintln!("failed to read config {}: {err}", args.config.display());
+            return ExitCode::from(2);
+        }
+    };
+    let scenario = match read_toml::<Scenario>(&args.scenario) {
+        Ok(s) => s,
+        Err(err) => {
+            eprintln!("failed to read scenario {}: {err}", args.scenario.display());
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(config, scenario) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("simulation error: {err}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn read_toml<T: for<'de> Deserialize<'de>>(path: &PathBuf) -> Result<T, Box<dyn std::error::Error>> {
+    let raw = fs::read_to_string(path)?;
+    Ok(toml::from_str(&raw)?)
+}
+
+/// Replays `scenario` against `config` through to timer expiry, prints the
+/// projected round totals and dividend APY, then claims every player and
+/// prints the resulting end-of-round distribution.
+fn run(config: GameConfig, scenario: Scenario) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = GameSim::new(config, scenario.round_start);
+
+    for player in &scenario.players {
+        sim.apply(Action::Register { player: player.name.clone() })?;
+    }
+
+    let mut buys: Vec<(&str, i64, u64)> = scenario
+        .players
+        .iter()
+        .flat_map(|p| p.buys.iter().map(move |b| (p.name.as_str(), b.timestamp, b.keys)))
+        .collect();
+    buys.sort_by_key(|(_, timestamp, _)| *timestamp);
+
+    for (player, timestamp, keys) in buys {
+        sim.apply(Action::Buy { player: player.to_string(), keys, timestamp })?;
+    }
+
+    let timer_end = sim.state.timer_end;
+    let total_keys = sim.state.total_keys;
+    // Marginal price of the next key at round end — the best available
+    // stand-in for "the price a holder actually paid," since individual
+    // purchase prices aren't retained per-player.
+    let marginal_key_price = sim
+        .state
+        .config
+        .base_price_lamports
+        .saturating_add(sim.state.config.price_increment_lamports.saturating_mul(total_keys));
+    let round_duration_secs = (timer_end - sim.state.round_start).max(1) as f64;
+    let dividend_per_key = sim.state.total_dividend_pool.checked_div(total_keys).unwrap_or(0);
+    let dividend_apy_bps = if marginal_key_price > 0 {
+        (dividend_per_key as f64 / marginal_key_price as f64) * (SECONDS_PER_YEAR / round_duration_secs)
+            * 10_000.0
+    } else {
+        0.0
+    };
+
+    println!("=== Projected round state at timer expiry ===");
+    println!("total_keys: {total_keys}");
+    println!("winner_pot_lamports: {}", sim.state.winner_pot);
+    println!("total_dividend_pool_lamports: {}", sim.state.total_dividend_pool);
+    println!("next_round_pot_lamports: {}", sim.state.next_round_pot);
+    println!("protocol_fees_collected_lamports: {}", sim.state.protocol_fees_collected);
+    println!("dividend_apy_bps_per_key: {dividend_apy_bps:.2}");
+
+    println!("\n=== End-of-round distribution ===");
+    let names: Vec<String> = scenario.players.iter().map(|p| p.name.clone()).collect();
+    for name in names {
+        let delta = sim.apply(Action::Claim { player: name.clone(), timestamp: timer_end })?;
+        if let StateDelta::Claimed { dividend_lamports, winner_lamports, total_lamports, .. } = delta {
+            println!("{name}: dividend={dividend_lamports} winner={winner_lamports} total={total_lamports}");
+        }
+    }
+
+    Ok(())
+}